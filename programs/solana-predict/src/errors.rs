@@ -64,4 +64,180 @@ pub enum PredictError {
     InvalidPythFeed,
     #[msg("Oracle price is stale")]
     OracleStale,
+    #[msg("Oracle threshold is too close to the current price")]
+    ThresholdTooClose,
+    #[msg("Invalid batch accounts or arguments")]
+    InvalidBatch,
+    #[msg("Creator must wait before creating another market")]
+    CreationCooldownActive,
+    #[msg("Outside the resolution price snapshot window")]
+    SnapshotWindowClosed,
+    #[msg("Payout rate must be greater than 0")]
+    InvalidPayoutRate,
+    #[msg("Payout reserve cannot cover the market's liabilities")]
+    InsufficientPayoutReserve,
+    #[msg("Market does not have a separate payout mint")]
+    NoPayoutMint,
+    #[msg("Oracle source is not allowed for this market category")]
+    OracleSourceNotAllowedForCategory,
+    #[msg("Maximum number of active markets reached")]
+    ActiveMarketLimitReached,
+    #[msg("Market has no default outcome on timeout")]
+    NoDefaultOutcome,
+    #[msg("Invalid program state query")]
+    InvalidProgramStateQuery,
+    #[msg("Program state account is not owned by the configured program")]
+    ProgramStateOwnerMismatch,
+    #[msg("Invalid early-bird bonus configuration")]
+    InvalidEarlyBirdConfig,
+    #[msg("Too many market admins")]
+    TooManyMarketAdmins,
+    #[msg("Dispute voting has ended")]
+    DisputeVotingClosed,
+    #[msg("Extension exceeds the maximum dispute voting period")]
+    DisputeVotingTooLong,
+    #[msg("Bet is too large relative to the pool")]
+    BetTooLargeForPool,
+    #[msg("Too soon after the last bet to resolve")]
+    ResolutionTooSoonAfterBet,
+    #[msg("Market is not configured for both-sides-win resolution")]
+    BothSidesWinDisabled,
+    #[msg("Invalid payout allocation")]
+    InvalidAllocation,
+    #[msg("Betting is still open")]
+    BettingStillOpen,
+    #[msg("Token account is frozen")]
+    AccountFrozen,
+    #[msg("Platform is in emergency shutdown")]
+    PlatformShutdown,
+    #[msg("Platform is not in emergency shutdown")]
+    PlatformNotShutdown,
+    #[msg("Invalid dust recipient for this market")]
+    InvalidDustRecipient,
+    #[msg("Dust recipient account required")]
+    DustAccountRequired,
+    #[msg("Fee destinations do not reconcile with fees charged")]
+    FeeAccountingMismatch,
+    #[msg("Market forking is disabled")]
+    ForkingDisabled,
+    #[msg("Fork child already exists or does not belong to this market")]
+    InvalidForkChild,
+    #[msg("Forked markets are final")]
+    ForkedMarketFinal,
+    #[msg("Dust forfeiture is disabled")]
+    DustForfeitureDisabled,
+    #[msg("Claim window is still open")]
+    ClaimWindowOpen,
+    #[msg("Position is owed more than the dust threshold")]
+    PositionAboveDustThreshold,
+    #[msg("Position was forfeited")]
+    PositionForfeited,
+    #[msg("Platform is already initialized")]
+    PlatformAlreadyInitialized,
+    #[msg("Dispute bond must be greater than zero")]
+    InvalidDisputeBond,
+    #[msg("Bet would exceed the platform TVL cap")]
+    PlatformTvlCapReached,
+    #[msg("Market is already marked for close")]
+    CloseAlreadyRequested,
+    #[msg("Market has not been marked for close")]
+    CloseNotRequested,
+    #[msg("Close grace window has not elapsed")]
+    CloseGraceActive,
+    #[msg("Market creator and resolvers cannot bet on this market")]
+    ResolverCannotBet,
+    #[msg("Liquidity providers are not supported on this market")]
+    LiquidityUnsupported,
+    #[msg("Treasury holds less than the requested amount")]
+    InsufficientTreasury,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Action needs approvals: use propose_action and execute_action")]
+    ApprovalRequired,
+    #[msg("Approvals are not enabled")]
+    ApprovalsDisabled,
+    #[msg("Approver set is invalid (duplicate keys, too many, or unreachable threshold)")]
+    InvalidApprovers,
+    #[msg("Signer already approved this action")]
+    AlreadyApproved,
+    #[msg("Action was already executed")]
+    ActionAlreadyExecuted,
+    #[msg("Action does not have enough approvals")]
+    InsufficientApprovals,
+    #[msg("Accounts passed do not match the proposed action")]
+    ActionAccountMismatch,
+    #[msg("Role registry is full")]
+    RoleRegistryFull,
+    #[msg("Switchboard feed account is invalid or does not match the market")]
+    InvalidSwitchboardFeed,
+    #[msg("Chainlink feed account is invalid or does not match the market")]
+    InvalidChainlinkFeed,
+    #[msg("Optimistic resolution is only available for ManualAdmin markets")]
+    ProposalUnsupported,
+    #[msg("Challenge window has not ended")]
+    ChallengeWindowOpen,
+    #[msg("Challenge window has ended")]
+    ChallengeWindowClosed,
+    #[msg("Proposed resolution was already challenged")]
+    AlreadyChallenged,
+    #[msg("Dispute has not been settled")]
+    DisputeNotSettled,
+    #[msg("Committee members or threshold are invalid")]
+    InvalidCommittee,
+    #[msg("Market is resolved by the resolution committee")]
+    CommitteeResolutionRequired,
+    #[msg("Signed attestation is missing, malformed or not from the market's attester")]
+    InvalidAttestation,
+    #[msg("Market does not resolve on a TWAP")]
+    TwapDisabled,
+    #[msg("Too soon after the previous price snapshot")]
+    SnapshotTooSoon,
+    #[msg("Not enough price snapshots in the TWAP window")]
+    InsufficientSnapshots,
+    #[msg("Oracle feed is not in the feed registry")]
+    FeedNotRegistered,
+    #[msg("Feed registry is full")]
+    FeedRegistryFull,
+    #[msg("Invalid feed registry entry")]
+    InvalidFeedEntry,
+    #[msg("Market has a default outcome; use resolve_on_timeout")]
+    DefaultOutcomeSet,
+    #[msg("Market is not cancelled")]
+    MarketNotCancelled,
+    #[msg("Scalar range must have lower < upper and no confidence split")]
+    InvalidScalarRange,
+    #[msg("Range market needs oracle_threshold < oracle_upper_threshold")]
+    InvalidRangeThresholds,
+    #[msg("Barrier not touched")]
+    BarrierNotTouched,
+    #[msg("Not available in this market's pricing mode")]
+    PricingModeUnsupported,
+    #[msg("Initial probability out of range")]
+    InvalidInitialProbability,
+    #[msg("Invalid fee ramp")]
+    InvalidFeeRamp,
+    #[msg("No creator fees to claim")]
+    NoCreatorFees,
+    #[msg("Creator fees still unclaimed")]
+    CreatorFeesUnclaimed,
+    #[msg("No referral fees to claim")]
+    NoReferralFees,
+    #[msg("A bettor cannot refer themselves")]
+    SelfReferral,
+    #[msg("Referral escrow does not match the referral account")]
+    InvalidReferralEscrow,
+    #[msg("Invalid fee tiers")]
+    InvalidFeeTiers,
+    #[msg("Round is not open")]
+    RoundNotOpen,
+    #[msg("Round is not locked")]
+    RoundNotLocked,
+    #[msg("Round is not resolved or cancelled")]
+    RoundNotSettled,
+    #[msg("Round position still has a payout to claim")]
+    RoundPayoutUnclaimed,
+    #[msg("Scheduled-round markets take bets through place_round_bet")]
+    RoundBetsOnly,
+    #[msg("Market is not paused")]
+    MarketNotPaused,
 }
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::state::market::{Outcome, OracleSource};
+use crate::state::dispute::DisputeStatus;
+use crate::state::approvals::AdminAction;
+use crate::state::platform::Role;
 
 #[event]
 pub struct PlatformInitialized {
@@ -14,6 +17,7 @@ pub struct MarketCreated {
     pub title: String,
     pub oracle_source: OracleSource,
     pub end_timestamp: i64,
+    pub collateral_decimals: u8,
 }
 
 #[event]
@@ -22,6 +26,7 @@ pub struct BetPlaced {
     pub user: Pubkey,
     pub outcome: Outcome,
     pub amount: u64,
+    pub scaled_amount_1e6: u64,
     pub shares: u64,
     pub new_yes_total: u64,
     pub new_no_total: u64,
@@ -34,6 +39,7 @@ pub struct BetCancelled {
     pub user: Pubkey,
     pub shares_burned: u64,
     pub refund_amount: u64,
+    pub scaled_amount_1e6: u64,
 }
 
 #[event]
@@ -43,6 +49,13 @@ pub struct RoundLocked {
     pub lock_price: i64,
 }
 
+#[event]
+pub struct MarketLocked {
+    pub market_id: u64,
+    pub yes_reserve: u64,
+    pub no_reserve: u64,
+}
+
 #[event]
 pub struct MarketResolved {
     pub market_id: u64,
@@ -51,12 +64,304 @@ pub struct MarketResolved {
     pub total_collateral: u64,
 }
 
+#[event]
+pub struct ResolutionPriceSnapshotted {
+    pub market_id: u64,
+    pub price: i64,
+    pub publish_time: i64,
+}
+
 #[event]
 pub struct PayoutClaimed {
     pub market_id: u64,
     pub user: Pubkey,
     pub amount: u64,
+    pub scaled_amount_1e6: u64,
+    pub payout_mint: Pubkey,
+    pub shares_burned: u64,
+}
+
+#[event]
+pub struct DustForfeited {
+    pub market_id: u64,
+    pub user: Pubkey,
+    pub yes_shares: u64,
+    pub no_shares: u64,
+    pub value: u64,             // collateral the position could still have claimed
+}
+
+#[event]
+pub struct PayoutMintSet {
+    pub market_id: u64,
+    pub payout_mint: Pubkey,
+    pub payout_rate: u64,
+    pub reserve_amount: u64,
+}
+
+#[event]
+pub struct MarketClosing {
+    pub market_id: u64,
+    pub yes_supply: u64,
+    pub no_supply: u64,
+    pub vault_balance: u64,
+}
+
+#[event]
+pub struct MarketAborted {
+    pub market_id: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct LiquidityRebalanced {
+    pub market_id: u64,
+    pub yes_add: u64,
+    pub no_add: u64,
+    pub yes_reserve: u64,
+    pub no_reserve: u64,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub market_id: u64,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub lp_tokens: u64,
+    pub lp_liquidity: u64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub market_id: u64,
+    pub provider: Pubkey,
+    pub lp_tokens: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct ActionProposed {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+}
+
+#[event]
+pub struct ActionApproved {
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ActionExecuted {
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+    pub action: AdminAction,
+}
+
+#[event]
+pub struct RoleChanged {
+    pub key: Pubkey,
+    pub role: Role,
+    pub granted: bool,
+}
+
+#[event]
+pub struct ResolutionProposed {
+    pub market_id: u64,
+    pub proposer: Pubkey,
+    pub outcome: Outcome,
+    pub bond: u64,
+    pub challenge_ends_at: i64,
+}
+
+#[event]
+pub struct ResolutionChallenged {
+    pub market_id: u64,
+    pub challenger: Pubkey,
+}
+
+#[event]
+pub struct ResolutionFinalized {
+    pub market_id: u64,
+    pub outcome: Option<Outcome>,
+    pub bond_recipient: Pubkey,
+}
+
+#[event]
+pub struct ResolutionCommitteeSet {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct OutcomeAttested {
+    pub market_id: u64,
+    pub member: Pubkey,
+    pub outcome: Outcome,
+    pub attestations: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct PriceSnapshotRecorded {
+    pub market_id: u64,
+    pub price: i64,
+    pub observed_at: i64,
+}
+
+#[event]
+pub struct FeedRegistered {
+    pub feed: Pubkey,
+    pub source: OracleSource,
+    pub symbol: String,
+    pub exponent: i32,
+}
+
+#[event]
+pub struct FeedDeregistered {
+    pub feed: Pubkey,
+}
+
+#[event]
+pub struct BarrierTouched {
+    pub market_id: u64,
+    pub price: i64,
+    pub threshold: i64,
+    pub caller: Pubkey,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub market_id: u64,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub referrer: Pubkey,
+    pub escrow: Pubkey,
+}
+
+#[event]
+pub struct ReferralFeePaid {
+    pub market_id: u64,
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralFeesClaimed {
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundResolved {
+    pub market_id: u64,
+    pub round_id: u64,
+    pub lock_price: i64,
+    pub close_price: i64,
+    pub outcome: Outcome,
+}
+
+#[event]
+pub struct RoundBetPlaced {
+    pub market_id: u64,
+    pub round_id: u64,
+    pub user: Pubkey,
+    pub outcome: Outcome,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct RoundPayoutClaimed {
+    pub market_id: u64,
+    pub round_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundClosed {
+    pub market_id: u64,
+    pub round_id: u64,
+    pub swept: u64,
+}
+
+#[event]
+pub struct MarketCreatorTransferred {
+    pub market_id: u64,
+    pub old_creator: Pubkey,
+    pub new_creator: Pubkey,
+}
+
+#[event]
+pub struct OracleSourceMigrated {
+    pub market_id: u64,
+    pub old_source: OracleSource,
+    pub new_source: OracleSource,
+    pub new_feed: Pubkey,
+    pub new_threshold: i64,
+}
+
+#[event]
+pub struct MarketCloseRequested {
+    pub market_id: u64,
+    pub closable_at: i64,
+}
+
+#[event]
+pub struct MarketCloseCancelled {
+    pub market_id: u64,
+}
+
+#[event]
+pub struct MarketCancelled {
+    pub market_id: u64,
+    pub cancelled_by: Pubkey,
+    pub refundable: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub market_id: u64,
+    pub user: Pubkey,
     pub shares_burned: u64,
+    pub amount: u64,
+    pub scaled_amount_1e6: u64,
+}
+
+#[event]
+pub struct EmergencyRefundClaimed {
+    pub market_id: u64,
+    pub user: Pubkey,
+    pub shares_burned: u64,
+    pub amount: u64,
+    pub scaled_amount_1e6: u64,
 }
 
 #[event]
@@ -73,6 +378,36 @@ pub struct DisputeSettled {
     pub new_outcome: Option<Outcome>,
 }
 
+#[event]
+pub struct MarketForked {
+    pub parent_market_id: u64,
+    pub child_market_id: u64,
+    pub outcome: Outcome,
+    pub collateral: u64,
+}
+
+#[event]
+pub struct ForkPositionMigrated {
+    pub parent_market_id: u64,
+    pub user: Pubkey,
+    pub yes_shares: u64,
+    pub no_shares: u64,
+}
+
+#[event]
+pub struct DisputeStatusChanged {
+    pub market_id: u64,
+    pub old: Option<DisputeStatus>, // None when the dispute was just opened
+    pub new: DisputeStatus,
+}
+
+#[event]
+pub struct DisputeVotingExtended {
+    pub market_id: u64,
+    pub old_ends_at: i64,
+    pub new_ends_at: i64,
+}
+
 #[event]
 pub struct RoundStarted {
     pub market_id: u64,
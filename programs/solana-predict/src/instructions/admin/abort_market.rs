@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Transfer, CloseAccount, close_account};
+use crate::state::{PlatformConfig, Role, Market, MarketStatus};
+use crate::events::MarketAborted;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct AbortMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        close = admin,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fees", market.key().as_ref()],
+        bump,
+    )]
+    pub creator_fee_escrow: Account<'info, TokenAccount>,
+
+    /// Creator's collateral account receiving the seed liquidity back
+    #[account(
+        mut,
+        token::mint = market.collateral_mint,
+        token::authority = market.creator,
+    )]
+    pub creator_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Tears down a market nobody has bet on yet: the seed liquidity goes back to
+/// the creator and the vault, creator fee escrow and market accounts are closed.
+pub fn process_abort_market(ctx: Context<AbortMarket>, market_id: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    // Guards: untouched market only (see close_market for settled ones)
+    require!(
+        market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+        PredictError::MarketNotCloseable
+    );
+    require!(
+        market.total_collateral == market.initial_liquidity
+            && ctx.accounts.yes_mint.supply == 0
+            && ctx.accounts.no_mint.supply == 0,
+        PredictError::OutstandingPositions
+    );
+    // A cross-mint reserve would be stranded; it is released after resolution
+    require!(market.payout_mint.is_none(), PredictError::MarketNotCloseable);
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds = &[
+        b"market" as &[u8],
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Refund everything in the vault (seed liquidity plus any stray deposits)
+    let refunded = ctx.accounts.vault.amount;
+    if refunded > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.creator_ata.to_account_info(),
+                    authority: ctx.accounts.market.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refunded,
+        )?;
+    }
+
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            destination: ctx.accounts.admin.to_account_info(),
+            authority: ctx.accounts.market.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    // No bets means no creator fees; anything sent there goes back with the seed
+    let stray_fees = ctx.accounts.creator_fee_escrow.amount;
+    if stray_fees > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_fee_escrow.to_account_info(),
+                    to: ctx.accounts.creator_ata.to_account_info(),
+                    authority: ctx.accounts.market.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            stray_fees,
+        )?;
+    }
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.creator_fee_escrow.to_account_info(),
+            destination: ctx.accounts.admin.to_account_info(),
+            authority: ctx.accounts.market.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    // Mints have zero supply and cannot be closed by SPL Token; with the market
+    // PDA closed by Anchor's `close = admin` constraint nothing can mint again.
+
+    let platform = &mut ctx.accounts.platform_config;
+    platform.active_market_count = platform.active_market_count.saturating_sub(1);
+    platform.debit_tvl(ctx.accounts.market.total_collateral);
+
+    emit!(MarketAborted {
+        market_id,
+        refunded,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, ActionProposal, AdminAction, MAX_APPROVERS};
+use crate::instructions::admin::pause::PlatformAdmin;
+use crate::events::{ActionProposed, ActionApproved};
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.is_approver(&proposer.key()) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [b"proposal", platform_config.proposal_count.to_le_bytes().as_ref()],
+        bump,
+        space = ActionProposal::LEN,
+    )]
+    pub proposal: Account<'info, ActionProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveAction<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.is_approver(&approver.key()) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, ActionProposal>,
+
+    pub approver: Signer<'info>,
+}
+
+/// Sets the M-of-N approver set that sensitive admin actions need once
+/// `threshold` > 0. The admin may only set it while approvals are off; after
+/// that it changes through an approved SetApprovers action.
+pub fn process_set_approvers(ctx: Context<PlatformAdmin>, approvers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let platform = &mut ctx.accounts.platform_config;
+    require!(!platform.approvals_enabled(), PredictError::ApprovalRequired);
+    platform.set_approvers(&approvers, threshold)
+}
+
+/// Opens a proposal for `action`, approved by the proposer
+pub fn process_propose_action(ctx: Context<ProposeAction>, action: AdminAction) -> Result<()> {
+    let platform = &mut ctx.accounts.platform_config;
+    require!(platform.approvals_enabled(), PredictError::ApprovalsDisabled);
+    if let AdminAction::SetApprovers { approvers, threshold } = action {
+        // Reject an unusable set now rather than at execution
+        let mut check = PlatformConfig::clone(platform);
+        check.set_approvers(&approvers, threshold)?;
+    }
+
+    let proposer = ctx.accounts.proposer.key();
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = platform.proposal_count;
+    proposal.action = action;
+    proposal.proposer = proposer;
+    proposal.approved_by = [Pubkey::default(); MAX_APPROVERS];
+    proposal.add_approval(proposer);
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+    platform.proposal_count = platform.proposal_count.checked_add(1).ok_or(PredictError::MathOverflow)?;
+
+    emit!(ActionProposed {
+        proposal_id: proposal.id,
+        proposer: proposal.proposer,
+        action,
+    });
+
+    Ok(())
+}
+
+pub fn process_approve_action(ctx: Context<ApproveAction>, proposal_id: u64) -> Result<()> {
+    let platform = &ctx.accounts.platform_config;
+    let proposal = &mut ctx.accounts.proposal;
+    let approver = ctx.accounts.approver.key();
+    require!(!proposal.executed, PredictError::ActionAlreadyExecuted);
+    require!(!proposal.has_approved(&approver), PredictError::AlreadyApproved);
+
+    // Approvals from keys since removed no longer count; free their slots
+    for key in proposal.approved_by.iter_mut() {
+        if !platform.is_approver(key) {
+            *key = Pubkey::default();
+        }
+    }
+    require!(proposal.add_approval(approver), PredictError::InvalidApprovers);
+
+    emit!(ActionApproved {
+        proposal_id,
+        approver,
+        approvals: platform.approval_count(proposal) as u8,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{PlatformConfig, Role, Market, MarketStatus};
+use crate::events::MarketCancelled;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CancelMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Calls off an unresolved market. Betting stops and the pool is frozen as at
+/// a resolution: share holders get it back pro-rata through claim_refund and
+/// LPs withdraw their part through remove_liquidity.
+pub fn process_cancel_market(ctx: Context<CancelMarket>, market_id: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(
+        matches!(
+            market.status,
+            MarketStatus::Pending | MarketStatus::Active | MarketStatus::Locked | MarketStatus::Resolving | MarketStatus::Paused
+        ),
+        PredictError::AlreadyResolved
+    );
+
+    market.status = MarketStatus::Cancelled;
+    market.resolved_at = Some(clock.unix_timestamp);
+    market.distributable_collateral = market.distributable_at_resolution(ctx.accounts.vault.amount);
+    market.lp_pool = market.lp_pool_at_resolution(ctx.accounts.vault.amount);
+
+    let platform = &mut ctx.accounts.platform_config;
+    platform.active_market_count = platform.active_market_count.saturating_sub(1);
+
+    emit!(MarketCancelled {
+        market_id,
+        cancelled_by: ctx.accounts.admin.key(),
+        refundable: market.distributable_collateral,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::Market;
+use crate::events::CreatorFeesClaimed;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = creator @ PredictError::Unauthorized,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fees", market.key().as_ref()],
+        bump,
+    )]
+    pub creator_fee_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.collateral_mint,
+        token::authority = creator,
+    )]
+    pub creator_ata: Account<'info, TokenAccount>,
+
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays the creator everything accrued in the market's creator fee escrow.
+/// Open at any time, so a creator needn't wait for resolution.
+pub fn process_claim_creator_fees(ctx: Context<ClaimCreatorFees>, market_id: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let amount = ctx.accounts.creator_fee_escrow.amount;
+    require!(amount > 0, PredictError::NoCreatorFees);
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator_fee_escrow.to_account_info(),
+                to: ctx.accounts.creator_ata.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    emit!(CreatorFeesClaimed {
+        market_id,
+        creator: market.creator,
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, Mint, TokenAccount, CloseAccount, close_account};
-use crate::state::{PlatformConfig, Market, MarketStatus};
+use crate::state::{PlatformConfig, Role, Market, MarketStatus};
+use crate::events::MarketClosing;
 use crate::errors::PredictError;
 
 #[derive(Accounts)]
@@ -36,9 +37,17 @@ pub struct CloseMarket<'info> {
     pub vault: Account<'info, TokenAccount>,
 
     #[account(
+        mut,
+        seeds = [b"creator_fees", market.key().as_ref()],
+        bump,
+    )]
+    pub creator_fee_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
         seeds = [b"platform_config"],
         bump = platform_config.bump,
-        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
     )]
     pub platform_config: Account<'info, PlatformConfig>,
 
@@ -58,18 +67,48 @@ pub fn process_close_market(ctx: Context<CloseMarket>, _market_id: u64) -> Resul
         PredictError::MarketNotCloseable
     );
 
+    // Audit record of the final state. Emitted ahead of the checks so a rejected
+    // close still logs which supply/balance blocked it; on success these are all
+    // zero and any share token left behind would be visible here.
+    emit!(MarketClosing {
+        market_id: market.market_id,
+        yes_supply: ctx.accounts.yes_mint.supply,
+        no_supply: ctx.accounts.no_mint.supply,
+        vault_balance: ctx.accounts.vault.amount,
+    });
+
     // Safety check: vault must be empty (all payouts claimed)
     require!(
         ctx.accounts.vault.amount == 0,
         PredictError::OutstandingPositions
     );
 
-    // Safety check: all share tokens must be burned (no outstanding positions)
+    // The creator's fees would be stranded once the market PDA is gone
+    require!(
+        ctx.accounts.creator_fee_escrow.amount == 0,
+        PredictError::CreatorFeesUnclaimed
+    );
+
+    // Safety check: all share tokens must be burned (no outstanding positions),
+    // unless whatever is left was forfeited as dust
     require!(
-        ctx.accounts.yes_mint.supply == 0 && ctx.accounts.no_mint.supply == 0,
+        market.dust_forfeited || (ctx.accounts.yes_mint.supply == 0 && ctx.accounts.no_mint.supply == 0),
         PredictError::OutstandingPositions
     );
 
+    // Two-step close: marked, and the grace window for cancel_close has passed
+    let grace = ctx.accounts.platform_config.close_grace_secs;
+    if grace > 0 {
+        require!(market.close_requested_at != 0, PredictError::CloseNotRequested);
+        require!(
+            Clock::get()?.unix_timestamp >= market.close_requested_at.saturating_add(grace),
+            PredictError::CloseGraceActive
+        );
+    }
+
+    // Rounding residue still tracked in the pool leaves with the market
+    ctx.accounts.platform_config.debit_tvl(market.total_collateral);
+
     let market_key = market.key();
     let market_id_bytes = market.market_id.to_le_bytes();
 
@@ -92,27 +131,19 @@ pub fn process_close_market(ctx: Context<CloseMarket>, _market_id: u64) -> Resul
         signer_seeds,
     ))?;
 
-    // Close YES mint account
     close_account(CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         CloseAccount {
-            account: ctx.accounts.yes_mint.to_account_info(),
+            account: ctx.accounts.creator_fee_escrow.to_account_info(),
             destination: ctx.accounts.admin.to_account_info(),
             authority: ctx.accounts.market.to_account_info(),
         },
         signer_seeds,
     ))?;
 
-    // Close NO mint account
-    close_account(CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        CloseAccount {
-            account: ctx.accounts.no_mint.to_account_info(),
-            destination: ctx.accounts.admin.to_account_info(),
-            authority: ctx.accounts.market.to_account_info(),
-        },
-        signer_seeds,
-    ))?;
+    // YES/NO mints stay behind: the SPL Token program cannot close mint
+    // accounts. Their supply is zero and their authority (this market PDA) is
+    // closed below, so no further shares can ever be minted.
 
     // Market account is closed by Anchor's `close = admin` constraint
 
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, CloseAccount, close_account};
+use crate::state::{PlatformConfig, Role, Market, RoundState, RoundStatus};
+use crate::events::RoundClosed;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64, round_id: u64)]
+pub struct CloseRound<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump,
+        close = admin,
+    )]
+    pub round_state: Account<'info, RoundState>,
+
+    /// CHECK: The round's stake vault. Only created by the round's first bet, so
+    /// may be uninitialized; otherwise deserialized in the handler.
+    #[account(
+        mut,
+        seeds = [b"round_vault", round_state.key().as_ref()],
+        bump,
+    )]
+    pub round_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == platform_config.treasury,
+        constraint = treasury.mint == market.collateral_mint @ PredictError::InvalidMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Closes a settled round once its claim window has passed, reclaiming the rent
+/// of its `RoundState` and stake vault. Stakes still unclaimed by then (and the
+/// pro-rata rounding residue) go to the treasury.
+pub fn process_close_round(ctx: Context<CloseRound>, market_id: u64, round_id: u64) -> Result<()> {
+    let round = &ctx.accounts.round_state;
+    require!(
+        round.status == RoundStatus::Resolved || round.status == RoundStatus::Cancelled,
+        PredictError::RoundNotSettled
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= round.end_ts.saturating_add(ctx.accounts.platform_config.claim_window_secs),
+        PredictError::ClaimWindowOpen
+    );
+
+    let mut swept = 0;
+    if !ctx.accounts.round_vault.data_is_empty() {
+        swept = TokenAccount::try_deserialize(&mut &ctx.accounts.round_vault.data.borrow()[..])?.amount;
+
+        // Round PDA is the authority for its vault
+        let market_key = ctx.accounts.market.key();
+        let round_bytes = round_id.to_le_bytes();
+        let seeds = &[
+            b"round",
+            market_key.as_ref(),
+            round_bytes.as_ref(),
+            &[round.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        if swept > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.round_vault.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                        authority: ctx.accounts.round_state.to_account_info(),
+                    },
+                    signer,
+                ),
+                swept,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.round_vault.to_account_info(),
+                destination: ctx.accounts.admin.to_account_info(),
+                authority: ctx.accounts.round_state.to_account_info(),
+            },
+            signer,
+        ))?;
+    }
+
+    emit!(RoundClosed {
+        market_id,
+        round_id,
+        swept,
+    });
+
+    // Round account is closed by Anchor's `close = admin` constraint
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, ResolutionCommittee};
+use crate::events::ResolutionCommitteeSet;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+pub struct SetResolutionCommittee<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"resolution_committee"],
+        bump,
+        space = ResolutionCommittee::LEN,
+    )]
+    pub committee: Account<'info, ResolutionCommittee>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the committee that resolves `committee_resolution` markets. Attestations
+/// already cast by removed members stop counting.
+pub fn process_set_resolution_committee(
+    ctx: Context<SetResolutionCommittee>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let committee = &mut ctx.accounts.committee;
+    committee.set_members(&members, threshold)?;
+    committee.bump = ctx.bumps.committee;
+
+    emit!(ResolutionCommitteeSet {
+        members: committee.members.iter().copied().filter(|k| *k != Pubkey::default()).collect(),
+        threshold,
+    });
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, Mint, TokenAccount, Transfer};
-use crate::state::{PlatformConfig, Market, MarketCategory, MarketStatus, OracleSource, Outcome};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{PlatformConfig, Role, CreatorState, CategoryPolicy, DustRecipient, FeedRegistry, FeeLedger, Market, MarketCategory, MarketStatus, OracleCache, OracleSource, Outcome, PricingMode, PriceCheckpoint, ProgramStateQuery, ResolutionProof, ScalarRange, MAX_MARKET_ADMINS, MIN_INITIAL_PROB_BPS, PRICE_HISTORY_LEN};
 use crate::events::MarketCreated;
 use crate::errors::PredictError;
+use crate::utils::{read_pyth_price, rescale_exponent, threshold_distance_bps};
 
 #[derive(Accounts)]
 #[instruction(market_id: u64)] // market_id is passed as instruction arg to derive seeds
@@ -14,7 +16,7 @@ pub struct CreateMarket<'info> {
         payer = admin,
         space = Market::LEN
     )]
-    pub market: Account<'info, Market>,
+    pub market: Box<Account<'info, Market>>,
 
     #[account(
         init,
@@ -46,18 +48,37 @@ pub struct CreateMarket<'info> {
     )]
     pub vault: Account<'info, TokenAccount>,
 
+    #[account(
+        init,
+        seeds = [b"creator_fees", market.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = collateral_mint,
+        token::authority = market,
+    )]
+    pub creator_fee_escrow: Box<Account<'info, TokenAccount>>,
+
     #[account(
         mut,
         seeds = [b"platform_config"],
         bump = platform_config.bump,
         has_one = collateral_mint,
-        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
     )]
     pub platform_config: Account<'info, PlatformConfig>,
 
     #[account(mut)]
     pub admin: Signer<'info>,
 
+    #[account(
+        init_if_needed,
+        seeds = [b"creator", admin.key().as_ref()],
+        bump,
+        payer = admin,
+        space = CreatorState::LEN
+    )]
+    pub creator_state: Account<'info, CreatorState>,
+
     /// Admin's collateral token account (wSOL ATA) for depositing initial liquidity
     #[account(
         mut,
@@ -67,6 +88,26 @@ pub struct CreateMarket<'info> {
     pub admin_ata: Account<'info, TokenAccount>,
 
     pub collateral_mint: Account<'info, Mint>,
+
+    /// CHECK: Category/oracle policy singleton. May be uninitialized, in which
+    /// case every pairing is allowed; otherwise deserialized in the handler.
+    #[account(
+        seeds = [b"category_policy"],
+        bump,
+    )]
+    pub category_policy: UncheckedAccount<'info>,
+
+    /// CHECK: Feed allowlist singleton. May be uninitialized, in which case any
+    /// feed is accepted; otherwise deserialized in the handler.
+    #[account(
+        seeds = [b"feed_registry"],
+        bump,
+    )]
+    pub feed_registry: UncheckedAccount<'info>,
+
+    /// Live Pyth feed (optional - only needed for Pyth markets when the
+    /// platform enforces a minimum threshold distance)
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
     
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -90,6 +131,350 @@ pub struct CreateMarketParams {
     pub round_duration: Option<i64>,
     pub fee_bps: u16,
     pub initial_liquidity: u64,
+    pub boundary_band: Option<u64>,
+    pub default_outcome_on_timeout: Option<Outcome>,
+    pub program_state_query: Option<ProgramStateQuery>,
+    pub early_bird_bonus_bps: Option<u16>,
+    pub early_bird_window_secs: Option<i64>,
+    pub early_bird_budget: Option<u64>,
+    pub market_admins: Option<Vec<Pubkey>>,
+    pub max_bet_pool_fraction_bps: Option<u16>,
+    pub confidence_split: Option<bool>,
+    pub both_sides_win: Option<bool>,
+    pub oracle_exponent: Option<i32>,
+    pub dust_recipient: Option<DustRecipient>,
+    pub end_slot: Option<u64>,
+    pub end_epoch: Option<u64>,
+    pub slot_start_pricing: Option<bool>,
+    pub creator_betting_restricted: Option<bool>,
+    pub committee_resolution: Option<bool>,
+    pub twap_window_secs: Option<i64>,
+    /// Scalar market bounds, in the threshold's exponent (oracle_threshold unused)
+    pub scalar_range: Option<ScalarRange>,
+    /// Range market: upper edge of the YES range, oracle_threshold being the lower
+    pub oracle_upper_threshold: Option<i64>,
+    /// One-touch market: resolves YES once check_barrier sees the threshold crossed
+    pub barrier: Option<bool>,
+    /// Defaults to Cpmm
+    pub pricing_mode: Option<PricingMode>,
+    /// YES probability the pools open at, in bps (defaults to 5000)
+    pub initial_prob_bps: Option<u16>,
+    /// Added to both reserves when pricing only, to dampen price impact
+    pub virtual_liquidity: Option<u64>,
+    /// Fee at lock; the fee ramps up to it from `fee_bps` (set with `fee_ramp_secs`)
+    pub max_fee_bps: Option<u16>,
+    /// Seconds before lock over which the fee ramps
+    pub fee_ramp_secs: Option<i64>,
+    /// Part of each bet (bps, out of `fee_bps`) paid to the creator's fee escrow
+    pub creator_fee_bps: Option<u16>,
+    /// Share (bps) of each trade fee kept in the pool for liquidity providers
+    pub lp_fee_bps: Option<u16>,
+}
+
+/// A problem `create_market` would reject the params for. Returned in full by
+/// `validate_market_params`; `create_market` fails with the first.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ValidationError {
+    Unauthorized,
+    PlatformPaused,
+    TitleTooLong,
+    DescriptionTooLong,
+    InvalidTimestamps,
+    StartTooFarInPast,
+    FeeExceedsMax,
+    InsufficientLiquidity,
+    InvalidProgramStateQuery,
+    ConfidenceSplitRequiresPyth,
+    InvalidDefaultOutcome,
+    InvalidDustRecipient,
+    TooManyMarketAdmins,
+    InvalidEarlyBirdConfig,
+    ActiveMarketLimitReached,
+    OracleSourceNotAllowedForCategory,
+    CreationCooldownActive,
+    MissingPythFeed,
+    InvalidPythFeed,
+    OracleStale,
+    ThresholdOutOfRange,
+    ThresholdTooClose,
+    SlotEndOnRecurring,
+    InvalidSwitchboardFeed,
+    InvalidChainlinkFeed,
+    CommitteeRequiresManualAdmin,
+    MissingAttester,
+    TwapRequiresPyth,
+    FeedNotRegistered,
+    PriceFeedRequired,
+    InvalidScalarRange,
+    InvalidRangeThresholds,
+    InvalidBarrier,
+    InvalidInitialProbability,
+    InvalidFeeRamp,
+    CreatorFeeExceedsFee,
+}
+
+impl From<ValidationError> for PredictError {
+    fn from(problem: ValidationError) -> Self {
+        match problem {
+            ValidationError::Unauthorized => PredictError::Unauthorized,
+            ValidationError::PlatformPaused => PredictError::PlatformPaused,
+            ValidationError::TitleTooLong => PredictError::TitleTooLong,
+            ValidationError::DescriptionTooLong => PredictError::DescriptionTooLong,
+            ValidationError::InvalidTimestamps
+            | ValidationError::StartTooFarInPast
+            | ValidationError::SlotEndOnRecurring => PredictError::InvalidTimestamps,
+            ValidationError::FeeExceedsMax | ValidationError::CreatorFeeExceedsFee => PredictError::FeeExceedsMax,
+            ValidationError::InsufficientLiquidity => PredictError::InsufficientLiquidity,
+            ValidationError::InvalidProgramStateQuery => PredictError::InvalidProgramStateQuery,
+            ValidationError::ConfidenceSplitRequiresPyth
+            | ValidationError::MissingPythFeed
+            | ValidationError::CommitteeRequiresManualAdmin
+            | ValidationError::TwapRequiresPyth
+            | ValidationError::PriceFeedRequired
+            | ValidationError::InvalidBarrier => PredictError::OracleMismatch,
+            ValidationError::InvalidDefaultOutcome => PredictError::InvalidOutcome,
+            ValidationError::InvalidDustRecipient => PredictError::InvalidDustRecipient,
+            ValidationError::TooManyMarketAdmins => PredictError::TooManyMarketAdmins,
+            ValidationError::InvalidEarlyBirdConfig => PredictError::InvalidEarlyBirdConfig,
+            ValidationError::ActiveMarketLimitReached => PredictError::ActiveMarketLimitReached,
+            ValidationError::OracleSourceNotAllowedForCategory => PredictError::OracleSourceNotAllowedForCategory,
+            ValidationError::CreationCooldownActive => PredictError::CreationCooldownActive,
+            ValidationError::InvalidPythFeed => PredictError::InvalidPythFeed,
+            ValidationError::OracleStale => PredictError::OracleStale,
+            ValidationError::ThresholdOutOfRange => PredictError::MathOverflow,
+            ValidationError::ThresholdTooClose => PredictError::ThresholdTooClose,
+            ValidationError::InvalidSwitchboardFeed => PredictError::InvalidSwitchboardFeed,
+            ValidationError::InvalidChainlinkFeed => PredictError::InvalidChainlinkFeed,
+            ValidationError::MissingAttester => PredictError::InvalidAttestation,
+            ValidationError::FeedNotRegistered => PredictError::FeedNotRegistered,
+            ValidationError::InvalidScalarRange => PredictError::InvalidScalarRange,
+            ValidationError::InvalidRangeThresholds => PredictError::InvalidRangeThresholds,
+            ValidationError::InvalidInitialProbability => PredictError::InvalidInitialProbability,
+            ValidationError::InvalidFeeRamp => PredictError::InvalidFeeRamp,
+        }
+    }
+}
+
+/// Platform and creator state a new market is validated against
+pub struct MarketParamsEnv<'a, 'info> {
+    pub platform: &'a PlatformConfig,
+    pub creator: Pubkey,
+    /// None when the creator has never created a market
+    pub creator_state: Option<&'a CreatorState>,
+    /// None until the admin sets a category policy
+    pub category_policy: Option<&'a CategoryPolicy>,
+    /// None until the admin registers a feed
+    pub feed_registry: Option<&'a FeedRegistry>,
+    pub pyth_price_feed: Option<&'a Account<'info, PriceUpdateV2>>,
+    pub now: i64,
+}
+
+/// Every check `create_market` runs on its params, in the order it runs them
+pub fn market_param_problems(params: &CreateMarketParams, env: &MarketParamsEnv) -> Vec<ValidationError> {
+    let platform = env.platform;
+    let mut problems = Vec::new();
+    let mut check = |ok: bool, problem: ValidationError| {
+        if !ok {
+            problems.push(problem);
+        }
+    };
+
+    check(platform.has_role(&env.creator, Role::Operator), ValidationError::Unauthorized);
+    check(!platform.paused, ValidationError::PlatformPaused);
+    check(params.title.len() <= 128, ValidationError::TitleTooLong);
+    check(params.description.len() <= 512, ValidationError::DescriptionTooLong);
+    check(
+        params.start_timestamp < params.lock_timestamp && params.lock_timestamp < params.end_timestamp,
+        ValidationError::InvalidTimestamps,
+    );
+    // A start far in the past would show the market as Active for a period it never was
+    if platform.max_backdate_secs > 0 {
+        check(
+            params.start_timestamp >= env.now.saturating_sub(platform.max_backdate_secs),
+            ValidationError::StartTooFarInPast,
+        );
+    }
+    // Rounds advance by round_duration seconds, which can't move a slot/epoch end
+    if params.end_slot.is_some() || params.end_epoch.is_some() {
+        check(!params.is_recurring, ValidationError::SlotEndOnRecurring);
+    }
+    check(params.fee_bps <= 1000, ValidationError::FeeExceedsMax);
+    check(params.creator_fee_bps.unwrap_or(0) <= params.fee_bps, ValidationError::CreatorFeeExceedsFee);
+    check(params.lp_fee_bps.unwrap_or(0) <= 10000, ValidationError::FeeExceedsMax);
+    check(params.initial_liquidity > 0, ValidationError::InsufficientLiquidity);
+
+    // ProgramState markets need a query (and only they may carry one)
+    let query_ok = match (params.oracle_source, params.program_state_query) {
+        (OracleSource::ProgramState, Some(query)) => {
+            matches!(query.size, 1 | 2 | 4 | 8) && params.oracle_feed != Pubkey::default()
+        }
+        (OracleSource::ProgramState, None) | (_, Some(_)) => false,
+        _ => true,
+    };
+    check(query_ok, ValidationError::InvalidProgramStateQuery);
+    match params.oracle_source {
+        OracleSource::Switchboard => check(params.oracle_feed != Pubkey::default(), ValidationError::InvalidSwitchboardFeed),
+        OracleSource::Chainlink => check(params.oracle_feed != Pubkey::default(), ValidationError::InvalidChainlinkFeed),
+        // oracle_feed holds the attester's key
+        OracleSource::SignedAttestation => check(params.oracle_feed != Pubkey::default(), ValidationError::MissingAttester),
+        _ => {}
+    }
+
+    // Split needs a ratio, which only a Pyth confidence band provides
+    if params.confidence_split.unwrap_or(false) {
+        check(params.oracle_source == OracleSource::Pyth, ValidationError::ConfidenceSplitRequiresPyth);
+    }
+    // The committee attests outcomes; a feed would make it redundant
+    if params.committee_resolution.unwrap_or(false) {
+        check(params.oracle_source == OracleSource::ManualAdmin, ValidationError::CommitteeRequiresManualAdmin);
+    }
+    // Snapshots are Pyth prints, taken within the market's lifetime
+    if let Some(window) = params.twap_window_secs.filter(|w| *w != 0) {
+        check(params.oracle_source == OracleSource::Pyth, ValidationError::TwapRequiresPyth);
+        check(
+            window > 0 && window <= params.end_timestamp.saturating_sub(params.start_timestamp),
+            ValidationError::InvalidTimestamps,
+        );
+    }
+    // The range is mapped from a price; a confidence split would be a second ratio
+    if let Some(range) = params.scalar_range {
+        check(
+            matches!(params.oracle_source, OracleSource::Pyth | OracleSource::Switchboard | OracleSource::Chainlink),
+            ValidationError::PriceFeedRequired,
+        );
+        check(
+            range.lower < range.upper && !params.confidence_split.unwrap_or(false),
+            ValidationError::InvalidScalarRange,
+        );
+    }
+    // Two edges of one price: a single-ratio split or a scalar range can't apply
+    if let Some(upper) = params.oracle_upper_threshold {
+        check(
+            matches!(params.oracle_source, OracleSource::Pyth | OracleSource::Switchboard | OracleSource::Chainlink),
+            ValidationError::PriceFeedRequired,
+        );
+        check(
+            params.oracle_threshold < upper
+                && params.scalar_range.is_none()
+                && !params.confidence_split.unwrap_or(false),
+            ValidationError::InvalidRangeThresholds,
+        );
+    }
+    // Touched or not is a plain comparison against a live Pyth print
+    if params.barrier.unwrap_or(false) {
+        check(
+            params.oracle_source == OracleSource::Pyth
+                && !params.confidence_split.unwrap_or(false)
+                && params.scalar_range.is_none()
+                && params.oracle_upper_threshold.is_none()
+                && params.twap_window_secs.unwrap_or(0) == 0,
+            ValidationError::InvalidBarrier,
+        );
+    }
+    check(params.default_outcome_on_timeout != Some(Outcome::Split), ValidationError::InvalidDefaultOutcome);
+    // Neither pool may start (nearly) empty
+    check(
+        params.initial_prob_bps.is_none_or(|p| (MIN_INITIAL_PROB_BPS..=10000 - MIN_INITIAL_PROB_BPS).contains(&p)),
+        ValidationError::InvalidInitialProbability,
+    );
+    // A ramp needs both ends, and only ever raises the fee
+    let ramp_ok = match (params.max_fee_bps, params.fee_ramp_secs) {
+        (None, None) => true,
+        (Some(max), Some(secs)) => max >= params.fee_bps && max <= 1000 && secs > 0,
+        _ => false,
+    };
+    check(ramp_ok, ValidationError::InvalidFeeRamp);
+
+    // Dust left in the vault of a one-off market would keep it from ever closing
+    if params.dust_recipient == Some(DustRecipient::Vault) {
+        check(params.is_recurring, ValidationError::InvalidDustRecipient);
+    }
+
+    check(
+        params.market_admins.as_ref().is_none_or(|admins| admins.len() <= MAX_MARKET_ADMINS),
+        ValidationError::TooManyMarketAdmins,
+    );
+
+    // Early-bird bonus: funded up front by the creator, never by other bettors
+    let early_bird_bonus_bps = params.early_bird_bonus_bps.unwrap_or(0);
+    if early_bird_bonus_bps > 0 {
+        check(
+            early_bird_bonus_bps <= 10000
+                && params.early_bird_window_secs.unwrap_or(0) > 0
+                && params.early_bird_budget.unwrap_or(0) > 0,
+            ValidationError::InvalidEarlyBirdConfig,
+        );
+    }
+
+    // Operator throttle on concurrently open markets
+    if platform.max_active_markets > 0 {
+        check(
+            platform.active_market_count < platform.max_active_markets,
+            ValidationError::ActiveMarketLimitReached,
+        );
+    }
+
+    // Enforce sensible category/oracle pairings once the admin has set a policy
+    if let Some(policy) = env.category_policy {
+        check(
+            policy.allows(params.category, params.oracle_source),
+            ValidationError::OracleSourceNotAllowedForCategory,
+        );
+    }
+
+    // Price feeds must be approved once the admin has started a registry
+    if let Some(registry) = env.feed_registry.filter(|r| r.is_enforced()) {
+        if matches!(params.oracle_source, OracleSource::Pyth | OracleSource::Switchboard | OracleSource::Chainlink) {
+            check(
+                registry.find(&params.oracle_feed, params.oracle_source).is_some(),
+                ValidationError::FeedNotRegistered,
+            );
+        }
+    }
+
+    // Throttle rapid successive creations by the same creator
+    if let Some(state) = env.creator_state {
+        if platform.creation_cooldown_secs > 0 && state.markets_created > 0 {
+            check(
+                env.now >= state.last_created_at.saturating_add(platform.creation_cooldown_secs),
+                ValidationError::CreationCooldownActive,
+            );
+        }
+    }
+
+    // Reject degenerate threshold markets whose outcome is already decided
+    if params.oracle_source == OracleSource::Pyth
+        && params.scalar_range.is_none()
+        && platform.min_threshold_distance_bps > 0
+    {
+        match env.pyth_price_feed {
+            None => check(false, ValidationError::MissingPythFeed),
+            Some(feed) if feed.key() != params.oracle_feed => check(false, ValidationError::InvalidPythFeed),
+            Some(feed) => match read_pyth_price(feed, env.now) {
+                Err(_) => check(false, ValidationError::OracleStale),
+                Ok(current_price) => {
+                    let declared_exponent = params.oracle_exponent
+                        .or_else(|| env.feed_registry.and_then(|r| r.exponent_of(&params.oracle_feed, params.oracle_source)));
+                    let current_price = match declared_exponent {
+                        Some(exponent) => rescale_exponent(current_price as i128, feed.price_message.exponent, exponent)
+                            .and_then(|p| i64::try_from(p).ok()),
+                        None => Some(current_price),
+                    };
+                    match current_price {
+                        None => check(false, ValidationError::ThresholdOutOfRange),
+                        // Both edges of a range market
+                        Some(price) => check(
+                            [Some(params.oracle_threshold), params.oracle_upper_threshold].into_iter().flatten()
+                                .all(|t| threshold_distance_bps(price, t) >= platform.min_threshold_distance_bps as u64),
+                            ValidationError::ThresholdTooClose,
+                        ),
+                    }
+                }
+            },
+        }
+    }
+
+    problems
 }
 
 pub fn process_create_market(
@@ -101,16 +486,43 @@ pub fn process_create_market(
     let market = &mut ctx.accounts.market;
     let clock = Clock::get()?;
 
-    // Validation
-    require!(!platform.paused, PredictError::PlatformPaused);
-    require!(params.title.len() <= 128, PredictError::TitleTooLong);
-    require!(params.description.len() <= 512, PredictError::DescriptionTooLong);
-    require!(
-        params.start_timestamp < params.lock_timestamp && params.lock_timestamp < params.end_timestamp,
-        PredictError::InvalidTimestamps
-    );
-    require!(params.fee_bps <= 1000, PredictError::FeeExceedsMax);
-    require!(params.initial_liquidity > 0, PredictError::InsufficientLiquidity);
+    // Validation (shared with validate_market_params)
+    let policy = if ctx.accounts.category_policy.data_is_empty() {
+        None
+    } else {
+        Some(CategoryPolicy::try_deserialize(&mut &ctx.accounts.category_policy.data.borrow()[..])?)
+    };
+    let registry = if ctx.accounts.feed_registry.data_is_empty() {
+        None
+    } else {
+        Some(Box::new(FeedRegistry::try_deserialize(&mut &ctx.accounts.feed_registry.data.borrow()[..])?))
+    };
+    let problems = market_param_problems(&params, &MarketParamsEnv {
+        platform,
+        creator: ctx.accounts.admin.key(),
+        creator_state: Some(&ctx.accounts.creator_state),
+        category_policy: policy.as_ref(),
+        feed_registry: registry.as_deref(),
+        pyth_price_feed: ctx.accounts.pyth_price_feed.as_ref(),
+        now: clock.unix_timestamp,
+    });
+    if let Some(problem) = problems.first() {
+        return Err(PredictError::from(*problem).into());
+    }
+
+    let confidence_split = params.confidence_split.unwrap_or(false);
+    let dust_recipient = params.dust_recipient.unwrap_or_default();
+    let market_admins = params.market_admins.clone().unwrap_or_default();
+    let early_bird_bonus_bps = params.early_bird_bonus_bps.unwrap_or(0);
+    let early_bird_window_secs = params.early_bird_window_secs.unwrap_or(0);
+    let early_bird_budget = params.early_bird_budget.unwrap_or(0);
+
+    // Throttle rapid successive creations by the same creator (checked above)
+    let creator_state = &mut ctx.accounts.creator_state;
+    creator_state.creator = ctx.accounts.admin.key();
+    creator_state.last_created_at = clock.unix_timestamp;
+    creator_state.markets_created = creator_state.markets_created.checked_add(1).ok_or(PredictError::MathOverflow)?;
+    creator_state.bump = ctx.bumps.creator_state;
 
     // Transfer initial liquidity from admin to vault (seeds CPMM pools)
     token::transfer(
@@ -125,6 +537,21 @@ pub fn process_create_market(
         params.initial_liquidity,
     )?;
 
+    // Early-bird budget sits in the vault, outside total_collateral until spent
+    if early_bird_bonus_bps > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin_ata.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.admin.to_account_info(),
+                },
+            ),
+            early_bird_budget,
+        )?;
+    }
+
     // Initialize Market
     market.market_id = market_id;
     market.creator = ctx.accounts.admin.key(); // Admin is creator for now
@@ -140,10 +567,16 @@ pub fn process_create_market(
     market.yes_mint = ctx.accounts.yes_mint.key();
     market.no_mint = ctx.accounts.no_mint.key();
     market.vault = ctx.accounts.vault.key();
-    // CPMM: seed equal YES/NO pools so k = initial_liquidity^2
-    market.total_yes_shares = params.initial_liquidity;
-    market.total_no_shares = params.initial_liquidity;
+    // CPMM: seed the YES/NO pools at the opening prior (equal pools by default)
+    market.initial_prob_bps = params.initial_prob_bps.unwrap_or(5000);
+    market.seed_reserves(params.initial_liquidity);
+    market.virtual_liquidity = params.virtual_liquidity.unwrap_or(0);
+    market.max_fee_bps = params.max_fee_bps.unwrap_or(params.fee_bps);
+    market.fee_ramp_secs = params.fee_ramp_secs.unwrap_or(0);
+    market.creator_fee_bps = params.creator_fee_bps.unwrap_or(0);
+    market.lp_fee_bps = params.lp_fee_bps.unwrap_or(0);
     market.total_collateral = params.initial_liquidity;
+    market.initial_liquidity = params.initial_liquidity;
     market.oracle_source = params.oracle_source;
     market.oracle_feed = params.oracle_feed;
     market.oracle_threshold = params.oracle_threshold;
@@ -159,6 +592,67 @@ pub fn process_create_market(
     market.round_duration = params.round_duration;
     market.current_round = 0;
     market.bump = ctx.bumps.market;
+    market.payout_mint = None; // same-mint by default, see set_payout_mint
+    market.payout_rate = 0;
+    market.boundary_band = params.boundary_band.unwrap_or(0);
+    market.resolution_conf = 0;
+    market.default_outcome_on_timeout = params.default_outcome_on_timeout;
+    market.program_state_query = params.program_state_query;
+    market.total_deposited = 0;
+    market.oracle_cache = OracleCache::default();
+    market.early_bird_bonus_bps = early_bird_bonus_bps;
+    market.early_bird_window_secs = early_bird_window_secs;
+    market.market_admins = market_admins;
+    market.distributable_collateral = 0;
+    market.resolution_proof = ResolutionProof::default();
+    market.max_bet_pool_fraction_bps = params.max_bet_pool_fraction_bps.unwrap_or(0);
+    market.confidence_split = confidence_split;
+    market.split_yes_bps = 0;
+    market.split_yes_pool = 0;
+    market.last_bet_slot = 0;
+    market.both_sides_win = params.both_sides_win.unwrap_or(false);
+    market.locked_yes_reserve = 0;
+    market.locked_no_reserve = 0;
+    market.unique_bettors = 0;
+    market.price_history = [PriceCheckpoint::default(); PRICE_HISTORY_LEN];
+    market.price_history_head = 0;
+    // Threshold's exponent: as declared, else the registered one, else the feed's current one
+    let declared_exponent = params.oracle_exponent
+        .or_else(|| registry.as_ref().and_then(|r| r.exponent_of(&params.oracle_feed, params.oracle_source)));
+    market.oracle_exponent = if params.oracle_source == OracleSource::Pyth {
+        let feed_exponent = ctx.accounts.pyth_price_feed.as_ref()
+            .filter(|feed| feed.key() == params.oracle_feed)
+            .map(|feed| feed.price_message.exponent);
+        declared_exponent.or(feed_exponent)
+    } else if matches!(params.oracle_source, OracleSource::Switchboard | OracleSource::Chainlink) {
+        declared_exponent
+    } else {
+        None
+    };
+    market.early_bird_budget = if early_bird_bonus_bps > 0 { early_bird_budget } else { 0 };
+    market.dust_recipient = dust_recipient;
+    market.pending_dust = 0;
+    market.claimed_payout = 0;
+    market.claimed_weight = 0;
+    market.fee_ledger = FeeLedger::default();
+    market.end_slot = params.end_slot;
+    market.end_epoch = params.end_epoch;
+    market.slot_start_pricing = params.slot_start_pricing.unwrap_or(false);
+    market.slot_start_reserves = [0; 2];
+    market.slot_start_slot = 0;
+    market.slot_flow = [0; 2];
+    market.close_requested_at = 0;
+    market.creator_betting_restricted = params.creator_betting_restricted.unwrap_or(false);
+    market.lp_mint = Pubkey::default();
+    market.lp_liquidity = 0;
+    market.lp_pool = 0;
+    market.lp_fees_accrued = 0;
+    market.committee_resolution = params.committee_resolution.unwrap_or(false);
+    market.twap_window_secs = params.twap_window_secs.unwrap_or(0);
+    market.scalar_range = params.scalar_range;
+    market.oracle_upper_threshold = params.oracle_upper_threshold;
+    market.barrier = params.barrier.unwrap_or(false);
+    market.pricing_mode = params.pricing_mode.unwrap_or_default();
 
     // Update Platform Config (increment total markets)
     // NOTE: In a real scenario, we might want to use the platform.total_markets as the market_id 
@@ -168,6 +662,8 @@ pub fn process_create_market(
     // but that makes client-side PDA derivation harder (requires fetching count first).
     // For now, we update the counter to track usage.
     platform.total_markets = platform.total_markets.checked_add(1).ok_or(PredictError::MathOverflow)?;
+    platform.active_market_count = platform.active_market_count.checked_add(1).ok_or(PredictError::MathOverflow)?;
+    platform.credit_tvl(params.initial_liquidity)?;
 
     emit!(MarketCreated {
         market_id,
@@ -175,6 +671,7 @@ pub fn process_create_market(
         title: market.title.clone(),
         oracle_source: market.oracle_source,
         end_timestamp: market.end_timestamp,
+        collateral_decimals: ctx.accounts.collateral_mint.decimals,
     });
 
     Ok(())
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::PlatformConfig;
+use crate::errors::PredictError;
+use super::pause::PlatformAdmin;
+
+/// Platform-wide backstop: pauses the platform and lets every holder of an
+/// unresolved market recover a pro-rata share of its pool via
+/// `claim_emergency_refund`. Stays in force until `resume_from_shutdown`.
+pub fn emergency_shutdown(ctx: Context<PlatformAdmin>) -> Result<()> {
+    // With approvals enabled this goes through execute_action
+    require!(!ctx.accounts.platform_config.approvals_enabled(), PredictError::ApprovalRequired);
+    apply_emergency_shutdown(&mut ctx.accounts.platform_config)
+}
+
+/// Lifts an emergency shutdown. The platform stays paused until unpaused explicitly.
+pub fn resume_from_shutdown(ctx: Context<PlatformAdmin>) -> Result<()> {
+    // With approvals enabled this goes through execute_action
+    require!(!ctx.accounts.platform_config.approvals_enabled(), PredictError::ApprovalRequired);
+    apply_resume_from_shutdown(&mut ctx.accounts.platform_config)
+}
+
+/// Shared by emergency_shutdown and execute_action
+pub fn apply_emergency_shutdown(platform: &mut PlatformConfig) -> Result<()> {
+    require!(!platform.shutdown, PredictError::PlatformShutdown);
+    platform.shutdown = true;
+    platform.paused = true;
+    msg!("Emergency shutdown: refunds open for all unresolved markets");
+    Ok(())
+}
+
+/// Shared by resume_from_shutdown and execute_action
+pub fn apply_resume_from_shutdown(platform: &mut PlatformConfig) -> Result<()> {
+    require!(platform.shutdown, PredictError::PlatformNotShutdown);
+    platform.shutdown = false;
+    msg!("Emergency shutdown lifted");
+    Ok(())
+}
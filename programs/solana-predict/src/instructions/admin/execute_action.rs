@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{PlatformConfig, Market, DisputeRecord, ActionProposal, AdminAction};
+use crate::instructions::admin::update_fees::transfer_from_treasury;
+use crate::instructions::admin::emergency_shutdown::{apply_emergency_shutdown, apply_resume_from_shutdown};
+use crate::instructions::dispute::settle_dispute::apply_dispute_settlement;
+use crate::events::ActionExecuted;
+use crate::errors::PredictError;
+
+/// Runs an approved proposal. Only the accounts its action needs are passed:
+/// - UpdateCollateralMint: `new_collateral_mint`, `new_treasury`
+/// - SettleDispute: `market`, `dispute_record`, `disputer`, `platform_admin`
+/// - WithdrawFees: `treasury`, `destination`
+/// - SetApprovers, EmergencyShutdown, ResumeFromShutdown: none
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.is_approver(&executor.key()) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, ActionProposal>,
+
+    pub new_collateral_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = executor,
+        seeds = [b"treasury", proposal.action.collateral_mint().as_ref()],
+        bump,
+        token::mint = new_collateral_mint,
+        token::authority = platform_config,
+    )]
+    pub new_treasury: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub market: Option<Account<'info, Market>>,
+
+    #[account(mut)]
+    pub dispute_record: Option<Account<'info, DisputeRecord>>,
+
+    /// CHECK: Must be the dispute's disputer, checked in the instruction logic
+    #[account(mut)]
+    pub disputer: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Must be the platform admin, checked in the instruction logic
+    #[account(mut)]
+    pub platform_admin: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub destination: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_execute_action(ctx: Context<ExecuteAction>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let platform = &ctx.accounts.platform_config;
+
+    // Guards
+    require!(platform.approvals_enabled(), PredictError::ApprovalsDisabled);
+    require!(!proposal.executed, PredictError::ActionAlreadyExecuted);
+    require!(
+        platform.approval_count(proposal) >= platform.approval_threshold as usize,
+        PredictError::InsufficientApprovals
+    );
+
+    let action = proposal.action;
+    match action {
+        AdminAction::UpdateCollateralMint { mint } => {
+            let new_mint = ctx.accounts.new_collateral_mint.as_ref().ok_or(PredictError::ActionAccountMismatch)?;
+            let new_treasury = ctx.accounts.new_treasury.as_ref().ok_or(PredictError::ActionAccountMismatch)?;
+            require!(new_mint.key() == mint, PredictError::ActionAccountMismatch);
+            let treasury = new_treasury.key();
+            let platform = &mut ctx.accounts.platform_config;
+            platform.collateral_mint = mint;
+            platform.treasury = treasury;
+        }
+        AdminAction::SettleDispute { market_id, result_outcome } => {
+            let market = ctx.accounts.market.as_mut().ok_or(PredictError::ActionAccountMismatch)?;
+            let dispute = ctx.accounts.dispute_record.as_mut().ok_or(PredictError::ActionAccountMismatch)?;
+            let disputer = ctx.accounts.disputer.as_ref().ok_or(PredictError::ActionAccountMismatch)?;
+            let platform_admin = ctx.accounts.platform_admin.as_ref().ok_or(PredictError::ActionAccountMismatch)?;
+            require!(market.market_id == market_id, PredictError::ActionAccountMismatch);
+            require!(dispute.market == market.key(), PredictError::ActionAccountMismatch);
+            require!(disputer.key() == dispute.disputer, PredictError::ActionAccountMismatch);
+            require!(platform_admin.key() == platform.admin, PredictError::ActionAccountMismatch);
+            apply_dispute_settlement(
+                market,
+                dispute,
+                result_outcome,
+                ctx.accounts.executor.key(),
+                disputer,
+                platform_admin,
+            )?;
+        }
+        AdminAction::WithdrawFees { destination, amount } => {
+            let treasury = ctx.accounts.treasury.as_ref().ok_or(PredictError::ActionAccountMismatch)?;
+            let dest = ctx.accounts.destination.as_ref().ok_or(PredictError::ActionAccountMismatch)?;
+            require!(treasury.key() == platform.treasury, PredictError::ActionAccountMismatch);
+            require!(dest.key() == destination, PredictError::ActionAccountMismatch);
+            require!(dest.mint == treasury.mint, PredictError::InvalidMint);
+            transfer_from_treasury(
+                &ctx.accounts.platform_config,
+                treasury,
+                dest,
+                ctx.accounts.token_program.to_account_info(),
+                amount,
+            )?;
+        }
+        AdminAction::SetApprovers { approvers, threshold } => {
+            ctx.accounts.platform_config.set_approvers(&approvers, threshold)?;
+        }
+        AdminAction::EmergencyShutdown => {
+            apply_emergency_shutdown(&mut ctx.accounts.platform_config)?;
+        }
+        AdminAction::ResumeFromShutdown => {
+            apply_resume_from_shutdown(&mut ctx.accounts.platform_config)?;
+        }
+    }
+
+    ctx.accounts.proposal.executed = true;
+
+    emit!(ActionExecuted {
+        proposal_id: ctx.accounts.proposal.id,
+        executor: ctx.accounts.executor.key(),
+        action,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, FeedRegistry, OracleSource, RegisteredFeed, FEED_SYMBOL_LEN};
+use crate::events::{FeedDeregistered, FeedRegistered};
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+pub struct RegisterFeed<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"feed_registry"],
+        bump,
+        space = FeedRegistry::LEN,
+    )]
+    pub feed_registry: Box<Account<'info, FeedRegistry>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterFeed<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump,
+    )]
+    pub feed_registry: Box<Account<'info, FeedRegistry>>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Approves `feed` for `source` markets, or updates its metadata if already
+/// listed. `exponent` is the default exponent of thresholds on the feed.
+pub fn process_register_feed(
+    ctx: Context<RegisterFeed>,
+    feed: Pubkey,
+    source: OracleSource,
+    symbol: String,
+    exponent: i32,
+) -> Result<()> {
+    require!(
+        matches!(source, OracleSource::Pyth | OracleSource::Switchboard | OracleSource::Chainlink),
+        PredictError::OracleMismatch
+    );
+    require!(
+        feed != Pubkey::default() && !symbol.is_empty() && symbol.len() <= FEED_SYMBOL_LEN,
+        PredictError::InvalidFeedEntry
+    );
+
+    let mut symbol_bytes = [0u8; FEED_SYMBOL_LEN];
+    symbol_bytes[..symbol.len()].copy_from_slice(symbol.as_bytes());
+
+    let registry = &mut ctx.accounts.feed_registry;
+    registry.register(RegisteredFeed { feed, source, symbol: symbol_bytes, exponent })?;
+    registry.bump = ctx.bumps.feed_registry;
+
+    emit!(FeedRegistered { feed, source, symbol, exponent });
+    Ok(())
+}
+
+/// Removes `feed` from the registry. Existing markets on it are unaffected;
+/// removing the last feed lifts the restriction altogether.
+pub fn process_deregister_feed(ctx: Context<DeregisterFeed>, feed: Pubkey) -> Result<()> {
+    ctx.accounts.feed_registry.deregister(&feed)?;
+    emit!(FeedDeregistered { feed });
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Transfer};
+use crate::state::{PlatformConfig, Role, Market, MarketStatus, Outcome, UserPosition, FORFEITED_ROUND};
+use crate::events::DustForfeited;
+use crate::errors::PredictError;
+use crate::instructions::betting::claim_payout::quote_claim;
+
+/// remaining_accounts per position: [user_position, user_yes_account, user_no_account]
+pub const FORFEIT_ACCOUNTS_PER_POSITION: usize = 3;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ForfeitDustPositions<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == platform_config.treasury,
+        constraint = treasury.mint == market.collateral_mint @ PredictError::InvalidMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Forfeits abandoned positions (passed via `remaining_accounts`) that are
+/// owed less than `min_claimable_dust`, once the claim window has passed.
+/// Their shares stay where they are but can no longer be claimed; their slice
+/// of the pool stays put for the remaining holders' pro-rata math. When the
+/// accounts passed hold every outstanding share, what is left of the pool
+/// goes to the treasury and the market may close. Positions forfeited by an
+/// earlier call may be passed again to complete that set.
+pub fn process_forfeit_dust_positions<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ForfeitDustPositions<'info>>,
+    market_id: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let platform = &ctx.accounts.platform_config;
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(platform.min_claimable_dust > 0, PredictError::DustForfeitureDisabled);
+    require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
+    // Leftover shares would carry into a recurring market's next series, and
+    // cross-mint pools are paid from the reserve
+    require!(!market.is_recurring && market.payout_mint.is_none(), PredictError::MarketNotCloseable);
+    let resolved_at = market.resolved_at.ok_or(PredictError::MarketNotResolved)?;
+    require!(
+        clock.unix_timestamp >= resolved_at.saturating_add(platform.claim_window_secs),
+        PredictError::ClaimWindowOpen
+    );
+
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len().is_multiple_of(FORFEIT_ACCOUNTS_PER_POSITION),
+        PredictError::InvalidBatch
+    );
+
+    let outcome = market.resolved_outcome.ok_or(PredictError::MarketNotResolved)?;
+    let yes_supply = ctx.accounts.yes_mint.supply;
+    let no_supply = ctx.accounts.no_mint.supply;
+    let vault_amount = ctx.accounts.vault.amount;
+    let mut forfeited_yes: u64 = 0;
+    let mut forfeited_no: u64 = 0;
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(remaining.len() / FORFEIT_ACCOUNTS_PER_POSITION);
+
+    for group in remaining.chunks(FORFEIT_ACCOUNTS_PER_POSITION) {
+        let [position_info, yes_info, no_info] = group else {
+            return err!(PredictError::InvalidBatch);
+        };
+
+        let mut position: Account<'info, UserPosition> = Account::try_from(position_info)?;
+        let (position_pda, _) = Pubkey::find_program_address(
+            &[b"position", market.key().as_ref(), position.user.as_ref()],
+            ctx.program_id,
+        );
+        require!(position_info.key() == position_pda, PredictError::NoPosition);
+        // A repeated position would count its shares twice toward the supply
+        require!(!seen.contains(&position_pda), PredictError::InvalidBatch);
+        seen.push(position_pda);
+
+        let yes_acc = TokenAccount::try_deserialize(&mut &yes_info.data.borrow()[..])?;
+        let no_acc = TokenAccount::try_deserialize(&mut &no_info.data.borrow()[..])?;
+        require!(yes_acc.mint == market.yes_mint && no_acc.mint == market.no_mint, PredictError::InvalidMint);
+        require!(yes_acc.owner == position.user && no_acc.owner == position.user, PredictError::Unauthorized);
+
+        forfeited_yes = forfeited_yes.checked_add(yes_acc.amount).ok_or(PredictError::MathOverflow)?;
+        forfeited_no = forfeited_no.checked_add(no_acc.amount).ok_or(PredictError::MathOverflow)?;
+        if position.round == FORFEITED_ROUND {
+            continue;
+        }
+
+        // What the position could still claim. Invalid refunds principal once,
+        // whichever share account is presented.
+        let mut value: u64 = 0;
+        for share_acc in [&yes_acc, &no_acc] {
+            if let Ok(quote) = quote_claim(market, &position, share_acc, yes_supply, no_supply, vault_amount) {
+                value = value.checked_add(quote.payout).ok_or(PredictError::MathOverflow)?;
+                if outcome == Outcome::Invalid {
+                    break;
+                }
+            }
+        }
+        require!(value < platform.min_claimable_dust, PredictError::PositionAboveDustThreshold);
+
+        position.round = FORFEITED_ROUND;
+        position.exit(ctx.program_id)?;
+
+        emit!(DustForfeited {
+            market_id,
+            user: position.user,
+            yes_shares: yes_acc.amount,
+            no_shares: no_acc.amount,
+            value,
+        });
+    }
+
+    // Everything outstanding is forfeited: the rest of the pool is unclaimable
+    if forfeited_yes != yes_supply || forfeited_no != no_supply {
+        msg!(
+            "{} YES / {} NO shares still outstanding",
+            yes_supply.saturating_sub(forfeited_yes),
+            no_supply.saturating_sub(forfeited_no)
+        );
+        return Ok(());
+    }
+
+    let swept = market.distributable_collateral.min(vault_amount);
+    if swept > 0 {
+        let market_id_bytes = market.market_id.to_le_bytes();
+        let seeds = &[
+            b"market",
+            market_id_bytes.as_ref(),
+            &[market.bump],
+        ];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            swept,
+        )?;
+    }
+    let released = swept.min(market.total_collateral);
+    market.total_collateral -= released;
+    ctx.accounts.platform_config.debit_tvl(released);
+    market.distributable_collateral = 0;
+    market.split_yes_pool = 0;
+    market.dust_forfeited = true;
+
+    msg!("Forfeited dust swept: {} to treasury", swept);
+    Ok(())
+}
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
-use crate::state::PlatformConfig;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{PlatformConfig, RoleGrant, MAX_APPROVERS, MAX_ROLE_GRANTS, DEFAULT_CHALLENGE_WINDOW_SECS, DEFAULT_CLAIM_WINDOW_SECS, DEFAULT_MAX_DISPUTE_VOTING_SECS, DEFAULT_RESOLUTION_TIMEOUT_SECS};
 use crate::events::PlatformInitialized;
 use crate::errors::PredictError;
 
 #[derive(Accounts)]
 pub struct InitPlatform<'info> {
+    /// init_if_needed so a second call reaches the handler and fails with
+    /// PlatformAlreadyInitialized instead of a generic "already in use"
     #[account(
-        init,
+        init_if_needed,
         seeds = [b"platform_config"],
         bump,
         payer = admin,
@@ -20,10 +22,19 @@ pub struct InitPlatform<'info> {
     
     pub system_program: Program<'info, System>,
     pub collateral_mint: Account<'info, Mint>,
+
+    /// Program-owned fee treasury for the collateral mint (see withdraw_fees)
     #[account(
-        constraint = treasury.mint == collateral_mint.key() @ PredictError::InvalidMint,
+        init_if_needed,
+        payer = admin,
+        seeds = [b"treasury", collateral_mint.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = platform_config,
     )]
     pub treasury: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 pub fn process_init_platform(
@@ -32,8 +43,13 @@ pub fn process_init_platform(
     dispute_bond: u64
 ) -> Result<()> {
     require!(fee_bps <= 1000, PredictError::FeeExceedsMax); // Max 10%
-    
+    // A zero bond would make disputes free to open
+    require!(dispute_bond > 0, PredictError::InvalidDisputeBond);
+
     let platform = &mut ctx.accounts.platform_config;
+    // A fresh account is zeroed; an initialized one always has an admin
+    require!(platform.admin == Pubkey::default(), PredictError::PlatformAlreadyInitialized);
+
     platform.admin = ctx.accounts.admin.key();
     platform.fee_bps = fee_bps;
     platform.treasury = ctx.accounts.treasury.key();
@@ -42,6 +58,32 @@ pub fn process_init_platform(
     platform.collateral_mint = ctx.accounts.collateral_mint.key();
     platform.dispute_bond_lamports = dispute_bond;
     platform.bump = ctx.bumps.platform_config;
+    platform.min_threshold_distance_bps = 0;
+    platform.creation_cooldown_secs = 0;
+    platform.default_max_slippage_bps = 0;
+    platform.resolution_grace_secs = 0;
+    platform.active_market_count = 0;
+    platform.max_active_markets = 0;
+    platform.resolution_timeout_secs = DEFAULT_RESOLUTION_TIMEOUT_SECS;
+    platform.max_backdate_secs = 0;
+    platform.max_dispute_voting_secs = DEFAULT_MAX_DISPUTE_VOTING_SECS;
+    platform.min_slots_before_resolve = 0;
+    platform.min_bettors_for_resolution = 0;
+    platform.shutdown = false;
+    platform.forking_enabled = false;
+    platform.min_claimable_dust = 0;
+    platform.claim_window_secs = DEFAULT_CLAIM_WINDOW_SECS;
+    platform.total_tvl = 0;
+    platform.max_platform_tvl = 0;
+    platform.close_grace_secs = 0;
+    platform.min_fee_absolute = 0;
+    platform.pending_admin = Pubkey::default();
+    platform.approvers = [Pubkey::default(); MAX_APPROVERS];
+    platform.approval_threshold = 0;
+    platform.proposal_count = 0;
+    platform.role_grants = [RoleGrant::default(); MAX_ROLE_GRANTS];
+    platform.resolution_bond_lamports = dispute_bond;
+    platform.challenge_window_secs = DEFAULT_CHALLENGE_WINDOW_SECS;
 
     emit!(PlatformInitialized {
         admin: platform.admin,
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus};
+use crate::events::MarketLocked;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct LockMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+/// Permissionless: moves an Active market to Locked once `lock_timestamp` has
+/// passed and freezes its reserves as the closing odds.
+pub fn process_lock_market(ctx: Context<LockMarket>, market_id: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(market.status == MarketStatus::Active, PredictError::MarketNotActive);
+    require!(clock.unix_timestamp >= market.lock_timestamp, PredictError::BettingStillOpen);
+
+    market.lock();
+
+    emit!(MarketLocked {
+        market_id,
+        yes_reserve: market.locked_yes_reserve,
+        no_reserve: market.locked_no_reserve,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, Role, Market, MarketStatus};
+use crate::events::{MarketCloseRequested, MarketCloseCancelled};
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CloseRequestCtx<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// First step of a close: starts the platform's close grace window, after
+/// which close_market may delete the market.
+pub fn process_mark_for_close(ctx: Context<CloseRequestCtx>, market_id: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    require!(
+        market.status == MarketStatus::Resolved || market.status == MarketStatus::Cancelled,
+        PredictError::MarketNotCloseable
+    );
+    require!(market.close_requested_at == 0, PredictError::CloseAlreadyRequested);
+
+    let now = Clock::get()?.unix_timestamp;
+    market.close_requested_at = now;
+
+    emit!(MarketCloseRequested {
+        market_id,
+        closable_at: now.saturating_add(ctx.accounts.platform_config.close_grace_secs),
+    });
+
+    Ok(())
+}
+
+/// Withdraws a pending close request during its grace window
+pub fn process_cancel_close(ctx: Context<CloseRequestCtx>, market_id: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    require!(market.close_requested_at != 0, PredictError::CloseNotRequested);
+    market.close_requested_at = 0;
+
+    emit!(MarketCloseCancelled { market_id });
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{PlatformConfig, Role, CategoryPolicy, Market, MarketStatus, OracleCache, OracleSource};
+use crate::events::OracleSourceMigrated;
+use crate::errors::PredictError;
+use crate::utils::{read_pyth_price, threshold_distance_bps};
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct MigrateOracleSource<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: Category/oracle policy singleton. May be uninitialized, in which
+    /// case every pairing is allowed; otherwise deserialized in the handler.
+    #[account(
+        seeds = [b"category_policy"],
+        bump,
+    )]
+    pub category_policy: UncheckedAccount<'info>,
+
+    /// Live Pyth feed (required when migrating to Pyth)
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
+}
+
+/// Points an unresolved market at a different oracle, re-running the checks
+/// create_market applies to the new source. A ProgramState target keeps the
+/// market's existing query.
+pub fn process_migrate_oracle_source(
+    ctx: Context<MigrateOracleSource>,
+    market_id: u64,
+    new_source: OracleSource,
+    new_feed: Pubkey,
+    new_threshold: i64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let platform = &ctx.accounts.platform_config;
+    let clock = Clock::get()?;
+
+    // Guards: a snapshotted, resolved or disputed market keeps its oracle
+    require!(
+        matches!(
+            market.status,
+            MarketStatus::Pending | MarketStatus::Active | MarketStatus::Locked | MarketStatus::Paused
+        ),
+        PredictError::AlreadyResolved
+    );
+    // Split needs a ratio, which only a Pyth confidence band provides;
+    // check_barrier reads Pyth
+    if market.confidence_split || market.barrier {
+        require!(new_source == OracleSource::Pyth, PredictError::OracleMismatch);
+    }
+    // Scalar bounds and a range's upper edge are in the creation-time exponent,
+    // which a new feed resets
+    require!(
+        market.scalar_range.is_none() && market.oracle_upper_threshold.is_none(),
+        PredictError::OracleMismatch
+    );
+    // Committee markets are resolved by attestation, not a feed
+    if market.committee_resolution {
+        require!(new_source == OracleSource::ManualAdmin, PredictError::OracleMismatch);
+    }
+    if !ctx.accounts.category_policy.data_is_empty() {
+        let policy = CategoryPolicy::try_deserialize(&mut &ctx.accounts.category_policy.data.borrow()[..])?;
+        require!(
+            policy.allows(market.category, new_source),
+            PredictError::OracleSourceNotAllowedForCategory
+        );
+    }
+
+    let (feed, exponent) = match new_source {
+        OracleSource::ManualAdmin => (Pubkey::default(), None),
+        OracleSource::Pyth => {
+            require!(new_feed != Pubkey::default(), PredictError::InvalidPythFeed);
+            let price_feed = ctx.accounts.pyth_price_feed.as_ref()
+                .ok_or(PredictError::OracleMismatch)?;
+            require!(price_feed.key() == new_feed, PredictError::InvalidPythFeed);
+            // The feed must be live now, and the threshold is taken in its current exponent
+            let current_price = read_pyth_price(price_feed, clock.unix_timestamp)?;
+            if platform.min_threshold_distance_bps > 0 {
+                require!(
+                    threshold_distance_bps(current_price, new_threshold) >= platform.min_threshold_distance_bps as u64,
+                    PredictError::ThresholdTooClose
+                );
+            }
+            (new_feed, Some(price_feed.price_message.exponent))
+        }
+        OracleSource::ProgramState => {
+            require!(
+                market.program_state_query.is_some() && new_feed != Pubkey::default(),
+                PredictError::InvalidProgramStateQuery
+            );
+            (new_feed, None)
+        }
+        // The threshold is taken in SWITCHBOARD_DEFAULT_EXPONENT at resolution
+        OracleSource::Switchboard => {
+            require!(new_feed != Pubkey::default(), PredictError::InvalidSwitchboardFeed);
+            (new_feed, None)
+        }
+        // The threshold is taken in the feed's decimals at resolution
+        OracleSource::Chainlink => {
+            require!(new_feed != Pubkey::default(), PredictError::InvalidChainlinkFeed);
+            (new_feed, None)
+        }
+        // new_feed is the attester's key
+        OracleSource::SignedAttestation => {
+            require!(new_feed != Pubkey::default(), PredictError::InvalidAttestation);
+            (new_feed, None)
+        }
+    };
+
+    let old_source = market.oracle_source;
+    market.oracle_source = new_source;
+    market.oracle_feed = feed;
+    market.oracle_threshold = new_threshold;
+    market.oracle_exponent = exponent;
+    market.oracle_cache = OracleCache::default();
+    // Only ProgramState markets may carry a query
+    if new_source != OracleSource::ProgramState {
+        market.program_state_query = None;
+    }
+
+    emit!(OracleSourceMigrated {
+        market_id,
+        old_source,
+        new_source,
+        new_feed: feed,
+        new_threshold,
+    });
+
+    Ok(())
+}
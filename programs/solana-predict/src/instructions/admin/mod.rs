@@ -4,7 +4,31 @@ pub mod close_market;
 pub mod pause;
 pub mod update_fees;
 pub mod update_collateral_mint;
-pub mod update_treasury;
+pub mod update_config;
+pub mod set_payout_mint;
+pub mod withdraw_payout_surplus;
+pub mod set_category_policy;
+pub mod restart_recurring;
+pub mod start_round;
+pub mod close_round;
+pub mod abort_market;
+pub mod cancel_market;
+pub mod reclaim_seed;
+pub mod sweep_vault_surplus;
+pub mod transfer_market_creator;
+pub mod lock_market;
+pub mod rebalance_liquidity;
+pub mod emergency_shutdown;
+pub mod forfeit_dust_positions;
+pub mod migrate_oracle_source;
+pub mod mark_for_close;
+pub mod transfer_admin;
+pub mod approvals;
+pub mod execute_action;
+pub mod roles;
+pub mod committee;
+pub mod feed_registry;
+pub mod claim_creator_fees;
 
 pub use init_platform::*;
 pub use create_market::*;
@@ -12,4 +36,28 @@ pub use close_market::*;
 pub use pause::*;
 pub use update_fees::*;
 pub use update_collateral_mint::*;
-pub use update_treasury::*;
+pub use update_config::*;
+pub use set_payout_mint::*;
+pub use withdraw_payout_surplus::*;
+pub use set_category_policy::*;
+pub use restart_recurring::*;
+pub use start_round::*;
+pub use close_round::*;
+pub use abort_market::*;
+pub use cancel_market::*;
+pub use reclaim_seed::*;
+pub use sweep_vault_surplus::*;
+pub use transfer_market_creator::*;
+pub use lock_market::*;
+pub use rebalance_liquidity::*;
+pub use emergency_shutdown::*;
+pub use forfeit_dust_positions::*;
+pub use migrate_oracle_source::*;
+pub use mark_for_close::*;
+pub use transfer_admin::*;
+pub use approvals::*;
+pub use execute_action::*;
+pub use roles::*;
+pub use committee::*;
+pub use feed_registry::*;
+pub use claim_creator_fees::*;
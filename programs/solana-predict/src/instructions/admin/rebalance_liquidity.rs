@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{PlatformConfig, Market, MarketStatus, PricingMode};
+use crate::events::LiquidityRebalanced;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct RebalanceLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = creator @ PredictError::Unauthorized,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = market.collateral_mint,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.collateral_mint,
+        token::authority = creator,
+    )]
+    pub creator_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Creator adds liquidity asymmetrically: `yes_add` to the YES reserve and
+/// `no_add` to the NO reserve, depositing their sum. No shares are minted, so
+/// the deposit backs existing positions and joins the creator's reclaimable
+/// seed; nothing can be withdrawn before resolution.
+pub fn process_rebalance_liquidity(
+    ctx: Context<RebalanceLiquidity>,
+    market_id: u64,
+    yes_add: u64,
+    no_add: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(!ctx.accounts.platform_config.paused, PredictError::PlatformPaused);
+    require!(market.status == MarketStatus::Active, PredictError::MarketNotActive);
+    require!(market.pricing_mode == PricingMode::Cpmm, PredictError::PricingModeUnsupported);
+    require!(clock.unix_timestamp < market.lock_timestamp, PredictError::BettingClosed);
+    let deposit = yes_add.checked_add(no_add).ok_or(PredictError::MathOverflow)?;
+    require!(deposit > 0, PredictError::InsufficientLiquidity);
+
+    let vault_before = ctx.accounts.vault.amount;
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator_ata.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+            },
+        ),
+        deposit,
+    )?;
+
+    // Reserves are credited 1:1 with collateral, so all of it must arrive
+    ctx.accounts.vault.reload()?;
+    let received = ctx.accounts.vault.amount
+        .checked_sub(vault_before)
+        .ok_or(PredictError::MathOverflow)?;
+    require!(received == deposit, PredictError::InsufficientVault);
+
+    market.total_yes_shares = market.total_yes_shares.checked_add(yes_add).ok_or(PredictError::MathOverflow)?;
+    market.total_no_shares = market.total_no_shares.checked_add(no_add).ok_or(PredictError::MathOverflow)?;
+    market.total_collateral = market.total_collateral.checked_add(deposit).ok_or(PredictError::MathOverflow)?;
+    ctx.accounts.platform_config.credit_tvl(deposit)?;
+    market.initial_liquidity = market.initial_liquidity.checked_add(deposit).ok_or(PredictError::MathOverflow)?;
+
+    emit!(LiquidityRebalanced {
+        market_id,
+        yes_add,
+        no_add,
+        yes_reserve: market.total_yes_shares,
+        no_reserve: market.total_no_shares,
+    });
+
+    Ok(())
+}
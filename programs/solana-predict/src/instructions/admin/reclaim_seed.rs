@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Transfer};
+use crate::state::{PlatformConfig, Market, MarketStatus, Outcome};
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ReclaimSeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = creator @ PredictError::Unauthorized,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.collateral_mint,
+        token::authority = creator,
+    )]
+    pub creator_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns unconsumed seed liquidity to the creator once every winning share
+/// has been redeemed: the lesser of the remaining seed and the vault balance,
+/// plus any unspent early-bird budget. Collateral set aside for LP holders
+/// stays in the vault.
+pub fn process_reclaim_seed(ctx: Context<ReclaimSeed>, _market_id: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Guards
+    require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
+    let outcome = market.resolved_outcome.ok_or(PredictError::MarketNotResolved)?;
+    let outstanding_winners = match outcome {
+        Outcome::Yes => ctx.accounts.yes_mint.supply,
+        Outcome::No => ctx.accounts.no_mint.supply,
+        Outcome::Invalid | Outcome::Split => ctx.accounts.yes_mint.supply + ctx.accounts.no_mint.supply,
+    };
+    require!(outstanding_winners == 0, PredictError::OutstandingPositions);
+
+    let budget = market.early_bird_budget.min(ctx.accounts.vault.amount);
+    let seed = market.initial_liquidity.min(
+        (ctx.accounts.vault.amount - budget).saturating_sub(market.lp_pool)
+    );
+    let amount = seed + budget;
+    require!(amount > 0, PredictError::InsufficientVault);
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.creator_ata.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    // Seed can only be reclaimed once
+    market.initial_liquidity -= seed;
+    let released = seed.min(market.total_collateral);
+    market.total_collateral -= released;
+    ctx.accounts.platform_config.debit_tvl(released);
+    market.early_bird_budget -= budget;
+
+    msg!("Reclaimed {} seed liquidity and {} early-bird budget", seed, budget);
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::{PlatformConfig, Role, Market, MarketStatus, PriceCheckpoint, ResolutionProof, PRICE_HISTORY_LEN};
+use crate::events::RoundStarted;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct RestartRecurring<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Starts a new series on a settled recurring market, keeping its PDA, mints
+/// and whatever liquidity is left in the vault.
+pub fn process_restart_recurring(
+    ctx: Context<RestartRecurring>,
+    market_id: u64,
+    new_start: i64,
+    new_lock: i64,
+    new_end: i64,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    begin_series(
+        &mut accounts.market,
+        &mut accounts.platform_config,
+        SeriesBalances {
+            yes_supply: accounts.yes_mint.supply,
+            no_supply: accounts.no_mint.supply,
+            vault_amount: accounts.vault.amount,
+        },
+        market_id,
+        new_start,
+        new_lock,
+        new_end,
+    )
+}
+
+/// Balances the restart checks read
+pub struct SeriesBalances {
+    pub yes_supply: u64,
+    pub no_supply: u64,
+    pub vault_amount: u64,
+}
+
+/// Re-opens a settled recurring market for the round at `new_start` /
+/// `new_lock` / `new_end`: re-seeds the pools from the residual liquidity,
+/// clears the previous round's state and bumps `current_round`.
+pub fn begin_series(
+    market: &mut Market,
+    platform: &mut PlatformConfig,
+    balances: SeriesBalances,
+    market_id: u64,
+    new_start: i64,
+    new_lock: i64,
+    new_end: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Guards: prior series must be resolved and every share redeemed
+    require!(market.is_recurring, PredictError::NotRecurring);
+    require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
+    require!(
+        balances.yes_supply == 0 && balances.no_supply == 0,
+        PredictError::OutstandingPositions
+    );
+    require!(
+        new_start < new_lock && new_lock < new_end && new_lock > clock.unix_timestamp,
+        PredictError::InvalidTimestamps
+    );
+
+    // A new series counts against the active-market cap again
+    if platform.max_active_markets > 0 {
+        require!(
+            platform.active_market_count < platform.max_active_markets,
+            PredictError::ActiveMarketLimitReached
+        );
+    }
+    platform.active_market_count = platform.active_market_count.checked_add(1).ok_or(PredictError::MathOverflow)?;
+
+    // Residual liquidity re-seeds the pools at the market's prior. Cross-mint vaults also hold
+    // collateral owed to the creator, so only the tracked pool carries over there.
+    let residual = if market.payout_mint.is_some() {
+        market.total_collateral
+    } else {
+        balances.vault_amount
+    };
+    require!(residual > 0, PredictError::InsufficientLiquidity);
+
+    market.seed_reserves(residual);
+    platform.debit_tvl(market.total_collateral);
+    platform.credit_tvl(residual)?;
+    market.total_collateral = residual;
+    market.initial_liquidity = residual;
+    market.total_deposited = 0;
+    market.distributable_collateral = 0;
+    market.resolution_proof = ResolutionProof::default();
+    market.split_yes_bps = 0;
+    market.split_yes_pool = 0;
+    market.locked_yes_reserve = 0;
+    market.locked_no_reserve = 0;
+    market.slot_start_slot = 0;
+    market.unique_bettors = 0;
+    market.price_history = [PriceCheckpoint::default(); PRICE_HISTORY_LEN];
+    market.price_history_head = 0;
+    // Withheld dust was part of the vault balance re-seeded above
+    market.pending_dust = 0;
+    market.claimed_payout = 0;
+    market.claimed_weight = 0;
+    if market.payout_mint.is_none() {
+        // Unspent early-bird budget was part of the vault balance re-seeded above
+        market.early_bird_budget = 0;
+    }
+    market.start_timestamp = new_start;
+    market.lock_timestamp = new_lock;
+    market.end_timestamp = new_end;
+    market.resolved_outcome = None;
+    market.resolution_price = None;
+    market.resolution_conf = 0;
+    market.resolved_at = None;
+    market.current_round = market.current_round.checked_add(1).ok_or(PredictError::MathOverflow)?;
+    market.status = if new_start <= clock.unix_timestamp {
+        MarketStatus::Active
+    } else {
+        MarketStatus::Pending
+    };
+
+    emit!(RoundStarted {
+        market_id,
+        round_id: market.current_round,
+        start_ts: new_start,
+        lock_ts: new_lock,
+        end_ts: new_end,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::state::Role;
+use crate::instructions::admin::pause::PlatformAdmin;
+use crate::events::RoleChanged;
+
+/// Grants `role` to `key` (see Role for what each covers)
+pub fn process_grant_role(ctx: Context<PlatformAdmin>, key: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.platform_config.set_role(key, role, true)?;
+    emit!(RoleChanged { key, role, granted: true });
+    Ok(())
+}
+
+pub fn process_revoke_role(ctx: Context<PlatformAdmin>, key: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.platform_config.set_role(key, role, false)?;
+    emit!(RoleChanged { key, role, granted: false });
+    Ok(())
+}
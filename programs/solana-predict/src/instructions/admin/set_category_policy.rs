@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, CategoryPolicy, MarketCategory, ALL_ORACLE_SOURCES, CATEGORY_COUNT};
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+pub struct SetCategoryPolicy<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"category_policy"],
+        bump,
+        payer = admin,
+        space = CategoryPolicy::LEN
+    )]
+    pub category_policy: Account<'info, CategoryPolicy>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the oracle sources allowed for `category` as a bitmask of
+/// `1 << OracleSource` (e.g. Pyth | Switchboard = 0b011). Unknown bits are dropped.
+pub fn process_set_category_policy(
+    ctx: Context<SetCategoryPolicy>,
+    category: MarketCategory,
+    allowed_sources: u8,
+) -> Result<()> {
+    let policy = &mut ctx.accounts.category_policy;
+
+    // Fresh account: start from "everything allowed" for the other categories
+    if policy.bump == 0 {
+        policy.allowed_sources = [ALL_ORACLE_SOURCES; CATEGORY_COUNT];
+        policy.bump = ctx.bumps.category_policy;
+    }
+
+    policy.allowed_sources[category as usize] = allowed_sources & ALL_ORACLE_SOURCES;
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Transfer};
+use crate::state::{Market, MarketStatus};
+use crate::events::PayoutMintSet;
+use crate::errors::PredictError;
+
+/// Switches a fresh market to cross-mint settlement: bets stay in collateral
+/// (held by the vault) while winners are paid from a creator-funded reserve of
+/// `payout_mint` at the fixed `payout_rate`. The reserve can be topped up later
+/// with a plain token transfer; resolution checks it covers total_collateral.
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SetPayoutMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = creator @ PredictError::Unauthorized,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+        payer = creator,
+        token::mint = payout_mint,
+        token::authority = market,
+    )]
+    pub payout_reserve: Account<'info, TokenAccount>,
+
+    pub payout_mint: Account<'info, Mint>,
+
+    /// Creator's payout-mint token account funding the reserve
+    #[account(
+        mut,
+        token::mint = payout_mint,
+        token::authority = creator,
+    )]
+    pub creator_payout_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn process_set_payout_mint(
+    ctx: Context<SetPayoutMint>,
+    market_id: u64,
+    payout_rate: u64,
+    reserve_amount: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // Guards: only before anyone holds shares priced in the old settlement terms
+    require!(
+        market.status == MarketStatus::Pending || market.status == MarketStatus::Active,
+        PredictError::MarketNotActive
+    );
+    require!(clock.unix_timestamp < market.lock_timestamp, PredictError::BettingClosed);
+    require!(
+        ctx.accounts.yes_mint.supply == 0 && ctx.accounts.no_mint.supply == 0,
+        PredictError::OutstandingPositions
+    );
+    require!(
+        ctx.accounts.payout_mint.key() != market.collateral_mint,
+        PredictError::InvalidMint
+    );
+    require!(payout_rate > 0, PredictError::InvalidPayoutRate);
+
+    if reserve_amount > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_payout_ata.to_account_info(),
+                    to: ctx.accounts.payout_reserve.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            reserve_amount,
+        )?;
+    }
+
+    market.payout_mint = Some(ctx.accounts.payout_mint.key());
+    market.payout_rate = payout_rate;
+
+    emit!(PayoutMintSet {
+        market_id,
+        payout_mint: ctx.accounts.payout_mint.key(),
+        payout_rate,
+        reserve_amount,
+    });
+
+    Ok(())
+}
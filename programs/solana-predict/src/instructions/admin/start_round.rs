@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::{PlatformConfig, Market, RoundState, RoundStatus};
+use crate::errors::PredictError;
+use super::restart_recurring::{begin_series, SeriesBalances};
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct StartRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        seeds = [b"round", market.key().as_ref(), (market.current_round + 1).to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        space = RoundState::LEN
+    )]
+    pub round_state: Account<'info, RoundState>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Anyone can start the next round; its times follow from the schedule
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the next round of a settled recurring market on its schedule: the
+/// previous round's start/lock/end moved forward by whole `round_duration`s,
+/// skipping any round whose betting window has already closed. The round is
+/// recorded in a `RoundState` PDA; restart_recurring remains for off-schedule
+/// restarts.
+pub fn process_start_round(ctx: Context<StartRound>, market_id: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    open_next_round(
+        &mut accounts.market,
+        &mut accounts.platform_config,
+        SeriesBalances {
+            yes_supply: accounts.yes_mint.supply,
+            no_supply: accounts.no_mint.supply,
+            vault_amount: accounts.vault.amount,
+        },
+        &mut accounts.round_state,
+        ctx.bumps.round_state,
+        market_id,
+    )
+}
+
+/// Begins the market's next scheduled round and fills its freshly created
+/// `RoundState`. Shared by start_round and crank_round.
+pub fn open_next_round(
+    market: &mut Account<Market>,
+    platform: &mut PlatformConfig,
+    balances: SeriesBalances,
+    round: &mut RoundState,
+    round_bump: u8,
+    market_id: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(!platform.paused, PredictError::PlatformPaused);
+
+    let duration = market.round_duration.filter(|d| *d > 0).ok_or(PredictError::NotRecurring)?;
+    // Fewest whole periods that put the lock in the future
+    let periods = now.saturating_sub(market.lock_timestamp).max(0) / duration + 1;
+    let shift = periods.checked_mul(duration).ok_or(PredictError::MathOverflow)?;
+    let shifted = |ts: i64| ts.checked_add(shift).ok_or(PredictError::MathOverflow);
+    let (start, lock, end) = (
+        shifted(market.start_timestamp)?,
+        shifted(market.lock_timestamp)?,
+        shifted(market.end_timestamp)?,
+    );
+
+    begin_series(market, platform, balances, market_id, start, lock, end)?;
+
+    round.market = market.key();
+    round.round_id = market.current_round;
+    round.status = RoundStatus::Open;
+    round.lock_price = None;
+    round.close_price = None;
+    round.total_yes = 0;
+    round.total_no = 0;
+    round.start_ts = start;
+    round.lock_ts = lock;
+    round.end_ts = end;
+    round.oracle_round_id = None;
+    round.outcome = None;
+    round.unique_bettors = 0;
+    round.bump = round_bump;
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{DustRecipient, PlatformConfig, Role, Market, MarketStatus};
+use crate::errors::PredictError;
+
+/// Same-mint markets only: once resolved, sends whatever the vault holds beyond
+/// the distributable pool, the LP pool and the unspent early-bird budget to the
+/// treasury. Withheld claim dust goes to the market's `dust_recipient` instead.
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SweepVaultSurplus<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Operator) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == platform_config.treasury,
+        constraint = treasury.mint == market.collateral_mint @ PredictError::InvalidMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// Creator's collateral account (required when the creator is owed dust)
+    #[account(
+        mut,
+        constraint = creator_ata.owner == market.creator @ PredictError::Unauthorized,
+        constraint = creator_ata.mint == market.collateral_mint @ PredictError::InvalidMint,
+    )]
+    pub creator_ata: Option<Account<'info, TokenAccount>>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn process_sweep_vault_surplus(ctx: Context<SweepVaultSurplus>, _market_id: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    // Guards
+    require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
+    // Cross-mint vaults are settled by withdraw_payout_surplus
+    require!(market.payout_mint.is_none(), PredictError::MarketNotCloseable);
+
+    // Vault dust stays put to re-seed the next series
+    let kept_dust = if market.dust_recipient == DustRecipient::Vault { market.pending_dust } else { 0 };
+    let reserved = market.distributable_collateral
+        .checked_add(market.early_bird_budget)
+        .and_then(|r| r.checked_add(kept_dust))
+        .and_then(|r| r.checked_add(market.lp_pool))
+        .ok_or(PredictError::MathOverflow)?;
+    let surplus = ctx.accounts.vault.amount.saturating_sub(reserved);
+    require!(surplus > 0, PredictError::InsufficientVault);
+
+    let creator_dust = if market.dust_recipient == DustRecipient::Creator {
+        market.pending_dust.min(surplus)
+    } else {
+        0
+    };
+    let treasury_amount = surplus - creator_dust;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if creator_dust > 0 {
+        let creator_ata = ctx.accounts.creator_ata.as_ref()
+            .ok_or(PredictError::DustAccountRequired)?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: creator_ata.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            creator_dust,
+        )?;
+    }
+    if treasury_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            treasury_amount,
+        )?;
+    }
+
+    if market.dust_recipient != DustRecipient::Vault {
+        ctx.accounts.market.pending_dust = 0;
+    }
+
+    msg!("Swept {} vault surplus ({} dust to creator)", surplus, creator_dust);
+    Ok(())
+}
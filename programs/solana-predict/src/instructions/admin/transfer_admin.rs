@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::PlatformConfig;
+use crate::instructions::admin::pause::PlatformAdmin;
+use crate::events::{AdminProposed, AdminTransferred};
+use crate::errors::PredictError;
+
+/// The proposed admin takes over by signing, so a mistyped key can't brick the platform
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.pending_admin == new_admin.key() @ PredictError::NotPendingAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub new_admin: Signer<'info>,
+}
+
+/// First step of an admin rotation: records `new_admin` as pending. The
+/// current admin stays in charge until it accepts; proposing the default key
+/// withdraws a pending proposal.
+pub fn process_propose_admin(ctx: Context<PlatformAdmin>, new_admin: Pubkey) -> Result<()> {
+    let platform = &mut ctx.accounts.platform_config;
+    platform.pending_admin = new_admin;
+
+    emit!(AdminProposed {
+        admin: platform.admin,
+        pending_admin: new_admin,
+    });
+
+    Ok(())
+}
+
+/// Second step: the pending admin signs to become the admin
+pub fn process_accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform_config;
+    // The default key never signs, but keep a withdrawn proposal unacceptable regardless
+    require!(platform.pending_admin != Pubkey::default(), PredictError::NotPendingAdmin);
+
+    let old_admin = platform.admin;
+    platform.admin = platform.pending_admin;
+    platform.pending_admin = Pubkey::default();
+
+    emit!(AdminTransferred {
+        old_admin,
+        new_admin: platform.admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, Market};
+use crate::events::MarketCreatorTransferred;
+use crate::errors::PredictError;
+
+/// Hands a market's creator rights (seed reclaim, payout mint, surplus) to a new
+/// wallet. Needs both the current creator and the platform admin.
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct TransferMarketCreator<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = creator @ PredictError::Unauthorized,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub creator: Signer<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn process_transfer_market_creator(
+    ctx: Context<TransferMarketCreator>,
+    market_id: u64,
+    new_creator: Pubkey,
+) -> Result<()> {
+    require!(new_creator != Pubkey::default(), PredictError::Unauthorized);
+
+    let market = &mut ctx.accounts.market;
+    let old_creator = market.creator;
+    market.creator = new_creator;
+
+    emit!(MarketCreatorTransferred {
+        market_id,
+        old_creator,
+        new_creator,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::state::PlatformConfig;
 use crate::errors::PredictError;
-use anchor_spl::token;
 
 #[derive(Accounts)]
 pub struct UpdateCollateralMint<'info> {
@@ -12,32 +12,29 @@ pub struct UpdateCollateralMint<'info> {
         constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
     )]
     pub platform_config: Account<'info, PlatformConfig>,
+    #[account(mut)]
     pub admin: Signer<'info>,
-    /// CHECK: Validated below as a valid SPL Mint account
-    pub new_collateral_mint: AccountInfo<'info>,
-    /// CHECK: Validated below as a token account for the new mint
-    pub new_treasury: AccountInfo<'info>,
+    pub new_collateral_mint: Account<'info, Mint>,
+    /// Fee treasury for the new mint; switching back reuses the existing one
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"treasury", new_collateral_mint.key().as_ref()],
+        bump,
+        token::mint = new_collateral_mint,
+        token::authority = platform_config,
+    )]
+    pub new_treasury: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
+/// Switches the collateral mint and, with it, the fee treasury. The treasury is
+/// always the program-owned `[b"treasury", mint]` PDA, so this is also the only
+/// way to change it (there is no separate update_treasury).
 pub fn update_collateral_mint(ctx: Context<UpdateCollateralMint>) -> Result<()> {
-    // Validate new_collateral_mint is a valid Mint account (owner = Token program, data length = Mint::LEN)
-    require!(
-        ctx.accounts.new_collateral_mint.owner == &anchor_spl::token::ID,
-        PredictError::InvalidMint
-    );
-    // Validate new_treasury is owned by Token program (it's a token account)
-    require!(
-        ctx.accounts.new_treasury.owner == &anchor_spl::token::ID,
-        PredictError::InvalidMint
-    );
-    // Validate treasury token account mint matches the new collateral mint
-    let treasury_data = anchor_spl::token::TokenAccount::try_deserialize(
-        &mut &ctx.accounts.new_treasury.data.borrow()[..]
-    ).map_err(|_| PredictError::InvalidMint)?;
-    require!(
-        treasury_data.mint == ctx.accounts.new_collateral_mint.key(),
-        PredictError::InvalidMint
-    );
+    // With approvals enabled this goes through execute_action
+    require!(!ctx.accounts.platform_config.approvals_enabled(), PredictError::ApprovalRequired);
     ctx.accounts.platform_config.collateral_mint = ctx.accounts.new_collateral_mint.key();
     ctx.accounts.platform_config.treasury = ctx.accounts.new_treasury.key();
     msg!("Collateral mint updated to {}", ctx.accounts.new_collateral_mint.key());
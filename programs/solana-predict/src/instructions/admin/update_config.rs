@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use crate::instructions::admin::pause::PlatformAdmin;
+use crate::state::FeeTier;
+use crate::errors::PredictError;
+
+/// Minimum distance (bps of current price) between an oracle market's threshold
+/// and the live feed price at creation. 0 disables the check.
+pub fn update_min_threshold_distance(ctx: Context<PlatformAdmin>, min_distance_bps: u16) -> Result<()> {
+    ctx.accounts.platform_config.min_threshold_distance_bps = min_distance_bps;
+    Ok(())
+}
+
+/// Minimum seconds between two market creations by the same creator. 0 disables.
+pub fn update_creation_cooldown(ctx: Context<PlatformAdmin>, cooldown_secs: i64) -> Result<()> {
+    require!(cooldown_secs >= 0, PredictError::InvalidTimestamps);
+    ctx.accounts.platform_config.creation_cooldown_secs = cooldown_secs;
+    Ok(())
+}
+
+/// Slippage applied when a bettor passes `min_shares_out == 0`, measured against
+/// the spot-price quote. 0 disables.
+pub fn update_default_max_slippage(ctx: Context<PlatformAdmin>, max_slippage_bps: u16) -> Result<()> {
+    require!(max_slippage_bps <= 10000, PredictError::SlippageExceeded);
+    ctx.accounts.platform_config.default_max_slippage_bps = max_slippage_bps;
+    Ok(())
+}
+
+/// Window after `end_timestamp` in which resolve_market accepts a price that is
+/// stale now but was fresh at `end_timestamp`. 0 disables.
+pub fn update_resolution_grace(ctx: Context<PlatformAdmin>, grace_secs: i64) -> Result<()> {
+    require!(grace_secs >= 0, PredictError::InvalidTimestamps);
+    ctx.accounts.platform_config.resolution_grace_secs = grace_secs;
+    Ok(())
+}
+
+/// Cap on markets created but not yet resolved. 0 = unlimited. Lowering it
+/// below the current count only blocks new creations.
+pub fn update_max_active_markets(ctx: Context<PlatformAdmin>, max_active_markets: u64) -> Result<()> {
+    ctx.accounts.platform_config.max_active_markets = max_active_markets;
+    Ok(())
+}
+
+/// Seconds past `end_timestamp` after which an unresolved market with a
+/// default outcome may be resolved by anyone.
+pub fn update_resolution_timeout(ctx: Context<PlatformAdmin>, timeout_secs: i64) -> Result<()> {
+    require!(timeout_secs >= 0, PredictError::InvalidTimestamps);
+    ctx.accounts.platform_config.resolution_timeout_secs = timeout_secs;
+    Ok(())
+}
+
+/// How far in the past a new market's `start_timestamp` may lie. 0 disables the check.
+pub fn update_max_backdate(ctx: Context<PlatformAdmin>, max_backdate_secs: i64) -> Result<()> {
+    require!(max_backdate_secs >= 0, PredictError::InvalidTimestamps);
+    ctx.accounts.platform_config.max_backdate_secs = max_backdate_secs;
+    Ok(())
+}
+
+/// Upper bound on a dispute's voting period, measured from when it was opened.
+pub fn update_max_dispute_voting(ctx: Context<PlatformAdmin>, max_voting_secs: i64) -> Result<()> {
+    require!(max_voting_secs > 0, PredictError::InvalidTimestamps);
+    ctx.accounts.platform_config.max_dispute_voting_secs = max_voting_secs;
+    Ok(())
+}
+
+/// Bond and challenge window for optimistic resolution (see propose_resolution).
+pub fn update_optimistic_resolution(ctx: Context<PlatformAdmin>, bond_lamports: u64, challenge_window_secs: i64) -> Result<()> {
+    // A zero bond would make proposals free to spam
+    require!(bond_lamports > 0, PredictError::InvalidDisputeBond);
+    require!(challenge_window_secs > 0, PredictError::InvalidTimestamps);
+    let platform = &mut ctx.accounts.platform_config;
+    platform.resolution_bond_lamports = bond_lamports;
+    platform.challenge_window_secs = challenge_window_secs;
+    Ok(())
+}
+
+/// Slots that must pass after a market's last bet before an oracle resolution.
+/// 0 disables the check.
+pub fn update_min_slots_before_resolve(ctx: Context<PlatformAdmin>, min_slots: u64) -> Result<()> {
+    ctx.accounts.platform_config.min_slots_before_resolve = min_slots;
+    Ok(())
+}
+
+/// Distinct bettors a market needs for `resolve_market` to pay out on the
+/// outcome; below it the market resolves Invalid. 0 disables the check.
+pub fn update_min_bettors_for_resolution(ctx: Context<PlatformAdmin>, min_bettors: u64) -> Result<()> {
+    ctx.accounts.platform_config.min_bettors_for_resolution = min_bettors;
+    Ok(())
+}
+
+/// Allows contested disputes to be settled by forking the market into one
+/// child per claimed outcome (see fork_market).
+pub fn update_forking_enabled(ctx: Context<PlatformAdmin>, enabled: bool) -> Result<()> {
+    ctx.accounts.platform_config.forking_enabled = enabled;
+    Ok(())
+}
+
+/// Positions owed less than this may be forfeited to the treasury once the
+/// claim window has passed (see forfeit_dust_positions). 0 disables forfeiture.
+pub fn update_min_claimable_dust(ctx: Context<PlatformAdmin>, min_claimable_dust: u64) -> Result<()> {
+    ctx.accounts.platform_config.min_claimable_dust = min_claimable_dust;
+    Ok(())
+}
+
+/// Time after resolution during which no position may be forfeited.
+pub fn update_claim_window(ctx: Context<PlatformAdmin>, claim_window_secs: i64) -> Result<()> {
+    require!(claim_window_secs >= 0, PredictError::InvalidTimestamps);
+    ctx.accounts.platform_config.claim_window_secs = claim_window_secs;
+    Ok(())
+}
+
+/// Wait between mark_for_close and close_market, during which cancel_close can
+/// still back out. 0 lets close_market run without a mark.
+pub fn update_close_grace(ctx: Context<PlatformAdmin>, close_grace_secs: i64) -> Result<()> {
+    require!(close_grace_secs >= 0, PredictError::InvalidTimestamps);
+    ctx.accounts.platform_config.close_grace_secs = close_grace_secs;
+    Ok(())
+}
+
+/// Flat minimum fee per bet, charged whenever the bps fee comes to less.
+/// 0 leaves the bps fee alone.
+pub fn update_min_fee_absolute(ctx: Context<PlatformAdmin>, min_fee_absolute: u64) -> Result<()> {
+    ctx.accounts.platform_config.min_fee_absolute = min_fee_absolute;
+    Ok(())
+}
+
+/// Cap on total collateral held across all markets; bets that would exceed it
+/// are rejected. 0 disables.
+pub fn update_max_platform_tvl(ctx: Context<PlatformAdmin>, max_platform_tvl: u64) -> Result<()> {
+    ctx.accounts.platform_config.max_platform_tvl = max_platform_tvl;
+    Ok(())
+}
+
+/// Volume tiers discounting bet fees for bettors tracked by a UserTier.
+/// An empty list turns discounts off.
+pub fn set_fee_tiers(ctx: Context<PlatformAdmin>, tiers: Vec<FeeTier>) -> Result<()> {
+    ctx.accounts.platform_config.set_fee_tiers(&tiers)
+}
+
+/// Slice (bps) of the platform's part of a bet fee paid to the bet's referrer.
+/// 0 disables referral payouts.
+pub fn update_referral_fee(ctx: Context<PlatformAdmin>, referral_fee_bps: u16) -> Result<()> {
+    require!(referral_fee_bps <= 10000, PredictError::FeeExceedsMax);
+    ctx.accounts.platform_config.referral_fee_bps = referral_fee_bps;
+    Ok(())
+}
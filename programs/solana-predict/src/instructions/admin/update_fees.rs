@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, Transfer, TokenAccount};
 use crate::state::PlatformConfig;
+use crate::events::FeesWithdrawn;
 use crate::errors::PredictError;
 
 #[derive(Accounts)]
@@ -29,38 +30,73 @@ pub struct WithdrawFees<'info> {
         constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
     )]
     pub platform_config: Account<'info, PlatformConfig>,
-    
-    /// CHECK: Configured treasury address, validated against platform_config constraint
-    #[account(mut, constraint = treasury.key() == platform_config.treasury)]
-    pub treasury: AccountInfo<'info>, // Assuming this is where fees ACCUMULATED?
-    // Wait, in `place_bet`, fees are sent DIRECTLY to `treasury`.
-    // So there is nothing to withdraw from the contract itself?
-    // Design said: "Admin withdraws accumulated fees from treasury vault."
-    // If treasury is a System Account (SOL) or Token Account owned by Admin?
-    // If fees are sent to `treasury` address immediately, then "Withdraw" implies moving from that address?
-    // Checks place_bet logic:
-    // `to: ctx.accounts.treasury.to_account_info()`
-    // So fees reside in the treasury account.
-    // If treasury account is a PDA owned by program, we need instruction to move it.
-    // But in `init_platform`, `treasury` is passed as an AccountInfo. We stored its key.
-    // If it's an arbitrary wallet (e.g. Admin's cold wallet), then we don't need withdrawal instruction.
-    // If it's a Program Owned Account (vault), we do.
-    // Design: "withdraw_fees(amount)".
-    // Implementation Plan: "Withdraw fees from treasury vault".
-    
-    // Let's assume Treasury is an ATA owned by the Program (PlatformConfig PDA).
-    // In `init_platform`: `pub treasury: AccountInfo`.
-    // We didn't enforce it to be a PDA.
-    // If the Admin set `treasury` to their own wallet, funds are already there.
-    
-    // I will implement `withdraw_fees` assuming the `treasury` stored in config IS the source,
-    // and we transfer FROM it to `destination`.
-    // This requires `treasury` to include the Program as authority or be a PDA.
-    // But `place_bet` sends TO it.
-    // If I just implement `update_fees`, that covers the parameter change.
-    // I will skip `withdraw_fees` implementation if `treasury` is external, 
-    // BUT to follow design I'll implement a `withdraw_from_vault` logic where `treasury` might be the vault?
-    
-    // Let's just implement `update_fees` in this file.
+
+    #[account(
+        mut,
+        address = platform_config.treasury,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = treasury.mint,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
     pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `amount` of accumulated fees out of the program-owned treasury
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    // With approvals enabled this goes through execute_action
+    require!(!ctx.accounts.platform_config.approvals_enabled(), PredictError::ApprovalRequired);
+    transfer_from_treasury(
+        &ctx.accounts.platform_config,
+        &ctx.accounts.treasury,
+        &ctx.accounts.destination,
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+    )
+}
+
+/// Pays `amount` from the treasury to `destination`, signed by the platform config
+pub fn transfer_from_treasury<'info>(
+    platform_config: &Account<'info, PlatformConfig>,
+    treasury: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, PredictError::InsufficientTreasury);
+    require!(amount <= treasury.amount, PredictError::InsufficientTreasury);
+
+    let seeds = &[
+        b"platform_config" as &[u8],
+        &[platform_config.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program,
+            Transfer {
+                from: treasury.to_account_info(),
+                to: destination.to_account_info(),
+                authority: platform_config.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    emit!(FeesWithdrawn {
+        mint: treasury.mint,
+        destination: destination.key(),
+        amount,
+        remaining: treasury.amount - amount,
+    });
+
+    Ok(())
 }
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Market, MarketStatus};
+use crate::errors::PredictError;
+use crate::utils::to_payout_amount;
+
+/// Cross-mint markets only: once resolved, the creator takes back the collateral
+/// already paid out in payout tokens (vault balance above total_collateral) and
+/// any reserve above what the remaining claims can still draw.
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct WithdrawPayoutSurplus<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = creator @ PredictError::Unauthorized,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.collateral_mint,
+        token::authority = creator,
+    )]
+    pub creator_collateral_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payout_reserve.mint,
+        token::authority = creator,
+    )]
+    pub creator_payout_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn process_withdraw_payout_surplus(ctx: Context<WithdrawPayoutSurplus>, _market_id: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(market.payout_mint.is_some(), PredictError::NoPayoutMint);
+    require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
+
+    let released_collateral = ctx.accounts.vault.amount.saturating_sub(market.total_collateral);
+    let outstanding = to_payout_amount(market.total_collateral, market.payout_rate)
+        .ok_or(PredictError::MathOverflow)?;
+    let reserve_surplus = ctx.accounts.payout_reserve.amount.saturating_sub(outstanding);
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if released_collateral > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.creator_collateral_ata.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            released_collateral,
+        )?;
+    }
+
+    if reserve_surplus > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payout_reserve.to_account_info(),
+                    to: ctx.accounts.creator_payout_ata.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            reserve_surplus,
+        )?;
+    }
+
+    msg!("Withdrew {} collateral and {} payout surplus", released_collateral, reserve_surplus);
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, Mint, TokenAccount, Burn, Transfer};
-use crate::state::{PlatformConfig, Market, MarketStatus, UserPosition, Outcome};
+use crate::state::{PlatformConfig, Market, MarketStatus, UserPosition, Outcome, PricingMode};
 use crate::events::BetCancelled;
 use crate::errors::PredictError;
+use crate::utils::{bps_fee, lp_share, record_fees, require_not_frozen, scale_to_display, FeeBreakdown};
 
 #[derive(Accounts)]
 pub struct CancelBet<'info> {
@@ -56,6 +57,7 @@ pub struct CancelBet<'info> {
     pub user_share_account: AccountInfo<'info>,
     
     #[account(
+        mut,
         seeds = [b"platform_config"],
         bump = platform_config.bump,
     )]
@@ -77,44 +79,61 @@ pub struct CancelBet<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn process_cancel_bet(
-    ctx: Context<CancelBet>,
-    market_id: u64,
-    shares_to_burn: u64,
-) -> Result<()> {
-    let market = &mut ctx.accounts.market;
-    let clock = Clock::get()?;
-
-    // Guards
-    require!(market.status == MarketStatus::Active, PredictError::MarketNotActive);
-    require!(clock.unix_timestamp < market.lock_timestamp, PredictError::BettingClosed);
+/// Token accounts touched when selling shares back to the pool
+pub struct CancelCpiAccounts<'info> {
+    pub share_mint: AccountInfo<'info>,
+    pub share_account: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub user_ata: AccountInfo<'info>,
+    pub treasury: AccountInfo<'info>,
+    pub user: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
 
-    // Identify which outcome user holds (simplification: assume user signals intent via share account or we check balance)
-    // Actually, checking user_position is better
-    // But `cancel_bet` usually requires specifying WHICH side if user holds both (hedging).
-    // The instruction args in design only say `shares_to_burn`.
-    // We infer side from `user_share_account` mint?
-    
-    // Let's verify `user_share_account` mint matches `yes_mint` or `no_mint`.
-    // We can load the account data to check mint.
-    let user_share_acc = TokenAccount::try_deserialize(&mut &ctx.accounts.user_share_account.data.borrow()[..])?;
-    let outcome = if user_share_acc.mint == market.yes_mint {
-        Outcome::Yes
-    } else if user_share_acc.mint == market.no_mint {
-        Outcome::No
-    } else {
-        return err!(PredictError::InvalidOutcome);
-    };
+/// Result of a CPMM sell against the current reserves
+pub struct CancelQuote {
+    pub raw_refund: u64,
+    pub fee: u64,
+    pub refund: u64,
+    pub new_yes: u64,
+    pub new_no: u64,
+}
 
+pub fn quote_cancel(market: &Market, outcome: Outcome, shares_to_burn: u64, now: i64) -> Result<CancelQuote> {
     // CPMM sell: reverse of buy
     // Selling YES: add shares back to yes_pool, remove collateral from no_pool
     // Selling NO:  add shares back to no_pool, remove collateral from yes_pool
-    let yes_pool = market.total_yes_shares as u128;
-    let no_pool = market.total_no_shares as u128;
+    // Priced against the virtual reserves, like place_bet
+    let (yes_pool, no_pool) = market.virtual_reserves();
+    let (yes_pool, no_pool) = (yes_pool as u128, no_pool as u128);
     let k = yes_pool.checked_mul(no_pool).ok_or(PredictError::MathOverflow)?;
     let burn_amount = shares_to_burn as u128;
 
-    let (raw_refund, new_yes, new_no) = if outcome == Outcome::Yes {
+    // Parimutuel: the stake comes straight back out of its bucket
+    let (raw_refund, new_yes, new_no) = if market.pricing_mode == PricingMode::Parimutuel {
+        (shares_to_burn, market.total_yes_shares, market.total_no_shares)
+    } else {
+        let (raw_refund, new_yes, new_no) = cpmm_sell(yes_pool, no_pool, k, burn_amount, outcome)?;
+        // Back to real reserves: the refund can't take more than the pool holds
+        (
+            raw_refund,
+            new_yes.checked_sub(market.virtual_liquidity).ok_or(PredictError::InsufficientLiquidity)?,
+            new_no.checked_sub(market.virtual_liquidity).ok_or(PredictError::InsufficientLiquidity)?,
+        )
+    };
+
+    require!(raw_refund > 0, PredictError::MathOverflow);
+
+    // Exit fee: the market's fee at `now`, rounded up like the bet fee
+    let fee = bps_fee(raw_refund, market.fee_bps_at(now));
+    let refund = raw_refund.checked_sub(fee).ok_or(PredictError::MathOverflow)?;
+
+    Ok(CancelQuote { raw_refund, fee, refund, new_yes, new_no })
+}
+
+/// (refund, new YES pool, new NO pool) for selling `burn_amount` into the pools
+fn cpmm_sell(yes_pool: u128, no_pool: u128, k: u128, burn_amount: u128, outcome: Outcome) -> Result<(u64, u64, u64)> {
+    Ok(if outcome == Outcome::Yes {
         let new_yes_pool = yes_pool.checked_add(burn_amount).ok_or(PredictError::MathOverflow)?;
         let new_no_pool = k.checked_div(new_yes_pool).ok_or(PredictError::MathOverflow)?;
         let refund = (no_pool.checked_sub(new_no_pool).ok_or(PredictError::MathOverflow)?) as u64;
@@ -124,22 +143,31 @@ pub fn process_cancel_bet(
         let new_yes_pool = k.checked_div(new_no_pool).ok_or(PredictError::MathOverflow)?;
         let refund = (yes_pool.checked_sub(new_yes_pool).ok_or(PredictError::MathOverflow)?) as u64;
         (refund, new_yes_pool as u64, new_no_pool as u64)
-    };
-
-    require!(raw_refund > 0, PredictError::MathOverflow);
+    })
+}
 
-    // Exit fee: use market.fee_bps (round up to prevent micro-transaction fee bypass)
-    let fee = ((raw_refund as u128 * market.fee_bps as u128 + 9999) / 10000) as u64;
-    let refund = raw_refund.checked_sub(fee).ok_or(PredictError::MathOverflow)?;
+/// Sells `shares_to_burn` of `outcome` back to the pool: burns the shares, pays the
+/// refund (net of exit fee) from the vault and updates reserves and the position.
+/// Callers are responsible for status/timing guards and account validation.
+/// Returns the refund paid to the user.
+pub fn execute_cancel<'info>(
+    market: &mut Account<'info, Market>,
+    position: &mut UserPosition,
+    platform: &mut PlatformConfig,
+    outcome: Outcome,
+    shares_to_burn: u64,
+    accounts: CancelCpiAccounts<'info>,
+) -> Result<u64> {
+    let CancelQuote { raw_refund, fee, refund, new_yes, new_no } = quote_cancel(market, outcome, shares_to_burn, Clock::get()?.unix_timestamp)?;
 
     // Burn Shares
     token::burn(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.clone(),
             Burn {
-                mint: if outcome == Outcome::Yes { ctx.accounts.yes_mint.to_account_info() } else { ctx.accounts.no_mint.to_account_info() },
-                from: ctx.accounts.user_share_account.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
+                mint: accounts.share_mint,
+                from: accounts.share_account,
+                authority: accounts.user,
             },
         ),
         shares_to_burn,
@@ -156,10 +184,10 @@ pub fn process_cancel_bet(
 
     token::transfer(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.clone(),
             Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.user_ata.to_account_info(),
+                from: accounts.vault.clone(),
+                to: accounts.user_ata,
                 authority: market.to_account_info(),
             },
             signer,
@@ -171,43 +199,127 @@ pub fn process_cancel_bet(
     // If fee > 0, does the Vault keep it or we send to treasury?
     // Design says "Transfer USDC from vault -> user". It implies vault keeps fee (collateral surplus).
     // Or we send fee to treasury.
-    if fee > 0 {
+    // LPs' part of the exit fee never leaves the vault
+    let lp_fee = lp_share(fee, market.active_lp_fee_bps());
+    let treasury_fee = fee - lp_fee;
+    if treasury_fee > 0 {
          token::transfer(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                accounts.token_program,
                 Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.treasury.to_account_info(),
+                    from: accounts.vault,
+                    to: accounts.treasury,
                     authority: market.to_account_info(),
                 },
                 signer,
             ),
-            fee,
+            treasury_fee,
         )?;
     }
+    record_fees(&mut market.fee_ledger, &FeeBreakdown {
+        total_fee: fee,
+        platform_fee: treasury_fee,
+        lp_fee,
+        net_amount: refund,
+        ..FeeBreakdown::default()
+    })?;
+    market.lp_fees_accrued = market.lp_fees_accrued.checked_add(lp_fee).ok_or(PredictError::MathOverflow)?;
 
     // Update State (CPMM pool reserves)
-    market.total_collateral = market.total_collateral.checked_sub(raw_refund).ok_or(PredictError::InsufficientVault)?;
+    let paid_out = raw_refund - lp_fee;
+    market.total_collateral = market.total_collateral.checked_sub(paid_out).ok_or(PredictError::InsufficientVault)?;
+    platform.debit_tvl(paid_out);
     market.total_yes_shares = new_yes;
     market.total_no_shares = new_no;
 
-    if outcome == Outcome::Yes {
-        ctx.accounts.user_position.yes_shares = ctx.accounts.user_position.yes_shares.checked_sub(shares_to_burn).ok_or(PredictError::InsufficientShares)?;
+    // Cost basis leaves with the shares at the average entry price
+    let (held, cost_basis) = if outcome == Outcome::Yes {
+        (&mut position.yes_shares, &mut position.yes_cost_basis)
     } else {
-        ctx.accounts.user_position.no_shares = ctx.accounts.user_position.no_shares.checked_sub(shares_to_burn).ok_or(PredictError::InsufficientShares)?;
-    }
+        (&mut position.no_shares, &mut position.no_cost_basis)
+    };
+    let cost_removed = if *held == 0 {
+        0
+    } else {
+        (*cost_basis as u128 * shares_to_burn.min(*held) as u128 / *held as u128) as u64
+    };
+    *held = held.checked_sub(shares_to_burn).ok_or(PredictError::InsufficientShares)?;
+    *cost_basis -= cost_removed;
+    position.total_refunded = position.total_refunded.checked_add(refund).ok_or(PredictError::MathOverflow)?;
     
     // We should also decrement `total_deposited` in position if we track net?
     // Or maybe not. Let's leave it as cumulative deposited?
     // Actually `total_deposited` usually means net principal exposed.
     // Let's decrement it by `refund` (principal returned).
-    ctx.accounts.user_position.total_deposited = ctx.accounts.user_position.total_deposited.saturating_sub(refund); 
+    // The market-wide total tracks the same principal for Invalid refunds.
+    let principal_returned = refund.min(position.total_deposited);
+    position.total_deposited -= principal_returned;
+    market.total_deposited = market.total_deposited.saturating_sub(principal_returned);
+
+    Ok(refund)
+}
+
+pub fn process_cancel_bet(
+    ctx: Context<CancelBet>,
+    market_id: u64,
+    shares_to_burn: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(market.status == MarketStatus::Active, PredictError::MarketNotActive);
+    require!(clock.unix_timestamp < market.lock_timestamp, PredictError::BettingClosed);
+
+    // Identify which outcome user holds (simplification: assume user signals intent via share account or we check balance)
+    // Actually, checking user_position is better
+    // But `cancel_bet` usually requires specifying WHICH side if user holds both (hedging).
+    // The instruction args in design only say `shares_to_burn`.
+    // We infer side from `user_share_account` mint?
+    
+    // Let's verify `user_share_account` mint matches `yes_mint` or `no_mint`.
+    // We can load the account data to check mint.
+    // Cached ATAs from init_position skip the deserialization.
+    let outcome = match ctx.accounts.user_position.cached_outcome(ctx.accounts.user_share_account.key) {
+        Some(outcome) => outcome,
+        None => {
+            let user_share_acc = TokenAccount::try_deserialize(&mut &ctx.accounts.user_share_account.data.borrow()[..])?;
+            if user_share_acc.mint == market.yes_mint {
+                Outcome::Yes
+            } else if user_share_acc.mint == market.no_mint {
+                Outcome::No
+            } else {
+                return err!(PredictError::InvalidOutcome);
+            }
+        }
+    };
+
+    // Share mints have no freeze authority; only the collateral side can be frozen
+    require_not_frozen(&[&ctx.accounts.vault, &ctx.accounts.user_ata, &ctx.accounts.treasury])?;
+
+    let refund = execute_cancel(
+        market,
+        &mut ctx.accounts.user_position,
+        &mut ctx.accounts.platform_config,
+        outcome,
+        shares_to_burn,
+        CancelCpiAccounts {
+            share_mint: if outcome == Outcome::Yes { ctx.accounts.yes_mint.to_account_info() } else { ctx.accounts.no_mint.to_account_info() },
+            share_account: ctx.accounts.user_share_account.to_account_info(),
+            vault: ctx.accounts.vault.to_account_info(),
+            user_ata: ctx.accounts.user_ata.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            user: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    )?;
 
     emit!(BetCancelled {
         market_id,
         user: ctx.accounts.user.key(),
         shares_burned: shares_to_burn,
         refund_amount: refund,
+        scaled_amount_1e6: scale_to_display(refund, ctx.accounts.collateral_mint.decimals),
     });
 
     Ok(())
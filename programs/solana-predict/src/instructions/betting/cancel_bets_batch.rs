@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Mint, TokenAccount};
+use crate::state::{PlatformConfig, Market, MarketStatus, UserPosition, Outcome};
+use crate::events::BetCancelled;
+use crate::errors::PredictError;
+use crate::utils::scale_to_display;
+use super::cancel_bet::{execute_cancel, CancelCpiAccounts};
+
+/// Max markets per batch — 7 accounts each keeps the transaction within size/compute limits
+pub const MAX_BATCH_CANCEL_MARKETS: usize = 3;
+/// remaining_accounts per market:
+/// [market, yes_mint, no_mint, vault, user_position, user_yes_account, user_no_account]
+pub const BATCH_CANCEL_ACCOUNTS_PER_MARKET: usize = 7;
+
+#[derive(Accounts)]
+pub struct CancelBetsBatch<'info> {
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == platform_config.treasury,
+        constraint = treasury.mint == collateral_mint.key() @ PredictError::InvalidMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub collateral_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Cancels the user's full position (both sides) in each market passed via
+/// `remaining_accounts`. Markets that are no longer open for cancellation
+/// (locked, resolved, paused...) are skipped; `min_refunds[i]` is the slippage
+/// floor on the total refund for market `i`.
+pub fn process_cancel_bets_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelBetsBatch<'info>>,
+    min_refunds: Vec<u64>,
+) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len().is_multiple_of(BATCH_CANCEL_ACCOUNTS_PER_MARKET),
+        PredictError::InvalidBatch
+    );
+    let market_count = remaining.len() / BATCH_CANCEL_ACCOUNTS_PER_MARKET;
+    require!(market_count <= MAX_BATCH_CANCEL_MARKETS, PredictError::InvalidBatch);
+    require!(min_refunds.len() == market_count, PredictError::InvalidBatch);
+
+    let clock = Clock::get()?;
+    let user_key = ctx.accounts.user.key();
+
+    for (i, group) in remaining.chunks(BATCH_CANCEL_ACCOUNTS_PER_MARKET).enumerate() {
+        let [market_info, yes_mint_info, no_mint_info, vault_info, position_info, yes_share_info, no_share_info] = group else {
+            return err!(PredictError::InvalidBatch);
+        };
+
+        let mut market: Account<'info, Market> = Account::try_from(market_info)?;
+        require!(market_info.is_writable, PredictError::InvalidBatch);
+
+        // Skip markets that can no longer be exited via cancel
+        if market.status != MarketStatus::Active || clock.unix_timestamp >= market.lock_timestamp {
+            msg!("Skipping market {}: not open for cancellation", market.market_id);
+            continue;
+        }
+
+        require!(yes_mint_info.key() == market.yes_mint, PredictError::InvalidMint);
+        require!(no_mint_info.key() == market.no_mint, PredictError::InvalidMint);
+        require!(vault_info.key() == market.vault, PredictError::InvalidMint);
+        require!(market.collateral_mint == ctx.accounts.collateral_mint.key(), PredictError::InvalidMint);
+
+        let (position_pda, _) = Pubkey::find_program_address(
+            &[b"position", market_info.key.as_ref(), user_key.as_ref()],
+            ctx.program_id,
+        );
+        require!(position_info.key() == position_pda, PredictError::NoPosition);
+        let mut position: Account<'info, UserPosition> = Account::try_from(position_info)?;
+
+        let mut total_refund: u64 = 0;
+        for (outcome, mint_info, share_info) in [
+            (Outcome::Yes, yes_mint_info, yes_share_info),
+            (Outcome::No, no_mint_info, no_share_info),
+        ] {
+            let held = if outcome == Outcome::Yes { position.yes_shares } else { position.no_shares };
+            if held == 0 {
+                continue;
+            }
+            let share_acc = TokenAccount::try_deserialize(&mut &share_info.data.borrow()[..])?;
+            require!(share_acc.mint == mint_info.key(), PredictError::InvalidMint);
+            require!(share_acc.owner == user_key, PredictError::Unauthorized);
+
+            let shares_to_burn = held.min(share_acc.amount);
+            if shares_to_burn == 0 {
+                continue;
+            }
+
+            let refund = execute_cancel(
+                &mut market,
+                &mut position,
+                &mut ctx.accounts.platform_config,
+                outcome,
+                shares_to_burn,
+                CancelCpiAccounts {
+                    share_mint: mint_info.clone(),
+                    share_account: share_info.clone(),
+                    vault: vault_info.clone(),
+                    user_ata: ctx.accounts.user_ata.to_account_info(),
+                    treasury: ctx.accounts.treasury.to_account_info(),
+                    user: ctx.accounts.user.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            )?;
+            total_refund = total_refund.checked_add(refund).ok_or(PredictError::MathOverflow)?;
+
+            emit!(BetCancelled {
+                market_id: market.market_id,
+                user: user_key,
+                shares_burned: shares_to_burn,
+                refund_amount: refund,
+                scaled_amount_1e6: scale_to_display(refund, ctx.accounts.collateral_mint.decimals),
+            });
+        }
+
+        require!(total_refund >= min_refunds[i], PredictError::SlippageExceeded);
+
+        // remaining_accounts are not persisted automatically
+        market.exit(ctx.program_id)?;
+        position.exit(ctx.program_id)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Burn, Transfer};
+use crate::state::{PlatformConfig, Market, MarketStatus, UserPosition, Outcome};
+use crate::events::PayoutClaimed;
+use crate::errors::PredictError;
+use crate::utils::scale_to_display;
+use super::claim_payout::{quote_claim, settle_claim, ClaimQuote};
+use super::place_bet::{execute_bet, BetCpiAccounts};
+
+#[derive(Accounts)]
+#[instruction(market_id: u64, next_market_id: u64)]
+pub struct ClaimAndRollover<'info> {
+    // --- Resolved market being claimed ---
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = collateral_mint
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    /// CHECK: Validated to match outcome mint
+    #[account(mut)]
+    pub user_share_account: AccountInfo<'info>,
+
+    // --- Active market receiving the proceeds ---
+    #[account(
+        mut,
+        seeds = [b"market", next_market_id.to_le_bytes().as_ref()],
+        bump = next_market.bump,
+    )]
+    pub next_market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"yes_mint", next_market.key().as_ref()],
+        bump
+    )]
+    pub next_yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"no_mint", next_market.key().as_ref()],
+        bump
+    )]
+    pub next_no_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", next_market.key().as_ref()],
+        bump,
+        token::mint = collateral_mint
+    )]
+    pub next_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"position", next_market.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = UserPosition::LEN
+    )]
+    pub next_user_position: Box<Account<'info, UserPosition>>,
+
+    /// CHECK: Validated in execute_bet to match outcome mint
+    #[account(mut)]
+    pub next_user_share_account: AccountInfo<'info>,
+
+    // --- Shared ---
+    #[account(
+        mut,
+        associated_token::mint = market.collateral_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == platform_config.treasury,
+        constraint = treasury.mint == collateral_mint.key() @ PredictError::InvalidMint,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fees", next_market.key().as_ref()],
+        bump,
+    )]
+    pub next_creator_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claims a resolved position and bets the whole payout on `outcome` in
+/// `next_market` within the same transaction. Only same-mint markets sharing
+/// a collateral mint can be chained.
+pub fn process_claim_and_rollover(
+    ctx: Context<ClaimAndRollover>,
+    market_id: u64,
+    _next_market_id: u64,
+    outcome: Outcome,
+    min_shares: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let next_market = &ctx.accounts.next_market;
+
+    // Proceeds must be the collateral the next market bets in
+    require!(market.payout_mint.is_none(), PredictError::InvalidMint);
+    require!(next_market.collateral_mint == market.collateral_mint, PredictError::InvalidMint);
+    require!(next_market.status == MarketStatus::Active, PredictError::MarketNotActive);
+
+    // 1. Claim
+    let user_share_acc = TokenAccount::try_deserialize(&mut &ctx.accounts.user_share_account.data.borrow()[..])?;
+    let quote = quote_claim(
+        market,
+        &ctx.accounts.user_position,
+        &user_share_acc,
+        ctx.accounts.yes_mint.supply,
+        ctx.accounts.no_mint.supply,
+        ctx.accounts.vault.amount,
+    )?;
+    let ClaimQuote { payout, shares, .. } = quote;
+
+    let burn_mint = if quote.burn_yes {
+        ctx.accounts.yes_mint.to_account_info()
+    } else {
+        ctx.accounts.no_mint.to_account_info()
+    };
+    if shares > 0 {
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: burn_mint,
+                    from: ctx.accounts.user_share_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+    }
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Payout lands in the user's ATA so the bet leg is a plain place_bet
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.user_ata.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        payout,
+    )?;
+
+    settle_claim(market, &mut ctx.accounts.user_position, &mut ctx.accounts.platform_config, &quote)?;
+
+    emit!(PayoutClaimed {
+        market_id,
+        user: ctx.accounts.user.key(),
+        amount: payout,
+        scaled_amount_1e6: scale_to_display(payout, ctx.accounts.collateral_mint.decimals),
+        payout_mint: market.collateral_mint,
+        shares_burned: shares,
+    });
+
+    // 2. Bet the proceeds
+    let share_mint = if outcome == Outcome::Yes {
+        ctx.accounts.next_yes_mint.to_account_info()
+    } else {
+        ctx.accounts.next_no_mint.to_account_info()
+    };
+    let accounts = BetCpiAccounts {
+        share_mint,
+        share_account: ctx.accounts.next_user_share_account.to_account_info(),
+        user_ata: ctx.accounts.user_ata.to_account_info(),
+        treasury: ctx.accounts.treasury.to_account_info(),
+        creator_fee_escrow: ctx.accounts.next_creator_fee_escrow.to_account_info(),
+        referral: None,
+        user: ctx.accounts.user.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+
+    execute_bet(
+        &mut ctx.accounts.next_market,
+        &mut ctx.accounts.next_user_position,
+        &mut ctx.accounts.next_vault,
+        &mut ctx.accounts.platform_config,
+        outcome,
+        payout,
+        min_shares,
+        ctx.accounts.collateral_mint.decimals,
+        0,
+        accounts,
+    )?;
+    ctx.accounts.next_user_position.bump = ctx.bumps.next_user_position;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::MarketStatus;
+use crate::events::EmergencyRefundClaimed;
+use crate::errors::PredictError;
+use crate::utils::scale_to_display;
+use super::claim_refund::{burn_and_refund, ClaimRefund};
+
+/// During an emergency shutdown, pays the user's pro-rata slice of an
+/// unresolved market's pool (see `burn_and_refund`). Takes the same accounts
+/// as claim_refund. The LPs' part of the pool stays for remove_liquidity.
+pub fn process_claim_emergency_refund(ctx: Context<ClaimRefund>, market_id: u64) -> Result<()> {
+    // Guards
+    require!(ctx.accounts.platform_config.shutdown, PredictError::PlatformNotShutdown);
+    require!(
+        !matches!(ctx.accounts.market.status, MarketStatus::Resolved | MarketStatus::Cancelled | MarketStatus::Forked),
+        PredictError::AlreadyResolved
+    );
+
+    // Unspent early-bird budget and the LPs' deposits and fees aren't part of the pool
+    let pool = ctx.accounts.market.distributable_at_resolution(ctx.accounts.vault.amount);
+    let (shares, refund) = burn_and_refund(ctx.accounts, pool)?;
+
+    let market = &mut ctx.accounts.market;
+    market.total_collateral = market.total_collateral.checked_sub(refund).ok_or(PredictError::InsufficientVault)?;
+    ctx.accounts.platform_config.debit_tvl(refund);
+    let position = &mut ctx.accounts.user_position;
+    if position.round == market.current_round {
+        market.total_deposited = market.total_deposited.saturating_sub(position.total_deposited);
+    }
+    position.total_deposited = 0;
+
+    emit!(EmergencyRefundClaimed {
+        market_id,
+        user: ctx.accounts.user.key(),
+        shares_burned: shares,
+        amount: refund,
+        scaled_amount_1e6: scale_to_display(refund, ctx.accounts.collateral_mint.decimals),
+    });
+
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, Mint, TokenAccount, Burn, Transfer};
-use crate::state::{Market, MarketStatus, UserPosition, Outcome};
+use crate::state::{DustRecipient, PlatformConfig, Market, MarketStatus, UserPosition, Outcome, FORFEITED_ROUND};
 use crate::events::PayoutClaimed;
 use crate::errors::PredictError;
+use crate::utils::{pro_rata_payout, require_not_frozen, scale_to_display, to_payout_amount};
 
 #[derive(Accounts)]
 pub struct ClaimPayout<'info> {
@@ -60,27 +61,62 @@ pub struct ClaimPayout<'info> {
 
     pub collateral_mint: Account<'info, Mint>,
 
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Payout reserve (optional - only for markets with a separate payout mint)
+    #[account(
+        mut,
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    /// User's payout-mint token account (required alongside payout_reserve)
+    #[account(mut)]
+    pub user_payout_account: Option<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn process_claim_payout(
-    ctx: Context<ClaimPayout>,
-    market_id: u64,
-) -> Result<()> {
-    let market = &mut ctx.accounts.market;
-    
+/// What a resolved position is owed, before any tokens move
+pub struct ClaimQuote {
+    pub outcome: Outcome,
+    pub payout: u64,
+    pub shares: u64,
+    pub deposited: u64,
+    pub burn_yes: bool,
+    /// Rounding residue withheld from a final claim (see `DustRecipient`)
+    pub dust: u64,
+}
+
+/// Validates the claim and computes the collateral payout for the shares in
+/// `user_share_acc` (or, for Invalid, the position's principal).
+pub fn quote_claim(
+    market: &Market,
+    position: &UserPosition,
+    user_share_acc: &TokenAccount,
+    yes_supply: u64,
+    no_supply: u64,
+    vault_amount: u64,
+) -> Result<ClaimQuote> {
     // Guards
     require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
     let outcome = market.resolved_outcome.clone().ok_or(PredictError::MarketNotResolved)?;
-    require!(ctx.accounts.user_position.total_claimed == 0, PredictError::AlreadyClaimed);
+    require!(position.round != FORFEITED_ROUND, PredictError::PositionForfeited);
+    // Split pays each side separately, so a hedged position claims once per
+    // share account (burning the shares prevents a repeat)
+    require!(position.total_claimed == 0 || outcome == Outcome::Split, PredictError::AlreadyClaimed);
 
-    // Read user balance
-    let user_share_acc = TokenAccount::try_deserialize(&mut &ctx.accounts.user_share_account.data.borrow()[..])?;
-    
     // For Invalid outcome, user can claim with either YES or NO shares (pro-rata across total supply)
+    // For Split, either mint is paid from its side's allocation
     // For Yes/No outcomes, user must hold the winning mint
-    if outcome == Outcome::Invalid {
+    if outcome == Outcome::Invalid || outcome == Outcome::Split {
         // Accept either YES or NO mint for Invalid outcome
         require!(
             user_share_acc.mint == market.yes_mint || user_share_acc.mint == market.no_mint,
@@ -96,31 +132,145 @@ pub fn process_claim_payout(
     }
     
     let shares = user_share_acc.amount;
-    require!(shares > 0, PredictError::NoPosition);
+    let (yes_supply, no_supply) = market.claim_supplies(yes_supply, no_supply);
+
+    // Invalid refunds are weighted by principal, not shares: CPMM pricing gives
+    // YES and NO buyers different shares per unit deposited.
+    let deposited = if market.current_round == position.round {
+        position.total_deposited
+    } else {
+        0
+    };
+    if outcome == Outcome::Invalid {
+        require!(deposited > 0, PredictError::NoPosition);
+    } else {
+        require!(shares > 0, PredictError::NoPosition);
+    }
 
     // Calculate Payout using mint supply (total outstanding winning tokens)
     // In CPMM, market.total_yes/no_shares are pool reserves, NOT total supply.
     // We use the mint's supply to get the actual total outstanding tokens.
+    // In Parimutuel mode the supply is the winning bucket, so winners split the pot.
+    // The pool is the distributable amount fixed at resolution, not the raw
+    // total_collateral, so anything else in the vault is never paid out.
+    let pool = market.distributable_collateral;
     let payout = if outcome == Outcome::Invalid {
-        let total_supply = ctx.accounts.yes_mint.supply + ctx.accounts.no_mint.supply;
-        if total_supply == 0 { 0 } else {
-            (shares as u128 * market.total_collateral as u128 / total_supply as u128) as u64
+        pro_rata_payout(deposited, pool, market.total_deposited)
+    } else if outcome == Outcome::Split {
+        let (yes_pool, no_pool) = market.split_pools();
+        if user_share_acc.mint == market.yes_mint {
+            pro_rata_payout(shares, yes_pool, yes_supply)
+        } else {
+            pro_rata_payout(shares, no_pool, no_supply)
         }
     } else {
         let winning_supply = match outcome {
-            Outcome::Yes => ctx.accounts.yes_mint.supply,
-            Outcome::No => ctx.accounts.no_mint.supply,
+            Outcome::Yes => yes_supply,
+            Outcome::No => no_supply,
             _ => 0,
         };
-        if winning_supply == 0 { 0 } else {
-            (shares as u128 * market.total_collateral as u128 / winning_supply as u128) as u64
-        }
+        pro_rata_payout(shares, pool, winning_supply)
     };
 
-    // Cap payout to vault balance to prevent last-claimer underflow from rounding
-    let payout = payout.min(ctx.accounts.vault.amount);
+    // Cap payout to vault balance to prevent last-claimer underflow from rounding.
+    // Cross-mint vaults also hold collateral the creator may withdraw, so cap to
+    // the pool instead.
+    let payout = if market.payout_mint.is_some() {
+        payout.min(market.total_collateral)
+    } else {
+        payout.min(vault_amount)
+    };
+
+    // The final claim drains the pool, rounding residue included. Unless the
+    // market gives that residue to the last claimer, pay its exact share of
+    // the pool as it stood at resolution and withhold the rest. Split sides
+    // keep separate pools and always pay the remainder out.
+    let (weight, remaining_weight) = if outcome == Outcome::Invalid {
+        (deposited, market.total_deposited)
+    } else {
+        (shares, match outcome {
+            Outcome::Yes => yes_supply,
+            Outcome::No => no_supply,
+            _ => 0,
+        })
+    };
+    let (payout, dust) = if market.dust_recipient != DustRecipient::LastClaimer
+        && market.payout_mint.is_none()
+        && outcome != Outcome::Split
+        && weight == remaining_weight
+    {
+        let initial_pool = pool.checked_add(market.claimed_payout).ok_or(PredictError::MathOverflow)?;
+        let initial_weight = remaining_weight.checked_add(market.claimed_weight).ok_or(PredictError::MathOverflow)?;
+        let exact = pro_rata_payout(weight, initial_pool, initial_weight).min(payout);
+        (exact, pool.saturating_sub(exact))
+    } else {
+        (payout, 0)
+    };
     require!(payout > 0, PredictError::NoPosition);
 
+    Ok(ClaimQuote {
+        outcome,
+        payout,
+        shares,
+        deposited,
+        burn_yes: user_share_acc.mint == market.yes_mint,
+        dust,
+    })
+}
+
+/// Books a paid claim against the market and the position. Withheld dust
+/// leaves the pool with it.
+pub fn settle_claim(market: &mut Market, position: &mut UserPosition, platform: &mut PlatformConfig, quote: &ClaimQuote) -> Result<()> {
+    let released = quote.payout.checked_add(quote.dust).ok_or(PredictError::MathOverflow)?;
+    market.total_collateral = market.total_collateral.checked_sub(released).ok_or(PredictError::InsufficientVault)?;
+    platform.debit_tvl(released);
+    market.distributable_collateral = market.distributable_collateral.checked_sub(released).ok_or(PredictError::InsufficientVault)?;
+    market.pending_dust = market.pending_dust.checked_add(quote.dust).ok_or(PredictError::MathOverflow)?;
+    market.claimed_payout = market.claimed_payout.checked_add(quote.payout).ok_or(PredictError::MathOverflow)?;
+    let weight = if quote.outcome == Outcome::Invalid { quote.deposited } else { quote.shares };
+    market.claimed_weight = market.claimed_weight.checked_add(weight).ok_or(PredictError::MathOverflow)?;
+    if quote.outcome == Outcome::Invalid {
+        market.total_deposited = market.total_deposited.saturating_sub(quote.deposited);
+    }
+    if quote.outcome == Outcome::Split && quote.burn_yes {
+        market.split_yes_pool = market.split_yes_pool.saturating_sub(quote.payout);
+    }
+    // Note: pool reserves (total_yes/no_shares) are NOT decremented during payout.
+    // In CPMM, these track AMM pool reserves, not token supply.
+    // The burn reduces mint supply, which is used as the payout denominator.
+    
+    position.total_claimed = position.total_claimed
+        .checked_add(quote.payout)
+        .ok_or(PredictError::MathOverflow)?;
+    // Claimed shares are settled. Outside Split a claim settles the whole
+    // position: the losing side is worth nothing.
+    if quote.outcome != Outcome::Split || quote.burn_yes {
+        position.yes_cost_basis = 0;
+    }
+    if quote.outcome != Outcome::Split || !quote.burn_yes {
+        position.no_cost_basis = 0;
+    }
+    Ok(())
+}
+
+pub fn process_claim_payout(
+    ctx: Context<ClaimPayout>,
+    market_id: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Read user balance
+    let user_share_acc = TokenAccount::try_deserialize(&mut &ctx.accounts.user_share_account.data.borrow()[..])?;
+    let quote = quote_claim(
+        market,
+        &ctx.accounts.user_position,
+        &user_share_acc,
+        ctx.accounts.yes_mint.supply,
+        ctx.accounts.no_mint.supply,
+        ctx.accounts.vault.amount,
+    )?;
+    let ClaimQuote { payout, shares, .. } = quote;
+
     // Burn Winning Shares
     // Wait, if I burn shares, I manipulate `total_winning_shares` for the NEXT claimer?
     // NO. `market.total_winning_shares` MUST remain constant during payout phase, 
@@ -135,23 +285,25 @@ pub fn process_claim_payout(
     // It works out proportionally IF we burn and transfer.
     
     // Burn shares from the correct mint — for Invalid outcome, determine mint from user's share account
-    let burn_mint = if user_share_acc.mint == market.yes_mint {
+    let burn_mint = if quote.burn_yes {
         ctx.accounts.yes_mint.to_account_info()
     } else {
         ctx.accounts.no_mint.to_account_info()
     };
 
-    token::burn(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: burn_mint,
-                from: ctx.accounts.user_share_account.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        shares,
-    )?;
+    if shares > 0 {
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: burn_mint,
+                    from: ctx.accounts.user_share_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+    }
 
     // Transfer Payout
     let market_id_bytes = market.market_id.to_le_bytes();
@@ -162,33 +314,60 @@ pub fn process_claim_payout(
     ];
     let signer = &[&seeds[..]];
 
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.user_ata.to_account_info(),
-                authority: market.to_account_info(),
-            },
-            signer,
-        ),
-        payout,
-    )?;
+    // Cross-mint markets pay the collateral value out of the reserve at the fixed rate
+    let (paid_amount, paid_mint) = if let Some(payout_mint) = market.payout_mint {
+        let reserve = ctx.accounts.payout_reserve.as_ref()
+            .ok_or(PredictError::InsufficientPayoutReserve)?;
+        let user_payout_account = ctx.accounts.user_payout_account.as_ref()
+            .ok_or(PredictError::InvalidMint)?;
+        require!(user_payout_account.mint == payout_mint, PredictError::InvalidMint);
+        require!(user_payout_account.owner == ctx.accounts.user.key(), PredictError::Unauthorized);
+        require_not_frozen(&[reserve, user_payout_account])?;
+
+        let amount = to_payout_amount(payout, market.payout_rate)
+            .ok_or(PredictError::MathOverflow)?
+            .min(reserve.amount);
+        require!(amount > 0, PredictError::InsufficientPayoutReserve);
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: reserve.to_account_info(),
+                    to: user_payout_account.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+        (amount, payout_mint)
+    } else {
+        require_not_frozen(&[&ctx.accounts.vault, &ctx.accounts.user_ata])?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            payout,
+        )?;
+        (payout, market.collateral_mint)
+    };
 
     // Update State
-    market.total_collateral = market.total_collateral.checked_sub(payout).ok_or(PredictError::InsufficientVault)?;
-    // Note: pool reserves (total_yes/no_shares) are NOT decremented during payout.
-    // In CPMM, these track AMM pool reserves, not token supply.
-    // The burn above reduces mint supply, which is used as the payout denominator.
-    
-    ctx.accounts.user_position.total_claimed = ctx.accounts.user_position.total_claimed
-        .checked_add(payout)
-        .ok_or(PredictError::MathOverflow)?;
+    settle_claim(market, &mut ctx.accounts.user_position, &mut ctx.accounts.platform_config, &quote)?;
 
     emit!(PayoutClaimed {
         market_id,
         user: ctx.accounts.user.key(),
-        amount: payout,
+        amount: paid_amount,
+        scaled_amount_1e6: scale_to_display(payout, ctx.accounts.collateral_mint.decimals),
+        payout_mint: paid_mint,
         shares_burned: shares,
     });
 
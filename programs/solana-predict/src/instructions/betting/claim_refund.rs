@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Burn, Transfer};
+use crate::state::{PlatformConfig, Market, MarketStatus, UserPosition};
+use crate::events::RefundClaimed;
+use crate::errors::PredictError;
+use crate::utils::{pro_rata_payout, scale_to_display};
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = collateral_mint
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.collateral_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = yes_mint,
+        token::authority = user,
+    )]
+    pub user_yes_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = no_mint,
+        token::authority = user,
+    )]
+    pub user_no_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub collateral_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays the user's pro-rata slice of the pool frozen by cancel_market; see
+/// `burn_and_refund`.
+pub fn process_claim_refund(ctx: Context<ClaimRefund>, market_id: u64) -> Result<()> {
+    // Guards
+    require!(ctx.accounts.market.status == MarketStatus::Cancelled, PredictError::MarketNotCancelled);
+
+    let pool = ctx.accounts.market.distributable_collateral;
+    let (shares, refund) = burn_and_refund(ctx.accounts, pool)?;
+
+    let market = &mut ctx.accounts.market;
+    market.distributable_collateral -= refund;
+    let released = refund.min(market.total_collateral);
+    market.total_collateral -= released;
+    ctx.accounts.platform_config.debit_tvl(released);
+
+    emit!(RefundClaimed {
+        market_id,
+        user: ctx.accounts.user.key(),
+        shares_burned: shares,
+        amount: refund,
+        scaled_amount_1e6: scale_to_display(refund, ctx.accounts.collateral_mint.decimals),
+    });
+
+    Ok(())
+}
+
+/// Burns all of the user's YES and NO shares and pays their pro-rata slice of
+/// `pool`, measured against the combined YES + NO supply. Burning keeps the
+/// ratio fixed for later claimers. Takes the burnt shares off the position and
+/// adds the refund to its total_claimed; the market's books are left to the
+/// caller. Returns (shares burnt, refund).
+pub fn burn_and_refund(accounts: &mut ClaimRefund, pool: u64) -> Result<(u64, u64)> {
+    let yes_shares = accounts.user_yes_account.amount;
+    let no_shares = accounts.user_no_account.amount;
+    let shares = yes_shares.checked_add(no_shares).ok_or(PredictError::MathOverflow)?;
+    require!(shares > 0, PredictError::NoPosition);
+
+    let supply = accounts.yes_mint.supply
+        .checked_add(accounts.no_mint.supply)
+        .ok_or(PredictError::MathOverflow)?;
+    let refund = pro_rata_payout(shares, pool, supply).min(accounts.vault.amount);
+    require!(refund > 0, PredictError::NoPosition);
+
+    for (mint, from, amount) in [
+        (accounts.yes_mint.to_account_info(), accounts.user_yes_account.to_account_info(), yes_shares),
+        (accounts.no_mint.to_account_info(), accounts.user_no_account.to_account_info(), no_shares),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        token::burn(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                Burn {
+                    mint,
+                    from,
+                    authority: accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    let market = &accounts.market;
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.vault.to_account_info(),
+                to: accounts.user_ata.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        refund,
+    )?;
+
+    let position = &mut accounts.user_position;
+    position.yes_shares = position.yes_shares.saturating_sub(yes_shares);
+    position.no_shares = position.no_shares.saturating_sub(no_shares);
+    position.total_claimed = position.total_claimed
+        .checked_add(refund)
+        .ok_or(PredictError::MathOverflow)?;
+
+    Ok((shares, refund))
+}
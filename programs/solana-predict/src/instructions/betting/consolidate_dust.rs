@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Burn, CloseAccount, Transfer};
+use crate::state::{PlatformConfig, Market, MarketStatus, UserPosition, Outcome};
+use crate::events::PayoutClaimed;
+use crate::errors::PredictError;
+use crate::utils::scale_to_display;
+use super::claim_payout::{quote_claim, settle_claim};
+
+/// Max markets per batch — 7 accounts each keeps the transaction within size/compute limits
+pub const MAX_CONSOLIDATE_MARKETS: usize = 3;
+/// remaining_accounts per market:
+/// [market, yes_mint, no_mint, vault, user_position, user_yes_account, user_no_account]
+pub const CONSOLIDATE_ACCOUNTS_PER_MARKET: usize = 7;
+
+#[derive(Accounts)]
+pub struct ConsolidateDust<'info> {
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub collateral_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Cleans up the user's positions in resolved markets passed via
+/// `remaining_accounts`: claims whatever is still owed, burns leftover
+/// (worthless) shares, and closes both share accounts and the position,
+/// returning their rent to the user. Markets that aren't resolved, pay out in
+/// another mint, or still owe the position something are skipped.
+pub fn process_consolidate_dust<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConsolidateDust<'info>>,
+) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len().is_multiple_of(CONSOLIDATE_ACCOUNTS_PER_MARKET),
+        PredictError::InvalidBatch
+    );
+    require!(
+        remaining.len() / CONSOLIDATE_ACCOUNTS_PER_MARKET <= MAX_CONSOLIDATE_MARKETS,
+        PredictError::InvalidBatch
+    );
+
+    let user_key = ctx.accounts.user.key();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let user_info = ctx.accounts.user.to_account_info();
+
+    for group in remaining.chunks(CONSOLIDATE_ACCOUNTS_PER_MARKET) {
+        let [market_info, yes_mint_info, no_mint_info, vault_info, position_info, yes_share_info, no_share_info] = group else {
+            return err!(PredictError::InvalidBatch);
+        };
+
+        let mut market: Account<'info, Market> = Account::try_from(market_info)?;
+        require!(market_info.is_writable, PredictError::InvalidBatch);
+
+        // Only same-mint resolved markets settle here; cross-mint claims need the reserve
+        if market.status != MarketStatus::Resolved || market.payout_mint.is_some() {
+            msg!("Skipping market {}: not resolved in collateral", market.market_id);
+            continue;
+        }
+        let outcome = market.resolved_outcome.ok_or(PredictError::MarketNotResolved)?;
+
+        require!(yes_mint_info.key() == market.yes_mint, PredictError::InvalidMint);
+        require!(no_mint_info.key() == market.no_mint, PredictError::InvalidMint);
+        require!(vault_info.key() == market.vault, PredictError::InvalidMint);
+        require!(market.collateral_mint == ctx.accounts.collateral_mint.key(), PredictError::InvalidMint);
+
+        let (position_pda, _) = Pubkey::find_program_address(
+            &[b"position", market_info.key.as_ref(), user_key.as_ref()],
+            ctx.program_id,
+        );
+        require!(position_info.key() == position_pda, PredictError::NoPosition);
+        let mut position: Account<'info, UserPosition> = Account::try_from(position_info)?;
+
+        let market_id_bytes = market.market_id.to_le_bytes();
+        let bump = [market.bump];
+        let seeds = &[b"market".as_ref(), market_id_bytes.as_ref(), &bump];
+        let signer = &[&seeds[..]];
+
+        // 1. Claim anything still owed on either share account
+        for (mint_info, share_info) in [(yes_mint_info, yes_share_info), (no_mint_info, no_share_info)] {
+            let share_acc = TokenAccount::try_deserialize(&mut &share_info.data.borrow()[..])?;
+            require!(share_acc.mint == mint_info.key(), PredictError::InvalidMint);
+            require!(share_acc.owner == user_key, PredictError::Unauthorized);
+            if share_acc.amount == 0 && outcome != Outcome::Invalid {
+                continue;
+            }
+
+            let vault_amount = TokenAccount::try_deserialize(&mut &vault_info.data.borrow()[..])?.amount;
+            let yes_supply = Mint::try_deserialize(&mut &yes_mint_info.data.borrow()[..])?.supply;
+            let no_supply = Mint::try_deserialize(&mut &no_mint_info.data.borrow()[..])?.supply;
+            // Nothing claimable (losing side, already claimed, dust payout)
+            let Ok(quote) = quote_claim(&market, &position, &share_acc, yes_supply, no_supply, vault_amount) else {
+                continue;
+            };
+
+            if quote.shares > 0 {
+                token::burn(
+                    CpiContext::new(
+                        token_program.clone(),
+                        Burn {
+                            mint: mint_info.clone(),
+                            from: share_info.clone(),
+                            authority: user_info.clone(),
+                        },
+                    ),
+                    quote.shares,
+                )?;
+            }
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    Transfer {
+                        from: vault_info.clone(),
+                        to: ctx.accounts.user_ata.to_account_info(),
+                        authority: market_info.clone(),
+                    },
+                    signer,
+                ),
+                quote.payout,
+            )?;
+            settle_claim(&mut market, &mut position, &mut ctx.accounts.platform_config, &quote)?;
+
+            emit!(PayoutClaimed {
+                market_id: market.market_id,
+                user: user_key,
+                amount: quote.payout,
+                scaled_amount_1e6: scale_to_display(quote.payout, ctx.accounts.collateral_mint.decimals),
+                payout_mint: market.collateral_mint,
+                shares_burned: quote.shares,
+            });
+        }
+
+        // 2. Close only once the position can no longer be owed anything
+        let claimed = position.total_claimed > 0;
+        let closeable = match outcome {
+            Outcome::Yes => claimed || position.yes_shares == 0,
+            Outcome::No => claimed || position.no_shares == 0,
+            Outcome::Invalid => claimed || position.round != market.current_round || position.total_deposited == 0,
+            Outcome::Split => true,
+        };
+        market.exit(ctx.program_id)?;
+        if !closeable {
+            msg!("Skipping close of position in market {}: payout outstanding", market.market_id);
+            position.exit(ctx.program_id)?;
+            continue;
+        }
+
+        for (mint_info, share_info) in [(yes_mint_info, yes_share_info), (no_mint_info, no_share_info)] {
+            let leftover = TokenAccount::try_deserialize(&mut &share_info.data.borrow()[..])?.amount;
+            if leftover > 0 {
+                token::burn(
+                    CpiContext::new(
+                        token_program.clone(),
+                        Burn {
+                            mint: mint_info.clone(),
+                            from: share_info.clone(),
+                            authority: user_info.clone(),
+                        },
+                    ),
+                    leftover,
+                )?;
+            }
+            token::close_account(CpiContext::new(
+                token_program.clone(),
+                CloseAccount {
+                    account: share_info.clone(),
+                    destination: user_info.clone(),
+                    authority: user_info.clone(),
+                },
+            ))?;
+        }
+        position.close(user_info.clone())?;
+        msg!("Consolidated position in market {}", market.market_id);
+    }
+
+    Ok(())
+}
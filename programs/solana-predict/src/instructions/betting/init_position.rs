@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, Mint, TokenAccount};
+use crate::state::{Market, UserPosition};
+use crate::errors::PredictError;
+
+/// Creates the user's position together with both share ATAs and caches their
+/// addresses, so later bets/cancels only need a key comparison.
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct InitPosition<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = UserPosition::LEN
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = yes_mint,
+        associated_token::authority = user,
+    )]
+    pub user_yes_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = no_mint,
+        associated_token::authority = user,
+    )]
+    pub user_no_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_init_position(ctx: Context<InitPosition>, _market_id: u64) -> Result<()> {
+    let position = &mut ctx.accounts.user_position;
+    position.user = ctx.accounts.user.key();
+    position.market = ctx.accounts.market.key();
+    position.round = ctx.accounts.market.current_round;
+    position.yes_account = ctx.accounts.user_yes_account.key();
+    position.no_account = ctx.accounts.user_no_account.key();
+    position.bump = ctx.bumps.user_position;
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Burn, MintTo};
+use crate::state::{Market, MarketStatus, UserPosition};
+use crate::events::ForkPositionMigrated;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+pub struct MigrateForkPosition<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Forked @ PredictError::MarketNotResolved,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(mut, token::mint = yes_mint, token::authority = user)]
+    pub user_yes_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = no_mint, token::authority = user)]
+    pub user_no_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = yes_child.key() == market.fork_children[0] @ PredictError::InvalidForkChild,
+    )]
+    pub yes_child: Box<Account<'info, Market>>,
+
+    #[account(mut, address = yes_child.yes_mint @ PredictError::InvalidMint)]
+    pub yes_child_yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, address = yes_child.no_mint @ PredictError::InvalidMint)]
+    pub yes_child_no_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = yes_child_yes_mint, token::authority = user)]
+    pub yes_child_user_yes: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = yes_child_no_mint, token::authority = user)]
+    pub yes_child_user_no: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"position", yes_child.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = UserPosition::LEN
+    )]
+    pub yes_child_position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        mut,
+        constraint = no_child.key() == market.fork_children[1] @ PredictError::InvalidForkChild,
+    )]
+    pub no_child: Box<Account<'info, Market>>,
+
+    #[account(mut, address = no_child.yes_mint @ PredictError::InvalidMint)]
+    pub no_child_yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, address = no_child.no_mint @ PredictError::InvalidMint)]
+    pub no_child_no_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = no_child_yes_mint, token::authority = user)]
+    pub no_child_user_yes: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = no_child_no_mint, token::authority = user)]
+    pub no_child_user_no: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"position", no_child.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = UserPosition::LEN
+    )]
+    pub no_child_position: Box<Account<'info, UserPosition>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Mints `yes`/`no` shares of a fork child to the user and books them on the
+/// user's position there.
+#[allow(clippy::too_many_arguments)]
+fn credit_child<'info>(
+    child: &mut Account<'info, Market>,
+    child_yes_mint: &Account<'info, Mint>,
+    child_no_mint: &Account<'info, Mint>,
+    user_yes: &Account<'info, TokenAccount>,
+    user_no: &Account<'info, TokenAccount>,
+    position: &mut UserPosition,
+    position_bump: u8,
+    user: Pubkey,
+    token_program: &AccountInfo<'info>,
+    yes: u64,
+    no: u64,
+    deposited: u64,
+) -> Result<()> {
+    let market_id_bytes = child.market_id.to_le_bytes();
+    let bump = [child.bump];
+    let seeds = &[b"market".as_ref(), market_id_bytes.as_ref(), &bump];
+    let signer = &[&seeds[..]];
+
+    for (mint, to, amount) in [(child_yes_mint, user_yes, yes), (child_no_mint, user_no, no)] {
+        if amount > 0 {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    MintTo {
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: child.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    position.user = user;
+    position.market = child.key();
+    position.bump = position_bump;
+    position.round = child.current_round;
+    position.yes_shares = position.yes_shares.checked_add(yes).ok_or(PredictError::MathOverflow)?;
+    position.no_shares = position.no_shares.checked_add(no).ok_or(PredictError::MathOverflow)?;
+    position.total_deposited = position.total_deposited.checked_add(deposited).ok_or(PredictError::MathOverflow)?;
+    child.total_deposited = child.total_deposited.checked_add(deposited).ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+/// Carries a position in a forked market into both children: burns the
+/// user's parent YES/NO shares and mints the same amounts in each child, so
+/// the holder can claim under either resolution.
+pub fn process_migrate_fork_position(ctx: Context<MigrateForkPosition>) -> Result<()> {
+    let yes = ctx.accounts.user_yes_account.amount;
+    let no = ctx.accounts.user_no_account.amount;
+    require!(yes > 0 || no > 0, PredictError::NoPosition);
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    for (mint, from, amount) in [
+        (ctx.accounts.yes_mint.to_account_info(), ctx.accounts.user_yes_account.to_account_info(), yes),
+        (ctx.accounts.no_mint.to_account_info(), ctx.accounts.user_no_account.to_account_info(), no),
+    ] {
+        if amount > 0 {
+            token::burn(
+                CpiContext::new(
+                    token_program.clone(),
+                    Burn {
+                        mint,
+                        from,
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    let market = &ctx.accounts.market;
+    let position = &mut ctx.accounts.user_position;
+    let deposited = if position.round == market.current_round { position.total_deposited } else { 0 };
+    position.yes_shares = position.yes_shares.saturating_sub(yes);
+    position.no_shares = position.no_shares.saturating_sub(no);
+    position.total_deposited = 0;
+
+    let user = ctx.accounts.user.key();
+    credit_child(
+        &mut ctx.accounts.yes_child,
+        &ctx.accounts.yes_child_yes_mint,
+        &ctx.accounts.yes_child_no_mint,
+        &ctx.accounts.yes_child_user_yes,
+        &ctx.accounts.yes_child_user_no,
+        &mut ctx.accounts.yes_child_position,
+        ctx.bumps.yes_child_position,
+        user,
+        &token_program,
+        yes,
+        no,
+        deposited,
+    )?;
+    credit_child(
+        &mut ctx.accounts.no_child,
+        &ctx.accounts.no_child_yes_mint,
+        &ctx.accounts.no_child_no_mint,
+        &ctx.accounts.no_child_user_yes,
+        &ctx.accounts.no_child_user_no,
+        &mut ctx.accounts.no_child_position,
+        ctx.bumps.no_child_position,
+        user,
+        &token_program,
+        yes,
+        no,
+        deposited,
+    )?;
+
+    emit!(ForkPositionMigrated {
+        parent_market_id: ctx.accounts.market.market_id,
+        user,
+        yes_shares: yes,
+        no_shares: no,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,27 @@
 pub mod place_bet;
 pub mod cancel_bet;
 pub mod claim_payout;
+pub mod cancel_bets_batch;
+pub mod init_position;
+pub mod claim_and_rollover;
+pub mod consolidate_dust;
+pub mod claim_emergency_refund;
+pub mod claim_refund;
+pub mod migrate_fork_position;
+pub mod referral;
+pub mod user_tier;
+pub mod round_bet;
 
 pub use place_bet::*;
 pub use cancel_bet::*;
 pub use claim_payout::*;
+pub use cancel_bets_batch::*;
+pub use init_position::*;
+pub use claim_and_rollover::*;
+pub use consolidate_dust::*;
+pub use claim_emergency_refund::*;
+pub use claim_refund::*;
+pub use migrate_fork_position::*;
+pub use referral::*;
+pub use user_tier::*;
+pub use round_bet::*;
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, Mint, TokenAccount, MintTo, Transfer};
-use crate::state::{PlatformConfig, Market, MarketStatus, UserPosition, Outcome};
-use crate::events::BetPlaced;
+use crate::state::{PlatformConfig, Market, MarketStatus, OracleSource, ReferralAccount, UserPosition, UserTier, Outcome, PricingMode};
+use crate::events::{BetPlaced, ReferralFeePaid};
 use crate::errors::PredictError;
+use crate::utils::{bet_fee_breakdown, record_fees, scale_to_display};
 
 #[derive(Accounts)]
 #[instruction(market_id: u64)]
@@ -82,6 +83,29 @@ pub struct PlaceBet<'info> {
     )]
     pub treasury: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"creator_fees", market.key().as_ref()],
+        bump,
+    )]
+    pub creator_fee_escrow: Account<'info, TokenAccount>,
+
+    /// Referrer credited with this bet (optional)
+    pub referral_account: Option<Box<Account<'info, ReferralAccount>>>,
+
+    /// The referrer's fee escrow (optional - required with `referral_account`)
+    #[account(mut)]
+    pub referral_fee_escrow: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The bettor's volume record (optional - discounts the fee by tier and
+    /// counts this bet towards it)
+    #[account(
+        mut,
+        seeds = [b"user_tier", user.key().as_ref()],
+        bump = user_tier.bump,
+    )]
+    pub user_tier: Option<Account<'info, UserTier>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -92,86 +116,242 @@ pub struct PlaceBet<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn process_place_bet(
-    ctx: Context<PlaceBet>,
-    market_id: u64,
+/// Token accounts touched when buying shares from the pool
+pub struct BetCpiAccounts<'info> {
+    pub share_mint: AccountInfo<'info>,
+    pub share_account: AccountInfo<'info>,
+    pub user_ata: AccountInfo<'info>,
+    pub treasury: AccountInfo<'info>,
+    pub creator_fee_escrow: AccountInfo<'info>,
+    /// Referrer and its fee escrow, for referred bets
+    pub referral: Option<(Pubkey, AccountInfo<'info>)>,
+    pub user: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Buys `outcome` shares with `amount` of collateral from `user_ata`: runs the
+/// betting guards, takes the fee, mints the shares and updates reserves and the
+/// position. `share_mint` must be the mint for `outcome`; callers set the
+/// position bump. Returns the shares minted.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_bet<'info>(
+    market: &mut Account<'info, Market>,
+    position: &mut UserPosition,
+    vault: &mut Account<'info, TokenAccount>,
+    platform: &mut PlatformConfig,
     outcome: Outcome,
     amount: u64,
     min_shares_out: u64,
-) -> Result<()> {
-    let market = &mut ctx.accounts.market;
-    let platform = &ctx.accounts.platform_config;
+    collateral_decimals: u8,
+    fee_discount_bps: u16,
+    accounts: BetCpiAccounts<'info>,
+) -> Result<u64> {
     let clock = Clock::get()?;
 
     // 1. Guard Checks
     require!(!platform.paused, PredictError::PlatformPaused);
     require!(market.status == MarketStatus::Active, PredictError::MarketNotActive);
+    // Scheduled rounds restart the market right after it resolves, which unclaimed
+    // shares would block: those markets take bets through place_round_bet
+    require!(!market.has_round_schedule(), PredictError::RoundBetsOnly);
     require!(clock.unix_timestamp < market.lock_timestamp, PredictError::BettingClosed);
     require!(amount >= market.min_bet, PredictError::BelowMinBet);
     if market.max_bet > 0 {
         require!(amount <= market.max_bet, PredictError::AboveMaxBet);
     }
     require!(outcome == Outcome::Yes || outcome == Outcome::No, PredictError::InvalidOutcome);
+    // Conflict of interest: whoever decides a ManualAdmin market may not bet on it
+    if market.creator_betting_restricted && market.oracle_source == OracleSource::ManualAdmin {
+        let user = accounts.user.key();
+        require!(
+            user != market.creator && !market.is_resolver(&user, platform),
+            PredictError::ResolverCannotBet
+        );
+    }
 
-    // Validate user share account before any transfers
-    let user_share_data = TokenAccount::try_deserialize(&mut &ctx.accounts.user_share_account.data.borrow()[..])?;
-    let target_mint = if outcome == Outcome::Yes { market.yes_mint } else { market.no_mint };
-    require!(user_share_data.mint == target_mint, PredictError::InvalidMint);
-    require!(user_share_data.owner == ctx.accounts.user.key(), PredictError::Unauthorized);
+    // Validate user share account before any transfers. Positions set up by
+    // init_position carry ATAs already checked at creation: a key compare is enough.
+    if position.has_cached_accounts() {
+        require!(
+            position.cached_outcome(accounts.share_account.key) == Some(outcome),
+            PredictError::InvalidMint
+        );
+    } else {
+        let user_share_data = TokenAccount::try_deserialize(&mut &accounts.share_account.data.borrow()[..])?;
+        let target_mint = if outcome == Outcome::Yes { market.yes_mint } else { market.no_mint };
+        require!(user_share_data.mint == target_mint, PredictError::InvalidMint);
+        require!(user_share_data.owner == accounts.user.key(), PredictError::Unauthorized);
+    }
 
     // 2. Fee Calculation (round up to prevent micro-bet fee bypass)
-    let fee = ((amount as u128 * market.fee_bps as u128 + 9999) / 10000) as u64;
-    let net_amount = amount.checked_sub(fee).ok_or(PredictError::MathOverflow)?;
-    require!(net_amount > 0, PredictError::BelowMinBet);
+    let fee_bps = discounted_fee_bps(market.fee_bps_at(clock.unix_timestamp), fee_discount_bps);
+    let referral_fee_bps = accounts.referral.as_ref().map(|_| platform.referral_fee_bps);
+    let fees = bet_fee_breakdown(
+        amount,
+        fee_bps,
+        market.creator_fee_bps,
+        market.active_lp_fee_bps(),
+        platform.min_fee_absolute,
+        referral_fee_bps,
+    )?;
+    let fee = fees.platform_fee;
+    let sent_amount = fees.net_amount;
+    require!(sent_amount > 0, PredictError::BelowMinBet);
 
     // 3. Transfer USDC
     // User -> Vault (net)
+    let vault_before = vault.amount;
     token::transfer(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.clone(),
             Transfer {
-                from: ctx.accounts.user_ata.to_account_info(),
-                to: ctx.accounts.vault.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
+                from: accounts.user_ata.clone(),
+                to: vault.to_account_info(),
+                authority: accounts.user.clone(),
             },
         ),
-        net_amount,
+        sent_amount,
     )?;
 
+    // Credit what actually arrived: a transfer-fee collateral delivers less than
+    // was sent, and over-crediting would leave total_collateral above the vault.
+    vault.reload()?;
+    let net_amount = vault.amount
+        .checked_sub(vault_before)
+        .ok_or(PredictError::MathOverflow)?;
+    require!(net_amount > 0, PredictError::BelowMinBet);
+
+    // Large orders must be split so the pool re-prices between the pieces
+    if market.max_bet_pool_fraction_bps > 0 && market.pricing_mode == PricingMode::Cpmm {
+        let reserve = if outcome == Outcome::Yes { market.total_yes_shares } else { market.total_no_shares };
+        let max_net = reserve as u128 * market.max_bet_pool_fraction_bps as u128 / 10000;
+        require!(net_amount as u128 <= max_net, PredictError::BetTooLargeForPool);
+    }
+
     // User -> Treasury (fee)
     if fee > 0 {
         token::transfer(
             CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+                accounts.token_program.clone(),
                 Transfer {
-                    from: ctx.accounts.user_ata.to_account_info(),
-                    to: ctx.accounts.treasury.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
+                    from: accounts.user_ata.clone(),
+                    to: accounts.treasury,
+                    authority: accounts.user.clone(),
                 },
             ),
             fee,
         )?;
     }
+    // User -> Creator fee escrow
+    if fees.creator_fee > 0 {
+        token::transfer(
+            CpiContext::new(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.user_ata.clone(),
+                    to: accounts.creator_fee_escrow,
+                    authority: accounts.user.clone(),
+                },
+            ),
+            fees.creator_fee,
+        )?;
+    }
+    // User -> Referrer's fee escrow
+    if let Some((referrer, escrow)) = accounts.referral.filter(|_| fees.referrer_fee > 0) {
+        token::transfer(
+            CpiContext::new(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.user_ata.clone(),
+                    to: escrow,
+                    authority: accounts.user.clone(),
+                },
+            ),
+            fees.referrer_fee,
+        )?;
+        emit!(ReferralFeePaid {
+            market_id: market.market_id,
+            referrer,
+            user: accounts.user.key(),
+            amount: fees.referrer_fee,
+        });
+    }
+    // User -> Vault (LP fee, pooled with the collateral LPs withdraw)
+    if fees.lp_fee > 0 {
+        token::transfer(
+            CpiContext::new(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.user_ata.clone(),
+                    to: vault.to_account_info(),
+                    authority: accounts.user.clone(),
+                },
+            ),
+            fees.lp_fee,
+        )?;
+        market.lp_fees_accrued = market.lp_fees_accrued.checked_add(fees.lp_fee).ok_or(PredictError::MathOverflow)?;
+        market.total_collateral = market.total_collateral.checked_add(fees.lp_fee).ok_or(PredictError::MathOverflow)?;
+        platform.credit_tvl(fees.lp_fee)?;
+    }
+    record_fees(&mut market.fee_ledger, &fees)?;
+
+    // Early-bird bonus: extra collateral from the market's pre-funded budget is
+    // added to the bet, so bonus shares are backed like any other deposit.
+    let early_bird_window_end = market.start_timestamp.saturating_add(market.early_bird_window_secs);
+    let bonus = if market.early_bird_bonus_bps > 0 && clock.unix_timestamp < early_bird_window_end {
+        let bonus = (net_amount as u128 * market.early_bird_bonus_bps as u128 / 10000) as u64;
+        bonus.min(market.early_bird_budget)
+    } else {
+        0
+    };
+    market.early_bird_budget -= bonus;
+    let credited_amount = net_amount.checked_add(bonus).ok_or(PredictError::MathOverflow)?;
+
+    // Global risk limit across all markets
+    platform.credit_tvl(credited_amount)?;
+    if platform.max_platform_tvl > 0 {
+        require!(platform.total_tvl <= platform.max_platform_tvl, PredictError::PlatformTvlCapReached);
+    }
 
-    // 4. Calculate Shares via CPMM
-    let yes_pool = market.total_yes_shares as u128;
-    let no_pool = market.total_no_shares as u128;
-    let k = yes_pool.checked_mul(no_pool).ok_or(PredictError::MathOverflow)?;
-    let net = net_amount as u128;
+    // 4. Parimutuel: the stake goes into the outcome's bucket one share per unit,
+    // priced at nothing until resolution splits the pot
+    let parimutuel = market.pricing_mode == PricingMode::Parimutuel;
 
-    let shares = if outcome == Outcome::Yes {
-        let new_no_pool = no_pool.checked_add(net).ok_or(PredictError::MathOverflow)?;
-        let new_yes_pool = k.checked_div(new_no_pool).ok_or(PredictError::MathOverflow)?;
-        (yes_pool.checked_sub(new_yes_pool).ok_or(PredictError::MathOverflow)?) as u64
+    // Calculate Shares via CPMM (against the slot-start snapshot if the market opted in)
+    let (yes_pool, no_pool) = market.pricing_reserves(clock.slot);
+    let (yes_pool, no_pool) = (yes_pool as u128, no_pool as u128);
+    let net = credited_amount as u128;
+    let (pool_out, pool_in) = if outcome == Outcome::Yes { (yes_pool, no_pool) } else { (no_pool, yes_pool) };
+    // Earlier same-side bets in the slot already bought the curve up to `prior`
+    let prior = market.slot_flow_before(&outcome, credited_amount) as u128;
+    let out_before = if parimutuel { 0 } else { cpmm_shares_out(pool_out, pool_in, prior)? };
+
+    let shares = if parimutuel {
+        credited_amount
     } else {
-        let new_yes_pool = yes_pool.checked_add(net).ok_or(PredictError::MathOverflow)?;
-        let new_no_pool = k.checked_div(new_yes_pool).ok_or(PredictError::MathOverflow)?;
-        (no_pool.checked_sub(new_no_pool).ok_or(PredictError::MathOverflow)?) as u64
+        let out_after = cpmm_shares_out(pool_out, pool_in, prior.checked_add(net).ok_or(PredictError::MathOverflow)?)?;
+        (out_after.checked_sub(out_before).ok_or(PredictError::MathOverflow)?) as u64
     };
 
     require!(shares > 0, PredictError::MathOverflow);
     
-    // Slippage Check
+    // Slippage Check. Naive clients passing 0 get the platform default, applied
+    // to the spot-price quote (net * pool_out / pool_in) before this trade.
+    let min_shares_out = if min_shares_out == 0 && platform.default_max_slippage_bps > 0 && !parimutuel {
+        let pool_out = pool_out - out_before;
+        let pool_in = pool_in + prior;
+        let quote_shares = net
+            .checked_mul(pool_out)
+            .and_then(|v| v.checked_div(pool_in))
+            .ok_or(PredictError::MathOverflow)?;
+        let floor = quote_shares
+            .checked_mul(10000 - platform.default_max_slippage_bps as u128)
+            .ok_or(PredictError::MathOverflow)?
+            / 10000;
+        floor.min(u64::MAX as u128) as u64
+    } else {
+        min_shares_out
+    };
     require!(shares >= min_shares_out, PredictError::SlippageExceeded);
 
     // Determine Mint and Mint To
@@ -191,18 +371,12 @@ pub fn process_place_bet(
     ];
     let signer = &[&seeds[..]];
 
-    let mint_account = if outcome == Outcome::Yes {
-        ctx.accounts.yes_mint.to_account_info()
-    } else {
-        ctx.accounts.no_mint.to_account_info()
-    };
-
     token::mint_to(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.clone(),
             MintTo {
-                mint: mint_account,
-                to: ctx.accounts.user_share_account.to_account_info(),
+                mint: accounts.share_mint,
+                to: accounts.share_account,
                 authority: market.to_account_info(),
             },
             signer,
@@ -212,47 +386,150 @@ pub fn process_place_bet(
 
     // 5. Update State (CPMM pool reserves)
     market.total_collateral = market.total_collateral
+        .checked_add(credited_amount)
+        .ok_or(PredictError::MathOverflow)?;
+    market.total_deposited = market.total_deposited
         .checked_add(net_amount)
         .ok_or(PredictError::MathOverflow)?;
-    if outcome == Outcome::Yes {
+    if parimutuel {
+        // Buckets are the share supplies; the reserves stay put
+    } else if outcome == Outcome::Yes {
         // User takes YES shares from pool, collateral adds to NO side
-        market.total_yes_shares = market.total_yes_shares.checked_sub(shares).ok_or(PredictError::MathOverflow)?;
-        market.total_no_shares = market.total_no_shares.checked_add(net_amount).ok_or(PredictError::MathOverflow)?;
+        // (more than the real reserve only when priced against virtual liquidity)
+        market.total_yes_shares = market.total_yes_shares.checked_sub(shares).ok_or(PredictError::InsufficientLiquidity)?;
+        market.total_no_shares = market.total_no_shares.checked_add(credited_amount).ok_or(PredictError::MathOverflow)?;
     } else {
         // User takes NO shares from pool, collateral adds to YES side
-        market.total_no_shares = market.total_no_shares.checked_sub(shares).ok_or(PredictError::MathOverflow)?;
-        market.total_yes_shares = market.total_yes_shares.checked_add(net_amount).ok_or(PredictError::MathOverflow)?;
+        market.total_no_shares = market.total_no_shares.checked_sub(shares).ok_or(PredictError::InsufficientLiquidity)?;
+        market.total_yes_shares = market.total_yes_shares.checked_add(credited_amount).ok_or(PredictError::MathOverflow)?;
     }
 
     // Update User Position
-    let position = &mut ctx.accounts.user_position;
-    position.user = ctx.accounts.user.key();
+    // First bet of this wallet in the current round
+    if position.round != market.current_round || position.last_bet_timestamp == 0 {
+        market.unique_bettors = market.unique_bettors.checked_add(1).ok_or(PredictError::MathOverflow)?;
+    }
+    // Recurring market restarted since this position was last used: start over
+    if position.round != market.current_round {
+        position.yes_shares = 0;
+        position.no_shares = 0;
+        position.total_deposited = 0;
+        position.total_claimed = 0;
+        position.yes_cost_basis = 0;
+        position.no_cost_basis = 0;
+        position.total_cost = 0;
+        position.total_refunded = 0;
+        position.round = market.current_round;
+    }
+    position.user = accounts.user.key();
     position.market = market.key();
     if outcome == Outcome::Yes {
         position.yes_shares = position.yes_shares
             .checked_add(shares)
             .ok_or(PredictError::MathOverflow)?;
+        position.yes_cost_basis = position.yes_cost_basis
+            .checked_add(amount)
+            .ok_or(PredictError::MathOverflow)?;
     } else {
         position.no_shares = position.no_shares
             .checked_add(shares)
             .ok_or(PredictError::MathOverflow)?;
+        position.no_cost_basis = position.no_cost_basis
+            .checked_add(amount)
+            .ok_or(PredictError::MathOverflow)?;
     }
+    position.total_cost = position.total_cost
+        .checked_add(amount)
+        .ok_or(PredictError::MathOverflow)?;
     position.total_deposited = position.total_deposited
         .checked_add(net_amount)
         .ok_or(PredictError::MathOverflow)?;
     position.last_bet_timestamp = clock.unix_timestamp;
-    position.bump = ctx.bumps.user_position;
+    market.last_bet_slot = clock.slot;
+    market.record_checkpoint(clock.slot);
 
     emit!(BetPlaced {
-        market_id,
-        user: ctx.accounts.user.key(),
+        market_id: market.market_id,
+        user: accounts.user.key(),
         outcome,
         amount,
+        scaled_amount_1e6: scale_to_display(amount, collateral_decimals),
         shares,
         new_yes_total: market.total_yes_shares,
         new_no_total: market.total_no_shares,
         timestamp: clock.unix_timestamp,
     });
 
+    Ok(shares)
+}
+
+/// Shares of `pool_out` a CPMM pays for `amount` added to `pool_in`
+fn cpmm_shares_out(pool_out: u128, pool_in: u128, amount: u128) -> Result<u128> {
+    let k = pool_out.checked_mul(pool_in).ok_or(PredictError::MathOverflow)?;
+    let new_pool_in = pool_in.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    let new_pool_out = k.checked_div(new_pool_in).ok_or(PredictError::MathOverflow)?;
+    Ok(pool_out.checked_sub(new_pool_out).ok_or(PredictError::MathOverflow)?)
+}
+
+/// `fee_bps` less a volume-tier discount of `discount_bps` (bps of the fee)
+pub fn discounted_fee_bps(fee_bps: u16, discount_bps: u16) -> u16 {
+    (fee_bps as u32 * (10000 - discount_bps.min(10000)) as u32 / 10000) as u16
+}
+
+pub fn process_place_bet(
+    ctx: Context<PlaceBet>,
+    _market_id: u64,
+    outcome: Outcome,
+    amount: u64,
+    min_shares_out: u64,
+) -> Result<()> {
+    let share_mint = if outcome == Outcome::Yes {
+        ctx.accounts.yes_mint.to_account_info()
+    } else {
+        ctx.accounts.no_mint.to_account_info()
+    };
+    let referral = match (&ctx.accounts.referral_account, &ctx.accounts.referral_fee_escrow) {
+        (Some(referral), Some(escrow)) => {
+            require!(escrow.key() == referral.escrow, PredictError::InvalidReferralEscrow);
+            require!(referral.referrer != ctx.accounts.user.key(), PredictError::SelfReferral);
+            Some((referral.referrer, escrow.to_account_info()))
+        }
+        (None, None) => None,
+        _ => return err!(PredictError::InvalidReferralEscrow),
+    };
+    let accounts = BetCpiAccounts {
+        share_mint,
+        share_account: ctx.accounts.user_share_account.to_account_info(),
+        user_ata: ctx.accounts.user_ata.to_account_info(),
+        treasury: ctx.accounts.treasury.to_account_info(),
+        creator_fee_escrow: ctx.accounts.creator_fee_escrow.to_account_info(),
+        referral,
+        user: ctx.accounts.user.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+
+    let fee_discount_bps = ctx.accounts.user_tier
+        .as_ref()
+        .map_or(0, |tier| ctx.accounts.platform_config.tier_discount_bps(tier.lifetime_volume));
+
+    execute_bet(
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.user_position,
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.platform_config,
+        outcome,
+        amount,
+        min_shares_out,
+        ctx.accounts.collateral_mint.decimals,
+        fee_discount_bps,
+        accounts,
+    )?;
+    ctx.accounts.user_position.bump = ctx.bumps.user_position;
+
+    // The bet counts towards the next tier, not this one
+    if let Some(tier) = ctx.accounts.user_tier.as_mut() {
+        tier.lifetime_volume = tier.lifetime_volume.saturating_add(amount);
+    }
+
     Ok(())
 }
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Transfer};
+use crate::state::{PlatformConfig, ReferralAccount};
+use crate::events::{ReferrerRegistered, ReferralFeesClaimed};
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump,
+        payer = referrer,
+        space = ReferralAccount::LEN
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    #[account(
+        init,
+        seeds = [b"referral_fees", referrer.key().as_ref()],
+        bump,
+        payer = referrer,
+        token::mint = collateral_mint,
+        token::authority = referral_account,
+    )]
+    pub referral_fee_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = collateral_mint,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub collateral_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump = referral_account.bump,
+        has_one = referrer @ PredictError::Unauthorized,
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    #[account(
+        mut,
+        address = referral_account.escrow,
+    )]
+    pub referral_fee_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = referral_fee_escrow.mint,
+        token::authority = referrer,
+    )]
+    pub referrer_ata: Account<'info, TokenAccount>,
+
+    pub referrer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Registers the signer as a referrer, with an escrow for its fee share in the
+/// platform's collateral. Once per key.
+pub fn process_register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referral = &mut ctx.accounts.referral_account;
+    referral.referrer = ctx.accounts.referrer.key();
+    referral.escrow = ctx.accounts.referral_fee_escrow.key();
+    referral.registered_at = Clock::get()?.unix_timestamp;
+    referral.bump = ctx.bumps.referral_account;
+
+    emit!(ReferrerRegistered {
+        referrer: referral.referrer,
+        escrow: referral.escrow,
+    });
+
+    Ok(())
+}
+
+/// Pays the referrer everything accrued in its escrow
+pub fn process_claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let amount = ctx.accounts.referral_fee_escrow.amount;
+    require!(amount > 0, PredictError::NoReferralFees);
+
+    let referrer = ctx.accounts.referrer.key();
+    let seeds = &[
+        b"referral",
+        referrer.as_ref(),
+        &[ctx.accounts.referral_account.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.referral_fee_escrow.to_account_info(),
+                to: ctx.accounts.referrer_ata.to_account_info(),
+                authority: ctx.accounts.referral_account.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    emit!(ReferralFeesClaimed { referrer, amount });
+
+    Ok(())
+}
@@ -0,0 +1,313 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Transfer};
+use crate::state::{PlatformConfig, Market, Outcome, RoundPosition, RoundState, RoundStatus};
+use crate::events::{RoundBetPlaced, RoundPayoutClaimed};
+use crate::errors::PredictError;
+use crate::utils::{bet_fee_breakdown, pro_rata_payout, record_fees};
+
+#[derive(Accounts)]
+#[instruction(market_id: u64, round_id: u64)]
+pub struct PlaceRoundBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump,
+    )]
+    pub round_state: Box<Account<'info, RoundState>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"round_vault", round_state.key().as_ref()],
+        bump,
+        payer = user,
+        token::mint = collateral_mint,
+        token::authority = round_state,
+    )]
+    pub round_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"round_position", round_state.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = RoundPosition::LEN
+    )]
+    pub round_position: Account<'info, RoundPosition>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.collateral_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == platform_config.treasury,
+        constraint = treasury.mint == collateral_mint.key() @ PredictError::InvalidMint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(address = market.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64, round_id: u64)]
+pub struct ClaimRoundPayout<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"round", market.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_state.bump,
+    )]
+    pub round_state: Box<Account<'info, RoundState>>,
+
+    #[account(
+        mut,
+        seeds = [b"round_vault", round_state.key().as_ref()],
+        bump,
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"round_position", round_state.key().as_ref(), user.key().as_ref()],
+        bump = round_position.bump,
+    )]
+    pub round_position: Account<'info, RoundPosition>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.collateral_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Stakes `amount` (less the market's fee) on `outcome` in an open round of a
+/// recurring market. Round stakes are pooled per round in their own vault and
+/// never touch the market's CPMM reserves.
+pub fn process_place_round_bet(
+    ctx: Context<PlaceRoundBet>,
+    market_id: u64,
+    round_id: u64,
+    outcome: Outcome,
+    amount: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let round = &mut ctx.accounts.round_state;
+    let platform = &ctx.accounts.platform_config;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Guards
+    require!(!platform.paused, PredictError::PlatformPaused);
+    require!(round.status == RoundStatus::Open, PredictError::RoundNotOpen);
+    require!(now >= round.start_ts, PredictError::MarketNotActive);
+    require!(now < round.lock_ts, PredictError::BettingClosed);
+    require!(amount >= market.min_bet, PredictError::BelowMinBet);
+    if market.max_bet > 0 {
+        require!(amount <= market.max_bet, PredictError::AboveMaxBet);
+    }
+    require!(outcome == Outcome::Yes || outcome == Outcome::No, PredictError::InvalidOutcome);
+
+    let fees = bet_fee_breakdown(amount, market.fee_bps, 0, 0, platform.min_fee_absolute, None)?;
+    require!(fees.net_amount > 0, PredictError::BelowMinBet);
+
+    // User -> Round vault (net)
+    let vault_before = ctx.accounts.round_vault.amount;
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_ata.to_account_info(),
+                to: ctx.accounts.round_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        fees.net_amount,
+    )?;
+    ctx.accounts.round_vault.reload()?;
+    let staked = ctx.accounts.round_vault.amount
+        .checked_sub(vault_before)
+        .ok_or(PredictError::MathOverflow)?;
+    require!(staked > 0, PredictError::BelowMinBet);
+
+    // User -> Treasury (fee)
+    if fees.platform_fee > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_ata.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            fees.platform_fee,
+        )?;
+    }
+    record_fees(&mut market.fee_ledger, &fees)?;
+
+    let position = &mut ctx.accounts.round_position;
+    // Freshly created position: a new participant in the round
+    if position.user == Pubkey::default() {
+        round.unique_bettors = round.unique_bettors.checked_add(1).ok_or(PredictError::MathOverflow)?;
+    }
+    position.round = round.key();
+    position.user = ctx.accounts.user.key();
+    position.bump = ctx.bumps.round_position;
+    if outcome == Outcome::Yes {
+        position.yes_amount = position.yes_amount.checked_add(staked).ok_or(PredictError::MathOverflow)?;
+        round.total_yes = round.total_yes.checked_add(staked).ok_or(PredictError::MathOverflow)?;
+    } else {
+        position.no_amount = position.no_amount.checked_add(staked).ok_or(PredictError::MathOverflow)?;
+        round.total_no = round.total_no.checked_add(staked).ok_or(PredictError::MathOverflow)?;
+    }
+
+    emit!(RoundBetPlaced {
+        market_id,
+        round_id,
+        user: position.user,
+        outcome,
+        amount: staked,
+        fee: fees.total_fee,
+    });
+
+    Ok(())
+}
+
+/// What `position` is owed from a settled round: the winning side splits both
+/// pools by stake; INVALID, a cancelled round, or a winning side nobody backed
+/// refunds every stake.
+pub fn round_payout(round: &RoundState, position: &RoundPosition) -> Result<u64> {
+    let refund = position.yes_amount.checked_add(position.no_amount).ok_or(PredictError::MathOverflow)?;
+    let outcome = match round.status {
+        RoundStatus::Resolved => round.outcome.ok_or(PredictError::MarketNotResolved)?,
+        RoundStatus::Cancelled => Outcome::Invalid,
+        _ => return err!(PredictError::MarketNotResolved),
+    };
+    let (stake, winning_total) = match outcome {
+        Outcome::Yes => (position.yes_amount, round.total_yes),
+        Outcome::No => (position.no_amount, round.total_no),
+        _ => return Ok(refund),
+    };
+    if winning_total == 0 {
+        return Ok(refund);
+    }
+    let pool = round.total_yes.checked_add(round.total_no).ok_or(PredictError::MathOverflow)?;
+    Ok(pro_rata_payout(stake, pool, winning_total))
+}
+
+/// Pays out a user's position in a settled round, once
+pub fn process_claim_round_payout(
+    ctx: Context<ClaimRoundPayout>,
+    market_id: u64,
+    round_id: u64,
+) -> Result<()> {
+    let round = &ctx.accounts.round_state;
+    let position = &mut ctx.accounts.round_position;
+    require!(!position.claimed, PredictError::AlreadyClaimed);
+
+    let payout = round_payout(round, position)?;
+    require!(payout > 0, PredictError::NoPosition);
+    position.claimed = true;
+
+    let market_key = ctx.accounts.market.key();
+    let round_bytes = round_id.to_le_bytes();
+    let seeds = &[
+        b"round",
+        market_key.as_ref(),
+        round_bytes.as_ref(),
+        &[round.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.round_vault.to_account_info(),
+                to: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.round_state.to_account_info(),
+            },
+            signer,
+        ),
+        payout,
+    )?;
+
+    emit!(RoundPayoutClaimed {
+        market_id,
+        round_id,
+        user: ctx.accounts.user.key(),
+        amount: payout,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseRoundPosition<'info> {
+    #[account(
+        mut,
+        has_one = user @ PredictError::Unauthorized,
+        close = user,
+    )]
+    pub round_position: Account<'info, RoundPosition>,
+
+    /// CHECK: The position's round. May already be closed by close_round, in
+    /// which case anything unclaimed was swept; otherwise deserialized in the handler.
+    #[account(address = round_position.round)]
+    pub round_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Returns a round position's rent to its owner once there is nothing left to
+/// claim on it: paid out, a losing stake, or its round already closed.
+pub fn process_close_round_position(ctx: Context<CloseRoundPosition>) -> Result<()> {
+    if !ctx.accounts.round_state.data_is_empty() {
+        let round = RoundState::try_deserialize(&mut &ctx.accounts.round_state.data.borrow()[..])?;
+        require!(
+            round.status == RoundStatus::Resolved || round.status == RoundStatus::Cancelled,
+            PredictError::RoundNotSettled
+        );
+        let position = &ctx.accounts.round_position;
+        require!(
+            position.claimed || round_payout(&round, position)? == 0,
+            PredictError::RoundPayoutUnclaimed
+        );
+    }
+
+    // Position account is closed by Anchor's `close = user` constraint
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::UserTier;
+
+#[derive(Accounts)]
+pub struct InitUserTier<'info> {
+    #[account(
+        init,
+        seeds = [b"user_tier", user.key().as_ref()],
+        bump,
+        payer = user,
+        space = UserTier::LEN
+    )]
+    pub user_tier: Account<'info, UserTier>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Starts tracking the signer's betting volume for fee tiers
+pub fn process_init_user_tier(ctx: Context<InitUserTier>) -> Result<()> {
+    let tier = &mut ctx.accounts.user_tier;
+    tier.user = ctx.accounts.user.key();
+    tier.lifetime_volume = 0;
+    tier.bump = ctx.bumps.user_tier;
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, Role, Market, DisputeRecord, DisputeStatus};
+use crate::events::DisputeVotingExtended;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+pub struct ExtendDisputeVoting<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", market.key().as_ref()],
+        bump = dispute_record.bump,
+        has_one = market,
+    )]
+    pub dispute_record: Account<'info, DisputeRecord>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Resolver) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn process_extend_dispute_voting(
+    ctx: Context<ExtendDisputeVoting>,
+    market_id: u64,
+    additional_secs: i64,
+) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute_record;
+    let clock = Clock::get()?;
+
+    // Guards: only a dispute still being voted on can be extended
+    require!(dispute.status == DisputeStatus::Open || dispute.status == DisputeStatus::VotingActive, PredictError::AlreadyResolved);
+    require!(clock.unix_timestamp < dispute.voting_ends_at, PredictError::DisputeVotingClosed);
+    require!(additional_secs > 0, PredictError::InvalidTimestamps);
+
+    let old_ends_at = dispute.voting_ends_at;
+    let new_ends_at = old_ends_at.checked_add(additional_secs).ok_or(PredictError::MathOverflow)?;
+    require!(
+        new_ends_at - dispute.created_at <= ctx.accounts.platform_config.max_dispute_voting_secs,
+        PredictError::DisputeVotingTooLong
+    );
+    dispute.voting_ends_at = new_ends_at;
+
+    emit!(DisputeVotingExtended {
+        market_id,
+        old_ends_at,
+        new_ends_at,
+    });
+
+    Ok(())
+}
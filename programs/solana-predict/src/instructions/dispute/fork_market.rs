@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Transfer};
+use crate::state::{PlatformConfig, Role, Market, MarketStatus, DisputeRecord, DisputeStatus, DustRecipient, Outcome};
+use crate::events::{DisputeStatusChanged, MarketForked};
+use crate::instructions::dispute::settle_dispute::release_dispute_bond;
+use crate::errors::PredictError;
+
+/// Settles a contested dispute by forking: creates the child market for one
+/// claimed outcome, resolved to it, and moves that outcome's slice of the pool
+/// into the child's vault. Called once per outcome; holders then carry their
+/// shares into both children with `migrate_fork_position`.
+#[derive(Accounts)]
+#[instruction(market_id: u64, child_id: u64)]
+pub struct ForkMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", market.key().as_ref()],
+        bump = dispute_record.bump,
+        has_one = market,
+        has_one = disputer,
+    )]
+    pub dispute_record: Box<Account<'info, DisputeRecord>>,
+
+    /// CHECK: Validated via has_one on dispute_record; the first fork returns the bond
+    #[account(mut)]
+    pub disputer: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [b"market", child_id.to_le_bytes().as_ref()],
+        bump,
+        payer = admin,
+        space = Market::LEN
+    )]
+    pub child_market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        seeds = [b"yes_mint", child_market.key().as_ref()],
+        bump,
+        payer = admin,
+        mint::decimals = 9,
+        mint::authority = child_market,
+    )]
+    pub child_yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [b"no_mint", child_market.key().as_ref()],
+        bump,
+        payer = admin,
+        mint::decimals = 9,
+        mint::authority = child_market,
+    )]
+    pub child_no_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [b"vault", child_market.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = collateral_mint,
+        token::authority = child_market,
+    )]
+    pub child_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Every market has a creator fee escrow; close_market expects one
+    #[account(
+        init,
+        seeds = [b"creator_fees", child_market.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = collateral_mint,
+        token::authority = child_market,
+    )]
+    pub child_creator_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.has_role(&admin.key(), Role::Resolver) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = collateral_mint.key() == market.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// `yes_bps`: the YES child's share of the pool, fixed by the first fork of
+/// the market (ignored afterwards).
+pub fn process_fork_market(
+    ctx: Context<ForkMarket>,
+    market_id: u64,
+    child_id: u64,
+    outcome: Outcome,
+    yes_bps: u16,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let platform = &mut ctx.accounts.platform_config;
+    let market = &mut ctx.accounts.market;
+    let dispute = &mut ctx.accounts.dispute_record;
+
+    // Guards: a fork settles the dispute, which with approvals enabled goes through execute_action
+    require!(!platform.approvals_enabled(), PredictError::ApprovalRequired);
+    require!(platform.forking_enabled, PredictError::ForkingDisabled);
+    require!(outcome == Outcome::Yes || outcome == Outcome::No, PredictError::InvalidOutcome);
+    // Cross-mint pools settle from the payout reserve, which can't be split here
+    require!(market.payout_mint.is_none(), PredictError::ForkingDisabled);
+
+    // The first fork settles the dispute and fixes the pool split
+    match market.status {
+        MarketStatus::Disputed => {
+            require!(
+                dispute.status == DisputeStatus::Open || dispute.status == DisputeStatus::VotingActive,
+                PredictError::AlreadyResolved
+            );
+            require!(yes_bps <= 10000, PredictError::InvalidAllocation);
+            market.set_split(yes_bps);
+            market.status = MarketStatus::Forked;
+
+            let old = dispute.status;
+            dispute.status = DisputeStatus::Forked;
+            dispute.resolved_at = Some(clock.unix_timestamp);
+            release_dispute_bond(dispute, &ctx.accounts.disputer)?;
+            emit!(DisputeStatusChanged {
+                market_id,
+                old: Some(old),
+                new: dispute.status,
+            });
+        }
+        MarketStatus::Forked => {}
+        _ => return err!(PredictError::MarketNotActive),
+    }
+
+    let index = if outcome == Outcome::Yes { 0 } else { 1 };
+    require!(market.fork_children[index] == Pubkey::default(), PredictError::InvalidForkChild);
+
+    // Move this outcome's slice of the pool into the child
+    let (yes_pool, no_pool) = market.split_pools();
+    let collateral = if outcome == Outcome::Yes { yes_pool } else { no_pool };
+    if collateral > 0 {
+        let market_id_bytes = market.market_id.to_le_bytes();
+        let seeds = &[
+            b"market",
+            market_id_bytes.as_ref(),
+            &[market.bump],
+        ];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.child_vault.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            collateral,
+        )?;
+    }
+    if outcome == Outcome::Yes {
+        market.split_yes_pool = 0;
+    }
+    market.distributable_collateral = market.distributable_collateral.checked_sub(collateral).ok_or(PredictError::InsufficientVault)?;
+    market.total_collateral = market.total_collateral.checked_sub(collateral).ok_or(PredictError::InsufficientVault)?;
+    market.fork_children[index] = ctx.accounts.child_market.key();
+
+    // Child: same market, resolved to `outcome`, funded with its slice
+    let child = &mut ctx.accounts.child_market;
+    let (yes_reserve, no_reserve) = market.closing_reserves();
+    child.market_id = child_id;
+    child.creator = market.creator;
+    child.title = market.title.clone();
+    child.description = market.description.clone();
+    child.category = market.category;
+    child.status = MarketStatus::Resolved;
+    child.collateral_mint = market.collateral_mint;
+    child.yes_mint = ctx.accounts.child_yes_mint.key();
+    child.no_mint = ctx.accounts.child_no_mint.key();
+    child.vault = ctx.accounts.child_vault.key();
+    child.total_yes_shares = yes_reserve;
+    child.total_no_shares = no_reserve;
+    child.total_collateral = collateral;
+    child.oracle_source = market.oracle_source;
+    child.oracle_feed = market.oracle_feed;
+    child.oracle_threshold = market.oracle_threshold;
+    child.oracle_exponent = market.oracle_exponent;
+    child.start_timestamp = market.start_timestamp;
+    child.lock_timestamp = market.lock_timestamp;
+    child.end_timestamp = market.end_timestamp;
+    child.resolved_outcome = Some(outcome);
+    child.resolution_price = market.resolution_price;
+    child.resolved_at = Some(clock.unix_timestamp);
+    child.min_bet = market.min_bet;
+    child.max_bet = market.max_bet;
+    child.fee_bps = market.fee_bps;
+    child.is_recurring = false;
+    child.bump = ctx.bumps.child_market;
+    child.distributable_collateral = collateral;
+    child.resolution_proof = market.resolution_proof;
+    child.resolution_proof.resolver = ctx.accounts.admin.key();
+    child.resolution_proof.resolved_at = clock.unix_timestamp;
+    child.dust_recipient = DustRecipient::LastClaimer;
+    child.parent_market = market.key();
+    child.fork_yes_supply = ctx.accounts.yes_mint.supply;
+    child.fork_no_supply = ctx.accounts.no_mint.supply;
+
+    platform.total_markets = platform.total_markets.checked_add(1).ok_or(PredictError::MathOverflow)?;
+
+    emit!(MarketForked {
+        parent_market_id: market_id,
+        child_market_id: child_id,
+        outcome,
+        collateral,
+    });
+
+    Ok(())
+}
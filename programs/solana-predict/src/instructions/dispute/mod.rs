@@ -1,5 +1,9 @@
 pub mod open_dispute;
 pub mod settle_dispute;
+pub mod extend_dispute_voting;
+pub mod fork_market;
 
 pub use open_dispute::*;
 pub use settle_dispute::*;
+pub use extend_dispute_voting::*;
+pub use fork_market::*;
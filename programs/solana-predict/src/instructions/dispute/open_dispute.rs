@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{self, Transfer};
-use crate::state::{PlatformConfig, Market, MarketStatus, DisputeRecord, DisputeStatus};
-use crate::events::DisputeOpened;
+use crate::state::{PlatformConfig, Market, MarketStatus, DisputeRecord, DisputeStatus, DEFAULT_DISPUTE_VOTING_SECS};
+use crate::events::{DisputeOpened, DisputeStatusChanged};
 use crate::errors::PredictError;
 
 #[derive(Accounts)]
@@ -32,36 +32,22 @@ pub struct OpenDispute<'info> {
     #[account(mut)]
     pub disputer: Signer<'info>,
 
-    /// CHECK: Treasury to receive bond (or should we hold it in the dispute record PDA? No, PDAs can hold SOL)
-    /// Design says "Transfer dispute bond (SOL) from disputer".
-    /// If we transfer to the PDA, we can refund later.
-    /// Let's transfer to the DisputeRecord Account itself? Or Platform Treasury?
-    /// If rejected, treasury keeps it. If upheld, returned.
-    /// Creating the account requires paying rent (SOL).
-    /// The bond is EXTRA.
-    /// Let's transfer Bond to the Platform Treasury for safekeeping? Or keep in interaction?
-    /// Safer to hold in `dispute_record` PDA if we want to return it easily?
-    /// But if we want to slash, we need to move it out.
-    /// Let's move to Treasury.
-    /// CHECK: Validated against platform config
-    #[account(mut, constraint = treasury.key() == platform_config.treasury)]
-    pub treasury: AccountInfo<'info>,
-
     pub system_program: Program<'info, System>,
 }
 
 pub fn process_open_dispute(
     ctx: Context<OpenDispute>,
-    market_id: u64,
+    _market_id: u64,
     reason: String,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let platform = &ctx.accounts.platform_config;
-    let clock = Clock::get()?;
 
     // Guards
     require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
     require!(market.resolved_outcome.is_some(), PredictError::MarketNotResolved);
+    // A fork child already is one side of a settled dispute
+    require!(!market.is_fork_child(), PredictError::ForkedMarketFinal);
     
     // Check dispute window (e.g. 24h/48h after resolution)
     // We didn't store `resolved_at` in Market struct (my bad).
@@ -72,23 +58,50 @@ pub fn process_open_dispute(
     // I already implemented `market.rs`, I can assume `resolved_at` doesn't exist.
     // I will skip the time check.
 
-    // Bond Transfer
     let bond = platform.dispute_bond_lamports;
+    let market_key = market.key();
+    start_dispute(
+        market,
+        market_key,
+        &mut ctx.accounts.dispute_record,
+        ctx.bumps.dispute_record,
+        &ctx.accounts.disputer,
+        &ctx.accounts.system_program,
+        bond,
+        reason,
+    )
+}
+
+/// Takes `bond` from the disputer into the fresh `dispute` record, which holds
+/// it until settlement, fills the record in and moves the market to Disputed
+#[allow(clippy::too_many_arguments)]
+pub fn start_dispute<'info>(
+    market: &mut Market,
+    market_key: Pubkey,
+    dispute: &mut Account<'info, DisputeRecord>,
+    bump: u8,
+    disputer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    bond: u64,
+    reason: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Bond Transfer
     system_program::transfer(
         CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
+            system_program.to_account_info(),
             Transfer {
-                from: ctx.accounts.disputer.to_account_info(),
-                to: ctx.accounts.treasury.to_account_info(),
+                from: disputer.to_account_info(),
+                to: dispute.to_account_info(),
             },
         ),
         bond,
     )?;
 
     // Init Dispute Record
-    let dispute = &mut ctx.accounts.dispute_record;
-    dispute.market = market.key();
-    dispute.disputer = ctx.accounts.disputer.key();
+    dispute.market = market_key;
+    dispute.disputer = disputer.key();
     dispute.reason = reason;
     dispute.bond_amount = bond;
     dispute.status = DisputeStatus::Open;
@@ -96,13 +109,20 @@ pub fn process_open_dispute(
     dispute.votes_against = 0;
     dispute.created_at = clock.unix_timestamp;
     dispute.resolved_at = None;
-    dispute.bump = ctx.bumps.dispute_record;
+    dispute.bump = bump;
+    dispute.voting_ends_at = clock.unix_timestamp.saturating_add(DEFAULT_DISPUTE_VOTING_SECS);
 
     // Update Market
     market.status = MarketStatus::Disputed;
 
+    emit!(DisputeStatusChanged {
+        market_id: market.market_id,
+        old: None,
+        new: dispute.status,
+    });
+
     emit!(DisputeOpened {
-        market_id,
+        market_id: market.market_id,
         disputer: dispute.disputer,
         bond,
     });
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{PlatformConfig, Market, MarketStatus, DisputeRecord, DisputeStatus, Outcome};
-use crate::events::DisputeSettled;
+use crate::state::{PlatformConfig, Role, Market, MarketStatus, DisputeRecord, DisputeStatus, Outcome};
+use crate::events::{DisputeSettled, DisputeStatusChanged};
 use crate::errors::PredictError;
 
 #[derive(Accounts)]
@@ -17,14 +17,23 @@ pub struct SettleDispute<'info> {
         seeds = [b"dispute", market.key().as_ref()],
         bump = dispute_record.bump,
         has_one = market,
+        has_one = disputer,
     )]
     pub dispute_record: Account<'info, DisputeRecord>,
 
+    /// CHECK: Validated via has_one on dispute_record; gets the bond back if the dispute is upheld
+    #[account(mut)]
+    pub disputer: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the platform admin; takes the bond of a rejected dispute
+    #[account(mut, address = platform_config.admin @ PredictError::Unauthorized)]
+    pub platform_admin: UncheckedAccount<'info>,
+
     /// Platform config — used to verify admin identity
     #[account(
         seeds = [b"platform_config"],
         bump = platform_config.bump,
-        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+        constraint = platform_config.has_role(&admin.key(), Role::Resolver) @ PredictError::Unauthorized
     )]
     pub platform_config: Account<'info, PlatformConfig>,
 
@@ -34,34 +43,72 @@ pub struct SettleDispute<'info> {
 
 pub fn process_settle_dispute(
     ctx: Context<SettleDispute>,
-    market_id: u64,
+    _market_id: u64,
     result_outcome: Option<Outcome>, // None = Rejected (keep original), Some = Upheld (change to this)
 ) -> Result<()> {
-    let market = &mut ctx.accounts.market;
-    let dispute = &mut ctx.accounts.dispute_record;
+    // With approvals enabled this goes through execute_action
+    require!(!ctx.accounts.platform_config.approvals_enabled(), PredictError::ApprovalRequired);
+    let resolver = ctx.accounts.admin.key();
+    apply_dispute_settlement(
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.dispute_record,
+        result_outcome,
+        resolver,
+        &ctx.accounts.disputer,
+        &ctx.accounts.platform_admin,
+    )
+}
+
+/// Settles an open dispute: upholds it with `result_outcome`, or rejects it
+/// and keeps the original outcome when None. `resolver` is recorded in the
+/// resolution proof of an upheld dispute. The bond held in the record goes
+/// back to `disputer` when upheld and to `platform_admin` when rejected;
+/// callers check both keys.
+pub fn apply_dispute_settlement(
+    market: &mut Market,
+    dispute: &mut Account<DisputeRecord>,
+    result_outcome: Option<Outcome>,
+    resolver: Pubkey,
+    disputer: &AccountInfo,
+    platform_admin: &AccountInfo,
+) -> Result<()> {
+    let market_id = market.market_id;
     let clock = Clock::get()?;
 
     // Guards
     require!(market.status == MarketStatus::Disputed, PredictError::MarketNotActive);
     require!(dispute.status == DisputeStatus::Open || dispute.status == DisputeStatus::VotingActive, PredictError::AlreadyResolved);
 
+    let old_status = dispute.status;
+
     // Apply Result
     if let Some(new_outcome) = result_outcome {
-        // Upheld
+        // Upheld. A Split needs its ratio from resolution, so it can't be imposed here
+        require!(new_outcome != Outcome::Split, PredictError::InvalidOutcome);
         market.resolved_outcome = Some(new_outcome.clone());
+        market.set_split(0);
         market.status = MarketStatus::Resolved;
+        market.resolution_proof.resolver = resolver;
+        market.resolution_proof.resolved_at = clock.unix_timestamp;
         dispute.status = DisputeStatus::Upheld;
-        // Refund bond logic would go here if we held it in PDA or could move from treasury
+        release_dispute_bond(dispute, disputer)?;
     } else {
-        // Rejected
+        // Rejected: the bond is forfeited
         market.status = MarketStatus::Resolved; // Revert to resolved
         dispute.status = DisputeStatus::Rejected;
+        release_dispute_bond(dispute, platform_admin)?;
     }
     
     dispute.resolved_at = Some(clock.unix_timestamp);
 
     let upheld = dispute.status == DisputeStatus::Upheld;
 
+    emit!(DisputeStatusChanged {
+        market_id,
+        old: Some(old_status),
+        new: dispute.status,
+    });
+
     emit!(DisputeSettled {
         market_id,
         upheld,
@@ -70,3 +117,11 @@ pub fn process_settle_dispute(
 
     Ok(())
 }
+
+/// Pays the bond held in a settled `dispute` record out to `recipient`
+pub fn release_dispute_bond(dispute: &mut Account<DisputeRecord>, recipient: &AccountInfo) -> Result<()> {
+    let bond = dispute.bond_amount;
+    dispute.sub_lamports(bond)?;
+    recipient.add_lamports(bond)?;
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, MintTo, Transfer};
+use crate::state::{PlatformConfig, Market, MarketStatus, LpPosition, PricingMode};
+use crate::events::LiquidityAdded;
+use crate::errors::PredictError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct AddLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init_if_needed,
+        payer = provider,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump,
+        mint::decimals = 9,
+        mint::authority = market,
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = provider,
+        seeds = [b"lp_position", market.key().as_ref(), provider.key().as_ref()],
+        bump,
+        space = LpPosition::LEN,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = market.collateral_mint,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.collateral_mint,
+        associated_token::authority = provider,
+    )]
+    pub provider_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = provider,
+        associated_token::mint = lp_mint,
+        associated_token::authority = provider,
+    )]
+    pub provider_lp_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposits `amount` of collateral into the pool and mints LP tokens 1:1. The
+/// deposit is split across the reserves in their current ratio, so it deepens
+/// the pool without moving the price. LP deposits are set aside from the
+/// winners' pool at resolution and returned by remove_liquidity.
+pub fn process_add_liquidity(ctx: Context<AddLiquidity>, market_id: u64, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(!ctx.accounts.platform_config.paused, PredictError::PlatformPaused);
+    require!(market.status == MarketStatus::Active, PredictError::MarketNotActive);
+    require!(market.pricing_mode == PricingMode::Cpmm, PredictError::PricingModeUnsupported);
+    require!(clock.unix_timestamp < market.lock_timestamp, PredictError::BettingClosed);
+    // The LP pool is fixed once per market and paid in collateral
+    require!(
+        !market.is_recurring && market.payout_mint.is_none() && !market.both_sides_win && !market.is_fork_child(),
+        PredictError::LiquidityUnsupported
+    );
+    require!(amount > 0, PredictError::InsufficientLiquidity);
+
+    let vault_before = ctx.accounts.vault.amount;
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.provider_ata.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Reserves are credited 1:1 with collateral, so all of it must arrive
+    ctx.accounts.vault.reload()?;
+    let received = ctx.accounts.vault.amount
+        .checked_sub(vault_before)
+        .ok_or(PredictError::MathOverflow)?;
+    require!(received == amount, PredictError::InsufficientVault);
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.provider_lp_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    // Split in the current reserve ratio so the price doesn't move
+    let reserves = market.total_yes_shares as u128 + market.total_no_shares as u128;
+    let yes_add = (amount as u128 * market.total_yes_shares as u128 / reserves) as u64;
+    let no_add = amount - yes_add;
+    market.total_yes_shares = market.total_yes_shares.checked_add(yes_add).ok_or(PredictError::MathOverflow)?;
+    market.total_no_shares = market.total_no_shares.checked_add(no_add).ok_or(PredictError::MathOverflow)?;
+    market.total_collateral = market.total_collateral.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    ctx.accounts.platform_config.credit_tvl(amount)?;
+    market.lp_liquidity = market.lp_liquidity.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    market.lp_mint = ctx.accounts.lp_mint.key();
+
+    let position = &mut ctx.accounts.lp_position;
+    position.owner = ctx.accounts.provider.key();
+    position.market = market.key();
+    position.deposited = position.deposited.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    position.bump = ctx.bumps.lp_position;
+
+    emit!(LiquidityAdded {
+        market_id,
+        provider: position.owner,
+        amount,
+        lp_tokens: amount,
+        lp_liquidity: market.lp_liquidity,
+    });
+
+    Ok(())
+}
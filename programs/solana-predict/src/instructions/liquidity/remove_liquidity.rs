@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, TokenAccount, Burn, Transfer};
+use crate::state::{PlatformConfig, Market, MarketStatus, LpPosition};
+use crate::events::LiquidityRemoved;
+use crate::errors::PredictError;
+use crate::utils::pro_rata_payout;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct RemoveLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump,
+        constraint = lp_mint.key() == market.lp_mint @ PredictError::InvalidMint
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    // LP tokens are transferable, so a holder may not have added liquidity
+    #[account(
+        init_if_needed,
+        payer = provider,
+        seeds = [b"lp_position", market.key().as_ref(), provider.key().as_ref()],
+        bump,
+        space = LpPosition::LEN,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        token::mint = market.collateral_mint,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.collateral_mint,
+        associated_token::authority = provider,
+    )]
+    pub provider_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = provider,
+    )]
+    pub provider_lp_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Burns `lp_tokens` and pays their pro-rata share of the LP pool set aside at
+/// resolution. A forked or cancelled market keeps its LP pool, so it can be
+/// withdrawn there too. During an emergency shutdown an unresolved market pays
+/// from the LP part of its pool, the part claim_emergency_refund leaves out.
+pub fn process_remove_liquidity(ctx: Context<RemoveLiquidity>, market_id: u64, lp_tokens: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let settled = matches!(market.status, MarketStatus::Resolved | MarketStatus::Forked | MarketStatus::Cancelled);
+    let shutdown = !settled && ctx.accounts.platform_config.shutdown;
+
+    // Guards
+    require!(settled || shutdown, PredictError::MarketNotResolved);
+    require!(
+        lp_tokens > 0 && lp_tokens <= ctx.accounts.provider_lp_account.amount,
+        PredictError::NoPosition
+    );
+
+    let lp_pool = if shutdown {
+        market.lp_pool_at_resolution(ctx.accounts.vault.amount)
+    } else {
+        market.lp_pool
+    };
+    let amount = pro_rata_payout(lp_tokens, lp_pool, ctx.accounts.lp_mint.supply)
+        .min(ctx.accounts.vault.amount);
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.provider_lp_account.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        lp_tokens,
+    )?;
+
+    if amount > 0 {
+        let market_id_bytes = market.market_id.to_le_bytes();
+        let seeds = &[
+            b"market",
+            market_id_bytes.as_ref(),
+            &[market.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.provider_ata.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+    }
+
+    if shutdown {
+        market.debit_lp_funds(amount);
+    } else {
+        market.lp_pool -= amount;
+    }
+    let released = amount.min(market.total_collateral);
+    market.total_collateral -= released;
+    ctx.accounts.platform_config.debit_tvl(released);
+
+    let position = &mut ctx.accounts.lp_position;
+    position.owner = ctx.accounts.provider.key();
+    position.market = market.key();
+    position.bump = ctx.bumps.lp_position;
+    position.withdrawn = position.withdrawn.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+
+    emit!(LiquidityRemoved {
+        market_id,
+        provider: position.owner,
+        lp_tokens,
+        amount,
+    });
+
+    Ok(())
+}
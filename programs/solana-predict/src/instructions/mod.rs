@@ -2,8 +2,12 @@ pub mod admin;
 pub mod betting;
 pub mod oracle;
 pub mod dispute;
+pub mod views;
+pub mod liquidity;
 
 pub use admin::*;
 pub use betting::*;
 pub use oracle::*;
 pub use dispute::*;
+pub use views::*;
+pub use liquidity::*;
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{PlatformConfig, Market, MarketStatus, Outcome, ResolutionCommittee, OutcomeAttestations};
+use crate::events::OutcomeAttested;
+use crate::errors::PredictError;
+use super::resolve_market::{apply_manual_resolution, require_payout_reserve};
+
+#[derive(Accounts)]
+pub struct AttestOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"resolution_committee"],
+        bump = committee.bump,
+        constraint = committee.is_member(&member.key()) @ PredictError::Unauthorized
+    )]
+    pub committee: Account<'info, ResolutionCommittee>,
+
+    #[account(
+        init_if_needed,
+        payer = member,
+        seeds = [b"attestations", market.key().as_ref()],
+        bump,
+        space = OutcomeAttestations::LEN,
+    )]
+    pub attestations: Box<Account<'info, OutcomeAttestations>>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Payout reserve (optional - only needed for markets with a separate payout mint)
+    #[account(
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// A committee member attests `outcome` for a committee market (and may change
+/// it until the market resolves). The attestation that brings one outcome to
+/// the committee threshold resolves the market.
+pub fn process_attest_outcome(ctx: Context<AttestOutcome>, market_id: u64, outcome: Outcome) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let committee = &ctx.accounts.committee;
+    let member = ctx.accounts.member.key();
+
+    require!(!ctx.accounts.platform_config.shutdown, PredictError::PlatformShutdown);
+    require!(market.committee_resolution, PredictError::Unauthorized);
+    require!(
+        market.status == MarketStatus::Active
+            || market.status == MarketStatus::Locked
+            || market.status == MarketStatus::Resolving,
+        PredictError::AlreadyResolved
+    );
+    require!(
+        outcome == Outcome::Yes || outcome == Outcome::No || outcome == Outcome::Invalid,
+        PredictError::InvalidOutcome
+    );
+
+    let attestations = &mut ctx.accounts.attestations;
+    if attestations.market == Pubkey::default() {
+        attestations.market = market.key();
+        attestations.bump = ctx.bumps.attestations;
+    }
+    attestations.record(committee, member, outcome)?;
+    let count = attestations.count(committee, outcome);
+
+    emit!(OutcomeAttested {
+        market_id,
+        member,
+        outcome,
+        attestations: count,
+        threshold: committee.threshold,
+    });
+
+    if count >= committee.threshold {
+        require_payout_reserve(market, ctx.accounts.payout_reserve.as_ref())?;
+        apply_manual_resolution(
+            market,
+            &mut ctx.accounts.platform_config,
+            ctx.accounts.vault.amount,
+            outcome,
+            member,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{PlatformConfig, Market, MarketStatus, OracleCache, Outcome, ResolutionProof};
+use crate::events::{BarrierTouched, MarketResolved};
+use crate::errors::PredictError;
+use crate::utils::{cache_pyth_print, read_pyth_price_with_conf};
+use super::resolve_market::require_payout_reserve;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CheckBarrier<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = pyth_price_feed.key() == market.oracle_feed @ PredictError::InvalidPythFeed
+    )]
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+
+    /// Payout reserve (optional - only needed for markets with a separate payout mint)
+    #[account(
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    /// Anyone can crank the barrier
+    pub caller: Signer<'info>,
+}
+
+/// Permissionless: resolves a barrier market YES as soon as the feed prints
+/// above `oracle_threshold`, even before expiry. Fails with BarrierNotTouched
+/// otherwise; an untouched barrier resolves NO through resolve_market at expiry.
+pub fn process_check_barrier(ctx: Context<CheckBarrier>, market_id: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(!ctx.accounts.platform_config.shutdown, PredictError::PlatformShutdown);
+    require!(market.barrier, PredictError::OracleMismatch);
+    require!(market.status == MarketStatus::Active || market.status == MarketStatus::Locked, PredictError::AlreadyResolved);
+
+    read_pyth_price_with_conf(&ctx.accounts.pyth_price_feed, clock.unix_timestamp)?;
+    let exponent = market.pyth_exponent(ctx.accounts.pyth_price_feed.price_message.exponent);
+    market.oracle_cache = cache_pyth_print(&ctx.accounts.pyth_price_feed, clock.slot, exponent)?;
+    let OracleCache { price, publish_time, posted_slot, .. } = market.oracle_cache;
+    require!(price > market.oracle_threshold, PredictError::BarrierNotTouched);
+
+    require_payout_reserve(market, ctx.accounts.payout_reserve.as_ref())?;
+
+    // Too few distinct bettors to trust the outcome: refund as INVALID
+    let min_bettors = ctx.accounts.platform_config.min_bettors_for_resolution;
+    let outcome = if min_bettors > 0 && market.unique_bettors < min_bettors {
+        Outcome::Invalid
+    } else {
+        Outcome::Yes
+    };
+
+    market.resolved_outcome = Some(outcome);
+    market.resolution_price = Some(price);
+    market.resolved_at = Some(clock.unix_timestamp);
+    market.status = MarketStatus::Resolved;
+    market.distributable_collateral = market.distributable_at_resolution(ctx.accounts.vault.amount);
+    market.lp_pool = market.lp_pool_at_resolution(ctx.accounts.vault.amount);
+    market.set_split(0);
+    market.resolution_proof = ResolutionProof {
+        oracle_feed: market.oracle_feed,
+        resolution_price: price,
+        publish_time,
+        feed_slot: posted_slot,
+        resolved_at: clock.unix_timestamp,
+        resolver: ctx.accounts.caller.key(),
+    };
+
+    let platform = &mut ctx.accounts.platform_config;
+    platform.active_market_count = platform.active_market_count.saturating_sub(1);
+
+    emit!(BarrierTouched {
+        market_id,
+        price,
+        threshold: market.oracle_threshold,
+        caller: ctx.accounts.caller.key(),
+    });
+    emit!(MarketResolved {
+        market_id,
+        outcome,
+        resolution_price: price,
+        total_collateral: market.total_collateral,
+    });
+
+    Ok(())
+}
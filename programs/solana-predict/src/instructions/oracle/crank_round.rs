@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{PlatformConfig, Market, RoundState};
+use crate::errors::PredictError;
+use crate::instructions::admin::restart_recurring::SeriesBalances;
+use crate::instructions::admin::start_round::open_next_round;
+use super::resolve_round::settle_round;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CrankRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    /// The round being resolved
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump = round_state.bump,
+    )]
+    pub round_state: Box<Account<'info, RoundState>>,
+
+    /// The round being opened
+    #[account(
+        init,
+        seeds = [b"round", market.key().as_ref(), (market.current_round + 1).to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        space = RoundState::LEN
+    )]
+    pub next_round_state: Box<Account<'info, RoundState>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+        constraint = yes_mint.key() == market.yes_mint @ PredictError::InvalidMint
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+        constraint = no_mint.key() == market.no_mint @ PredictError::InvalidMint
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        constraint = pyth_price_feed.key() == market.oracle_feed @ PredictError::InvalidPythFeed
+    )]
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+
+    /// Payout reserve (optional - only needed for markets with a separate payout mint)
+    #[account(
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    /// Anyone may crank; pays for the next round's account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless keeper step for recurring rounds: resolves the locked round
+/// once it has ended (resolve_round) and opens the next one on the schedule
+/// (start_round) in the same call.
+pub fn process_crank_round(ctx: Context<CrankRound>, market_id: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    let vault_amount = accounts.vault.amount;
+
+    settle_round(
+        &mut accounts.market,
+        &mut accounts.round_state,
+        &mut accounts.platform_config,
+        vault_amount,
+        &accounts.pyth_price_feed,
+        accounts.payout_reserve.as_ref(),
+        accounts.payer.key(),
+        market_id,
+    )?;
+
+    open_next_round(
+        &mut accounts.market,
+        &mut accounts.platform_config,
+        SeriesBalances {
+            yes_supply: accounts.yes_mint.supply,
+            no_supply: accounts.no_mint.supply,
+            vault_amount,
+        },
+        &mut accounts.next_round_state,
+        ctx.bumps.next_round_state,
+        market_id,
+    )
+}
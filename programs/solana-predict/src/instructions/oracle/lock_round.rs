@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{Market, MarketStatus, OracleSource, RoundState, RoundStatus};
+use crate::events::RoundLocked;
+use crate::errors::PredictError;
+use crate::utils::{cache_pyth_print, read_pyth_price_with_conf, SNAPSHOT_WINDOW_SECS};
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct LockRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump = round_state.bump,
+    )]
+    pub round_state: Account<'info, RoundState>,
+
+    #[account(
+        constraint = pyth_price_feed.key() == market.oracle_feed @ PredictError::InvalidPythFeed
+    )]
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+/// Permissionless: records the feed value at the current round's `lock_ts` as
+/// its lock price and closes the round to new bets.
+pub fn process_lock_round(ctx: Context<LockRound>, market_id: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let round = &mut ctx.accounts.round_state;
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(market.oracle_source == OracleSource::Pyth, PredictError::OracleMismatch);
+    require!(round.status == RoundStatus::Open, PredictError::RoundNotOpen);
+    require!(clock.unix_timestamp >= round.lock_ts, PredictError::BettingStillOpen);
+    require!(
+        clock.unix_timestamp <= round.lock_ts + SNAPSHOT_WINDOW_SECS,
+        PredictError::SnapshotWindowClosed
+    );
+
+    read_pyth_price_with_conf(&ctx.accounts.pyth_price_feed, clock.unix_timestamp)?;
+    let exponent = market.pyth_exponent(ctx.accounts.pyth_price_feed.price_message.exponent);
+    let price = cache_pyth_print(&ctx.accounts.pyth_price_feed, clock.slot, exponent)?.price;
+
+    round.lock_price = Some(price);
+    round.status = RoundStatus::Locked;
+    if market.status == MarketStatus::Active {
+        market.lock();
+    }
+
+    emit!(RoundLocked {
+        market_id,
+        round_id: round.round_id,
+        lock_price: price,
+    });
+
+    Ok(())
+}
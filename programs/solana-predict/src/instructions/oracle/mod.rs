@@ -1,3 +1,25 @@
 pub mod resolve_market;
+pub mod snapshot_resolution_price;
+pub mod record_price_snapshot;
+pub mod check_barrier;
+pub mod resolve_on_timeout;
+pub mod refresh_oracle_cache;
+pub mod resolve_both_sides;
+pub mod optimistic_resolution;
+pub mod attest_outcome;
+pub mod lock_round;
+pub mod resolve_round;
+pub mod crank_round;
 
 pub use resolve_market::*;
+pub use snapshot_resolution_price::*;
+pub use record_price_snapshot::*;
+pub use check_barrier::*;
+pub use resolve_on_timeout::*;
+pub use refresh_oracle_cache::*;
+pub use resolve_both_sides::*;
+pub use optimistic_resolution::*;
+pub use attest_outcome::*;
+pub use lock_round::*;
+pub use resolve_round::*;
+pub use crank_round::*;
@@ -0,0 +1,313 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::TokenAccount;
+use crate::state::{
+    PlatformConfig, Market, MarketStatus, OracleSource, Outcome,
+    ResolutionProposal, DisputeRecord, DisputeStatus,
+};
+use crate::events::{ResolutionChallenged, ResolutionFinalized, ResolutionProposed};
+use crate::errors::PredictError;
+use crate::instructions::dispute::open_dispute::start_dispute;
+use super::resolve_market::{apply_manual_resolution, require_payout_reserve};
+
+#[derive(Accounts)]
+pub struct ProposeResolution<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [b"resolution_proposal", market.key().as_ref()],
+        bump,
+        payer = proposer,
+        space = ResolutionProposal::LEN
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Payout reserve (optional - only needed for markets with a separate payout mint)
+    #[account(
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    /// Anyone may propose; the bond is held in `proposal`
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeResolution<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution_proposal", market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+
+    #[account(
+        init,
+        seeds = [b"dispute", market.key().as_ref()],
+        bump,
+        payer = challenger,
+        space = DisputeRecord::LEN
+    )]
+    pub dispute_record: Account<'info, DisputeRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution_proposal", market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+
+    /// CHECK: Validated via has_one on proposal; receives the rent (and the bond unless forfeited)
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Dispute opened by the challenge (optional - only needed once challenged)
+    #[account(
+        seeds = [b"dispute", market.key().as_ref()],
+        bump = dispute_record.bump,
+    )]
+    pub dispute_record: Option<Account<'info, DisputeRecord>>,
+
+    /// Challenger (optional - only needed when an upheld challenge takes the bond)
+    /// CHECK: Must be the dispute's disputer, checked in the instruction logic
+    #[account(mut)]
+    pub challenger: Option<UncheckedAccount<'info>>,
+
+    /// Anyone can finalize once the window has passed or the dispute is settled
+    pub caller: Signer<'info>,
+}
+
+/// Unresolved and not held by a pause or dispute: a proposal may still apply
+fn awaiting_resolution(market: &Market) -> bool {
+    market.resolved_outcome.is_none()
+        && matches!(
+            market.status,
+            MarketStatus::Active | MarketStatus::Locked | MarketStatus::Resolving
+        )
+}
+
+/// Anyone posts `outcome` for an ended ManualAdmin market with the platform's
+/// resolution bond. Unchallenged for `challenge_window_secs`, it is applied by
+/// finalize_resolution; a resolver may still resolve the market directly.
+pub fn process_propose_resolution(
+    ctx: Context<ProposeResolution>,
+    market_id: u64,
+    outcome: Outcome,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let platform = &ctx.accounts.platform_config;
+    let clock = Clock::get()?;
+
+    require!(!platform.shutdown, PredictError::PlatformShutdown);
+    require!(market.oracle_source == OracleSource::ManualAdmin, PredictError::ProposalUnsupported);
+    require!(!market.committee_resolution, PredictError::CommitteeResolutionRequired);
+    require!(
+        market.status == MarketStatus::Active || market.status == MarketStatus::Locked,
+        PredictError::AlreadyResolved
+    );
+    // Unlike a resolver, a proposer can't resolve early
+    require!(market.end_reached(&clock), PredictError::RoundIncomplete);
+    require!(
+        outcome == Outcome::Yes || outcome == Outcome::No || outcome == Outcome::Invalid,
+        PredictError::InvalidOutcome
+    );
+    require_payout_reserve(market, ctx.accounts.payout_reserve.as_ref())?;
+
+    let bond = platform.resolution_bond_lamports;
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.proposer.to_account_info(),
+                to: ctx.accounts.proposal.to_account_info(),
+            },
+        ),
+        bond,
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.market = market.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.outcome = outcome;
+    proposal.bond_amount = bond;
+    proposal.proposed_at = clock.unix_timestamp;
+    proposal.challenge_ends_at = clock.unix_timestamp
+        .checked_add(platform.challenge_window_secs)
+        .ok_or(PredictError::MathOverflow)?;
+    proposal.challenged = false;
+    proposal.bump = ctx.bumps.proposal;
+
+    market.status = MarketStatus::Resolving;
+
+    emit!(ResolutionProposed {
+        market_id,
+        proposer: proposal.proposer,
+        outcome,
+        bond,
+        challenge_ends_at: proposal.challenge_ends_at,
+    });
+
+    Ok(())
+}
+
+/// Contests a proposal within its window. The proposed outcome is applied and
+/// immediately disputed (with the platform's dispute bond), so the existing
+/// dispute path decides it: settle_dispute upholds the challenge with a new
+/// outcome or rejects it and keeps the proposal's.
+pub fn process_challenge_resolution(
+    ctx: Context<ChallengeResolution>,
+    market_id: u64,
+    reason: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    let market = &mut ctx.accounts.market;
+
+    require!(!ctx.accounts.platform_config.shutdown, PredictError::PlatformShutdown);
+    require!(!proposal.challenged, PredictError::AlreadyChallenged);
+    require!(clock.unix_timestamp < proposal.challenge_ends_at, PredictError::ChallengeWindowClosed);
+    require!(awaiting_resolution(market), PredictError::AlreadyResolved);
+
+    apply_manual_resolution(
+        market,
+        &mut ctx.accounts.platform_config,
+        ctx.accounts.vault.amount,
+        proposal.outcome,
+        proposal.proposer,
+    )?;
+
+    let market_key = market.key();
+    start_dispute(
+        market,
+        market_key,
+        &mut ctx.accounts.dispute_record,
+        ctx.bumps.dispute_record,
+        &ctx.accounts.challenger,
+        &ctx.accounts.system_program,
+        ctx.accounts.platform_config.dispute_bond_lamports,
+        reason,
+    )?;
+    proposal.challenged = true;
+
+    emit!(ResolutionChallenged {
+        market_id,
+        challenger: ctx.accounts.challenger.key(),
+    });
+
+    Ok(())
+}
+
+/// Closes a proposal. Unchallenged, it applies the proposed outcome once the
+/// window has passed and returns the bond. Challenged, it waits for the dispute
+/// to settle: a rejected challenge returns the bond to the proposer, an upheld
+/// (or forked) one pays it to the challenger.
+pub fn process_finalize_resolution(ctx: Context<FinalizeResolution>, market_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &ctx.accounts.proposal;
+    let market = &mut ctx.accounts.market;
+
+    let bond_recipient = if proposal.challenged {
+        let dispute = ctx.accounts.dispute_record.as_ref().ok_or(PredictError::DisputeNotSettled)?;
+        match dispute.status {
+            DisputeStatus::Rejected => proposal.proposer,
+            DisputeStatus::Upheld | DisputeStatus::Forked => {
+                let challenger = ctx.accounts.challenger.as_ref().ok_or(PredictError::Unauthorized)?;
+                require!(challenger.key() == dispute.disputer, PredictError::Unauthorized);
+                ctx.accounts.proposal.sub_lamports(proposal.bond_amount)?;
+                challenger.add_lamports(proposal.bond_amount)?;
+                dispute.disputer
+            }
+            DisputeStatus::Open | DisputeStatus::VotingActive => return err!(PredictError::DisputeNotSettled),
+        }
+    } else if awaiting_resolution(market) {
+        require!(!ctx.accounts.platform_config.shutdown, PredictError::PlatformShutdown);
+        require!(clock.unix_timestamp >= proposal.challenge_ends_at, PredictError::ChallengeWindowOpen);
+        apply_manual_resolution(
+            market,
+            &mut ctx.accounts.platform_config,
+            ctx.accounts.vault.amount,
+            proposal.outcome,
+            proposal.proposer,
+        )?;
+        proposal.proposer
+    } else {
+        // A resolver settled the market first: nothing to apply, the bond goes back
+        proposal.proposer
+    };
+
+    emit!(ResolutionFinalized {
+        market_id,
+        outcome: market.resolved_outcome,
+        bond_recipient,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{Market, MarketStatus, OracleSource, PriceSnapshot, PriceSnapshots, MAX_PRICE_SNAPSHOTS};
+use crate::events::PriceSnapshotRecorded;
+use crate::errors::PredictError;
+use crate::utils::{cache_pyth_print, read_pyth_price_with_conf};
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct RecordPriceSnapshot<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"price_snapshots", market.key().as_ref()],
+        bump,
+        payer = cranker,
+        space = PriceSnapshots::LEN
+    )]
+    pub price_snapshots: Box<Account<'info, PriceSnapshots>>,
+
+    #[account(
+        constraint = pyth_price_feed.key() == market.oracle_feed @ PredictError::InvalidPythFeed
+    )]
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+
+    /// Anyone may crank; pays for the ring on first use
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: records the current feed value into the market's ring during
+/// its final `twap_window_secs`, at most MAX_PRICE_SNAPSHOTS times per window.
+pub fn process_record_price_snapshot(
+    ctx: Context<RecordPriceSnapshot>,
+    market_id: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let snapshots = &mut ctx.accounts.price_snapshots;
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(market.oracle_source == OracleSource::Pyth, PredictError::OracleMismatch);
+    require!(market.twap_window_secs > 0, PredictError::TwapDisabled);
+    require!(market.status == MarketStatus::Active || market.status == MarketStatus::Locked, PredictError::AlreadyResolved);
+    require!(
+        clock.unix_timestamp >= market.end_timestamp - market.twap_window_secs
+            && clock.unix_timestamp <= market.end_timestamp,
+        PredictError::SnapshotWindowClosed
+    );
+    // Spaced so the ring covers the whole window
+    let min_interval = (market.twap_window_secs / MAX_PRICE_SNAPSHOTS as i64).max(1);
+    let last = snapshots.latest().observed_at;
+    require!(last == 0 || clock.unix_timestamp - last >= min_interval, PredictError::SnapshotTooSoon);
+
+    read_pyth_price_with_conf(&ctx.accounts.pyth_price_feed, clock.unix_timestamp)?;
+    let exponent = market.pyth_exponent(ctx.accounts.pyth_price_feed.price_message.exponent);
+    let price = cache_pyth_print(&ctx.accounts.pyth_price_feed, clock.slot, exponent)?.price;
+
+    snapshots.market = market.key();
+    snapshots.bump = ctx.bumps.price_snapshots;
+    snapshots.push(PriceSnapshot { price, observed_at: clock.unix_timestamp });
+
+    emit!(PriceSnapshotRecorded {
+        market_id,
+        price,
+        observed_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{Market, MarketStatus, OracleSource};
+use crate::errors::PredictError;
+use crate::utils::cache_pyth_print;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct RefreshOracleCache<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        constraint = pyth_price_feed.key() == market.oracle_feed @ PredictError::InvalidPythFeed
+    )]
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+/// Permissionless: loads the feed's current print into the market's oracle
+/// cache so later instructions in the same slot can skip the feed read.
+/// Staleness is checked by the consumer, against its own rules.
+pub fn process_refresh_oracle_cache(ctx: Context<RefreshOracleCache>, _market_id: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(market.oracle_source == OracleSource::Pyth, PredictError::OracleMismatch);
+    // Once snapshotted, the cache holds the resolution print: leave it alone
+    require!(
+        market.status == MarketStatus::Active || market.status == MarketStatus::Locked,
+        PredictError::AlreadyResolved
+    );
+
+    if market.oracle_cache.is_valid_at(clock.slot) {
+        msg!("Oracle cache hit (slot {})", clock.slot);
+        return Ok(());
+    }
+    let exponent = market.pyth_exponent(ctx.accounts.pyth_price_feed.price_message.exponent);
+    market.oracle_cache = cache_pyth_print(&ctx.accounts.pyth_price_feed, clock.slot, exponent)?;
+
+    Ok(())
+}
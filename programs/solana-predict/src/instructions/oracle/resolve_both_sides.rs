@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{PlatformConfig, Market, MarketStatus, Outcome, ResolutionProof};
+use crate::events::MarketResolved;
+use crate::errors::PredictError;
+use super::resolve_market::require_payout_reserve;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ResolveBothSides<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = market.is_resolver(&admin.key(), &platform_config) @ PredictError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Payout reserve (optional - only needed for markets with a separate payout mint)
+    #[account(
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Resolves a both-sides-win market as a Split with explicit allocations: YES
+/// holders share `yes_allocation`, NO holders `no_allocation`. Both sides get a
+/// non-zero floor and together they must account for the whole pool.
+pub fn process_resolve_both_sides(
+    ctx: Context<ResolveBothSides>,
+    market_id: u64,
+    yes_allocation: u64,
+    no_allocation: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(!ctx.accounts.platform_config.shutdown, PredictError::PlatformShutdown);
+    require!(market.both_sides_win, PredictError::BothSidesWinDisabled);
+    require!(
+        market.status == MarketStatus::Active
+            || market.status == MarketStatus::Locked
+            || market.status == MarketStatus::Resolving,
+        PredictError::AlreadyResolved
+    );
+
+    require!(yes_allocation > 0 && no_allocation > 0, PredictError::InvalidAllocation);
+    let total = yes_allocation.checked_add(no_allocation).ok_or(PredictError::MathOverflow)?;
+    require!(total == market.total_collateral, PredictError::InvalidAllocation);
+    require!(
+        total <= market.distributable_at_resolution(ctx.accounts.vault.amount),
+        PredictError::InsufficientVault
+    );
+
+    require_payout_reserve(market, ctx.accounts.payout_reserve.as_ref())?;
+
+    market.resolved_outcome = Some(Outcome::Split);
+    market.resolution_price = None;
+    market.resolved_at = Some(clock.unix_timestamp);
+    market.status = MarketStatus::Resolved;
+    market.distributable_collateral = total;
+    market.split_yes_pool = yes_allocation;
+    market.split_yes_bps = (yes_allocation as u128 * 10000 / total as u128) as u16;
+    market.resolution_proof = ResolutionProof {
+        oracle_feed: market.oracle_feed,
+        resolved_at: clock.unix_timestamp,
+        resolver: ctx.accounts.admin.key(),
+        ..ResolutionProof::default()
+    };
+
+    let platform = &mut ctx.accounts.platform_config;
+    platform.active_market_count = platform.active_market_count.saturating_sub(1);
+
+    msg!("Market {} split {} YES / {} NO", market_id, yes_allocation, no_allocation);
+
+    emit!(MarketResolved {
+        market_id,
+        outcome: Outcome::Split,
+        resolution_price: 0,
+        total_collateral: market.total_collateral,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
+use solana_sdk_ids::sysvar::instructions as instructions_sysvar;
+use anchor_spl::token::TokenAccount;
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
-use crate::state::{PlatformConfig, Market, MarketStatus, OracleSource, Outcome};
+use crate::state::{PlatformConfig, Market, MarketStatus, OracleSource, Outcome, PriceSnapshots, ResolutionProof};
 use crate::events::MarketResolved;
 use crate::errors::PredictError;
+use crate::utils::{cache_chainlink_print, cache_pyth_print, cache_switchboard_print, chainlink_decimals, confidence_split_bps, parse_attestation, read_preceding_ed25519, scalar_long_bps, read_program_state_value, require_fresh_at_close, to_payout_amount, twap, within_boundary_band, CHAINLINK_STORE_PROGRAM_ID, SWITCHBOARD_DEFAULT_EXPONENT, SWITCHBOARD_PROGRAM_ID};
 
 #[derive(Accounts)]
 pub struct ResolveMarket<'info> {
@@ -13,6 +16,8 @@ pub struct ResolveMarket<'info> {
     )]
     pub market: Account<'info, Market>,
 
+    /// A resolver for ManualAdmin markets. Oracle markets derive the outcome from
+    /// the feed, so anyone may resolve them once they have ended.
     #[account(mut)]
     pub admin: Signer<'info>,
 
@@ -20,13 +25,155 @@ pub struct ResolveMarket<'info> {
         mut,
         seeds = [b"platform_config"],
         bump = platform_config.bump,
-        constraint = platform_config.admin == admin.key() @ PredictError::Unauthorized
+        constraint = market.oracle_source != OracleSource::ManualAdmin
+            || market.is_resolver(&admin.key(), &platform_config) @ PredictError::Unauthorized
     )]
     pub platform_config: Account<'info, PlatformConfig>,
 
-    /// The Pyth price feed account (optional - only needed for Pyth oracle markets)
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// The Pyth price feed account (optional - only needed for Pyth oracle markets,
+    /// and not when the market's oracle cache was filled in this slot)
     /// CHECK: We validate this is the correct feed in the instruction logic
     pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
+
+    /// Watched account (optional - only needed for ProgramState oracle markets)
+    /// CHECK: Key and owner validated against the market's query in the instruction logic
+    pub state_account: Option<UncheckedAccount<'info>>,
+
+    /// Switchboard pull feed (optional - only needed for Switchboard oracle markets)
+    /// CHECK: Key and owner validated against the market's feed in the instruction logic
+    pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+    /// Chainlink feed (optional - only needed for Chainlink oracle markets)
+    /// CHECK: Key and owner validated against the market's feed in the instruction logic
+    pub chainlink_feed: Option<UncheckedAccount<'info>>,
+
+    /// Instructions sysvar (optional - only needed for SignedAttestation markets)
+    /// CHECK: Address checked; read through instruction introspection
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Payout reserve (optional - only needed for markets with a separate payout mint)
+    #[account(
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    /// Recorded prints (optional - only needed for Pyth markets with a TWAP window)
+    #[account(
+        seeds = [b"price_snapshots", market.key().as_ref()],
+        bump = price_snapshots.bump,
+    )]
+    pub price_snapshots: Option<Box<Account<'info, PriceSnapshots>>>,
+}
+
+/// Cross-mint markets: every winning claim is paid from the reserve, so it must
+/// cover the whole collateral pool at the fixed rate before we can resolve
+pub fn require_payout_reserve(market: &Market, payout_reserve: Option<&Account<TokenAccount>>) -> Result<()> {
+    if market.payout_mint.is_some() {
+        let reserve = payout_reserve.ok_or(PredictError::InsufficientPayoutReserve)?;
+        let required = to_payout_amount(market.total_collateral, market.payout_rate)
+            .ok_or(PredictError::MathOverflow)?;
+        require!(reserve.amount >= required, PredictError::InsufficientPayoutReserve);
+    }
+    Ok(())
+}
+
+/// Resolves a ManualAdmin market to `outcome` outside resolve_market (optimistic
+/// proposals, committee attestations), crediting `resolver` in the proof
+pub fn apply_manual_resolution(
+    market: &mut Market,
+    platform: &mut PlatformConfig,
+    vault_amount: u64,
+    outcome: Outcome,
+    resolver: Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Too few distinct bettors to trust the outcome: refund as INVALID
+    let min_bettors = platform.min_bettors_for_resolution;
+    let outcome = if min_bettors > 0 && market.unique_bettors < min_bettors {
+        Outcome::Invalid
+    } else {
+        outcome
+    };
+
+    market.resolved_outcome = Some(outcome);
+    market.resolution_price = None;
+    market.resolved_at = Some(clock.unix_timestamp);
+    market.status = MarketStatus::Resolved;
+    market.distributable_collateral = market.distributable_at_resolution(vault_amount);
+    market.lp_pool = market.lp_pool_at_resolution(vault_amount);
+    market.set_split(0);
+    market.resolution_proof = ResolutionProof {
+        oracle_feed: market.oracle_feed,
+        resolved_at: clock.unix_timestamp,
+        resolver,
+        ..ResolutionProof::default()
+    };
+
+    platform.active_market_count = platform.active_market_count.saturating_sub(1);
+
+    emit!(MarketResolved {
+        market_id: market.market_id,
+        outcome,
+        resolution_price: 0,
+        total_collateral: market.total_collateral,
+    });
+
+    Ok(())
+}
+
+/// Outcome of a price-fed market (and the YES share when it splits):
+/// Scalar market: YES share interpolated over its range (Split unless at a bound)
+/// Range market: YES inside (oracle_threshold, oracle_upper_threshold], NO outside,
+/// INVALID within the boundary band of either edge
+/// Confidence split enabled and the conf band straddles the threshold: partial credit
+/// Too close to call (within boundary band / confidence): refund as INVALID
+/// If price > oracle_threshold, resolve as YES
+/// If price <= oracle_threshold, resolve as NO
+fn price_outcome(market: &Market, price: i64, conf: u64) -> (Outcome, u16) {
+    if let Some(range) = market.scalar_range {
+        let long_bps = scalar_long_bps(price, range.lower, range.upper);
+        msg!("Scalar: {} bps LONG over [{}, {}]", long_bps, range.lower, range.upper);
+        return match long_bps {
+            0 => (Outcome::No, 0),
+            10000 => (Outcome::Yes, 0),
+            bps => (Outcome::Split, bps),
+        };
+    }
+    if let Some(upper) = market.oracle_upper_threshold {
+        return if within_boundary_band(price, conf, market.oracle_threshold, market.boundary_band)
+            || within_boundary_band(price, conf, upper, market.boundary_band)
+        {
+            (Outcome::Invalid, 0)
+        } else if price > market.oracle_threshold && price <= upper {
+            (Outcome::Yes, 0)
+        } else {
+            (Outcome::No, 0)
+        };
+    }
+    let split = if market.confidence_split {
+        confidence_split_bps(price, conf, market.oracle_threshold)
+    } else {
+        None
+    };
+    if let Some(yes_bps) = split {
+        msg!("Confidence split: {} bps YES", yes_bps);
+        (Outcome::Split, yes_bps)
+    } else if within_boundary_band(price, conf, market.oracle_threshold, market.boundary_band) {
+        (Outcome::Invalid, 0)
+    } else if price > market.oracle_threshold {
+        (Outcome::Yes, 0)
+    } else {
+        (Outcome::No, 0)
+    }
 }
 
 pub fn process_resolve_market(
@@ -38,76 +185,228 @@ pub fn process_resolve_market(
     let clock = Clock::get()?;
 
     // Guards
-    require!(market.status == MarketStatus::Active || market.status == MarketStatus::Locked, PredictError::AlreadyResolved);
+    require!(!ctx.accounts.platform_config.shutdown, PredictError::PlatformShutdown);
+    require!(
+        market.status == MarketStatus::Active
+            || market.status == MarketStatus::Locked
+            || market.status == MarketStatus::Resolving,
+        PredictError::AlreadyResolved
+    );
     
-    // Check timestamp unless ManualAdmin (Early Resolution allowed)
+    // Check end (timestamp, or slot/epoch) unless ManualAdmin (Early Resolution allowed).
+    // This is also what keeps permissionless oracle resolution from firing early.
     if market.oracle_source != OracleSource::ManualAdmin {
-        require!(clock.unix_timestamp >= market.end_timestamp, PredictError::RoundIncomplete);
+        require!(market.end_reached(&clock), PredictError::RoundIncomplete);
+        // Leave arbitrage time to correct a price pushed around a large bet
+        let min_slots = ctx.accounts.platform_config.min_slots_before_resolve;
+        if min_slots > 0 {
+            require!(
+                clock.slot > market.last_bet_slot.saturating_add(min_slots),
+                PredictError::ResolutionTooSoonAfterBet
+            );
+        }
     }
 
+    require_payout_reserve(market, ctx.accounts.payout_reserve.as_ref())?;
+
     // Final outcome to be set
-    let final_outcome: Outcome;
+    let mut final_outcome: Outcome;
     let resolution_price: Option<i64>;
+    // (publish_time, feed_slot) of the value used, for the resolution proof
+    let mut published = (0i64, 0u64);
+    // YES share of the pool when resolving Split
+    let mut split_yes_bps = 0u16;
 
     // Oracle Logic
     match market.oracle_source {
         OracleSource::ManualAdmin => {
             // Admin (signer) provides outcome directly
-            // Verified admin via constraint on platform_config
+            // Verified admin (platform or market admin) via constraint on platform_config
+            require!(!market.committee_resolution, PredictError::CommitteeResolutionRequired);
             require!(outcome == Outcome::Yes || outcome == Outcome::No || outcome == Outcome::Invalid, PredictError::InvalidOutcome);
             final_outcome = outcome;
             resolution_price = None;
         },
         OracleSource::Pyth => {
-            // Require Pyth price feed account
-            let price_feed = ctx.accounts.pyth_price_feed.as_ref()
-                .ok_or(PredictError::OracleMismatch)?;
-            
-            // SC-3 FIX: Validate that the Pyth feed account matches the market's stored oracle_feed
-            require!(
-                price_feed.key() == market.oracle_feed,
-                PredictError::InvalidPythFeed
-            );
-            
-            // Get the latest price from PriceUpdateV2
-            let price_data = &price_feed.price_message;
-            
-            // H-1 FIX: Check oracle staleness (reject prices older than 60 seconds)
-            let price_timestamp = price_data.publish_time;
-            require!(
-                clock.unix_timestamp - price_timestamp <= 60,
-                PredictError::OracleStale
-            );
-            
-            // Price is stored with an exponent (e.g., price * 10^expo)
-            // Normalize to a comparable integer (we'll use the raw price)
-            let current_price = price_data.price;
-            
-            // Compare against threshold
-            // If current_price > oracle_threshold, resolve as YES
-            // If current_price <= oracle_threshold, resolve as NO
-            if current_price > market.oracle_threshold {
-                final_outcome = Outcome::Yes;
+            let (current_price, conf) = if market.twap_window_secs > 0 {
+                // Averaged over the final window from record_price_snapshot cranks;
+                // a single late print can't move it, so no confidence band applies
+                let snapshots = ctx.accounts.price_snapshots.as_ref()
+                    .ok_or(PredictError::InsufficientSnapshots)?;
+                let price = twap(
+                    &snapshots.ordered(),
+                    market.end_timestamp - market.twap_window_secs,
+                    market.end_timestamp,
+                ).ok_or(PredictError::InsufficientSnapshots)?;
+                published = (snapshots.latest().observed_at, 0);
+                (price, 0)
+            } else if market.status == MarketStatus::Resolving {
+                // Price was pinned at end_timestamp by snapshot_resolution_price,
+                // which also left its print in the cache
+                published = (market.oracle_cache.publish_time, market.oracle_cache.posted_slot);
+                (market.resolution_price.ok_or(PredictError::OracleMismatch)?, market.resolution_conf)
             } else {
-                final_outcome = Outcome::No;
-            }
+                // Reuse a print already read in this slot (e.g. by refresh_oracle_cache)
+                let print = if market.oracle_cache.is_valid_at(clock.slot) {
+                    msg!("Oracle cache hit (slot {})", clock.slot);
+                    market.oracle_cache
+                } else {
+                    // Require Pyth price feed account
+                    let price_feed = ctx.accounts.pyth_price_feed.as_ref()
+                        .ok_or(PredictError::OracleMismatch)?;
+
+                    // SC-3 FIX: Validate that the Pyth feed account matches the market's stored oracle_feed
+                    require!(
+                        price_feed.key() == market.oracle_feed,
+                        PredictError::InvalidPythFeed
+                    );
+                    // Feed exponent may have changed since creation: rescale to the market's
+                    let exponent = market.pyth_exponent(price_feed.price_message.exponent);
+                    market.oracle_cache = cache_pyth_print(price_feed, clock.slot, exponent)?;
+                    market.oracle_cache
+                };
+
+                // H-1 FIX: Check oracle staleness (reject prices older than 60 seconds),
+                // relaxed within the platform grace for a print that was fresh at end_timestamp.
+                // Price is stored with an exponent (e.g., price * 10^expo)
+                // The cached print is already rescaled to the market's oracle_exponent
+                require_fresh_at_close(
+                    print.publish_time,
+                    clock.unix_timestamp,
+                    market.end_timestamp,
+                    ctx.accounts.platform_config.resolution_grace_secs,
+                )?;
+                published = (print.publish_time, print.posted_slot);
+                (print.price, print.conf)
+            };
+            
+            (final_outcome, split_yes_bps) = price_outcome(market, current_price, conf);
             
             resolution_price = Some(current_price);
             
             msg!("Pyth price: {}, threshold: {}, outcome: {:?}", 
                 current_price, market.oracle_threshold, final_outcome);
         },
+        OracleSource::ProgramState => {
+            let query = market.program_state_query.ok_or(PredictError::InvalidProgramStateQuery)?;
+            let state_account = ctx.accounts.state_account.as_ref()
+                .ok_or(PredictError::OracleMismatch)?;
+            require!(state_account.key() == market.oracle_feed, PredictError::OracleMismatch);
+            require!(*state_account.owner == query.program, PredictError::ProgramStateOwnerMismatch);
+
+            let value = read_program_state_value(&state_account.try_borrow_data()?, query.offset, query.size)?;
+            final_outcome = if query.comparator.holds(value, market.oracle_threshold) {
+                Outcome::Yes
+            } else {
+                Outcome::No
+            };
+            resolution_price = Some(value);
+            published = (clock.unix_timestamp, clock.slot);
+
+            msg!("Program state value: {}, threshold: {}, outcome: {:?}",
+                value, market.oracle_threshold, final_outcome);
+        },
         OracleSource::Switchboard => {
-            // TODO: Implement Switchboard if needed
-            return err!(PredictError::OracleMismatch);
+            let feed = ctx.accounts.switchboard_feed.as_ref()
+                .ok_or(PredictError::OracleMismatch)?;
+            require!(
+                feed.key() == market.oracle_feed && *feed.owner == SWITCHBOARD_PROGRAM_ID,
+                PredictError::InvalidSwitchboardFeed
+            );
+            let exponent = *market.oracle_exponent.get_or_insert(SWITCHBOARD_DEFAULT_EXPONENT);
+            let print = cache_switchboard_print(&feed.try_borrow_data()?, clock.slot, exponent)?;
+            require_fresh_at_close(
+                print.publish_time,
+                clock.unix_timestamp,
+                market.end_timestamp,
+                ctx.accounts.platform_config.resolution_grace_secs,
+            )?;
+            market.oracle_cache = print;
+            published = (print.publish_time, print.posted_slot);
+
+            (final_outcome, split_yes_bps) = price_outcome(market, print.price, print.conf);
+            resolution_price = Some(print.price);
+
+            msg!("Switchboard value: {}, threshold: {}, outcome: {:?}",
+                print.price, market.oracle_threshold, final_outcome);
+        },
+        OracleSource::Chainlink => {
+            let feed = ctx.accounts.chainlink_feed.as_ref()
+                .ok_or(PredictError::OracleMismatch)?;
+            require!(
+                feed.key() == market.oracle_feed && *feed.owner == CHAINLINK_STORE_PROGRAM_ID,
+                PredictError::InvalidChainlinkFeed
+            );
+            let data = feed.try_borrow_data()?;
+            // Without a declared exponent the threshold is in the feed's decimals
+            let decimals = chainlink_decimals(&data)?;
+            let exponent = *market.oracle_exponent.get_or_insert(-(decimals as i32));
+            let print = cache_chainlink_print(&data, clock.slot, exponent)?;
+            require_fresh_at_close(
+                print.publish_time,
+                clock.unix_timestamp,
+                market.end_timestamp,
+                ctx.accounts.platform_config.resolution_grace_secs,
+            )?;
+            market.oracle_cache = print;
+            published = (print.publish_time, print.posted_slot);
+
+            (final_outcome, split_yes_bps) = price_outcome(market, print.price, print.conf);
+            resolution_price = Some(print.price);
+
+            msg!("Chainlink answer: {}, threshold: {}, outcome: {:?}",
+                print.price, market.oracle_threshold, final_outcome);
+        },
+        OracleSource::SignedAttestation => {
+            // The attester signs off-chain; an Ed25519 program instruction right
+            // before this one carries the signature, so any caller can submit it
+            let sysvar = ctx.accounts.instructions_sysvar.as_ref()
+                .ok_or(PredictError::InvalidAttestation)?;
+            let (attester, message) = read_preceding_ed25519(sysvar)?;
+            require!(attester == market.oracle_feed, PredictError::InvalidAttestation);
+            let (attested_id, attested_outcome, timestamp) = parse_attestation(&message)?;
+            require!(attested_id == market.market_id, PredictError::InvalidAttestation);
+            // Signed after this round ended (no replay of an earlier round's), and not post-dated
+            require!(
+                timestamp >= market.end_timestamp && timestamp <= clock.unix_timestamp,
+                PredictError::InvalidAttestation
+            );
+            final_outcome = attested_outcome;
+            resolution_price = None;
+            published = (timestamp, clock.slot);
+
+            msg!("Attested outcome {:?} signed at {}", final_outcome, timestamp);
         },
     }
 
+    // Too few distinct bettors to trust the outcome (e.g. one wallet wash-trading
+    // the market): refund as INVALID for insufficient participation
+    let min_bettors = ctx.accounts.platform_config.min_bettors_for_resolution;
+    if min_bettors > 0 && market.unique_bettors < min_bettors {
+        msg!("Insufficient participation: {} of {} bettors", market.unique_bettors, min_bettors);
+        final_outcome = Outcome::Invalid;
+        split_yes_bps = 0;
+    }
+
     // Update State
     market.resolved_outcome = Some(final_outcome.clone());
     market.resolution_price = resolution_price;
     market.resolved_at = Some(clock.unix_timestamp);
     market.status = MarketStatus::Resolved;
+    market.distributable_collateral = market.distributable_at_resolution(ctx.accounts.vault.amount);
+    market.lp_pool = market.lp_pool_at_resolution(ctx.accounts.vault.amount);
+    market.set_split(split_yes_bps);
+    market.resolution_proof = ResolutionProof {
+        oracle_feed: market.oracle_feed,
+        resolution_price: resolution_price.unwrap_or(0),
+        publish_time: published.0,
+        feed_slot: published.1,
+        resolved_at: clock.unix_timestamp,
+        resolver: ctx.accounts.admin.key(),
+    };
+
+    let platform = &mut ctx.accounts.platform_config;
+    platform.active_market_count = platform.active_market_count.saturating_sub(1);
     
     emit!(MarketResolved {
         market_id,
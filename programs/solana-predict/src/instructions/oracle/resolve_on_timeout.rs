@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{PlatformConfig, Market, MarketStatus, Outcome, ResolutionProof};
+use crate::events::MarketResolved;
+use crate::errors::PredictError;
+use super::resolve_market::require_payout_reserve;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ResolveOnTimeout<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Payout reserve (optional - only needed for markets with a separate payout mint)
+    #[account(
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    /// Anyone can apply the default once the timeout has lapsed
+    pub caller: Signer<'info>,
+}
+
+pub fn process_resolve_on_timeout(ctx: Context<ResolveOnTimeout>, market_id: u64) -> Result<()> {
+    let default_outcome = ctx.accounts.market.default_outcome_on_timeout.ok_or(PredictError::NoDefaultOutcome)?;
+    resolve_after_timeout(ctx, market_id, default_outcome)
+}
+
+/// For markets without a default outcome: once nobody has resolved them within
+/// the timeout (admin gone, feed dead), anyone resolves them Invalid so bettors
+/// can get their collateral back.
+pub fn process_force_invalidate(ctx: Context<ResolveOnTimeout>, market_id: u64) -> Result<()> {
+    // A declared default takes precedence; that's resolve_on_timeout's job
+    require!(ctx.accounts.market.default_outcome_on_timeout.is_none(), PredictError::DefaultOutcomeSet);
+    resolve_after_timeout(ctx, market_id, Outcome::Invalid)
+}
+
+fn resolve_after_timeout(ctx: Context<ResolveOnTimeout>, market_id: u64, default_outcome: Outcome) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(!ctx.accounts.platform_config.shutdown, PredictError::PlatformShutdown);
+    require!(
+        market.status == MarketStatus::Active
+            || market.status == MarketStatus::Locked
+            || market.status == MarketStatus::Resolving,
+        PredictError::AlreadyResolved
+    );
+
+    // Oracle / admin get the whole timeout window to resolve first
+    let deadline = market.end_timestamp
+        .checked_add(ctx.accounts.platform_config.resolution_timeout_secs)
+        .ok_or(PredictError::MathOverflow)?;
+    require!(clock.unix_timestamp >= deadline, PredictError::RoundIncomplete);
+
+    require_payout_reserve(market, ctx.accounts.payout_reserve.as_ref())?;
+
+    market.resolved_outcome = Some(default_outcome);
+    market.resolution_price = None;
+    market.resolved_at = Some(clock.unix_timestamp);
+    market.status = MarketStatus::Resolved;
+    market.distributable_collateral = market.distributable_at_resolution(ctx.accounts.vault.amount);
+    market.lp_pool = market.lp_pool_at_resolution(ctx.accounts.vault.amount);
+    market.resolution_proof = ResolutionProof {
+        oracle_feed: market.oracle_feed,
+        resolved_at: clock.unix_timestamp,
+        resolver: ctx.accounts.caller.key(),
+        ..ResolutionProof::default()
+    };
+
+    let platform = &mut ctx.accounts.platform_config;
+    platform.active_market_count = platform.active_market_count.saturating_sub(1);
+
+    msg!("Market {} resolved to {:?} after timeout", market_id, default_outcome);
+
+    emit!(MarketResolved {
+        market_id,
+        outcome: default_outcome,
+        resolution_price: 0,
+        total_collateral: market.total_collateral,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{PlatformConfig, Market, MarketStatus, OracleSource, Outcome, ResolutionProof, RoundState, RoundStatus};
+use crate::events::{MarketResolved, RoundResolved};
+use crate::errors::PredictError;
+use crate::utils::{cache_pyth_print, require_fresh_at_close};
+use super::resolve_market::require_payout_reserve;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ResolveRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump = round_state.bump,
+    )]
+    pub round_state: Account<'info, RoundState>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = pyth_price_feed.key() == market.oracle_feed @ PredictError::InvalidPythFeed
+    )]
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+
+    /// Payout reserve (optional - only needed for markets with a separate payout mint)
+    #[account(
+        seeds = [b"payout_reserve", market.key().as_ref()],
+        bump,
+    )]
+    pub payout_reserve: Option<Account<'info, TokenAccount>>,
+
+    /// Anyone may crank once the round has ended
+    pub cranker: Signer<'info>,
+}
+
+/// Up/down outcome of a round: YES if the close is above the lock price, NO if
+/// below, INVALID (refund) when flat
+pub fn round_outcome(lock_price: i64, close_price: i64) -> Outcome {
+    match close_price.cmp(&lock_price) {
+        std::cmp::Ordering::Greater => Outcome::Yes,
+        std::cmp::Ordering::Less => Outcome::No,
+        std::cmp::Ordering::Equal => Outcome::Invalid,
+    }
+}
+
+/// Permissionless: reads the close price at the current round's `end_ts` and
+/// resolves the round, and the market with it, against its lock price.
+pub fn process_resolve_round(ctx: Context<ResolveRound>, market_id: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    settle_round(
+        &mut accounts.market,
+        &mut accounts.round_state,
+        &mut accounts.platform_config,
+        accounts.vault.amount,
+        &accounts.pyth_price_feed,
+        accounts.payout_reserve.as_ref(),
+        accounts.cranker.key(),
+        market_id,
+    )
+}
+
+/// Resolves `round`, the market's current round, and the market with it from
+/// the close print in `feed`. Shared by resolve_round and crank_round.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_round(
+    market: &mut Market,
+    round: &mut RoundState,
+    platform: &mut PlatformConfig,
+    vault_amount: u64,
+    feed: &PriceUpdateV2,
+    payout_reserve: Option<&Account<TokenAccount>>,
+    resolver: Pubkey,
+    market_id: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(!platform.shutdown, PredictError::PlatformShutdown);
+    require!(market.oracle_source == OracleSource::Pyth, PredictError::OracleMismatch);
+    require!(round.status == RoundStatus::Locked, PredictError::RoundNotLocked);
+    require!(
+        market.status == MarketStatus::Active || market.status == MarketStatus::Locked,
+        PredictError::AlreadyResolved
+    );
+    require!(clock.unix_timestamp >= round.end_ts, PredictError::RoundIncomplete);
+    let lock_price = round.lock_price.ok_or(PredictError::RoundNotLocked)?;
+
+    require_payout_reserve(market, payout_reserve)?;
+
+    // Same freshness rule as resolve_market, in the exponent the lock price was read in
+    let exponent = market.pyth_exponent(feed.price_message.exponent);
+    market.oracle_cache = cache_pyth_print(feed, clock.slot, exponent)?;
+    let print = market.oracle_cache;
+    require_fresh_at_close(
+        print.publish_time,
+        clock.unix_timestamp,
+        round.end_ts,
+        platform.resolution_grace_secs,
+    )?;
+    let close_price = print.price;
+
+    let mut outcome = round_outcome(lock_price, close_price);
+    // Round bets are counted on the round, CPMM bets on the market; a wallet may
+    // be in both, so the larger count is the one known to be distinct
+    let bettors = market.unique_bettors.max(round.unique_bettors);
+    let min_bettors = platform.min_bettors_for_resolution;
+    if min_bettors > 0 && bettors < min_bettors {
+        msg!("Insufficient participation: {} of {} bettors", bettors, min_bettors);
+        outcome = Outcome::Invalid;
+    }
+
+    round.close_price = Some(close_price);
+    round.outcome = Some(outcome);
+    round.status = RoundStatus::Resolved;
+
+    market.resolved_outcome = Some(outcome);
+    market.resolution_price = Some(close_price);
+    market.resolution_conf = print.conf;
+    market.resolved_at = Some(clock.unix_timestamp);
+    market.status = MarketStatus::Resolved;
+    market.distributable_collateral = market.distributable_at_resolution(vault_amount);
+    market.lp_pool = market.lp_pool_at_resolution(vault_amount);
+    market.set_split(0);
+    market.resolution_proof = ResolutionProof {
+        oracle_feed: market.oracle_feed,
+        resolution_price: close_price,
+        publish_time: print.publish_time,
+        feed_slot: print.posted_slot,
+        resolved_at: clock.unix_timestamp,
+        resolver,
+    };
+
+    platform.active_market_count = platform.active_market_count.saturating_sub(1);
+
+    emit!(RoundResolved {
+        market_id,
+        round_id: round.round_id,
+        lock_price,
+        close_price,
+        outcome,
+    });
+    emit!(MarketResolved {
+        market_id,
+        outcome,
+        resolution_price: close_price,
+        total_collateral: market.total_collateral,
+    });
+
+    Ok(())
+}
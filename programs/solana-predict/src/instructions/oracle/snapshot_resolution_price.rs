@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::state::{Market, MarketStatus, OracleCache, OracleSource};
+use crate::events::ResolutionPriceSnapshotted;
+use crate::errors::PredictError;
+use crate::utils::{cache_pyth_print, read_pyth_price_with_conf, MAX_ORACLE_STALENESS_SECS, SNAPSHOT_WINDOW_SLOTS};
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SnapshotResolutionPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        constraint = pyth_price_feed.key() == market.oracle_feed @ PredictError::InvalidPythFeed
+    )]
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+/// Permissionless: pins the resolution price to the feed value right after
+/// `end_timestamp`, so a late `resolve_market` can't pick up a later print.
+/// Only a print from the first MAX_ORACLE_STALENESS_SECS after the end is
+/// accepted (SNAPSHOT_WINDOW_SLOTS after the end slot/epoch for chain-native
+/// markets), so the caller can't choose one from a later swing either.
+pub fn process_snapshot_resolution_price(
+    ctx: Context<SnapshotResolutionPrice>,
+    market_id: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // Guards
+    require!(market.oracle_source == OracleSource::Pyth, PredictError::OracleMismatch);
+    require!(market.status == MarketStatus::Active || market.status == MarketStatus::Locked, PredictError::AlreadyResolved);
+    require!(market.end_reached(&clock), PredictError::SnapshotWindowClosed);
+    let feed = &ctx.accounts.pyth_price_feed;
+    if market.end_slot.is_none() && market.end_epoch.is_none() {
+        require!(
+            clock.unix_timestamp <= market.end_timestamp + MAX_ORACLE_STALENESS_SECS
+                && feed.price_message.publish_time >= market.end_timestamp,
+            PredictError::SnapshotWindowClosed
+        );
+    } else {
+        // No wall-clock end: the same window in slots from the slot it ended at
+        let epoch_start = match market.end_epoch {
+            Some(epoch) => EpochSchedule::get()?.get_first_slot_in_epoch(epoch),
+            None => 0,
+        };
+        let end_slot = market.end_slot.unwrap_or(0).max(epoch_start);
+        require!(
+            clock.slot <= end_slot.saturating_add(SNAPSHOT_WINDOW_SLOTS) && feed.posted_slot >= end_slot,
+            PredictError::SnapshotWindowClosed
+        );
+    }
+
+    // Staleness check on the raw print; price and conf are used in the market's exponent
+    read_pyth_price_with_conf(&ctx.accounts.pyth_price_feed, clock.unix_timestamp)?;
+    let exponent = market.pyth_exponent(ctx.accounts.pyth_price_feed.price_message.exponent);
+    market.oracle_cache = cache_pyth_print(&ctx.accounts.pyth_price_feed, clock.slot, exponent)?;
+    let OracleCache { price, conf, publish_time, .. } = market.oracle_cache;
+
+    market.resolution_price = Some(price);
+    market.resolution_conf = conf;
+    market.status = MarketStatus::Resolving;
+
+    emit!(ResolutionPriceSnapshotted {
+        market_id,
+        price,
+        publish_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::{Market, MarketStatus, UserPosition, Outcome};
+use crate::errors::PredictError;
+use crate::utils::pro_rata_payout;
+
+/// Read-only context for a user's claim status on a resolved market
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ClaimStatusView<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"position", market.key().as_ref(), user_position.user.as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    /// User's YES share account (omit if none)
+    #[account(
+        constraint = user_yes_account.mint == market.yes_mint @ PredictError::InvalidMint,
+        constraint = user_yes_account.owner == user_position.user @ PredictError::Unauthorized,
+    )]
+    pub user_yes_account: Option<Account<'info, TokenAccount>>,
+
+    /// User's NO share account (omit if none)
+    #[account(
+        constraint = user_no_account.mint == market.no_mint @ PredictError::InvalidMint,
+        constraint = user_no_account.owner == user_position.user @ PredictError::Unauthorized,
+    )]
+    pub user_no_account: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClaimStatus {
+    /// Live share balance claim_payout would burn (0 once claimed)
+    pub claimable_shares: u64,
+    /// Collateral already paid out to this position
+    pub already_claimed: u64,
+    /// Collateral claim_payout would pay right now
+    pub remaining_entitlement: u64,
+}
+
+/// Mirrors claim_payout: winning shares are valued pro-rata against the live
+/// mint supply (Invalid: principal against total principal) and capped like the
+/// payout itself. Positions are claimed once.
+pub fn process_get_claim_status(ctx: Context<ClaimStatusView>, _market_id: u64) -> Result<ClaimStatus> {
+    claim_status(ctx.accounts)
+}
+
+pub fn claim_status(accounts: &ClaimStatusView) -> Result<ClaimStatus> {
+    let market = &accounts.market;
+    require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
+    let outcome = market.resolved_outcome.ok_or(PredictError::MarketNotResolved)?;
+
+    let already_claimed = accounts.user_position.total_claimed;
+    if already_claimed > 0 && outcome != Outcome::Split {
+        return Ok(ClaimStatus { claimable_shares: 0, already_claimed, remaining_entitlement: 0 });
+    }
+
+    let yes_balance = accounts.user_yes_account.as_ref().map_or(0, |a| a.amount);
+    let no_balance = accounts.user_no_account.as_ref().map_or(0, |a| a.amount);
+    let position = &accounts.user_position;
+    let pool = market.distributable_collateral;
+    let (yes_supply, no_supply) = market.claim_supplies(accounts.yes_mint.supply, accounts.no_mint.supply);
+    let (claimable_shares, entitlement) = match outcome {
+        Outcome::Yes => (yes_balance, pro_rata_payout(yes_balance, pool, yes_supply)),
+        Outcome::No => (no_balance, pro_rata_payout(no_balance, pool, no_supply)),
+        // Refunded by principal; claim_payout burns one share account per call
+        Outcome::Invalid => {
+            let deposited = if position.round == market.current_round { position.total_deposited } else { 0 };
+            (yes_balance.max(no_balance), pro_rata_payout(deposited, pool, market.total_deposited))
+        }
+        // Each side from its own allocation; one claim per share account
+        Outcome::Split => {
+            let (yes_pool, no_pool) = market.split_pools();
+            (
+                yes_balance + no_balance,
+                pro_rata_payout(yes_balance, yes_pool, accounts.yes_mint.supply)
+                    + pro_rata_payout(no_balance, no_pool, accounts.no_mint.supply),
+            )
+        }
+    };
+
+    let cap = if market.payout_mint.is_some() {
+        market.total_collateral
+    } else {
+        accounts.vault.amount
+    };
+    let remaining_entitlement = entitlement.min(cap);
+
+    Ok(ClaimStatus { claimable_shares, already_claimed, remaining_entitlement })
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::Market;
+use crate::utils::pro_rata_payout;
+
+/// Read-only context for views that need the outstanding share supplies
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct MarketSupplyView<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub no_mint: Account<'info, Mint>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OutcomeExposure {
+    /// Outstanding YES shares (mint supply)
+    pub yes_supply: u64,
+    /// Outstanding NO shares (mint supply)
+    pub no_supply: u64,
+    /// Total paid to YES holders if YES wins
+    pub yes_payout: u64,
+    /// Total paid to NO holders if NO wins
+    pub no_payout: u64,
+    /// Payout per whole YES share (1e9 base units) if YES wins
+    pub yes_payout_per_share: u64,
+    /// Payout per whole NO share (1e9 base units) if NO wins
+    pub no_payout_per_share: u64,
+}
+
+/// One whole share in base units (share mints use 9 decimals)
+pub const SHARE_UNIT: u64 = 1_000_000_000;
+
+/// Implied payouts at the current pool, using the same pro-rata math as claim_payout
+pub fn process_get_outcome_exposure(ctx: Context<MarketSupplyView>, _market_id: u64) -> Result<OutcomeExposure> {
+    let pool = ctx.accounts.market.total_collateral;
+    let yes_supply = ctx.accounts.yes_mint.supply;
+    let no_supply = ctx.accounts.no_mint.supply;
+
+    Ok(OutcomeExposure {
+        yes_supply,
+        no_supply,
+        yes_payout: pro_rata_payout(yes_supply, pool, yes_supply),
+        no_payout: pro_rata_payout(no_supply, pool, no_supply),
+        yes_payout_per_share: pro_rata_payout(SHARE_UNIT, pool, yes_supply),
+        no_payout_per_share: pro_rata_payout(SHARE_UNIT, pool, no_supply),
+    })
+}
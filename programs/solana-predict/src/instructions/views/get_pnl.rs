@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::{MarketStatus, Outcome};
+use crate::instructions::betting::cancel_bet::quote_cancel;
+use super::get_claim_status::{claim_status, ClaimStatusView};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Pnl {
+    /// Collateral received (claims and cancel refunds) less the cost of the
+    /// shares given up for it
+    pub realized_pnl: i64,
+    /// Current value of the shares still held less their cost basis
+    pub unrealized_pnl: i64,
+}
+
+/// A position's P&L this round. Held shares are marked at what cancel_bet
+/// would refund for them before resolution, and at what claim_payout would pay
+/// once resolved. Costs include the entry fee.
+pub fn process_get_pnl(ctx: Context<ClaimStatusView>, _market_id: u64) -> Result<Pnl> {
+    let market = &ctx.accounts.market;
+    let position = &ctx.accounts.user_position;
+    if position.round != market.current_round {
+        return Ok(Pnl { realized_pnl: 0, unrealized_pnl: 0 });
+    }
+
+    let held_cost = position.yes_cost_basis as i128 + position.no_cost_basis as i128;
+    let disposed_cost = position.total_cost as i128 - held_cost;
+    let proceeds = position.total_claimed as i128 + position.total_refunded as i128;
+
+    let mark = if market.status == MarketStatus::Resolved {
+        claim_status(ctx.accounts)?.remaining_entitlement
+    } else {
+        let now = Clock::get()?.unix_timestamp;
+        let sell = |outcome: Outcome, shares: u64| {
+            if shares == 0 { 0 } else { quote_cancel(market, outcome, shares, now).map_or(0, |q| q.refund) }
+        };
+        sell(Outcome::Yes, position.yes_shares).saturating_add(sell(Outcome::No, position.no_shares))
+    };
+
+    Ok(Pnl {
+        realized_pnl: clamp_i64(proceeds - disposed_cost),
+        unrealized_pnl: clamp_i64(mark as i128 - held_cost),
+    })
+}
+
+fn clamp_i64(value: i128) -> i64 {
+    value.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, Market};
+use crate::utils::depth_to_move_price;
+
+/// Read-only context for market views (simulate and read return data)
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct MarketView<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolDepth {
+    /// Constant-product invariant k = yes_reserve * no_reserve
+    pub k: u128,
+    /// Net collateral needed to move the YES price up by 1pp (0 if not reachable)
+    pub yes_depth: u64,
+    /// Net collateral needed to move the NO price up by 1pp (0 if not reachable)
+    pub no_depth: u64,
+}
+
+/// Price move used for the depth metric (1 percentage point)
+pub const DEPTH_MOVE_BPS: u64 = 100;
+
+pub fn process_get_pool_depth(ctx: Context<MarketView>, _market_id: u64) -> Result<PoolDepth> {
+    // Once locked, report the closing pool; until then the reserves bets are
+    // actually priced against, virtual liquidity included
+    let market = &ctx.accounts.market;
+    let (yes, no) = if market.locked_yes_reserve == 0 {
+        market.virtual_reserves()
+    } else {
+        market.closing_reserves()
+    };
+
+    Ok(PoolDepth {
+        k: yes as u128 * no as u128,
+        yes_depth: depth_to_move_price(yes, no, DEPTH_MOVE_BPS).unwrap_or(0),
+        no_depth: depth_to_move_price(no, yes, DEPTH_MOVE_BPS).unwrap_or(0),
+    })
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+use crate::instructions::views::MarketView;
+use crate::state::PriceCheckpoint;
+
+/// Reserve checkpoints recorded by bets, oldest first: at most
+/// PRICE_HISTORY_LEN entries, at least PRICE_CHECKPOINT_INTERVAL_SLOTS apart.
+/// Implied YES probability at a checkpoint is no_reserve / (yes + no).
+pub fn process_get_price_history(ctx: Context<MarketView>, _market_id: u64) -> Result<Vec<PriceCheckpoint>> {
+    Ok(ctx.accounts.market.ordered_price_history())
+}
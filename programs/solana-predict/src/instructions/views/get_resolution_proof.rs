@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+use crate::instructions::views::MarketView;
+use crate::state::{MarketStatus, ResolutionProof};
+use crate::errors::PredictError;
+
+/// How a resolved market was resolved (feed, value, publish time/slot, resolver)
+pub fn process_get_resolution_proof(ctx: Context<MarketView>, _market_id: u64) -> Result<ResolutionProof> {
+    let market = &ctx.accounts.market;
+    require!(market.status == MarketStatus::Resolved, PredictError::MarketNotResolved);
+    Ok(market.resolution_proof)
+}
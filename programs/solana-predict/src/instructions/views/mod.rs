@@ -0,0 +1,23 @@
+pub mod get_pool_depth;
+pub mod get_claim_status;
+pub mod get_outcome_exposure;
+pub mod get_resolution_proof;
+pub mod quote_bet_curve;
+pub mod suggest_bet_size;
+pub mod quote_fee;
+pub mod get_price_history;
+pub mod verify_fee_accounting;
+pub mod validate_market_params;
+pub mod get_pnl;
+
+pub use get_pool_depth::*;
+pub use get_claim_status::*;
+pub use get_outcome_exposure::*;
+pub use get_resolution_proof::*;
+pub use quote_bet_curve::*;
+pub use suggest_bet_size::*;
+pub use quote_fee::*;
+pub use get_price_history::*;
+pub use verify_fee_accounting::*;
+pub use validate_market_params::*;
+pub use get_pnl::*;
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::instructions::views::MarketView;
+use crate::state::{Outcome, PricingMode};
+use crate::errors::PredictError;
+use crate::utils::{bet_fee_breakdown, calculate_amm_shares};
+
+/// Upper bound on sizes per call to stay well within compute
+pub const MAX_QUOTE_SIZES: usize = 32;
+
+/// Shares-out for each gross bet size (fee deducted as in place_bet) against
+/// the current reserves. Sizes whose quote overflows return 0. Parimutuel
+/// markets mint one share per unit staked, so their quote is the net amount.
+pub fn process_quote_bet_curve(
+    ctx: Context<MarketView>,
+    _market_id: u64,
+    outcome: Outcome,
+    sizes: Vec<u64>,
+) -> Result<Vec<u64>> {
+    let market = &ctx.accounts.market;
+    let min_fee = ctx.accounts.platform_config.min_fee_absolute;
+
+    require!(outcome == Outcome::Yes || outcome == Outcome::No, PredictError::InvalidOutcome);
+    require!(sizes.len() <= MAX_QUOTE_SIZES, PredictError::InvalidBatch);
+
+    let (yes_reserve, no_reserve) = market.virtual_reserves();
+    let fee_bps = market.fee_bps_at(Clock::get()?.unix_timestamp);
+    let quotes = sizes
+        .iter()
+        .map(|&amount| {
+            let net = bet_fee_breakdown(amount, fee_bps, market.creator_fee_bps, market.active_lp_fee_bps(), min_fee, None).map_or(0, |f| f.net_amount);
+            if net == 0 || market.pricing_mode == PricingMode::Parimutuel {
+                return net;
+            }
+            calculate_amm_shares(
+                net,
+                yes_reserve,
+                no_reserve,
+                outcome == Outcome::Yes,
+            )
+            .unwrap_or(0)
+        })
+        .collect();
+
+    Ok(quotes)
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, Outcome, PlatformConfig, UserTier};
+use crate::errors::PredictError;
+use crate::instructions::betting::place_bet::discounted_fee_bps;
+use crate::utils::{bet_fee_breakdown, FeeBreakdown};
+
+/// Market view plus the bettor's optional volume tier
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct QuoteFee<'info> {
+    #[account(
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// The bettor's tier, if the bet will pass it
+    pub user_tier: Option<Account<'info, UserTier>>,
+}
+
+/// Fee a `place_bet` of `amount` on `outcome` would be charged, split by
+/// recipient. Same computation as the bet itself; pass the bet's `referrer`
+/// and `user_tier`, if any, to see the referral share and tier discount.
+pub fn process_quote_fee(
+    ctx: Context<QuoteFee>,
+    _market_id: u64,
+    outcome: Outcome,
+    amount: u64,
+    referrer: Option<Pubkey>,
+) -> Result<FeeBreakdown> {
+    let market = &ctx.accounts.market;
+    require!(outcome == Outcome::Yes || outcome == Outcome::No, PredictError::InvalidOutcome);
+    let platform = &ctx.accounts.platform_config;
+    let discount_bps = ctx.accounts.user_tier
+        .as_ref()
+        .map_or(0, |tier| platform.tier_discount_bps(tier.lifetime_volume));
+    let fee_bps = discounted_fee_bps(market.fee_bps_at(Clock::get()?.unix_timestamp), discount_bps);
+    let referral_fee_bps = referrer.map(|_| platform.referral_fee_bps);
+    bet_fee_breakdown(
+        amount,
+        fee_bps,
+        market.creator_fee_bps,
+        market.active_lp_fee_bps(),
+        platform.min_fee_absolute,
+        referral_fee_bps,
+    )
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::instructions::views::MarketView;
+use crate::state::{Outcome, PricingMode};
+use crate::errors::PredictError;
+use crate::utils::fractional_kelly_size;
+
+/// Fractional-Kelly stake (gross, fee included) for a bettor with `edge_bps`
+/// over the outcome's current implied price and a `bankroll` of collateral.
+/// See `fractional_kelly_size` for the fraction and the slippage cap.
+/// Parimutuel markets have no price until resolution, so there is no edge to size.
+pub fn process_suggest_bet_size(
+    ctx: Context<MarketView>,
+    _market_id: u64,
+    outcome: Outcome,
+    edge_bps: u64,
+    bankroll: u64,
+) -> Result<u64> {
+    let market = &ctx.accounts.market;
+
+    require!(outcome == Outcome::Yes || outcome == Outcome::No, PredictError::InvalidOutcome);
+    require!(market.pricing_mode == PricingMode::Cpmm, PredictError::PricingModeUnsupported);
+
+    let (yes_reserve, no_reserve) = market.virtual_reserves();
+    let (pool_out, pool_in) = if outcome == Outcome::Yes {
+        (yes_reserve, no_reserve)
+    } else {
+        (no_reserve, yes_reserve)
+    };
+
+    let fee_bps = market.fee_bps_at(Clock::get()?.unix_timestamp);
+    fractional_kelly_size(pool_out, pool_in, edge_bps, bankroll, fee_bps)
+        .ok_or_else(|| error!(PredictError::MathOverflow))
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::instructions::admin::create_market::{market_param_problems, CreateMarketParams, MarketParamsEnv, ValidationError};
+use crate::state::{PlatformConfig, CreatorState, CategoryPolicy, FeedRegistry};
+
+#[derive(Accounts)]
+pub struct ValidateMarketParams<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Would-be creator; only its key is read
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: Creator's throttle state. Uninitialized until their first market.
+    #[account(
+        seeds = [b"creator", creator.key().as_ref()],
+        bump,
+    )]
+    pub creator_state: UncheckedAccount<'info>,
+
+    /// CHECK: Category/oracle policy singleton. May be uninitialized.
+    #[account(
+        seeds = [b"category_policy"],
+        bump,
+    )]
+    pub category_policy: UncheckedAccount<'info>,
+
+    /// CHECK: Feed allowlist singleton. May be uninitialized.
+    #[account(
+        seeds = [b"feed_registry"],
+        bump,
+    )]
+    pub feed_registry: UncheckedAccount<'info>,
+
+    /// Live Pyth feed (optional, as for create_market)
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
+}
+
+/// Runs every check create_market would run on `params` and returns all the
+/// problems found, so a UI can show them together. Empty means create_market
+/// would accept the params as of now.
+pub fn process_validate_market_params(
+    ctx: Context<ValidateMarketParams>,
+    params: CreateMarketParams,
+) -> Result<Vec<ValidationError>> {
+    let creator_state = if ctx.accounts.creator_state.data_is_empty() {
+        None
+    } else {
+        Some(CreatorState::try_deserialize(&mut &ctx.accounts.creator_state.data.borrow()[..])?)
+    };
+    let policy = if ctx.accounts.category_policy.data_is_empty() {
+        None
+    } else {
+        Some(CategoryPolicy::try_deserialize(&mut &ctx.accounts.category_policy.data.borrow()[..])?)
+    };
+    let registry = if ctx.accounts.feed_registry.data_is_empty() {
+        None
+    } else {
+        Some(Box::new(FeedRegistry::try_deserialize(&mut &ctx.accounts.feed_registry.data.borrow()[..])?))
+    };
+
+    Ok(market_param_problems(&params, &MarketParamsEnv {
+        platform: &ctx.accounts.platform_config,
+        creator: ctx.accounts.creator.key(),
+        creator_state: creator_state.as_ref(),
+        category_policy: policy.as_ref(),
+        feed_registry: registry.as_deref(),
+        pyth_price_feed: ctx.accounts.pyth_price_feed.as_ref(),
+        now: Clock::get()?.unix_timestamp,
+    }))
+}
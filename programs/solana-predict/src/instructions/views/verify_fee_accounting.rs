@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+use crate::instructions::views::MarketView;
+use crate::state::FeeLedger;
+use crate::errors::PredictError;
+
+/// Checks that the fees credited to each destination add up to the fees
+/// charged on the market, and returns the ledger.
+pub fn process_verify_fee_accounting(ctx: Context<MarketView>, _market_id: u64) -> Result<FeeLedger> {
+    let ledger = ctx.accounts.market.fee_ledger;
+    require!(
+        ledger.credited() == Some(ledger.fees_accrued),
+        PredictError::FeeAccountingMismatch
+    );
+    Ok(ledger)
+}
@@ -7,7 +7,10 @@ pub mod events;
 pub mod utils;
 
 use instructions::*;
-use state::market::Outcome;
+use state::market::{FeeLedger, MarketCategory, OracleSource, Outcome, PriceCheckpoint, ResolutionProof};
+use state::approvals::AdminAction;
+use state::platform::{FeeTier, Role};
+use utils::FeeBreakdown;
 
 declare_id!("F4JxF7aePgrKKwmVM9tXHUadeTKNLXwFMZFQoiBowLcr");
 
@@ -23,6 +26,10 @@ pub mod solana_predict {
         instructions::admin::create_market::process_create_market(ctx, market_id, params)
     }
 
+    pub fn init_position(ctx: Context<InitPosition>, market_id: u64) -> Result<()> {
+        instructions::betting::init_position::process_init_position(ctx, market_id)
+    }
+
     pub fn place_bet(ctx: Context<PlaceBet>, market_id: u64, outcome: Outcome, amount: u64, min_shares: u64) -> Result<()> {
         instructions::betting::place_bet::process_place_bet(ctx, market_id, outcome, amount, min_shares)
     }
@@ -31,14 +38,70 @@ pub mod solana_predict {
         instructions::betting::cancel_bet::process_cancel_bet(ctx, market_id, shares_to_burn)
     }
 
+    pub fn cancel_bets_batch<'info>(ctx: Context<'_, '_, 'info, 'info, CancelBetsBatch<'info>>, min_refunds: Vec<u64>) -> Result<()> {
+        instructions::betting::cancel_bets_batch::process_cancel_bets_batch(ctx, min_refunds)
+    }
+
+    pub fn consolidate_dust<'info>(ctx: Context<'_, '_, 'info, 'info, ConsolidateDust<'info>>) -> Result<()> {
+        instructions::betting::consolidate_dust::process_consolidate_dust(ctx)
+    }
+
     pub fn claim_payout(ctx: Context<ClaimPayout>, market_id: u64) -> Result<()> {
         instructions::betting::claim_payout::process_claim_payout(ctx, market_id)
     }
 
+    pub fn claim_and_rollover(ctx: Context<ClaimAndRollover>, market_id: u64, next_market_id: u64, outcome: Outcome, min_shares: u64) -> Result<()> {
+        instructions::betting::claim_and_rollover::process_claim_and_rollover(ctx, market_id, next_market_id, outcome, min_shares)
+    }
+
     pub fn resolve_market(ctx: Context<ResolveMarket>, market_id: u64, outcome: Outcome) -> Result<()> {
         instructions::oracle::resolve_market::process_resolve_market(ctx, market_id, outcome)
     }
 
+    pub fn resolve_on_timeout(ctx: Context<ResolveOnTimeout>, market_id: u64) -> Result<()> {
+        instructions::oracle::resolve_on_timeout::process_resolve_on_timeout(ctx, market_id)
+    }
+
+    pub fn force_invalidate(ctx: Context<ResolveOnTimeout>, market_id: u64) -> Result<()> {
+        instructions::oracle::resolve_on_timeout::process_force_invalidate(ctx, market_id)
+    }
+
+    pub fn propose_resolution(ctx: Context<ProposeResolution>, market_id: u64, outcome: Outcome) -> Result<()> {
+        instructions::oracle::optimistic_resolution::process_propose_resolution(ctx, market_id, outcome)
+    }
+
+    pub fn challenge_resolution(ctx: Context<ChallengeResolution>, market_id: u64, reason: String) -> Result<()> {
+        instructions::oracle::optimistic_resolution::process_challenge_resolution(ctx, market_id, reason)
+    }
+
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>, market_id: u64) -> Result<()> {
+        instructions::oracle::optimistic_resolution::process_finalize_resolution(ctx, market_id)
+    }
+
+    pub fn attest_outcome(ctx: Context<AttestOutcome>, market_id: u64, outcome: Outcome) -> Result<()> {
+        instructions::oracle::attest_outcome::process_attest_outcome(ctx, market_id, outcome)
+    }
+
+    pub fn resolve_both_sides(ctx: Context<ResolveBothSides>, market_id: u64, yes_allocation: u64, no_allocation: u64) -> Result<()> {
+        instructions::oracle::resolve_both_sides::process_resolve_both_sides(ctx, market_id, yes_allocation, no_allocation)
+    }
+
+    pub fn refresh_oracle_cache(ctx: Context<RefreshOracleCache>, market_id: u64) -> Result<()> {
+        instructions::oracle::refresh_oracle_cache::process_refresh_oracle_cache(ctx, market_id)
+    }
+
+    pub fn snapshot_resolution_price(ctx: Context<SnapshotResolutionPrice>, market_id: u64) -> Result<()> {
+        instructions::oracle::snapshot_resolution_price::process_snapshot_resolution_price(ctx, market_id)
+    }
+
+    pub fn record_price_snapshot(ctx: Context<RecordPriceSnapshot>, market_id: u64) -> Result<()> {
+        instructions::oracle::record_price_snapshot::process_record_price_snapshot(ctx, market_id)
+    }
+
+    pub fn check_barrier(ctx: Context<CheckBarrier>, market_id: u64) -> Result<()> {
+        instructions::oracle::check_barrier::process_check_barrier(ctx, market_id)
+    }
+
     pub fn open_dispute(ctx: Context<OpenDispute>, market_id: u64, reason: String) -> Result<()> {
         instructions::dispute::open_dispute::process_open_dispute(ctx, market_id, reason)
     }
@@ -47,14 +110,72 @@ pub mod solana_predict {
         instructions::dispute::settle_dispute::process_settle_dispute(ctx, market_id, result_outcome)
     }
 
-    pub fn pause_platform(ctx: Context<PlatformAdmin>) -> Result<()> {
+    pub fn fork_market(ctx: Context<ForkMarket>, market_id: u64, child_id: u64, outcome: Outcome, yes_bps: u16) -> Result<()> {
+        instructions::dispute::fork_market::process_fork_market(ctx, market_id, child_id, outcome, yes_bps)
+    }
+
+    pub fn migrate_fork_position(ctx: Context<MigrateForkPosition>) -> Result<()> {
+        instructions::betting::migrate_fork_position::process_migrate_fork_position(ctx)
+    }
+
+    pub fn extend_dispute_voting(ctx: Context<ExtendDisputeVoting>, market_id: u64, additional_secs: i64) -> Result<()> {
+        instructions::dispute::extend_dispute_voting::process_extend_dispute_voting(ctx, market_id, additional_secs)
+    }
+
+    pub fn pause_platform(ctx: Context<PlatformPauser>) -> Result<()> {
         instructions::admin::pause::pause_platform(ctx)
     }
 
-    pub fn unpause_platform(ctx: Context<PlatformAdmin>) -> Result<()> {
+    pub fn unpause_platform(ctx: Context<PlatformPauser>) -> Result<()> {
         instructions::admin::pause::unpause_platform(ctx)
     }
 
+    pub fn emergency_shutdown(ctx: Context<PlatformAdmin>) -> Result<()> {
+        instructions::admin::emergency_shutdown::emergency_shutdown(ctx)
+    }
+
+    pub fn resume_from_shutdown(ctx: Context<PlatformAdmin>) -> Result<()> {
+        instructions::admin::emergency_shutdown::resume_from_shutdown(ctx)
+    }
+
+    pub fn claim_emergency_refund(ctx: Context<ClaimRefund>, market_id: u64) -> Result<()> {
+        instructions::betting::claim_emergency_refund::process_claim_emergency_refund(ctx, market_id)
+    }
+
+    pub fn claim_refund(ctx: Context<ClaimRefund>, market_id: u64) -> Result<()> {
+        instructions::betting::claim_refund::process_claim_refund(ctx, market_id)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::betting::referral::process_register_referrer(ctx)
+    }
+
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        instructions::betting::referral::process_claim_referral_fees(ctx)
+    }
+
+    pub fn init_user_tier(ctx: Context<InitUserTier>) -> Result<()> {
+        instructions::betting::user_tier::process_init_user_tier(ctx)
+    }
+
+    pub fn place_round_bet(
+        ctx: Context<PlaceRoundBet>,
+        market_id: u64,
+        round_id: u64,
+        outcome: Outcome,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::betting::round_bet::process_place_round_bet(ctx, market_id, round_id, outcome, amount)
+    }
+
+    pub fn claim_round_payout(ctx: Context<ClaimRoundPayout>, market_id: u64, round_id: u64) -> Result<()> {
+        instructions::betting::round_bet::process_claim_round_payout(ctx, market_id, round_id)
+    }
+
+    pub fn close_round_position(ctx: Context<CloseRoundPosition>) -> Result<()> {
+        instructions::betting::round_bet::process_close_round_position(ctx)
+    }
+
     pub fn pause_market(ctx: Context<ToggleMarketCtx>, market_id: u64) -> Result<()> {
         instructions::admin::pause::pause_market(ctx, market_id)
     }
@@ -63,6 +184,10 @@ pub mod solana_predict {
         instructions::admin::pause::unpause_market(ctx, market_id)
     }
 
+    pub fn lock_market(ctx: Context<LockMarket>, market_id: u64) -> Result<()> {
+        instructions::admin::lock_market::process_lock_market(ctx, market_id)
+    }
+
     pub fn update_fees(ctx: Context<UpdateFees>, new_fee_bps: u16) -> Result<()> {
         instructions::admin::update_fees::update_fees(ctx, new_fee_bps)
     }
@@ -71,11 +196,269 @@ pub mod solana_predict {
         instructions::admin::close_market::process_close_market(ctx, market_id)
     }
 
+    pub fn mark_for_close(ctx: Context<CloseRequestCtx>, market_id: u64) -> Result<()> {
+        instructions::admin::mark_for_close::process_mark_for_close(ctx, market_id)
+    }
+
+    pub fn cancel_close(ctx: Context<CloseRequestCtx>, market_id: u64) -> Result<()> {
+        instructions::admin::mark_for_close::process_cancel_close(ctx, market_id)
+    }
+
+    pub fn abort_market(ctx: Context<AbortMarket>, market_id: u64) -> Result<()> {
+        instructions::admin::abort_market::process_abort_market(ctx, market_id)
+    }
+
+    pub fn cancel_market(ctx: Context<CancelMarket>, market_id: u64) -> Result<()> {
+        instructions::admin::cancel_market::process_cancel_market(ctx, market_id)
+    }
+
+    pub fn reclaim_seed(ctx: Context<ReclaimSeed>, market_id: u64) -> Result<()> {
+        instructions::admin::reclaim_seed::process_reclaim_seed(ctx, market_id)
+    }
+
+    pub fn forfeit_dust_positions<'info>(ctx: Context<'_, '_, 'info, 'info, ForfeitDustPositions<'info>>, market_id: u64) -> Result<()> {
+        instructions::admin::forfeit_dust_positions::process_forfeit_dust_positions(ctx, market_id)
+    }
+
+    pub fn sweep_vault_surplus(ctx: Context<SweepVaultSurplus>, market_id: u64) -> Result<()> {
+        instructions::admin::sweep_vault_surplus::process_sweep_vault_surplus(ctx, market_id)
+    }
+
+    pub fn propose_admin(ctx: Context<PlatformAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::admin::transfer_admin::process_propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::admin::transfer_admin::process_accept_admin(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<PlatformAdmin>, key: Pubkey, role: Role) -> Result<()> {
+        instructions::admin::roles::process_grant_role(ctx, key, role)
+    }
+
+    pub fn revoke_role(ctx: Context<PlatformAdmin>, key: Pubkey, role: Role) -> Result<()> {
+        instructions::admin::roles::process_revoke_role(ctx, key, role)
+    }
+
+    pub fn set_resolution_committee(ctx: Context<SetResolutionCommittee>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::admin::committee::process_set_resolution_committee(ctx, members, threshold)
+    }
+
+    pub fn register_feed(
+        ctx: Context<RegisterFeed>,
+        feed: Pubkey,
+        source: OracleSource,
+        symbol: String,
+        exponent: i32,
+    ) -> Result<()> {
+        instructions::admin::feed_registry::process_register_feed(ctx, feed, source, symbol, exponent)
+    }
+
+    pub fn deregister_feed(ctx: Context<DeregisterFeed>, feed: Pubkey) -> Result<()> {
+        instructions::admin::feed_registry::process_deregister_feed(ctx, feed)
+    }
+
+    pub fn set_approvers(ctx: Context<PlatformAdmin>, approvers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::admin::approvals::process_set_approvers(ctx, approvers, threshold)
+    }
+
+    pub fn propose_action(ctx: Context<ProposeAction>, action: AdminAction) -> Result<()> {
+        instructions::admin::approvals::process_propose_action(ctx, action)
+    }
+
+    pub fn approve_action(ctx: Context<ApproveAction>, proposal_id: u64) -> Result<()> {
+        instructions::admin::approvals::process_approve_action(ctx, proposal_id)
+    }
+
+    pub fn execute_action(ctx: Context<ExecuteAction>) -> Result<()> {
+        instructions::admin::execute_action::process_execute_action(ctx)
+    }
+
+    pub fn transfer_market_creator(ctx: Context<TransferMarketCreator>, market_id: u64, new_creator: Pubkey) -> Result<()> {
+        instructions::admin::transfer_market_creator::process_transfer_market_creator(ctx, market_id, new_creator)
+    }
+
+    pub fn migrate_oracle_source(ctx: Context<MigrateOracleSource>, market_id: u64, new_source: OracleSource, new_feed: Pubkey, new_threshold: i64) -> Result<()> {
+        instructions::admin::migrate_oracle_source::process_migrate_oracle_source(ctx, market_id, new_source, new_feed, new_threshold)
+    }
+
+    pub fn rebalance_liquidity(ctx: Context<RebalanceLiquidity>, market_id: u64, yes_add: u64, no_add: u64) -> Result<()> {
+        instructions::admin::rebalance_liquidity::process_rebalance_liquidity(ctx, market_id, yes_add, no_add)
+    }
+
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, market_id: u64, amount: u64) -> Result<()> {
+        instructions::liquidity::add_liquidity::process_add_liquidity(ctx, market_id, amount)
+    }
+
+    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, market_id: u64, lp_tokens: u64) -> Result<()> {
+        instructions::liquidity::remove_liquidity::process_remove_liquidity(ctx, market_id, lp_tokens)
+    }
+
     pub fn update_collateral_mint(ctx: Context<UpdateCollateralMint>) -> Result<()> {
         instructions::admin::update_collateral_mint::update_collateral_mint(ctx)
     }
 
-    pub fn update_treasury(ctx: Context<UpdateTreasury>) -> Result<()> {
-        instructions::admin::update_treasury::update_treasury(ctx)
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        instructions::admin::update_fees::withdraw_fees(ctx, amount)
+    }
+
+    pub fn update_min_threshold_distance(ctx: Context<PlatformAdmin>, min_distance_bps: u16) -> Result<()> {
+        instructions::admin::update_config::update_min_threshold_distance(ctx, min_distance_bps)
+    }
+
+    pub fn update_creation_cooldown(ctx: Context<PlatformAdmin>, cooldown_secs: i64) -> Result<()> {
+        instructions::admin::update_config::update_creation_cooldown(ctx, cooldown_secs)
+    }
+
+    pub fn update_default_max_slippage(ctx: Context<PlatformAdmin>, max_slippage_bps: u16) -> Result<()> {
+        instructions::admin::update_config::update_default_max_slippage(ctx, max_slippage_bps)
+    }
+
+    pub fn update_resolution_grace(ctx: Context<PlatformAdmin>, grace_secs: i64) -> Result<()> {
+        instructions::admin::update_config::update_resolution_grace(ctx, grace_secs)
+    }
+
+    pub fn update_max_active_markets(ctx: Context<PlatformAdmin>, max_active_markets: u64) -> Result<()> {
+        instructions::admin::update_config::update_max_active_markets(ctx, max_active_markets)
+    }
+
+    pub fn update_resolution_timeout(ctx: Context<PlatformAdmin>, timeout_secs: i64) -> Result<()> {
+        instructions::admin::update_config::update_resolution_timeout(ctx, timeout_secs)
+    }
+
+    pub fn update_max_backdate(ctx: Context<PlatformAdmin>, max_backdate_secs: i64) -> Result<()> {
+        instructions::admin::update_config::update_max_backdate(ctx, max_backdate_secs)
+    }
+
+    pub fn update_max_dispute_voting(ctx: Context<PlatformAdmin>, max_voting_secs: i64) -> Result<()> {
+        instructions::admin::update_config::update_max_dispute_voting(ctx, max_voting_secs)
+    }
+
+    pub fn update_optimistic_resolution(ctx: Context<PlatformAdmin>, bond_lamports: u64, challenge_window_secs: i64) -> Result<()> {
+        instructions::admin::update_config::update_optimistic_resolution(ctx, bond_lamports, challenge_window_secs)
+    }
+
+    pub fn update_min_slots_before_resolve(ctx: Context<PlatformAdmin>, min_slots: u64) -> Result<()> {
+        instructions::admin::update_config::update_min_slots_before_resolve(ctx, min_slots)
+    }
+
+    pub fn update_min_bettors_for_resolution(ctx: Context<PlatformAdmin>, min_bettors: u64) -> Result<()> {
+        instructions::admin::update_config::update_min_bettors_for_resolution(ctx, min_bettors)
+    }
+
+    pub fn update_min_claimable_dust(ctx: Context<PlatformAdmin>, min_claimable_dust: u64) -> Result<()> {
+        instructions::admin::update_config::update_min_claimable_dust(ctx, min_claimable_dust)
+    }
+
+    pub fn update_claim_window(ctx: Context<PlatformAdmin>, claim_window_secs: i64) -> Result<()> {
+        instructions::admin::update_config::update_claim_window(ctx, claim_window_secs)
+    }
+
+    pub fn update_max_platform_tvl(ctx: Context<PlatformAdmin>, max_platform_tvl: u64) -> Result<()> {
+        instructions::admin::update_config::update_max_platform_tvl(ctx, max_platform_tvl)
+    }
+
+    pub fn update_referral_fee(ctx: Context<PlatformAdmin>, referral_fee_bps: u16) -> Result<()> {
+        instructions::admin::update_config::update_referral_fee(ctx, referral_fee_bps)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<PlatformAdmin>, tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::admin::update_config::set_fee_tiers(ctx, tiers)
+    }
+
+    pub fn update_close_grace(ctx: Context<PlatformAdmin>, close_grace_secs: i64) -> Result<()> {
+        instructions::admin::update_config::update_close_grace(ctx, close_grace_secs)
+    }
+
+    pub fn update_min_fee_absolute(ctx: Context<PlatformAdmin>, min_fee_absolute: u64) -> Result<()> {
+        instructions::admin::update_config::update_min_fee_absolute(ctx, min_fee_absolute)
+    }
+
+    pub fn update_forking_enabled(ctx: Context<PlatformAdmin>, enabled: bool) -> Result<()> {
+        instructions::admin::update_config::update_forking_enabled(ctx, enabled)
+    }
+
+    pub fn set_payout_mint(ctx: Context<SetPayoutMint>, market_id: u64, payout_rate: u64, reserve_amount: u64) -> Result<()> {
+        instructions::admin::set_payout_mint::process_set_payout_mint(ctx, market_id, payout_rate, reserve_amount)
+    }
+
+    pub fn withdraw_payout_surplus(ctx: Context<WithdrawPayoutSurplus>, market_id: u64) -> Result<()> {
+        instructions::admin::withdraw_payout_surplus::process_withdraw_payout_surplus(ctx, market_id)
+    }
+
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>, market_id: u64) -> Result<()> {
+        instructions::admin::claim_creator_fees::process_claim_creator_fees(ctx, market_id)
+    }
+
+    pub fn set_category_policy(ctx: Context<SetCategoryPolicy>, category: MarketCategory, allowed_sources: u8) -> Result<()> {
+        instructions::admin::set_category_policy::process_set_category_policy(ctx, category, allowed_sources)
+    }
+
+    pub fn restart_recurring(ctx: Context<RestartRecurring>, market_id: u64, new_start: i64, new_lock: i64, new_end: i64) -> Result<()> {
+        instructions::admin::restart_recurring::process_restart_recurring(ctx, market_id, new_start, new_lock, new_end)
+    }
+
+    pub fn start_round(ctx: Context<StartRound>, market_id: u64) -> Result<()> {
+        instructions::admin::start_round::process_start_round(ctx, market_id)
+    }
+
+    pub fn close_round(ctx: Context<CloseRound>, market_id: u64, round_id: u64) -> Result<()> {
+        instructions::admin::close_round::process_close_round(ctx, market_id, round_id)
+    }
+
+    pub fn lock_round(ctx: Context<LockRound>, market_id: u64) -> Result<()> {
+        instructions::oracle::lock_round::process_lock_round(ctx, market_id)
+    }
+
+    pub fn resolve_round(ctx: Context<ResolveRound>, market_id: u64) -> Result<()> {
+        instructions::oracle::resolve_round::process_resolve_round(ctx, market_id)
+    }
+
+    pub fn crank_round(ctx: Context<CrankRound>, market_id: u64) -> Result<()> {
+        instructions::oracle::crank_round::process_crank_round(ctx, market_id)
+    }
+
+    pub fn get_pool_depth(ctx: Context<MarketView>, market_id: u64) -> Result<PoolDepth> {
+        instructions::views::get_pool_depth::process_get_pool_depth(ctx, market_id)
+    }
+
+    pub fn get_claim_status(ctx: Context<ClaimStatusView>, market_id: u64) -> Result<ClaimStatus> {
+        instructions::views::get_claim_status::process_get_claim_status(ctx, market_id)
+    }
+
+    pub fn get_pnl(ctx: Context<ClaimStatusView>, market_id: u64) -> Result<Pnl> {
+        instructions::views::get_pnl::process_get_pnl(ctx, market_id)
+    }
+
+    pub fn get_outcome_exposure(ctx: Context<MarketSupplyView>, market_id: u64) -> Result<OutcomeExposure> {
+        instructions::views::get_outcome_exposure::process_get_outcome_exposure(ctx, market_id)
+    }
+
+    pub fn get_resolution_proof(ctx: Context<MarketView>, market_id: u64) -> Result<ResolutionProof> {
+        instructions::views::get_resolution_proof::process_get_resolution_proof(ctx, market_id)
+    }
+
+    pub fn quote_bet_curve(ctx: Context<MarketView>, market_id: u64, outcome: Outcome, sizes: Vec<u64>) -> Result<Vec<u64>> {
+        instructions::views::quote_bet_curve::process_quote_bet_curve(ctx, market_id, outcome, sizes)
+    }
+
+    pub fn suggest_bet_size(ctx: Context<MarketView>, market_id: u64, outcome: Outcome, edge_bps: u64, bankroll: u64) -> Result<u64> {
+        instructions::views::suggest_bet_size::process_suggest_bet_size(ctx, market_id, outcome, edge_bps, bankroll)
+    }
+
+    pub fn quote_fee(ctx: Context<QuoteFee>, market_id: u64, outcome: Outcome, amount: u64, referrer: Option<Pubkey>) -> Result<FeeBreakdown> {
+        instructions::views::quote_fee::process_quote_fee(ctx, market_id, outcome, amount, referrer)
+    }
+
+    pub fn get_price_history(ctx: Context<MarketView>, market_id: u64) -> Result<Vec<PriceCheckpoint>> {
+        instructions::views::get_price_history::process_get_price_history(ctx, market_id)
+    }
+
+    pub fn verify_fee_accounting(ctx: Context<MarketView>, market_id: u64) -> Result<FeeLedger> {
+        instructions::views::verify_fee_accounting::process_verify_fee_accounting(ctx, market_id)
+    }
+
+    pub fn validate_market_params(ctx: Context<ValidateMarketParams>, params: CreateMarketParams) -> Result<Vec<ValidationError>> {
+        instructions::views::validate_market_params::process_validate_market_params(ctx, params)
     }
 }
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::market::Outcome;
+
+/// Upper bound on approvers (keeps PlatformConfig::LEN and ActionProposal::LEN fixed)
+pub const MAX_APPROVERS: usize = 5;
+
+/// Admin action that needs `approval_threshold` approvals once approvals are
+/// enabled. Carries the arguments of the instruction it stands in for.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum AdminAction {
+    /// update_collateral_mint
+    UpdateCollateralMint { mint: Pubkey },
+    /// settle_dispute
+    SettleDispute { market_id: u64, result_outcome: Option<Outcome> },
+    /// withdraw_fees to `destination`
+    WithdrawFees { destination: Pubkey, amount: u64 },
+    /// set_approvers; default keys are empty slots
+    SetApprovers { approvers: [Pubkey; MAX_APPROVERS], threshold: u8 },
+    /// emergency_shutdown
+    EmergencyShutdown,
+    /// resume_from_shutdown
+    ResumeFromShutdown,
+}
+
+impl AdminAction {
+    /// Mint whose treasury execute_action may need to create (default otherwise)
+    pub fn collateral_mint(&self) -> Pubkey {
+        match self {
+            AdminAction::UpdateCollateralMint { mint } => *mint,
+            _ => Pubkey::default(),
+        }
+    }
+}
+
+/// An admin action waiting for approvals. Approvals count only while the
+/// approving key is still in the platform's approver set.
+#[account]
+pub struct ActionProposal {
+    pub id: u64,
+    pub action: AdminAction,
+    pub proposer: Pubkey,
+    pub approved_by: [Pubkey; MAX_APPROVERS], // filled in order, default = empty
+    pub created_at: i64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl ActionProposal {
+    // 8 (discriminator) + 8 (id)
+    // 1 + 32 * MAX_APPROVERS + 1 (action: largest variant is SetApprovers)
+    // 32 (proposer) + 32 * MAX_APPROVERS (approved_by)
+    // 8 (created_at) + 1 (executed) + 1 (bump)
+    pub const LEN: usize = 8 + 8 + (1 + 32 * MAX_APPROVERS + 1) + 32 + 32 * MAX_APPROVERS + 8 + 1 + 1;
+
+    pub fn has_approved(&self, key: &Pubkey) -> bool {
+        self.approved_by.contains(key)
+    }
+
+    /// Records `key`'s approval in the first empty slot
+    pub fn add_approval(&mut self, key: Pubkey) -> bool {
+        match self.approved_by.iter_mut().find(|k| **k == Pubkey::default()) {
+            Some(slot) => {
+                *slot = key;
+                true
+            }
+            None => false,
+        }
+    }
+}
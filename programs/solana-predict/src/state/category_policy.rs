@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::state::market::{MarketCategory, OracleSource};
+
+/// Bitmask with every OracleSource allowed
+pub const ALL_ORACLE_SOURCES: u8 = 0b111111;
+/// Number of MarketCategory variants
+pub const CATEGORY_COUNT: usize = 6;
+
+/// Platform-wide allowed oracle sources per market category.
+/// `allowed_sources[category]` is a bitmask of `1 << oracle_source`.
+#[account]
+pub struct CategoryPolicy {
+    pub allowed_sources: [u8; CATEGORY_COUNT],
+    pub bump: u8,
+}
+
+impl CategoryPolicy {
+    pub const LEN: usize = 8 + CATEGORY_COUNT + 1;
+
+    pub fn allows(&self, category: MarketCategory, source: OracleSource) -> bool {
+        self.allowed_sources[category as usize] & (1 << source as u8) != 0
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictError;
+use crate::state::market::Outcome;
+
+/// Upper bound on committee members (keeps ResolutionCommittee::LEN fixed)
+pub const MAX_COMMITTEE_MEMBERS: usize = 7;
+
+/// Platform-wide committee resolving markets created with `committee_resolution`
+#[account]
+pub struct ResolutionCommittee {
+    pub members: [Pubkey; MAX_COMMITTEE_MEMBERS], // default = empty slot
+    pub threshold: u8,                           // matching attestations that resolve a market
+    pub bump: u8,
+}
+
+impl ResolutionCommittee {
+    pub const LEN: usize = 8 + 32 * MAX_COMMITTEE_MEMBERS + 1 + 1;
+
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.members.contains(key)
+    }
+
+    /// Replaces the committee. `members` must be distinct with room for
+    /// `threshold` (at least 1) of them to agree.
+    pub fn set_members(&mut self, members: &[Pubkey], threshold: u8) -> Result<()> {
+        let keys: Vec<Pubkey> = members.iter().copied().filter(|k| *k != Pubkey::default()).collect();
+        require!(keys.len() <= MAX_COMMITTEE_MEMBERS, PredictError::InvalidCommittee);
+        require!(threshold > 0 && threshold as usize <= keys.len(), PredictError::InvalidCommittee);
+        for (i, key) in keys.iter().enumerate() {
+            require!(!keys[..i].contains(key), PredictError::InvalidCommittee);
+        }
+        self.members = [Pubkey::default(); MAX_COMMITTEE_MEMBERS];
+        self.members[..keys.len()].copy_from_slice(&keys);
+        self.threshold = threshold;
+        Ok(())
+    }
+}
+
+/// One member's attested outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Attestation {
+    pub member: Pubkey,
+    pub outcome: Outcome,
+}
+
+impl Default for Attestation {
+    fn default() -> Self {
+        Self { member: Pubkey::default(), outcome: Outcome::Invalid }
+    }
+}
+
+/// Committee attestations collected for one market (see attest_outcome)
+#[account]
+pub struct OutcomeAttestations {
+    pub market: Pubkey,
+    pub attestations: [Attestation; MAX_COMMITTEE_MEMBERS], // default member = empty slot
+    pub bump: u8,
+}
+
+impl OutcomeAttestations {
+    pub const LEN: usize = 8 + 32 + 33 * MAX_COMMITTEE_MEMBERS + 1;
+
+    /// Records (or changes) `member`'s attestation. Slots held by keys that
+    /// have since left the committee are reused.
+    pub fn record(&mut self, committee: &ResolutionCommittee, member: Pubkey, outcome: Outcome) -> Result<()> {
+        let slot = self.attestations.iter().position(|a| a.member == member)
+            .or_else(|| self.attestations.iter().position(|a| !committee.is_member(&a.member)))
+            .ok_or(PredictError::InvalidCommittee)?;
+        self.attestations[slot] = Attestation { member, outcome };
+        Ok(())
+    }
+
+    /// Current members attesting `outcome`
+    pub fn count(&self, committee: &ResolutionCommittee, outcome: Outcome) -> u8 {
+        self.attestations.iter()
+            .filter(|a| a.outcome == outcome && committee.is_member(&a.member))
+            .count() as u8
+    }
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Per-creator bookkeeping for market-creation throttling
+#[account]
+pub struct CreatorState {
+    pub creator: Pubkey,
+    pub last_created_at: i64,
+    pub markets_created: u64,
+    pub bump: u8,
+}
+
+impl CreatorState {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
+}
@@ -13,10 +13,14 @@ pub struct DisputeRecord {
     pub created_at: i64,
     pub resolved_at: Option<i64>,
     pub bump: u8,
+    pub voting_ends_at: i64,
 }
 
+/// Voting period granted when a dispute is opened
+pub const DEFAULT_DISPUTE_VOTING_SECS: i64 = 48 * 60 * 60;
+
 impl DisputeRecord {
-    pub const LEN: usize = 8 + 32 + 32 + (4 + 256) + 8 + 1 + 8 + 8 + 8 + 9 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + (4 + 256) + 8 + 1 + 8 + 8 + 8 + 9 + 1 + 8;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
@@ -25,4 +29,6 @@ pub enum DisputeStatus {
     VotingActive,
     Upheld,
     Rejected,
+    /// Settled by forking the market into one child per claimed outcome
+    Forked,
 }
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictError;
+use crate::state::market::OracleSource;
+
+/// Upper bound on registered feeds (keeps FeedRegistry::LEN fixed)
+pub const MAX_REGISTERED_FEEDS: usize = 16;
+/// Bytes of a feed's symbol, zero-padded (e.g. "SOL/USD")
+pub const FEED_SYMBOL_LEN: usize = 16;
+
+/// An admin-approved price feed with the metadata creators rely on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RegisteredFeed {
+    pub feed: Pubkey, // default = empty slot
+    pub source: OracleSource,
+    pub symbol: [u8; FEED_SYMBOL_LEN],
+    pub exponent: i32, // thresholds of markets on this feed default to it
+}
+
+impl Default for RegisteredFeed {
+    fn default() -> Self {
+        Self {
+            feed: Pubkey::default(),
+            source: OracleSource::Pyth,
+            symbol: [0; FEED_SYMBOL_LEN],
+            exponent: 0,
+        }
+    }
+}
+
+/// Platform-wide allowlist of price feeds. Once any feed is registered,
+/// Pyth / Switchboard / Chainlink markets can only be created on listed feeds.
+#[account]
+pub struct FeedRegistry {
+    pub feeds: [RegisteredFeed; MAX_REGISTERED_FEEDS],
+    pub bump: u8,
+}
+
+impl FeedRegistry {
+    pub const LEN: usize = 8 + (32 + 1 + FEED_SYMBOL_LEN + 4) * MAX_REGISTERED_FEEDS + 1;
+
+    /// False while no feed is registered: the registry doesn't restrict anything yet
+    pub fn is_enforced(&self) -> bool {
+        self.feeds.iter().any(|f| f.feed != Pubkey::default())
+    }
+
+    pub fn find(&self, feed: &Pubkey, source: OracleSource) -> Option<&RegisteredFeed> {
+        self.feeds.iter().find(|f| f.feed != Pubkey::default() && f.feed == *feed && f.source == source)
+    }
+
+    /// Registered exponent of `feed`, the default for thresholds on it
+    pub fn exponent_of(&self, feed: &Pubkey, source: OracleSource) -> Option<i32> {
+        self.find(feed, source).map(|entry| entry.exponent)
+    }
+
+    /// Adds `entry`, or updates the metadata of an already listed feed
+    pub fn register(&mut self, entry: RegisteredFeed) -> Result<()> {
+        let slot = self.feeds.iter().position(|f| f.feed == entry.feed)
+            .or_else(|| self.feeds.iter().position(|f| f.feed == Pubkey::default()))
+            .ok_or(PredictError::FeedRegistryFull)?;
+        self.feeds[slot] = entry;
+        Ok(())
+    }
+
+    pub fn deregister(&mut self, feed: &Pubkey) -> Result<()> {
+        let slot = self.feeds.iter().position(|f| f.feed != Pubkey::default() && f.feed == *feed)
+            .ok_or(PredictError::FeedNotRegistered)?;
+        self.feeds[slot] = RegisteredFeed::default();
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// One provider's liquidity in one market. The provider's claim is the LP
+/// tokens they hold; this records what went in and what came back out.
+#[account]
+pub struct LpPosition {
+    pub owner: Pubkey,
+    pub market: Pubkey,
+    pub deposited: u64,          // collateral added with add_liquidity
+    pub withdrawn: u64,          // collateral returned by remove_liquidity
+    pub bump: u8,
+}
+
+impl LpPosition {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
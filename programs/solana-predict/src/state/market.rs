@@ -1,4 +1,18 @@
 use anchor_lang::prelude::*;
+use crate::state::platform::{PlatformConfig, Role};
+
+/// Upper bound on per-market operators (keeps Market::LEN fixed)
+pub const MAX_MARKET_ADMINS: usize = 4;
+
+/// Reserve checkpoints kept per market (oldest overwritten first)
+pub const PRICE_HISTORY_LEN: usize = 16;
+
+/// Minimum slots between checkpoints (~10s): bets within the interval of the
+/// last checkpoint don't record a new one
+pub const PRICE_CHECKPOINT_INTERVAL_SLOTS: u64 = 25;
+
+/// Most lopsided opening prior (1%/99%): keeps both seeded pools non-empty
+pub const MIN_INITIAL_PROB_BPS: u16 = 100;
 
 #[account]
 pub struct Market {
@@ -31,6 +45,67 @@ pub struct Market {
     pub round_duration: Option<i64>,
     pub current_round: u64,
     pub bump: u8,
+    pub payout_mint: Option<Pubkey>, // settle winners in this mint instead of collateral
+    pub payout_rate: u64,            // payout units per PAYOUT_RATE_SCALE collateral units
+    pub boundary_band: u64,          // |price - threshold| within this resolves Invalid (0 = off)
+    pub resolution_conf: u64,        // Pyth confidence of the snapshotted resolution_price
+    pub initial_liquidity: u64,      // seed liquidity of the current series (less any reclaimed)
+    pub default_outcome_on_timeout: Option<Outcome>, // applied by resolve_on_timeout
+    pub program_state_query: Option<ProgramStateQuery>, // ProgramState markets: how to read oracle_feed
+    pub total_deposited: u64,        // net principal across positions this round (Invalid refund weights)
+    pub oracle_cache: OracleCache,   // last Pyth print read for this market
+    pub early_bird_bonus_bps: u16,   // extra collateral credited to bets in the window (0 = off)
+    pub early_bird_window_secs: i64, // bonus window after start_timestamp
+    pub early_bird_budget: u64,      // unspent bonus collateral, held in the vault outside total_collateral
+    pub market_admins: Vec<Pubkey>,  // max MAX_MARKET_ADMINS; may pause/resolve this market only
+    pub distributable_collateral: u64, // pool claimable by winners, fixed at resolution (less claims)
+    pub resolution_proof: ResolutionProof, // how the market was resolved (see get_resolution_proof)
+    pub max_bet_pool_fraction_bps: u16, // max net bet as bps of the bought outcome's reserve (0 = off)
+    pub confidence_split: bool,      // Pyth: resolve Split when the conf band straddles the threshold
+    pub split_yes_bps: u16,          // Split: YES holders' share of the distributable pool
+    pub split_yes_pool: u64,         // Split: unclaimed YES allocation (NO gets the rest of distributable)
+    pub last_bet_slot: u64,          // slot of the most recent bet (see min_slots_before_resolve)
+    pub both_sides_win: bool,        // may be resolved with explicit YES/NO allocations (resolve_both_sides)
+    pub locked_yes_reserve: u64,     // YES reserve frozen at lock: closing odds (0 = not yet locked)
+    pub locked_no_reserve: u64,      // NO reserve frozen at lock
+    pub unique_bettors: u64,         // distinct wallets that bet this round
+    pub price_history: [PriceCheckpoint; PRICE_HISTORY_LEN], // ring of post-bet reserves (see get_price_history)
+    pub price_history_head: u8,      // next ring slot to write
+    pub oracle_exponent: Option<i32>, // price feeds: exponent oracle_threshold and stored prices are expressed in
+    pub dust_recipient: DustRecipient, // where the final claim's rounding residue goes
+    pub pending_dust: u64,           // residue withheld from the final claim, in the vault outside the pool
+    pub claimed_payout: u64,         // collateral paid out this round
+    pub claimed_weight: u64,         // shares (or Invalid principal) redeemed this round
+    pub fee_ledger: FeeLedger,       // lifetime fees charged and where they were credited
+    pub parent_market: Pubkey,       // fork child: the disputed market it was split from (default = not a fork)
+    pub fork_children: [Pubkey; 2],  // forked parent: YES and NO children (default = not created)
+    pub fork_yes_supply: u64,        // fork child: parent YES supply at the fork (claim denominator)
+    pub fork_no_supply: u64,         // fork child: parent NO supply at the fork
+    pub dust_forfeited: bool,        // every outstanding share was forfeited; may close with nonzero supply
+    pub end_slot: Option<u64>,       // chain-native markets: resolvable from this slot instead of end_timestamp
+    pub end_epoch: Option<u64>,      // chain-native markets: resolvable from this epoch instead of end_timestamp
+    pub slot_start_pricing: bool,    // bets within a slot are priced against the reserves at its first bet
+    pub slot_start_reserves: [u64; 2], // (YES, NO) reserves snapshotted at the first bet of slot_start_slot
+    pub slot_start_slot: u64,        // slot the snapshot was taken in
+    pub slot_flow: [u64; 2],         // collateral bet on (YES, NO) so far in slot_start_slot
+    pub close_requested_at: i64,     // mark_for_close time (0 = not marked)
+    pub creator_betting_restricted: bool, // ManualAdmin: the creator and resolvers may not bet
+    pub lp_mint: Pubkey,             // LP token mint (default until the first add_liquidity)
+    pub lp_liquidity: u64,           // collateral added by liquidity providers, in total_collateral
+    pub lp_pool: u64,                // set aside for LP holders at resolution (less withdrawals)
+    pub committee_resolution: bool,  // ManualAdmin: resolved only by ResolutionCommittee attestations
+    pub twap_window_secs: i64,       // Pyth: resolve on the TWAP of this final window (0 = spot print)
+    pub scalar_range: Option<ScalarRange>, // scalar markets: YES (LONG) share interpolated over the range
+    pub oracle_upper_threshold: Option<i64>, // range markets: YES when oracle_threshold < price <= this
+    pub barrier: bool,               // Pyth one-touch: YES as soon as check_barrier sees price > oracle_threshold
+    pub pricing_mode: PricingMode,
+    pub initial_prob_bps: u16,       // YES probability each round's pools open at
+    pub virtual_liquidity: u64,      // added to both reserves when pricing, never paid out
+    pub max_fee_bps: u16,            // fee reached at lock when ramping
+    pub fee_ramp_secs: i64,          // ramp length before lock (0 = flat fee_bps)
+    pub creator_fee_bps: u16,        // part of each bet fee escrowed for the creator
+    pub lp_fee_bps: u16,             // share (bps) of each trade fee kept in the vault for LPs
+    pub lp_fees_accrued: u64,        // trade fees kept for LPs, in total_collateral
 }
 
 impl Market {
@@ -47,7 +122,248 @@ impl Market {
     // 1+8 (resolved_at option)
     // 1 (is_recurring) + 1+8 (round_duration option) + 8 (current_round)
     // 1 (bump)
-    pub const LEN: usize = 8 + 8 + 32 + (4 + 128) + (4 + 512) + 1 + 1 + 32 * 4 + 8 * 3 + 1 + 32 + 8 + 8 * 3 + 2 + 9 + 9 + 8 * 2 + 2 + 1 + 9 + 8 + 1;
+    // 1+32 (payout_mint option) + 8 (payout_rate)
+    // 8 (boundary_band) + 8 (resolution_conf) + 8 (initial_liquidity)
+    // 1+1 (default_outcome_on_timeout option)
+    // 1+32+4+1+1 (program_state_query option) + 8 (total_deposited)
+    // 8+8+8+8+8 (oracle_cache)
+    // 2 (early_bird_bonus_bps) + 8 (early_bird_window_secs) + 8 (early_bird_budget)
+    // 4 + 32 * MAX_MARKET_ADMINS (market_admins) + 8 (distributable_collateral)
+    // 32+8+8+8+8+32 (resolution_proof) + 2 (max_bet_pool_fraction_bps)
+    // 1 (confidence_split) + 2 (split_yes_bps) + 8 (split_yes_pool) + 8 (last_bet_slot)
+    // 1 (both_sides_win) + 8 (locked_yes_reserve) + 8 (locked_no_reserve)
+    // 8 (unique_bettors)
+    // 24 * PRICE_HISTORY_LEN (price_history) + 1 (price_history_head)
+    // 1+4 (oracle_exponent option)
+    // 1 (dust_recipient) + 8 (pending_dust) + 8 (claimed_payout) + 8 (claimed_weight)
+    // 8*6 (fee_ledger)
+    // 32 (parent_market) + 32 * 2 (fork_children) + 8 (fork_yes_supply) + 8 (fork_no_supply)
+    // 1 (dust_forfeited)
+    // 1+8 (end_slot option) + 1+8 (end_epoch option)
+    // 1 (slot_start_pricing) + 8 * 2 (slot_start_reserves) + 8 (slot_start_slot) + 8 * 2 (slot_flow)
+    // 8 (close_requested_at) + 1 (creator_betting_restricted)
+    // 32 (lp_mint) + 8 (lp_liquidity) + 8 (lp_pool)
+    // 1 (committee_resolution) + 8 (twap_window_secs)
+    // 1+16 (scalar_range option) + 1+8 (oracle_upper_threshold option) + 1 (barrier)
+    // 1 (pricing_mode) + 2 (initial_prob_bps) + 8 (virtual_liquidity)
+    // 2 (max_fee_bps) + 8 (fee_ramp_secs) + 2 (creator_fee_bps)
+    // 2 (lp_fee_bps) + 8 (lp_fees_accrued)
+    pub const LEN: usize = 8 + 8 + 32 + (4 + 128) + (4 + 512) + 1 + 1 + 32 * 4 + 8 * 3 + 1 + 32 + 8 + 8 * 3 + 2 + 9 + 9 + 8 * 2 + 2 + 1 + 9 + 8 + 1 + 33 + 8 + 8 + 8 + 8 + 2 + 39 + 8 + 40 + 2 + 8 + 8 + 4 + 32 * MAX_MARKET_ADMINS + 8 + 96 + 2 + 1 + 2 + 8 + 8 + 1 + 8 + 8 + 8 + 24 * PRICE_HISTORY_LEN + 1 + 5 + 1 + 8 + 8 + 8 + 48 + 32 + 64 + 8 + 8 + 1 + 9 + 9 + 1 + 16 + 8 + 16 + 8 + 1 + 32 + 8 + 8 + 1 + 8 + 17 + 9 + 1 + 1 + 2 + 8 + 2 + 8 + 2 + 2 + 8;
+
+    /// Collateral backing the market at resolution: the tracked pool, but never
+    /// more than the vault actually holds for it (the unspent early-bird budget
+    /// and anything else sitting in the vault are excluded). Cross-mint markets
+    /// pay from the reserve, so the tracked pool is the liability.
+    fn pool_at_resolution(&self, vault_amount: u64) -> u64 {
+        if self.payout_mint.is_some() {
+            self.total_collateral
+        } else {
+            self.total_collateral.min(vault_amount.saturating_sub(self.early_bird_budget))
+        }
+    }
+
+    /// Collateral winners may split at resolution: the pool less what is set
+    /// aside for liquidity providers
+    pub fn distributable_at_resolution(&self, vault_amount: u64) -> u64 {
+        self.pool_at_resolution(vault_amount) - self.lp_pool_at_resolution(vault_amount)
+    }
+
+    /// Collateral set aside for LP holders at resolution: their deposits and
+    /// the trade fees accrued to them, as far as the pool still covers them
+    pub fn lp_pool_at_resolution(&self, vault_amount: u64) -> u64 {
+        self.lp_liquidity
+            .saturating_add(self.lp_fees_accrued)
+            .min(self.pool_at_resolution(vault_amount))
+    }
+
+    /// Takes `amount` paid to LPs before resolution out of their deposits and
+    /// accrued fees (fees first), keeping `lp_pool_at_resolution` pro-rata for
+    /// the LP tokens still outstanding
+    pub fn debit_lp_funds(&mut self, amount: u64) {
+        let from_fees = amount.min(self.lp_fees_accrued);
+        self.lp_fees_accrued -= from_fees;
+        self.lp_liquidity = self.lp_liquidity.saturating_sub(amount - from_fees);
+    }
+
+    /// Whether start_round / crank_round open this market's rounds on a schedule
+    pub fn has_round_schedule(&self) -> bool {
+        self.round_duration.is_some_and(|d| d > 0)
+    }
+
+    /// Share (bps) of a trade fee kept for LPs: only once there are any
+    pub fn active_lp_fee_bps(&self) -> u16 {
+        if self.lp_liquidity > 0 { self.lp_fee_bps } else { 0 }
+    }
+
+    /// Splits the distributable pool for a Split resolution: `yes_bps` of it to
+    /// YES holders, the rest to NO holders.
+    pub fn set_split(&mut self, yes_bps: u16) {
+        self.split_yes_bps = yes_bps;
+        self.split_yes_pool = (self.distributable_collateral as u128 * yes_bps as u128 / 10000) as u64;
+    }
+
+    /// Remaining (YES, NO) allocations of a Split resolution
+    pub fn split_pools(&self) -> (u64, u64) {
+        (self.split_yes_pool, self.distributable_collateral.saturating_sub(self.split_yes_pool))
+    }
+
+    /// Stops betting and freezes the closing reserves. Only the first lock of a
+    /// round records them (seeded pools are never empty), so a re-lock after an
+    /// unpause can't overwrite the closing odds.
+    pub fn lock(&mut self) {
+        self.status = MarketStatus::Locked;
+        if self.locked_yes_reserve == 0 {
+            self.locked_yes_reserve = self.total_yes_shares;
+            self.locked_no_reserve = self.total_no_shares;
+        }
+    }
+
+    /// (YES, NO) reserves at close: the frozen values once locked, else live
+    pub fn closing_reserves(&self) -> (u64, u64) {
+        if self.locked_yes_reserve == 0 {
+            (self.total_yes_shares, self.total_no_shares)
+        } else {
+            (self.locked_yes_reserve, self.locked_no_reserve)
+        }
+    }
+
+    /// Appends the current reserves to the price history, unless the latest
+    /// checkpoint is less than PRICE_CHECKPOINT_INTERVAL_SLOTS old.
+    pub fn record_checkpoint(&mut self, slot: u64) {
+        let head = self.price_history_head as usize % PRICE_HISTORY_LEN;
+        let last = self.price_history[(head + PRICE_HISTORY_LEN - 1) % PRICE_HISTORY_LEN];
+        if last.slot != 0 && slot < last.slot.saturating_add(PRICE_CHECKPOINT_INTERVAL_SLOTS) {
+            return;
+        }
+        self.price_history[head] = PriceCheckpoint {
+            slot,
+            yes_reserve: self.total_yes_shares,
+            no_reserve: self.total_no_shares,
+        };
+        self.price_history_head = ((head + 1) % PRICE_HISTORY_LEN) as u8;
+    }
+
+    /// Recorded checkpoints, oldest first
+    pub fn ordered_price_history(&self) -> Vec<PriceCheckpoint> {
+        let head = self.price_history_head as usize % PRICE_HISTORY_LEN;
+        (0..PRICE_HISTORY_LEN)
+            .map(|i| self.price_history[(head + i) % PRICE_HISTORY_LEN])
+            .filter(|c| c.slot != 0)
+            .collect()
+    }
+
+    /// Exponent Pyth prints are rescaled to before use. Fixed at creation; a
+    /// market created without a feed adopts the exponent of the first print read.
+    pub fn pyth_exponent(&mut self, feed_exponent: i32) -> i32 {
+        *self.oracle_exponent.get_or_insert(feed_exponent)
+    }
+
+    /// Fee charged on bets and cancels at `now`: `fee_bps` until `fee_ramp_secs`
+    /// before lock, then rising linearly to `max_fee_bps` at lock, so late
+    /// informed trades pay more
+    pub fn fee_bps_at(&self, now: i64) -> u16 {
+        let ramp_start = self.lock_timestamp.saturating_sub(self.fee_ramp_secs);
+        if self.fee_ramp_secs <= 0 || self.max_fee_bps <= self.fee_bps || now <= ramp_start {
+            return self.fee_bps;
+        }
+        let elapsed = now.min(self.lock_timestamp) - ramp_start;
+        let rise = (self.max_fee_bps - self.fee_bps) as i64 * elapsed / self.fee_ramp_secs;
+        self.fee_bps + rise as u16
+    }
+
+    /// True once the market may be resolved by its oracle: from `end_slot` /
+    /// `end_epoch` when either is set, else from `end_timestamp`
+    pub fn end_reached(&self, clock: &Clock) -> bool {
+        if self.end_slot.is_none() && self.end_epoch.is_none() {
+            return clock.unix_timestamp >= self.end_timestamp;
+        }
+        self.end_slot.is_none_or(|slot| clock.slot >= slot)
+            && self.end_epoch.is_none_or(|epoch| clock.epoch >= epoch)
+    }
+
+    /// Seeds the CPMM pools with `liquidity` so YES opens at `initial_prob_bps`
+    /// (YES price = NO reserve / both reserves). The reserves add up to twice
+    /// the liquidity at any prior, as equal pools always have.
+    pub fn seed_reserves(&mut self, liquidity: u64) {
+        let both = liquidity as u128 * 2;
+        let no_reserve = (both * self.initial_prob_bps as u128 / 10000) as u64;
+        self.total_no_shares = no_reserve;
+        self.total_yes_shares = (both - no_reserve as u128) as u64;
+    }
+
+    /// Live (YES, NO) reserves with the virtual liquidity added, as trades are
+    /// priced. Deeper virtual pools move less per bet; only the real reserves
+    /// change.
+    pub fn virtual_reserves(&self) -> (u64, u64) {
+        (
+            self.total_yes_shares.saturating_add(self.virtual_liquidity),
+            self.total_no_shares.saturating_add(self.virtual_liquidity),
+        )
+    }
+
+    /// (YES, NO) reserves a bet placed in `slot` is priced against. With
+    /// slot-start pricing every bet in a slot sees the reserves as they were
+    /// before its first bet, so reordering bets within a slot changes nothing;
+    /// the live reserves still move with each bet. See `slot_flow_before` for
+    /// how same-side bets in the slot stack up on the snapshot.
+    pub fn pricing_reserves(&mut self, slot: u64) -> (u64, u64) {
+        if !self.slot_start_pricing {
+            return self.virtual_reserves();
+        }
+        if self.slot_start_slot != slot {
+            self.slot_start_reserves = [self.total_yes_shares, self.total_no_shares];
+            self.slot_start_slot = slot;
+            self.slot_flow = [0; 2];
+        }
+        (
+            self.slot_start_reserves[0].saturating_add(self.virtual_liquidity),
+            self.slot_start_reserves[1].saturating_add(self.virtual_liquidity),
+        )
+    }
+
+    /// Collateral already bet on `outcome` in the current slot (0 without
+    /// slot-start pricing), then adds `amount` to it. A bet is priced as the
+    /// next stretch of the snapshot curve after this flow, so splitting an
+    /// order within a slot buys exactly what one bet of the total would, while
+    /// bets on the other side still don't move its price.
+    pub fn slot_flow_before(&mut self, outcome: &Outcome, amount: u64) -> u64 {
+        if !self.slot_start_pricing {
+            return 0;
+        }
+        let side = if *outcome == Outcome::Yes { 0 } else { 1 };
+        let prior = self.slot_flow[side];
+        self.slot_flow[side] = prior.saturating_add(amount);
+        prior
+    }
+
+    /// True for a child created by `fork_market`
+    pub fn is_fork_child(&self) -> bool {
+        self.parent_market != Pubkey::default()
+    }
+
+    /// Outstanding (YES, NO) supply claims are paid against. A fork child mints
+    /// its shares as holders migrate, so it pays against the parent's supply
+    /// at the fork, less what has already been redeemed.
+    pub fn claim_supplies(&self, yes_supply: u64, no_supply: u64) -> (u64, u64) {
+        if !self.is_fork_child() {
+            return (yes_supply, no_supply);
+        }
+        match self.resolved_outcome {
+            Some(Outcome::Yes) => (self.fork_yes_supply.saturating_sub(self.claimed_weight), no_supply),
+            Some(Outcome::No) => (yes_supply, self.fork_no_supply.saturating_sub(self.claimed_weight)),
+            _ => (yes_supply, no_supply),
+        }
+    }
+
+    /// Platform admin, or one of this market's designated operators
+    pub fn is_operator(&self, key: &Pubkey, platform_admin: &Pubkey) -> bool {
+        key == platform_admin || self.market_admins.contains(key)
+    }
+
+    /// May resolve this market: one of its operators, or a platform Resolver
+    pub fn is_resolver(&self, key: &Pubkey, platform: &PlatformConfig) -> bool {
+        self.is_operator(key, &platform.admin) || platform.has_role(key, Role::Resolver)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
@@ -60,6 +376,8 @@ pub enum MarketStatus {
     Disputed,
     Cancelled,
     Paused,
+    /// Split into per-outcome children by `fork_market`; settles through them
+    Forked,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
@@ -77,6 +395,32 @@ pub enum Outcome {
     Yes,
     No,
     Invalid,
+    /// Partial credit: YES and NO holders split the pool (see `Market::split_yes_bps`)
+    Split,
+}
+
+/// How bets are turned into shares
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace, Debug, Default)]
+pub enum PricingMode {
+    /// Shares bought from the YES/NO reserves at the constant-product price
+    #[default]
+    Cpmm,
+    /// One share per unit staked into the outcome's bucket; winners split the
+    /// whole pot pro-rata. The reserves don't move.
+    Parimutuel,
+}
+
+/// Recipient of the rounding residue pro-rata payouts leave behind. The last
+/// claimer is paid the remaining pool; under any other setting it gets its
+/// exact share and `sweep_vault_surplus` routes the residue.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace, Debug, Default)]
+pub enum DustRecipient {
+    /// Stays in the vault and re-seeds the next series (recurring markets only)
+    Vault,
+    Treasury,
+    #[default]
+    LastClaimer,
+    Creator,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
@@ -84,4 +428,112 @@ pub enum OracleSource {
     Pyth,
     Switchboard,
     ManualAdmin,
+    ProgramState,
+    Chainlink,
+    /// Outcome signed off-chain by the attester stored in `oracle_feed`
+    SignedAttestation,
+}
+
+/// Last Pyth print read for a market. Only reused within the slot it was read
+/// in, so a later step can't resolve on a print that has since moved.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct OracleCache {
+    pub price: i64,
+    pub conf: u64,
+    pub publish_time: i64,
+    pub posted_slot: u64,       // slot the print was posted to the feed account
+    pub slot: u64,              // 0 = empty
+}
+
+impl OracleCache {
+    pub fn is_valid_at(&self, slot: u64) -> bool {
+        self.slot != 0 && self.slot == slot
+    }
+}
+
+/// Pool reserves after the first bet in a checkpoint interval
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace, Debug)]
+pub struct PriceCheckpoint {
+    pub slot: u64,              // 0 = empty
+    pub yes_reserve: u64,
+    pub no_reserve: u64,
+}
+
+/// Fees charged on a market and the destinations credited with them. Every
+/// fee is split in full, so the destinations always sum to `fees_accrued`
+/// (see verify_fee_accounting).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace, Debug)]
+pub struct FeeLedger {
+    pub fees_accrued: u64,
+    pub treasury: u64,
+    pub creator: u64,
+    pub referrer: u64,
+    pub lp: u64,
+    pub insurance: u64,
+}
+
+impl FeeLedger {
+    /// Sum of all destination counters (None on overflow)
+    pub fn credited(&self) -> Option<u64> {
+        [self.creator, self.referrer, self.lp, self.insurance]
+            .iter()
+            .try_fold(self.treasury, |sum, x| sum.checked_add(*x))
+    }
+}
+
+/// Record of a market's resolution: the oracle account and value used, when
+/// that value was published (and in which slot), and who resolved it when.
+/// Price, publish time and feed slot are 0 for ManualAdmin and timeout resolutions.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct ResolutionProof {
+    pub oracle_feed: Pubkey,
+    pub resolution_price: i64,
+    pub publish_time: i64,
+    pub feed_slot: u64,
+    pub resolved_at: i64,
+    pub resolver: Pubkey,
+}
+
+/// How `value` read from the watched account compares to `oracle_threshold`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace, Debug)]
+pub enum Comparator {
+    GreaterThan,
+    GreaterOrEqual,
+    LessThan,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparator {
+    pub fn holds(&self, value: i64, threshold: i64) -> bool {
+        match self {
+            Comparator::GreaterThan => value > threshold,
+            Comparator::GreaterOrEqual => value >= threshold,
+            Comparator::LessThan => value < threshold,
+            Comparator::LessOrEqual => value <= threshold,
+            Comparator::Equal => value == threshold,
+            Comparator::NotEqual => value != threshold,
+        }
+    }
+}
+
+/// Scalar market bounds, in the market's oracle exponent. A resolution price
+/// at or below `lower` pays everything to NO (SHORT), at or above `upper`
+/// everything to YES (LONG), and in between splits the pool linearly.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+pub struct ScalarRange {
+    pub lower: i64,
+    pub upper: i64,
+}
+
+/// Cross-program oracle: resolve YES when the little-endian integer of `size`
+/// bytes at `offset` in `oracle_feed` (owned by `program`) satisfies
+/// `comparator` against `oracle_threshold`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+pub struct ProgramStateQuery {
+    pub program: Pubkey,
+    pub offset: u32,
+    pub size: u8,               // 1, 2, 4 (unsigned) or 8 (signed i64)
+    pub comparator: Comparator,
 }
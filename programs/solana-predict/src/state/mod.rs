@@ -3,9 +3,29 @@ pub mod market;
 pub mod position;
 pub mod round;
 pub mod dispute;
+pub mod creator;
+pub mod category_policy;
+pub mod liquidity;
+pub mod approvals;
+pub mod resolution_proposal;
+pub mod committee;
+pub mod price_snapshots;
+pub mod feed_registry;
+pub mod referral;
+pub mod user_tier;
 
 pub use platform::*;
 pub use market::*;
 pub use position::*;
 pub use round::*;
 pub use dispute::*;
+pub use creator::*;
+pub use category_policy::*;
+pub use liquidity::*;
+pub use approvals::*;
+pub use resolution_proposal::*;
+pub use committee::*;
+pub use price_snapshots::*;
+pub use feed_registry::*;
+pub use referral::*;
+pub use user_tier::*;
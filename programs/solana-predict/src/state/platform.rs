@@ -1,17 +1,197 @@
 use anchor_lang::prelude::*;
+use crate::errors::PredictError;
+use crate::state::approvals::{ActionProposal, MAX_APPROVERS};
 
 #[account]
 pub struct PlatformConfig {
     pub admin: Pubkey,              // 32
     pub fee_bps: u16,               // 2
-    pub treasury: Pubkey,           // 32
+    pub treasury: Pubkey,           // 32 (program-owned fee account for collateral_mint)
     pub paused: bool,               // 1
     pub total_markets: u64,         // 8
     pub collateral_mint: Pubkey,    // 32 (wSOL or other SPL mint)
     pub dispute_bond_lamports: u64, // 8
     pub bump: u8,                   // 1
+    pub min_threshold_distance_bps: u16, // 2 (0 = disabled)
+    pub creation_cooldown_secs: i64,     // 8 (0 = disabled)
+    pub default_max_slippage_bps: u16,   // 2 (0 = disabled)
+    pub resolution_grace_secs: i64,      // 8 (0 = disabled)
+    pub active_market_count: u64,        // 8 (Pending/Active/Locked, not yet resolved)
+    pub max_active_markets: u64,         // 8 (0 = unlimited)
+    pub resolution_timeout_secs: i64,    // 8 (after end_timestamp, see resolve_on_timeout)
+    pub max_backdate_secs: i64,          // 8 (0 = disabled)
+    pub max_dispute_voting_secs: i64,    // 8 (cap on a dispute's total voting period)
+    pub min_slots_before_resolve: u64,   // 8 (oracle markets: slots after the last bet, 0 = disabled)
+    pub min_bettors_for_resolution: u64, // 8 (fewer distinct bettors resolves Invalid, 0 = disabled)
+    pub shutdown: bool,                  // 1 (emergency shutdown: pro-rata refunds, no resolution)
+    pub forking_enabled: bool,           // 1 (contested disputes may be settled with fork_market)
+    pub min_claimable_dust: u64,         // 8 (positions owed less may be forfeited after the claim window, 0 = disabled)
+    pub claim_window_secs: i64,          // 8 (after resolution, before dust positions may be forfeited)
+    pub total_tvl: u64,                  // 8 (sum of every market's total_collateral)
+    pub max_platform_tvl: u64,           // 8 (bets may not push total_tvl above this, 0 = unlimited)
+    pub close_grace_secs: i64,           // 8 (mark_for_close to close_market, 0 = single-step close)
+    pub min_fee_absolute: u64,           // 8 (floor on each bet's fee, 0 = bps fee only)
+    pub pending_admin: Pubkey,           // 32 (proposed by propose_admin, default = none)
+    pub approvers: [Pubkey; MAX_APPROVERS], // 32 * MAX_APPROVERS (default = empty slot)
+    pub approval_threshold: u8,          // 1 (approvals an AdminAction needs, 0 = admin acts alone)
+    pub proposal_count: u64,             // 8 (next ActionProposal id)
+    pub role_grants: [RoleGrant; MAX_ROLE_GRANTS], // 33 * MAX_ROLE_GRANTS (default key = empty slot)
+    pub resolution_bond_lamports: u64,   // 8 (bond posted with propose_resolution)
+    pub challenge_window_secs: i64,      // 8 (after propose_resolution, before finalize_resolution)
+    pub referral_fee_bps: u16,           // 2 (slice of the platform's fee paid to a bet's referrer, 0 = disabled)
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS], // 10 * MAX_FEE_TIERS (ascending by min_volume, default = empty slot)
 }
 
+/// Upper bound on keys holding roles (keeps PlatformConfig::LEN fixed)
+pub const MAX_ROLE_GRANTS: usize = 8;
+
+/// Upper bound on volume fee tiers (keeps PlatformConfig::LEN fixed)
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Fee discount for bettors whose lifetime volume reaches `min_volume`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Debug)]
+pub struct FeeTier {
+    pub min_volume: u64,
+    pub discount_bps: u16,       // off the market's fee
+}
+
+/// Delegated slice of the admin's authority. The admin holds every role.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Role {
+    /// pause/unpause the platform and any market
+    Pauser,
+    /// create, restart, migrate, close and clean up markets
+    Operator,
+    /// resolve ManualAdmin markets and settle, extend or fork disputes
+    Resolver,
+}
+
+impl Role {
+    pub fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+/// Roles held by one key, as a bitmask of Role::bit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RoleGrant {
+    pub key: Pubkey,
+    pub roles: u8,
+}
+
+/// Default wait past end_timestamp before resolve_on_timeout may apply a default outcome
+pub const DEFAULT_RESOLUTION_TIMEOUT_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Default time holders have to claim before dust positions may be forfeited
+pub const DEFAULT_CLAIM_WINDOW_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Default cap on a dispute's voting period, extensions included
+pub const DEFAULT_MAX_DISPUTE_VOTING_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Default time a proposed resolution may be challenged
+pub const DEFAULT_CHALLENGE_WINDOW_SECS: i64 = 24 * 60 * 60;
+
 impl PlatformConfig {
-    pub const LEN: usize = 8 + 32 + 2 + 32 + 1 + 8 + 32 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 2 + 32 + 1 + 8 + 32 + 8 + 1 + 2 + 8 + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 32 * MAX_APPROVERS + 1 + 8 + 33 * MAX_ROLE_GRANTS + 8 + 8 + 2 + 10 * MAX_FEE_TIERS;
+
+    /// Collateral entering a market's pool
+    pub fn credit_tvl(&mut self, amount: u64) -> Result<()> {
+        self.total_tvl = self.total_tvl.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Collateral leaving a market's pool. Saturates: markets created before
+    /// TVL was tracked were never credited.
+    pub fn debit_tvl(&mut self, amount: u64) {
+        self.total_tvl = self.total_tvl.saturating_sub(amount);
+    }
+
+    /// The admin, or a key granted `role`
+    pub fn has_role(&self, key: &Pubkey, role: Role) -> bool {
+        *key == self.admin
+            || self.role_grants.iter().any(|g| g.key == *key && g.roles & role.bit() != 0)
+    }
+
+    /// Adds (`granted`) or removes `role` for `key`. A key left with no roles
+    /// frees its slot.
+    pub fn set_role(&mut self, key: Pubkey, role: Role, granted: bool) -> Result<()> {
+        require!(key != Pubkey::default(), PredictError::Unauthorized);
+        let slot = match self.role_grants.iter().position(|g| g.key == key) {
+            Some(i) => i,
+            None if !granted => return Ok(()),
+            None => self.role_grants.iter()
+                .position(|g| g.key == Pubkey::default())
+                .ok_or(PredictError::RoleRegistryFull)?,
+        };
+        let grant = &mut self.role_grants[slot];
+        grant.key = key;
+        if granted {
+            grant.roles |= role.bit();
+        } else {
+            grant.roles &= !role.bit();
+        }
+        if grant.roles == 0 {
+            *grant = RoleGrant::default();
+        }
+        Ok(())
+    }
+
+    /// True once sensitive admin actions need approvals (see execute_action)
+    pub fn approvals_enabled(&self) -> bool {
+        self.approval_threshold > 0
+    }
+
+    pub fn is_approver(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.approvers.contains(key)
+    }
+
+    /// Approvals on `proposal` from keys still in the approver set
+    pub fn approval_count(&self, proposal: &ActionProposal) -> usize {
+        proposal.approved_by.iter().filter(|k| self.is_approver(k)).count()
+    }
+
+    /// Discount (bps off the fee) for a bettor with `volume` behind them: the
+    /// highest tier reached
+    pub fn tier_discount_bps(&self, volume: u64) -> u16 {
+        self.fee_tiers
+            .iter()
+            .filter(|tier| tier.discount_bps > 0 && volume >= tier.min_volume)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Replaces the fee tiers. Thresholds must rise and discounts must not
+    /// fall from one tier to the next; an empty list turns discounts off.
+    pub fn set_fee_tiers(&mut self, tiers: &[FeeTier]) -> Result<()> {
+        require!(tiers.len() <= MAX_FEE_TIERS, PredictError::InvalidFeeTiers);
+        for (i, tier) in tiers.iter().enumerate() {
+            require!(tier.discount_bps > 0 && tier.discount_bps <= 10000, PredictError::InvalidFeeTiers);
+            if i > 0 {
+                let prev = tiers[i - 1];
+                require!(
+                    tier.min_volume > prev.min_volume && tier.discount_bps >= prev.discount_bps,
+                    PredictError::InvalidFeeTiers
+                );
+            }
+        }
+        self.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+        self.fee_tiers[..tiers.len()].copy_from_slice(tiers);
+        Ok(())
+    }
+
+    /// Replaces the approver set. Default keys are ignored; `threshold` must be
+    /// reachable, and 0 hands sensitive actions back to the admin alone.
+    pub fn set_approvers(&mut self, approvers: &[Pubkey], threshold: u8) -> Result<()> {
+        let keys: Vec<Pubkey> = approvers.iter().copied().filter(|k| *k != Pubkey::default()).collect();
+        require!(keys.len() <= MAX_APPROVERS, PredictError::InvalidApprovers);
+        require!(threshold as usize <= keys.len(), PredictError::InvalidApprovers);
+        for (i, key) in keys.iter().enumerate() {
+            require!(!keys[..i].contains(key), PredictError::InvalidApprovers);
+        }
+        self.approvers = [Pubkey::default(); MAX_APPROVERS];
+        self.approvers[..keys.len()].copy_from_slice(&keys);
+        self.approval_threshold = threshold;
+        Ok(())
+    }
 }
@@ -1,4 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::state::market::Outcome;
+
+/// `round` of a position forfeited by forfeit_dust_positions: never a live round
+pub const FORFEITED_ROUND: u64 = u64::MAX;
 
 #[account]
 pub struct UserPosition {
@@ -10,8 +14,32 @@ pub struct UserPosition {
     pub total_claimed: u64,
     pub last_bet_timestamp: i64,
     pub bump: u8,
+    pub round: u64,              // market.current_round this position belongs to
+    pub yes_account: Pubkey,     // cached YES ATA (set by init_position, default otherwise)
+    pub no_account: Pubkey,      // cached NO ATA
+    pub yes_cost_basis: u64,     // collateral paid (fees included) for the YES shares still held
+    pub no_cost_basis: u64,      // same for NO; cost_basis / shares is the average entry price
+    pub total_cost: u64,         // collateral paid for every bet this round
+    pub total_refunded: u64,     // collateral returned by cancels this round
 }
 
 impl UserPosition {
-    pub const LEN: usize = 8 + 32 + 32 + 8 * 4 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 8 * 4 + 8 + 1 + 8 + 32 * 2 + 8 * 4;
+
+    pub fn has_cached_accounts(&self) -> bool {
+        self.yes_account != Pubkey::default() && self.no_account != Pubkey::default()
+    }
+
+    /// Outcome whose cached ATA is `share_account`, if any
+    pub fn cached_outcome(&self, share_account: &Pubkey) -> Option<Outcome> {
+        if !self.has_cached_accounts() {
+            None
+        } else if *share_account == self.yes_account {
+            Some(Outcome::Yes)
+        } else if *share_account == self.no_account {
+            Some(Outcome::No)
+        } else {
+            None
+        }
+    }
 }
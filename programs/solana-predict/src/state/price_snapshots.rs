@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+/// Ring size of a market's TWAP observations
+pub const MAX_PRICE_SNAPSHOTS: usize = 32;
+
+/// One Pyth print recorded by record_price_snapshot, in the market's exponent
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct PriceSnapshot {
+    pub price: i64,
+    pub observed_at: i64, // unix time of the crank (0 = empty)
+}
+
+/// Pyth observations over a market's final window, averaged at resolution
+#[account]
+pub struct PriceSnapshots {
+    pub market: Pubkey,
+    pub snapshots: [PriceSnapshot; MAX_PRICE_SNAPSHOTS],
+    pub head: u8, // next ring slot to write
+    pub bump: u8,
+}
+
+impl PriceSnapshots {
+    pub const LEN: usize = 8 + 32 + 16 * MAX_PRICE_SNAPSHOTS + 1 + 1;
+
+    pub fn latest(&self) -> PriceSnapshot {
+        let head = self.head as usize % MAX_PRICE_SNAPSHOTS;
+        self.snapshots[(head + MAX_PRICE_SNAPSHOTS - 1) % MAX_PRICE_SNAPSHOTS]
+    }
+
+    pub fn push(&mut self, snapshot: PriceSnapshot) {
+        let head = self.head as usize % MAX_PRICE_SNAPSHOTS;
+        self.snapshots[head] = snapshot;
+        self.head = ((head + 1) % MAX_PRICE_SNAPSHOTS) as u8;
+    }
+
+    /// Recorded snapshots, oldest first
+    pub fn ordered(&self) -> Vec<PriceSnapshot> {
+        let head = self.head as usize % MAX_PRICE_SNAPSHOTS;
+        (0..MAX_PRICE_SNAPSHOTS)
+            .map(|i| self.snapshots[(head + i) % MAX_PRICE_SNAPSHOTS])
+            .filter(|s| s.observed_at != 0)
+            .collect()
+    }
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// A registered referrer. Bets naming it route a slice of the platform's fee
+/// to `escrow`, which only the referrer can drain.
+#[account]
+pub struct ReferralAccount {
+    pub referrer: Pubkey,
+    pub escrow: Pubkey,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl ReferralAccount {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::market::Outcome;
+
+/// Outcome posted by anyone for a ManualAdmin market (see propose_resolution).
+/// Holds the proposer's bond until the market settles.
+#[account]
+pub struct ResolutionProposal {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub outcome: Outcome,
+    pub bond_amount: u64,       // lamports, held in this account
+    pub proposed_at: i64,
+    pub challenge_ends_at: i64, // finalize_resolution may apply the outcome from here
+    pub challenged: bool,       // escalated to a dispute by challenge_resolution
+    pub bump: u8,
+}
+
+impl ResolutionProposal {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 1 + 1;
+}
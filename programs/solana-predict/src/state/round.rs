@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::Outcome;
 
 #[account]
 pub struct RoundState {
@@ -7,17 +8,20 @@ pub struct RoundState {
     pub status: RoundStatus,
     pub lock_price: Option<i64>,
     pub close_price: Option<i64>,
-    pub total_yes: u64,
+    pub total_yes: u64,         // net stakes placed via place_round_bet
     pub total_no: u64,
     pub start_ts: i64,
     pub lock_ts: i64,
     pub end_ts: i64,
     pub oracle_round_id: Option<u64>,
+    /// Winning side once resolved: YES up, NO down, INVALID flat
+    pub outcome: Option<Outcome>,
+    pub unique_bettors: u64,    // distinct wallets with a RoundPosition
     pub bump: u8,
 }
 
 impl RoundState {
-    pub const LEN: usize = 8 + 32 + 8 + 1 + 9 + 9 + 8 * 3 + 8 * 3 + 9 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 9 + 9 + 8 * 3 + 8 * 3 + 9 + 2 + 8 + 1;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
@@ -27,3 +31,19 @@ pub enum RoundStatus {
     Resolved,
     Cancelled,
 }
+
+/// A user's stakes in one round of a recurring market, settled parimutuel-style
+/// against the round's totals by `claim_round_payout`
+#[account]
+pub struct RoundPosition {
+    pub round: Pubkey,
+    pub user: Pubkey,
+    pub yes_amount: u64,
+    pub no_amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+impl RoundPosition {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 1;
+}
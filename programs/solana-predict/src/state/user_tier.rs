@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// A bettor's lifetime betting volume, which sets their fee tier (see
+/// `PlatformConfig::fee_tiers`). Only bets placed with it passed count.
+#[account]
+pub struct UserTier {
+    pub user: Pubkey,
+    pub lifetime_volume: u64,   // gross collateral bet
+    pub bump: u8,
+}
+
+impl UserTier {
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::errors::PredictError;
+
+/// Rejects frozen token accounts up front. A freezable collateral mint (e.g.
+/// USDC) can freeze the vault or a user account, and the token program would
+/// otherwise fail the transfer with an opaque error.
+pub fn require_not_frozen(accounts: &[&TokenAccount]) -> Result<()> {
+    require!(accounts.iter().all(|a| !a.is_frozen()), PredictError::AccountFrozen);
+    Ok(())
+}
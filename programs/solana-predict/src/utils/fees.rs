@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictError;
+use crate::state::FeeLedger;
+
+/// Fee a bet of `gross_amount` incurs and where it goes. Shared by `place_bet`
+/// and `quote_fee` so a quote always matches what is charged.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FeeBreakdown {
+    /// Total fee taken from the bet
+    pub total_fee: u64,
+    /// Share sent to the platform treasury
+    pub platform_fee: u64,
+    /// Share paid to the market creator
+    pub creator_fee: u64,
+    /// Share paid to the referrer
+    pub referrer_fee: u64,
+    /// Share left in the pool for liquidity providers
+    pub lp_fee: u64,
+    /// Share set aside for the insurance fund
+    pub insurance_fee: u64,
+    /// Collateral sent to the vault (gross amount less the fee)
+    pub net_amount: u64,
+}
+
+/// Bet fee at `fee_bps`, rounded up so micro-bets can't bypass it, and never
+/// below the platform's `min_fee`. `creator_fee_bps` of the bet (rounded down)
+/// is carved out of the fee for the creator; of what is left, `lp_fee_bps`
+/// stays in the pool for LPs, a referred bet pays `referral_fee_bps` of the
+/// remainder to its referrer and the rest goes to the treasury.
+pub fn bet_fee_breakdown(
+    gross_amount: u64,
+    fee_bps: u16,
+    creator_fee_bps: u16,
+    lp_fee_bps: u16,
+    min_fee: u64,
+    referral_fee_bps: Option<u16>,
+) -> Result<FeeBreakdown> {
+    let total_fee = bps_fee(gross_amount, fee_bps).max(min_fee);
+    // A bet that can't cover the floor is too small
+    let net_amount = gross_amount.checked_sub(total_fee).ok_or(PredictError::BelowMinBet)?;
+    let creator_fee = ((gross_amount as u128 * creator_fee_bps as u128 / 10000) as u64).min(total_fee);
+    let lp_fee = lp_share(total_fee - creator_fee, lp_fee_bps);
+    let platform_share = total_fee - creator_fee - lp_fee;
+    let referrer_fee = referral_fee_bps
+        .map_or(0, |bps| (platform_share as u128 * bps.min(10000) as u128 / 10000) as u64);
+    Ok(FeeBreakdown {
+        total_fee,
+        platform_fee: platform_share - referrer_fee,
+        creator_fee,
+        referrer_fee,
+        lp_fee,
+        net_amount,
+        ..FeeBreakdown::default()
+    })
+}
+
+/// `fee_bps` of `amount`, rounded up so micro-amounts can't bypass the fee.
+/// Used for both the bet fee and the cancel_bet exit fee.
+pub fn bps_fee(amount: u64, fee_bps: u16) -> u64 {
+    (amount as u128 * fee_bps as u128).div_ceil(10000) as u64
+}
+
+/// Part of `fee` kept in the pool for LPs at `lp_fee_bps`
+pub fn lp_share(fee: u64, lp_fee_bps: u16) -> u64 {
+    (fee as u128 * lp_fee_bps.min(10000) as u128 / 10000) as u64
+}
+
+/// Books a charged fee against the market's ledger: the total, and each
+/// destination it was credited to.
+pub fn record_fees(ledger: &mut FeeLedger, fees: &FeeBreakdown) -> Result<()> {
+    let add = |counter: u64, amount: u64| counter.checked_add(amount).ok_or(PredictError::MathOverflow);
+    ledger.fees_accrued = add(ledger.fees_accrued, fees.total_fee)?;
+    ledger.treasury = add(ledger.treasury, fees.platform_fee)?;
+    ledger.creator = add(ledger.creator, fees.creator_fee)?;
+    ledger.referrer = add(ledger.referrer, fees.referrer_fee)?;
+    ledger.lp = add(ledger.lp, fees.lp_fee)?;
+    ledger.insurance = add(ledger.insurance, fees.insurance_fee)?;
+    Ok(())
+}
@@ -55,3 +55,118 @@ use anchor_lang::prelude::*;
         
         Some(shares_from_swap as u64)
     }
+
+    /// Integer square root (floor) via Newton's method
+    pub fn isqrt(n: u128) -> u128 {
+        if n < 2 {
+            return n;
+        }
+        let mut x = n;
+        let mut y = x.div_ceil(2);
+        while y < x {
+            x = y;
+            y = (x + n / x) / 2;
+        }
+        x
+    }
+
+    /// Net collateral needed to push an outcome's implied price up by `move_bps`
+    /// (percentage points in bps, e.g. 100 = 1pp).
+    ///
+    /// `pool_out` is the reserve of the outcome being bought, `pool_in` the opposite
+    /// reserve (price = pool_in / (pool_out + pool_in)). With k fixed, the target
+    /// price p' is reached when new_in^2 = k * p' / (1 - p').
+    /// Returns None if the target price is >= 100% or the pools are empty.
+    pub fn depth_to_move_price(pool_out: u64, pool_in: u64, move_bps: u64) -> Option<u64> {
+        const SCALE: u128 = 1_000_000; // price precision (ppm)
+        let pool_out = pool_out as u128;
+        let pool_in = pool_in as u128;
+        let total = pool_out.checked_add(pool_in)?;
+        if pool_out == 0 || pool_in == 0 {
+            return None;
+        }
+        let k = pool_out.checked_mul(pool_in)?;
+        let price = pool_in.checked_mul(SCALE)? / total;
+        let target = price.checked_add((move_bps as u128).checked_mul(SCALE / 10000)?)?;
+        if target >= SCALE {
+            return None;
+        }
+        let new_in_sq = match k.checked_mul(target) {
+            Some(v) => v / (SCALE - target),
+            None => (k / (SCALE - target)).checked_mul(target)?,
+        };
+        let new_in = isqrt(new_in_sq);
+        let depth = new_in.saturating_sub(pool_in);
+        u64::try_from(depth).ok()
+    }
+
+    /// Platform-wide display convention: event amounts are also reported at 6 decimals
+    pub const DISPLAY_DECIMALS: u8 = 6;
+
+    /// Normalizes a raw token amount with `decimals` to DISPLAY_DECIMALS (truncating).
+    pub fn scale_to_display(amount: u64, decimals: u8) -> u64 {
+        let amount = amount as u128;
+        let scaled = if decimals >= DISPLAY_DECIMALS {
+            match 10u128.checked_pow((decimals - DISPLAY_DECIMALS) as u32) {
+                Some(div) => amount / div,
+                None => 0,
+            }
+        } else {
+            amount.saturating_mul(10u128.pow((DISPLAY_DECIMALS - decimals) as u32))
+        };
+        scaled.min(u64::MAX as u128) as u64
+    }
+
+    /// Fixed-point scale for `Market::payout_rate`
+    pub const PAYOUT_RATE_SCALE: u128 = 1_000_000_000;
+
+    /// Converts a collateral amount into payout-mint units at the market's fixed rate.
+    pub fn to_payout_amount(collateral_amount: u64, payout_rate: u64) -> Option<u64> {
+        let amount = (collateral_amount as u128)
+            .checked_mul(payout_rate as u128)?
+            / PAYOUT_RATE_SCALE;
+        u64::try_from(amount).ok()
+    }
+
+    /// Pro-rata share of `pool` for `shares` out of `supply` outstanding (0 if none outstanding).
+    pub fn pro_rata_payout(shares: u64, pool: u64, supply: u64) -> u64 {
+        if supply == 0 {
+            return 0;
+        }
+        (shares as u128 * pool as u128 / supply as u128) as u64
+    }
+
+    /// Fraction of full Kelly used by `fractional_kelly_size`, in bps (quarter Kelly)
+    pub const KELLY_FRACTION_BPS: u128 = 2500;
+
+    /// Suggested gross bet for a bettor who believes the outcome is `edge_bps`
+    /// more likely than its implied price (pools as in `depth_to_move_price`).
+    ///
+    /// Full Kelly for a binary share at price p with belief q = p + edge stakes
+    /// (q - p) / (1 - p) of the bankroll; this stakes KELLY_FRACTION_BPS of that.
+    /// The stake is capped at the bet that moves the price to q, past which the
+    /// CPMM slippage leaves no edge, grossed up by `fee_bps` as in place_bet.
+    pub fn fractional_kelly_size(pool_out: u64, pool_in: u64, edge_bps: u64, bankroll: u64, fee_bps: u16) -> Option<u64> {
+        const SCALE: u128 = 1_000_000; // price precision (ppm)
+        let total = (pool_out as u128).checked_add(pool_in as u128)?;
+        if pool_out == 0 || pool_in == 0 || edge_bps == 0 {
+            return Some(0);
+        }
+        let price = (pool_in as u128).checked_mul(SCALE)? / total;
+        let edge = (edge_bps as u128).checked_mul(SCALE / 10000)?.min(SCALE - price);
+        let kelly = (bankroll as u128)
+            .checked_mul(edge)?
+            .checked_mul(KELLY_FRACTION_BPS)?
+            / (SCALE - price)
+            / 10000;
+
+        // No slippage cap when the believed probability is 100%
+        let size = match depth_to_move_price(pool_out, pool_in, edge_bps) {
+            Some(depth) => {
+                let gross_cap = (depth as u128).checked_mul(10000)? / (10000 - fee_bps.min(9999) as u128);
+                kelly.min(gross_cap)
+            }
+            None => kelly,
+        };
+        u64::try_from(size.min(bankroll as u128)).ok()
+    }
@@ -1,3 +1,9 @@
 pub mod math;
+pub mod oracle;
+pub mod fees;
+pub mod accounts;
 
 pub use math::*;
+pub use oracle::*;
+pub use fees::*;
+pub use accounts::*;
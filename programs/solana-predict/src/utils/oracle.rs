@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::ed25519_program;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::errors::PredictError;
+use crate::state::{OracleCache, Outcome, PriceSnapshot};
+
+/// Maximum age (seconds) of a Pyth price before it is considered stale
+pub const MAX_ORACLE_STALENESS_SECS: i64 = 60;
+
+/// Window (seconds) after `lock_ts` in which a round's lock price may be snapshotted
+pub const SNAPSHOT_WINDOW_SECS: i64 = 300;
+
+/// MAX_ORACLE_STALENESS_SECS in ~400ms slots, for markets that end on a slot or epoch
+pub const SNAPSHOT_WINDOW_SLOTS: u64 = 150;
+
+/// Reads the latest price from a Pyth feed, rejecting stale prints.
+/// Returns the raw (un-normalized) price.
+pub fn read_pyth_price(price_feed: &PriceUpdateV2, now: i64) -> Result<i64> {
+    read_pyth_price_with_conf(price_feed, now).map(|(price, _)| price)
+}
+
+/// Same as `read_pyth_price`, also returning the confidence interval.
+pub fn read_pyth_price_with_conf(price_feed: &PriceUpdateV2, now: i64) -> Result<(i64, u64)> {
+    let price_data = &price_feed.price_message;
+    require!(
+        now - price_data.publish_time <= MAX_ORACLE_STALENESS_SECS,
+        PredictError::OracleStale
+    );
+    Ok((price_data.price, price_data.conf))
+}
+
+/// Freshness rule for a print used to resolve at `end_timestamp`. Besides a
+/// print that is fresh now, accepts, while `now <= end + grace_secs`, a print
+/// published at or before `end` that was still fresh at `end` (feed went quiet
+/// right after close).
+pub fn require_fresh_at_close(publish_time: i64, now: i64, end_timestamp: i64, grace_secs: i64) -> Result<()> {
+    let fresh_now = now - publish_time <= MAX_ORACLE_STALENESS_SECS;
+    let fresh_at_close = grace_secs > 0
+        && now <= end_timestamp.saturating_add(grace_secs)
+        && publish_time <= end_timestamp
+        && end_timestamp - publish_time <= MAX_ORACLE_STALENESS_SECS;
+    require!(fresh_now || fresh_at_close, PredictError::OracleStale);
+    Ok(())
+}
+
+/// Rescales a Pyth value published with exponent `from` to exponent `to`,
+/// truncating when `to` is coarser. None on overflow.
+///
+/// Signed feeds (funding rates, spreads) go negative: the magnitude is scaled
+/// and the sign reapplied, so `-x` always rescales to the negation of `x` and
+/// truncation moves a value toward zero, never across it. For a strict
+/// `price > threshold` test this keeps negative prints exact: -250.5 stays
+/// above a threshold of -251.
+pub fn rescale_exponent(value: i128, from: i32, to: i32) -> Option<i128> {
+    let diff = from.checked_sub(to)?;
+    let factor = 10u128.checked_pow(diff.unsigned_abs())?;
+    let magnitude = if diff >= 0 {
+        value.unsigned_abs().checked_mul(factor)?
+    } else {
+        value.unsigned_abs() / factor
+    };
+    let scaled = i128::try_from(magnitude).ok()?;
+    Some(if value < 0 { -scaled } else { scaled })
+}
+
+/// Snapshot of the feed's current print for `OracleCache` reuse within `slot`,
+/// with price and confidence expressed in `exponent` (the market's convention).
+pub fn cache_pyth_print(price_feed: &PriceUpdateV2, slot: u64, exponent: i32) -> Result<OracleCache> {
+    let price_data = &price_feed.price_message;
+    let price = rescale_exponent(price_data.price as i128, price_data.exponent, exponent)
+        .and_then(|p| i64::try_from(p).ok())
+        .ok_or(PredictError::MathOverflow)?;
+    let conf = rescale_exponent(price_data.conf as i128, price_data.exponent, exponent)
+        .and_then(|c| u64::try_from(c).ok())
+        .ok_or(PredictError::MathOverflow)?;
+    Ok(OracleCache {
+        price,
+        conf,
+        publish_time: price_data.publish_time,
+        posted_slot: price_feed.posted_slot,
+        slot,
+    })
+}
+
+/// True when `price` is too close to `threshold` to call: within the market's
+/// `band` or, once a band is configured, within the oracle confidence `conf`.
+/// A zero band disables the check.
+pub fn within_boundary_band(price: i64, conf: u64, threshold: i64, band: u64) -> bool {
+    if band == 0 {
+        return false;
+    }
+    let diff = (price as i128 - threshold as i128).unsigned_abs();
+    diff <= band.max(conf) as u128
+}
+
+/// YES share (bps) for a confidence split: the fraction of the band
+/// [price - conf, price + conf] lying above `threshold`. None unless the band
+/// strictly straddles the threshold.
+pub fn confidence_split_bps(price: i64, conf: u64, threshold: i64) -> Option<u16> {
+    if conf == 0 {
+        return None;
+    }
+    let high = price as i128 + conf as i128;
+    let low = price as i128 - conf as i128;
+    let threshold = threshold as i128;
+    if threshold <= low || threshold >= high {
+        return None;
+    }
+    Some(((high - threshold) * 10000 / (2 * conf as i128)) as u16)
+}
+
+/// YES (LONG) share (bps) of a scalar market resolving at `price`: its
+/// position between `lower` and `upper`, clamped to [0, 10000].
+pub fn scalar_long_bps(price: i64, lower: i64, upper: i64) -> u16 {
+    if price <= lower {
+        return 0;
+    }
+    if price >= upper {
+        return 10000;
+    }
+    ((price as i128 - lower as i128) * 10000 / (upper as i128 - lower as i128)) as u16
+}
+
+/// Distance between `threshold` and `price`, in bps of |price|.
+/// Returns u64::MAX when price is zero (any threshold is "far").
+pub fn threshold_distance_bps(price: i64, threshold: i64) -> u64 {
+    let price_abs = (price as i128).unsigned_abs();
+    if price_abs == 0 {
+        return u64::MAX;
+    }
+    let diff = (threshold as i128 - price as i128).unsigned_abs();
+    let bps = diff.saturating_mul(10000) / price_abs;
+    bps.min(u64::MAX as u128) as u64
+}
+
+/// Reads the integer a ProgramState market watches: `size` little-endian bytes at
+/// `offset`. Sizes 1, 2 and 4 are unsigned; 8 is a signed i64.
+pub fn read_program_state_value(data: &[u8], offset: u32, size: u8) -> Result<i64> {
+    let start = offset as usize;
+    let end = start.checked_add(size as usize).ok_or(PredictError::InvalidProgramStateQuery)?;
+    let bytes = data.get(start..end).ok_or(PredictError::InvalidProgramStateQuery)?;
+    let value = match size {
+        1 => bytes[0] as i64,
+        2 => u16::from_le_bytes([bytes[0], bytes[1]]) as i64,
+        4 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64,
+        8 => i64::from_le_bytes(bytes.try_into().map_err(|_| PredictError::InvalidProgramStateQuery)?),
+        _ => return err!(PredictError::InvalidProgramStateQuery),
+    };
+    Ok(value)
+}
+
+/// Switchboard On-Demand program, owner of every pull feed account
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Switchboard feed values are fixed-point i128 with 18 decimals (exponent -18)
+pub const SWITCHBOARD_EXPONENT: i32 = -18;
+
+/// Exponent a Switchboard market's threshold is taken in when none was declared
+pub const SWITCHBOARD_DEFAULT_EXPONENT: i32 = -8;
+
+// PullFeedAccountData offsets, counted from the start of the account data
+// (8-byte discriminator included)
+const SB_LAST_UPDATE_TIMESTAMP_OFFSET: usize = 2216;
+const SB_RESULT_VALUE_OFFSET: usize = 2264;
+const SB_RESULT_STD_DEV_OFFSET: usize = 2280;
+const SB_RESULT_SLOT_OFFSET: usize = 2368;
+
+/// `N` raw bytes at `offset` of a foreign account, None past its end
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Option<[u8; N]> {
+    data.get(offset..offset.checked_add(N)?)?.try_into().ok()
+}
+
+/// Snapshot of a Switchboard pull feed's current result, with value and standard
+/// deviation (used as the confidence) rescaled to `exponent`. The caller checks
+/// the account's key and owner; staleness is left to `require_fresh_at_close`.
+pub fn cache_switchboard_print(data: &[u8], slot: u64, exponent: i32) -> Result<OracleCache> {
+    let field = |offset| read_bytes(data, offset).ok_or(PredictError::InvalidSwitchboardFeed);
+    let value = i128::from_le_bytes(field(SB_RESULT_VALUE_OFFSET)?);
+    let std_dev = i128::from_le_bytes(field(SB_RESULT_STD_DEV_OFFSET)?);
+    let price = rescale_exponent(value, SWITCHBOARD_EXPONENT, exponent)
+        .and_then(|p| i64::try_from(p).ok())
+        .ok_or(PredictError::MathOverflow)?;
+    let conf = rescale_exponent(std_dev, SWITCHBOARD_EXPONENT, exponent)
+        .and_then(|c| u64::try_from(c).ok())
+        .ok_or(PredictError::MathOverflow)?;
+    Ok(OracleCache {
+        price,
+        conf,
+        publish_time: i64::from_le_bytes(read_bytes(data, SB_LAST_UPDATE_TIMESTAMP_OFFSET).ok_or(PredictError::InvalidSwitchboardFeed)?),
+        posted_slot: u64::from_le_bytes(read_bytes(data, SB_RESULT_SLOT_OFFSET).ok_or(PredictError::InvalidSwitchboardFeed)?),
+        slot,
+    })
+}
+
+/// Chainlink data feeds store program, owner of every feed (transmissions) account
+pub const CHAINLINK_STORE_PROGRAM_ID: Pubkey = pubkey!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
+
+// Transmissions account: discriminator (8) | header (192) | ring of Transmission (48 each).
+// Header offsets below are counted from the start of the account data.
+const CL_DECIMALS_OFFSET: usize = 138;
+const CL_LIVE_LENGTH_OFFSET: usize = 148;
+const CL_LIVE_CURSOR_OFFSET: usize = 152;
+const CL_TRANSMISSIONS_OFFSET: usize = 8 + 192;
+// Transmission: slot (u64) | timestamp (u32) | padding (4) | answer (i128) | padding (16)
+const CL_TRANSMISSION_LEN: usize = 48;
+const CL_ANSWER_OFFSET: usize = 16;
+
+/// Decimals a Chainlink feed's answers are published with
+pub fn chainlink_decimals(data: &[u8]) -> Result<u8> {
+    let [decimals] = read_bytes(data, CL_DECIMALS_OFFSET).ok_or(PredictError::InvalidChainlinkFeed)?;
+    Ok(decimals)
+}
+
+/// Snapshot of a Chainlink feed's latest round, with the answer rescaled from the
+/// feed's decimals to `exponent`. Chainlink publishes no confidence, so `conf` is
+/// zero. The caller checks the account's key and owner; staleness is left to
+/// `require_fresh_at_close`.
+pub fn cache_chainlink_print(data: &[u8], slot: u64, exponent: i32) -> Result<OracleCache> {
+    let live_length = u32::from_le_bytes(read_bytes(data, CL_LIVE_LENGTH_OFFSET).ok_or(PredictError::InvalidChainlinkFeed)?) as usize;
+    let live_cursor = u32::from_le_bytes(read_bytes(data, CL_LIVE_CURSOR_OFFSET).ok_or(PredictError::InvalidChainlinkFeed)?) as usize;
+    require!(live_length > 0, PredictError::InvalidChainlinkFeed);
+    // The cursor points at the next round to write; the latest sits just behind it
+    let latest = (live_cursor + live_length - 1) % live_length;
+    let round = CL_TRANSMISSIONS_OFFSET + latest * CL_TRANSMISSION_LEN;
+
+    let round_slot = u64::from_le_bytes(read_bytes(data, round).ok_or(PredictError::InvalidChainlinkFeed)?);
+    let timestamp = u32::from_le_bytes(read_bytes(data, round + 8).ok_or(PredictError::InvalidChainlinkFeed)?);
+    let answer = i128::from_le_bytes(read_bytes(data, round + CL_ANSWER_OFFSET).ok_or(PredictError::InvalidChainlinkFeed)?);
+    let decimals = chainlink_decimals(data)?;
+    let price = rescale_exponent(answer, -(decimals as i32), exponent)
+        .and_then(|p| i64::try_from(p).ok())
+        .ok_or(PredictError::MathOverflow)?;
+    Ok(OracleCache {
+        price,
+        conf: 0,
+        publish_time: timestamp as i64,
+        posted_slot: round_slot,
+        slot,
+    })
+}
+
+/// Length of the message a SignedAttestation attester signs:
+/// market_id (u64 LE) | outcome (u8: 0 Yes, 1 No, 2 Invalid) | timestamp (i64 LE)
+pub const ATTESTATION_MESSAGE_LEN: usize = 8 + 1 + 8;
+
+// Ed25519 program instruction: num_signatures (u8) | padding (u8) | offsets (14 per
+// signature) | data. Offsets are u16: signature, signature ix, public key,
+// public key ix, message, message size, message ix.
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+/// Instruction index meaning "this instruction's own data"
+const ED25519_SAME_IX: u16 = u16::MAX;
+
+/// Signer and message of the single signature checked by the Ed25519 program
+/// instruction placed immediately before the current one. The runtime has
+/// already verified the signature; only data carried in that instruction itself
+/// is accepted, so the key and message can't be swapped in from elsewhere.
+pub fn read_preceding_ed25519(instructions_sysvar: &AccountInfo) -> Result<(Pubkey, Vec<u8>)> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, PredictError::InvalidAttestation);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions_sysvar)?;
+    require!(ix.program_id == ed25519_program::ID, PredictError::InvalidAttestation);
+
+    let data = &ix.data;
+    require!(data.first() == Some(&1), PredictError::InvalidAttestation);
+    let offsets = data
+        .get(ED25519_OFFSETS_START..ED25519_OFFSETS_START + ED25519_OFFSETS_LEN)
+        .ok_or(PredictError::InvalidAttestation)?;
+    let field = |i: usize| u16::from_le_bytes([offsets[2 * i], offsets[2 * i + 1]]);
+    let (sig_ix, key_offset, key_ix, msg_offset, msg_len, msg_ix) =
+        (field(1), field(2) as usize, field(3), field(4) as usize, field(5) as usize, field(6));
+    require!(
+        sig_ix == ED25519_SAME_IX && key_ix == ED25519_SAME_IX && msg_ix == ED25519_SAME_IX,
+        PredictError::InvalidAttestation
+    );
+
+    let key: [u8; 32] = read_bytes(data, key_offset).ok_or(PredictError::InvalidAttestation)?;
+    let message = data.get(msg_offset..msg_offset + msg_len).ok_or(PredictError::InvalidAttestation)?;
+    Ok((Pubkey::new_from_array(key), message.to_vec()))
+}
+
+/// Decodes an attestation message into (market_id, outcome, timestamp)
+pub fn parse_attestation(message: &[u8]) -> Result<(u64, Outcome, i64)> {
+    require!(message.len() == ATTESTATION_MESSAGE_LEN, PredictError::InvalidAttestation);
+    let market_id = u64::from_le_bytes(read_bytes(message, 0).ok_or(PredictError::InvalidAttestation)?);
+    let outcome = match message[8] {
+        0 => Outcome::Yes,
+        1 => Outcome::No,
+        2 => Outcome::Invalid,
+        _ => return err!(PredictError::InvalidAttestation),
+    };
+    let timestamp = i64::from_le_bytes(read_bytes(message, 9).ok_or(PredictError::InvalidAttestation)?);
+    Ok((market_id, outcome, timestamp))
+}
+
+/// Fewest observations in the window a TWAP is computed from
+pub const MIN_TWAP_SNAPSHOTS: usize = 2;
+
+/// Time-weighted average of `snapshots` (oldest first) over [start, end]: each
+/// price holds from its observation until the next one, the last until `end`.
+/// Observations outside the window are ignored. None with fewer than
+/// MIN_TWAP_SNAPSHOTS in it.
+pub fn twap(snapshots: &[PriceSnapshot], start: i64, end: i64) -> Option<i64> {
+    let window: Vec<&PriceSnapshot> = snapshots.iter()
+        .filter(|s| s.observed_at >= start && s.observed_at <= end)
+        .collect();
+    if window.len() < MIN_TWAP_SNAPSHOTS {
+        return None;
+    }
+    let mut weighted: i128 = 0;
+    let mut total: i128 = 0;
+    for (i, s) in window.iter().enumerate() {
+        let until = window.get(i + 1).map_or(end, |next| next.observed_at);
+        let held = (until - s.observed_at) as i128;
+        weighted = weighted.checked_add((s.price as i128).checked_mul(held)?)?;
+        total += held;
+    }
+    if total == 0 {
+        return window.last().map(|s| s.price);
+    }
+    i64::try_from(weighted / total).ok()
+}
@@ -0,0 +1,3080 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { SolanaPredict } from "../target/types/solana_predict";
+import * as token from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  PublicKey,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+} from "@solana/web3.js";
+
+/**
+ * Betting Feature Tests
+ * ----------------------
+ * Tests cover:
+ *   1. cancel_bets_batch across multiple markets
+ *   2. Same-mint and cross-mint (payout_mint) settlement
+ *   3. restart_recurring on a settled recurring market
+ *   4. init_position with cached share ATAs
+ *   5. Default slippage protection for min_shares_out = 0
+ *   6. Collateral credited from the vault balance delta
+ *   7. get_claim_status before and after a claim
+ *   8. Invalid refunds weighted by principal, not share supply
+ *   9. Early-bird bonus funded from a creator budget
+ *  10. Distributable collateral fixed at resolution; vault surplus sweep
+ *  11. claim_and_rollover from a resolved market into an active one
+ *  12. Per-market cap on a single bet's share of the pool
+ *  13. Both-sides-win resolution with explicit YES/NO allocations
+ *  14. lock_market freezes the closing reserves
+ *  15. Minimum distinct bettors for resolution
+ *  16. quote_fee matches the fees place_bet charges
+ *  17. consolidate_dust closes positions across resolved markets
+ *  18. Emergency shutdown with pro-rata refunds across unresolved markets (LP deposits kept for LPs)
+ *  19. Rounding dust routed to the market's configured recipient
+ *  20. verify_fee_accounting reconciles fee destinations with fees charged
+ *  21. Forking a disputed market into per-outcome children
+ *  22. Dust positions forfeited to the treasury so the market can close
+ *  23. Platform-wide TVL cap on bets
+ *  24. Slot-start pricing makes bet order within a slot irrelevant
+ *  25. get_pnl after a buy, a cancel and a resolution
+ *  26. Platform fee floor on micro-bets
+ *  27. Liquidity providers: add_liquidity and remove_liquidity after resolution
+ *  28. cancel_market and pro-rata claim_refund
+ *  29. Parimutuel pricing: stakes in outcome buckets, winners split the pot
+ *  30. Virtual liquidity dampens price impact without moving real reserves
+ *  31. Creator fee split escrowed per market and claim_creator_fees
+ *  32. Referral fee share: register_referrer, referred bets, claim_referral_fees
+ *  33. Trade fees accrued to liquidity providers and paid on remove_liquidity
+ *  34. Volume fee tiers tracked by a UserTier account
+ *  35. start_round opens the next scheduled round of a recurring market
+ */
+
+describe("Betting Feature Tests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.solanaPredict as Program<SolanaPredict>;
+  const admin = provider.wallet;
+  const adminPayer = (admin as any).payer as Keypair;
+
+  const userA = Keypair.generate();
+
+  const WSOL_MINT = new PublicKey("So11111111111111111111111111111111111111112");
+
+  const [platformConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_config")],
+    program.programId
+  );
+
+  let treasuryAta: PublicKey;
+
+  // Unique market IDs to avoid collisions with other test files
+  const BASE_ID = Math.floor(Date.now() / 1000) * 1000 + 800;
+
+  function deriveMarketPda(marketId: BN) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+  function deriveVault(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+  function deriveYesMint(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+  function deriveNoMint(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+  function derivePosition(marketPda: PublicKey, user: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), marketPda.toBuffer(), user.toBuffer()],
+      program.programId
+    );
+  }
+
+  async function fundWsol(owner: Keypair, lamports: number) {
+    const ata = await token.getAssociatedTokenAddress(WSOL_MINT, owner.publicKey);
+    try {
+      await token.getAccount(provider.connection, ata);
+    } catch {
+      await token.createAssociatedTokenAccount(
+        provider.connection,
+        adminPayer,
+        WSOL_MINT,
+        owner.publicKey
+      );
+    }
+    const tx = new anchor.web3.Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: owner.publicKey,
+        toPubkey: ata,
+        lamports,
+      }),
+      token.createSyncNativeInstruction(ata)
+    );
+    await provider.sendAndConfirm(tx, [owner]);
+    return ata;
+  }
+
+  async function createTestMarket(marketId: BN, overrides: any = {}) {
+    const [marketPda] = deriveMarketPda(marketId);
+    const now = Math.floor(Date.now() / 1000);
+    const initialLiquidity = LAMPORTS_PER_SOL;
+
+    const adminAta = await fundWsol(adminPayer, initialLiquidity);
+
+    await program.methods
+      .createMarket(marketId, {
+        title: "Betting Test Market",
+        description: "Test",
+        category: { crypto: {} },
+        oracleSource: { manualAdmin: {} },
+        oracleFeed: PublicKey.default,
+        oracleThreshold: new BN(0),
+        startTimestamp: new BN(now - 60),
+        lockTimestamp: new BN(now + 3600),
+        endTimestamp: new BN(now + 7200),
+        minBet: new BN(10_000_000),
+        maxBet: new BN(0),
+        isRecurring: false,
+        roundDuration: null,
+        feeBps: 250,
+        initialLiquidity: new BN(initialLiquidity),
+        ...overrides,
+      } as any)
+      .accounts({ adminAta })
+      .rpc();
+
+    return marketPda;
+  }
+
+  function derivePayoutReserve(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("payout_reserve"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+
+  async function placeBet(user: Keypair, marketId: BN, side: "yes" | "no", amount: number) {
+    const [marketPda] = deriveMarketPda(marketId);
+    const [shareMint] = side === "yes" ? deriveYesMint(marketPda) : deriveNoMint(marketPda);
+    await fundWsol(user, amount);
+    const shareAta = await token.getOrCreateAssociatedTokenAccount(
+      provider.connection, adminPayer, shareMint, user.publicKey
+    );
+    await program.methods
+      .placeBet(marketId, side === "yes" ? { yes: {} } : { no: {} }, new BN(amount), new BN(0))
+      .accounts({
+        user: user.publicKey,
+        userShareAccount: shareAta.address,
+        platformConfig,
+        treasury: treasuryAta,
+        collateralMint: WSOL_MINT,
+      })
+      .signers([user])
+      .rpc();
+    return shareAta.address;
+  }
+
+  before(async () => {
+    for (const u of [userA]) {
+      const sig = await provider.connection.requestAirdrop(u.publicKey, 20 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    // Fees accrue in the program-owned treasury for the collateral mint
+    [treasuryAta] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), WSOL_MINT.toBuffer()],
+      program.programId
+    );
+
+    // Init platform (skip if already initialized by other test suite)
+    try {
+      await program.account.platformConfig.fetch(platformConfig);
+    } catch {
+      await program.methods
+        .initPlatform(250, new BN(1_000_000))
+        .accounts({
+          platformConfig,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          collateralMint: WSOL_MINT,
+          treasury: treasuryAta,
+        })
+        .rpc();
+    }
+  });
+
+  // =========================================================================
+  // 1. cancel_bets_batch
+  // =========================================================================
+  describe("cancel_bets_batch", () => {
+    it("Cancels full positions across two markets in one transaction", async () => {
+      const marketIds = [new BN(BASE_ID + 1), new BN(BASE_ID + 2)];
+      const remainingAccounts = [];
+
+      for (const marketId of marketIds) {
+        const marketPda = await createTestMarket(marketId);
+        const [yesMint] = deriveYesMint(marketPda);
+        const [noMint] = deriveNoMint(marketPda);
+        const [vault] = deriveVault(marketPda);
+        const [position] = derivePosition(marketPda, userA.publicKey);
+
+        const yesAta = await placeBet(userA, marketId, "yes", Math.floor(0.2 * LAMPORTS_PER_SOL));
+        const noAta = (await token.getOrCreateAssociatedTokenAccount(
+          provider.connection, adminPayer, noMint, userA.publicKey
+        )).address;
+
+        remainingAccounts.push(
+          { pubkey: marketPda, isWritable: true, isSigner: false },
+          { pubkey: yesMint, isWritable: true, isSigner: false },
+          { pubkey: noMint, isWritable: true, isSigner: false },
+          { pubkey: vault, isWritable: true, isSigner: false },
+          { pubkey: position, isWritable: true, isSigner: false },
+          { pubkey: yesAta, isWritable: true, isSigner: false },
+          { pubkey: noAta, isWritable: true, isSigner: false },
+        );
+      }
+
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey);
+      const balBefore = Number((await token.getAccount(provider.connection, userAta)).amount);
+
+      await program.methods
+        .cancelBetsBatch([new BN(1), new BN(1)])
+        .accounts({
+          userAta,
+          platformConfig,
+          treasury: treasuryAta,
+          user: userA.publicKey,
+          collateralMint: WSOL_MINT,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([userA])
+        .rpc();
+
+      const balAfter = Number((await token.getAccount(provider.connection, userAta)).amount);
+      assert.isAbove(balAfter, balBefore, "User should receive refunds");
+
+      for (const marketId of marketIds) {
+        const [marketPda] = deriveMarketPda(marketId);
+        const [position] = derivePosition(marketPda, userA.publicKey);
+        const pos = await program.account.userPosition.fetch(position);
+        assert.equal(pos.yesShares.toNumber(), 0, "YES position should be fully cancelled");
+        assert.equal(pos.noShares.toNumber(), 0, "NO position should be empty");
+      }
+      console.log(`  ✓ Batch cancel refunded ${(balAfter - balBefore) / LAMPORTS_PER_SOL} SOL across 2 markets`);
+    });
+  });
+
+  // =========================================================================
+  // 2. Payout mint
+  // =========================================================================
+  describe("Payout mint", () => {
+    it("Same-mint market pays winners in collateral", async () => {
+      const marketId = new BN(BASE_ID + 3);
+      const marketPda = await createTestMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const [vault] = deriveVault(marketPda);
+      const [userPosition] = derivePosition(marketPda, userA.publicKey);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.isNull(market.payoutMint, "payout_mint defaults to None");
+
+      const yesAta = await placeBet(userA, marketId, "yes", Math.floor(0.2 * LAMPORTS_PER_SOL));
+
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey);
+      const balBefore = Number((await token.getAccount(provider.connection, userAta)).amount);
+
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault,
+          userPosition,
+          userAta,
+          userShareAccount: yesAta,
+          user: userA.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([userA])
+        .rpc();
+
+      const balAfter = Number((await token.getAccount(provider.connection, userAta)).amount);
+      assert.isAbove(balAfter, balBefore, "Payout should arrive in collateral");
+      console.log(`  ✓ Same-mint payout: ${(balAfter - balBefore) / LAMPORTS_PER_SOL} SOL`);
+    });
+
+    it("Cross-mint market pays winners from the payout reserve", async () => {
+      const marketId = new BN(BASE_ID + 4);
+      const marketPda = await createTestMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const [vault] = deriveVault(marketPda);
+      const [userPosition] = derivePosition(marketPda, userA.publicKey);
+      const [payoutReserve] = derivePayoutReserve(marketPda);
+
+      // 6-decimal stable payout token, 150 units per SOL
+      const usdMint = await token.createMint(
+        provider.connection, adminPayer, admin.publicKey, null, 6
+      );
+      const creatorUsd = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, usdMint, admin.publicKey
+      );
+      const userUsd = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, usdMint, userA.publicKey
+      );
+      await token.mintTo(provider.connection, adminPayer, usdMint, creatorUsd.address, adminPayer, 10_000_000_000);
+      const PAYOUT_RATE = 150_000_000; // per 1e9 collateral units
+
+      // Reserve covers only the seeded liquidity for now
+      await program.methods
+        .setPayoutMint(marketId, new BN(PAYOUT_RATE), new BN(150_000_000))
+        .accounts({
+          market: marketPda,
+          payoutMint: usdMint,
+          creatorPayoutAta: creatorUsd.address,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      const configured = await program.account.market.fetch(marketPda);
+      assert.ok(configured.payoutMint.equals(usdMint));
+      assert.equal(configured.payoutRate.toNumber(), PAYOUT_RATE);
+
+      const yesAta = await placeBet(userA, marketId, "yes", Math.floor(0.2 * LAMPORTS_PER_SOL));
+
+      // Bets grew the pool beyond the reserve -> resolution must fail
+      try {
+        await program.methods
+          .resolveMarket(marketId, { yes: {} })
+          .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null, payoutReserve })
+          .rpc();
+        assert.fail("Should have thrown InsufficientPayoutReserve");
+      } catch (e: any) {
+        assert.include(e.message, "InsufficientPayoutReserve");
+        console.log("  ✓ Underfunded reserve blocks resolution");
+      }
+
+      // Creator tops up with a plain transfer, then resolution succeeds
+      await token.transfer(
+        provider.connection, adminPayer, creatorUsd.address, payoutReserve, adminPayer, 100_000_000
+      );
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null, payoutReserve })
+        .rpc();
+
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey);
+      const solBefore = Number((await token.getAccount(provider.connection, userAta)).amount);
+      const usdBefore = Number((await token.getAccount(provider.connection, userUsd.address)).amount);
+
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault,
+          userPosition,
+          userAta,
+          userShareAccount: yesAta,
+          user: userA.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve,
+          userPayoutAccount: userUsd.address,
+        })
+        .signers([userA])
+        .rpc();
+
+      const solAfter = Number((await token.getAccount(provider.connection, userAta)).amount);
+      const usdAfter = Number((await token.getAccount(provider.connection, userUsd.address)).amount);
+      assert.equal(solAfter, solBefore, "No collateral leaves the vault");
+      assert.isAbove(usdAfter, usdBefore, "Payout should arrive in the payout mint");
+
+      const claimed = (await program.account.userPosition.fetch(userPosition)).totalClaimed;
+      const expected = claimed.mul(new BN(PAYOUT_RATE)).div(new BN(1_000_000_000)).toNumber();
+      assert.equal(usdAfter - usdBefore, expected, "Paid at the fixed rate");
+      console.log(`  ✓ Cross-mint payout: ${(usdAfter - usdBefore) / 1e6} USD`);
+    });
+  });
+
+  // =========================================================================
+  // 3. restart_recurring
+  // =========================================================================
+  describe("restart_recurring", () => {
+    it("Restarts a settled recurring market with its residual liquidity", async () => {
+      const marketId = new BN(BASE_ID + 5);
+      const marketPda = await createTestMarket(marketId, { isRecurring: true });
+      const [noMint] = deriveNoMint(marketPda);
+      const [vault] = deriveVault(marketPda);
+      const [position] = derivePosition(marketPda, userA.publicKey);
+
+      // Only a losing NO bet: no winners, so the vault keeps its liquidity
+      const noAta = await placeBet(userA, marketId, "no", Math.floor(0.2 * LAMPORTS_PER_SOL));
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      // Outstanding (worthless) NO shares block the restart
+      const now = Math.floor(Date.now() / 1000);
+      const restart = () =>
+        program.methods
+          .restartRecurring(marketId, new BN(now - 10), new BN(now + 3600), new BN(now + 7200))
+          .accounts({ market: marketPda, admin: admin.publicKey })
+          .rpc();
+      try {
+        await restart();
+        assert.fail("Should have thrown OutstandingPositions");
+      } catch (e: any) {
+        assert.include(e.message, "OutstandingPositions");
+      }
+
+      // Loser burns the worthless shares, settling the series
+      const noBalance = (await token.getAccount(provider.connection, noAta)).amount;
+      await token.burn(provider.connection, adminPayer, noAta, noMint, userA, noBalance);
+      await restart();
+
+      const vaultBalance = Number((await token.getAccount(provider.connection, vault)).amount);
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(market.status.active, "New series should be Active");
+      assert.isNull(market.resolvedOutcome, "Outcome cleared");
+      assert.equal(market.currentRound.toNumber(), 1);
+      assert.equal(market.totalYesShares.toNumber(), vaultBalance, "YES pool re-seeded from vault");
+      assert.equal(market.totalNoShares.toNumber(), vaultBalance, "NO pool re-seeded from vault");
+      assert.equal(market.totalCollateral.toNumber(), vaultBalance);
+
+      // Betting in the new series starts a fresh position
+      await placeBet(userA, marketId, "yes", Math.floor(0.1 * LAMPORTS_PER_SOL));
+      const pos = await program.account.userPosition.fetch(position);
+      assert.equal(pos.round.toNumber(), 1);
+      assert.equal(pos.noShares.toNumber(), 0, "Previous series shares dropped");
+      assert.isAbove(pos.yesShares.toNumber(), 0);
+      console.log(`  ✓ Restarted with ${vaultBalance / LAMPORTS_PER_SOL} SOL residual liquidity`);
+    });
+
+    it("Rejects restarting a non-recurring market", async () => {
+      const marketId = new BN(BASE_ID + 6);
+      const marketPda = await createTestMarket(marketId);
+      await program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      try {
+        await program.methods
+          .restartRecurring(marketId, new BN(now - 10), new BN(now + 3600), new BN(now + 7200))
+          .accounts({ market: marketPda, admin: admin.publicKey })
+          .rpc();
+        assert.fail("Should have thrown NotRecurring");
+      } catch (e: any) {
+        assert.include(e.message, "NotRecurring");
+        console.log("  ✓ Non-recurring market cannot be restarted");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 4. init_position
+  // =========================================================================
+  describe("init_position", () => {
+    it("Creates the position and both share ATAs, then bets through the cache", async () => {
+      const marketId = new BN(BASE_ID + 7);
+      const marketPda = await createTestMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const [position] = derivePosition(marketPda, userA.publicKey);
+      const yesAta = await token.getAssociatedTokenAddress(yesMint, userA.publicKey);
+      const noAta = await token.getAssociatedTokenAddress(noMint, userA.publicKey);
+
+      await program.methods
+        .initPosition(marketId)
+        .accounts({ user: userA.publicKey })
+        .signers([userA])
+        .rpc();
+
+      const pos = await program.account.userPosition.fetch(position);
+      assert.ok(pos.yesAccount.equals(yesAta), "YES ATA cached");
+      assert.ok(pos.noAccount.equals(noAta), "NO ATA cached");
+      assert.ok(await provider.connection.getAccountInfo(noAta), "NO ATA created up front");
+
+      const AMOUNT = Math.floor(0.1 * LAMPORTS_PER_SOL);
+      await fundWsol(userA, 2 * AMOUNT);
+      const bet = (outcome: any, shareAccount: PublicKey) =>
+        program.methods
+          .placeBet(marketId, outcome, new BN(AMOUNT), new BN(0))
+          .accounts({
+            user: userA.publicKey,
+            userShareAccount: shareAccount,
+            platformConfig,
+            treasury: treasuryAta,
+            collateralMint: WSOL_MINT,
+          })
+          .signers([userA])
+          .rpc();
+
+      // The NO ATA is not the cached account for a YES bet
+      try {
+        await bet({ yes: {} }, noAta);
+        assert.fail("Should have thrown InvalidMint");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidMint");
+      }
+
+      await bet({ yes: {} }, yesAta);
+      const after = await program.account.userPosition.fetch(position);
+      const balance = Number((await token.getAccount(provider.connection, yesAta)).amount);
+      assert.equal(after.yesShares.toNumber(), balance, "Shares minted into the cached ATA");
+      console.log(`  ✓ Bet via cached ATA minted ${balance} YES shares`);
+    });
+  });
+
+  // =========================================================================
+  // 5. Default slippage protection
+  // =========================================================================
+  describe("Default slippage protection", () => {
+    const marketId = new BN(BASE_ID + 8);
+
+    before(async () => {
+      await createTestMarket(marketId);
+      await program.methods
+        .updateDefaultMaxSlippage(500) // 5%
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateDefaultMaxSlippage(0)
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Protects a min_shares_out = 0 bet with the platform default", async () => {
+      // ~2% price impact on a 1 SOL pool: within the default
+      await placeBet(userA, marketId, "yes", Math.floor(0.02 * LAMPORTS_PER_SOL));
+
+      // ~16% price impact: rejected instead of filling unprotected
+      try {
+        await placeBet(userA, marketId, "yes", Math.floor(0.2 * LAMPORTS_PER_SOL));
+        assert.fail("Should have thrown SlippageExceeded");
+      } catch (e: any) {
+        assert.include(e.message, "SlippageExceeded");
+        console.log("  ✓ Default slippage floor rejected a high-impact bet");
+      }
+    });
+
+    it("Lets an explicit min_shares_out override the default", async () => {
+      const [marketPda] = deriveMarketPda(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const AMOUNT = Math.floor(0.2 * LAMPORTS_PER_SOL);
+      await fundWsol(userA, AMOUNT);
+      const yesAta = await token.getAssociatedTokenAddress(yesMint, userA.publicKey);
+      const before = Number((await token.getAccount(provider.connection, yesAta)).amount);
+
+      await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(AMOUNT), new BN(1))
+        .accounts({
+          user: userA.publicKey,
+          userShareAccount: yesAta,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .signers([userA])
+        .rpc();
+
+      const after = Number((await token.getAccount(provider.connection, yesAta)).amount);
+      assert.isAbove(after, before, "Explicit min_shares_out bet filled");
+      console.log("  ✓ Explicit min_shares_out overrides the default");
+    });
+  });
+
+  // =========================================================================
+  // 6. Received-amount accounting
+  // =========================================================================
+  describe("Received-amount accounting", () => {
+    it("Keeps total_collateral equal to the real vault balance across bets", async () => {
+      const marketId = new BN(BASE_ID + 9);
+      const marketPda = await createTestMarket(marketId);
+      const [vault] = deriveVault(marketPda);
+
+      await placeBet(userA, marketId, "yes", Math.floor(0.3 * LAMPORTS_PER_SOL));
+      await placeBet(userA, marketId, "no", Math.floor(0.7 * LAMPORTS_PER_SOL));
+      await placeBet(userA, marketId, "yes", 123_456_789);
+
+      const market = await program.account.market.fetch(marketPda);
+      const vaultAccount = await token.getAccount(provider.connection, vault);
+      assert.equal(
+        market.totalCollateral.toString(),
+        vaultAccount.amount.toString(),
+        "total_collateral should match the vault balance"
+      );
+      console.log("  ✓ total_collateral matches vault:", vaultAccount.amount.toString());
+    });
+  });
+
+  // =========================================================================
+  // 7. get_claim_status
+  // =========================================================================
+  describe("get_claim_status", () => {
+    it("Reports the claimable entitlement, then the claimed amount", async () => {
+      const marketId = new BN(BASE_ID + 10);
+      const marketPda = await createTestMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const [vault] = deriveVault(marketPda);
+      const [userPosition] = derivePosition(marketPda, userA.publicKey);
+
+      const yesAta = await placeBet(userA, marketId, "yes", Math.floor(0.2 * LAMPORTS_PER_SOL));
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const viewAccounts = {
+        market: marketPda,
+        yesMint,
+        noMint,
+        vault,
+        userPosition,
+        userYesAccount: yesAta,
+        userNoAccount: null,
+      };
+      const pending = await program.methods.getClaimStatus(marketId).accounts(viewAccounts).view();
+      const shares = (await token.getAccount(provider.connection, yesAta)).amount;
+      assert.equal(pending.claimableShares.toString(), shares.toString());
+      assert.equal(pending.alreadyClaimed.toNumber(), 0);
+      assert.isTrue(pending.remainingEntitlement.gtn(0), "Winner should be entitled to a payout");
+
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey);
+      const balBefore = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault,
+          userPosition,
+          userAta,
+          userShareAccount: yesAta,
+          user: userA.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([userA])
+        .rpc();
+      const balAfter = (await token.getAccount(provider.connection, userAta)).amount;
+      assert.equal(
+        (balAfter - balBefore).toString(),
+        pending.remainingEntitlement.toString(),
+        "View should match the amount actually paid"
+      );
+
+      const claimed = await program.methods.getClaimStatus(marketId).accounts(viewAccounts).view();
+      assert.equal(claimed.claimableShares.toNumber(), 0);
+      assert.equal(claimed.alreadyClaimed.toString(), pending.remainingEntitlement.toString());
+      assert.equal(claimed.remainingEntitlement.toNumber(), 0);
+      console.log("  ✓ Claim status:", pending.remainingEntitlement.toString(), "-> claimed");
+    });
+  });
+
+  // =========================================================================
+  // 8. Invalid refunds by principal
+  // =========================================================================
+  describe("Invalid refunds by principal", () => {
+    const userB = Keypair.generate();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userB.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    it("Refunds equal deposits on YES and NO equally", async () => {
+      const marketId = new BN(BASE_ID + 11);
+      const marketPda = await createTestMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const [vault] = deriveVault(marketPda);
+
+      const AMOUNT = Math.floor(0.5 * LAMPORTS_PER_SOL);
+      const yesAta = await placeBet(userA, marketId, "yes", AMOUNT);
+      const noAta = await placeBet(userB, marketId, "no", AMOUNT);
+
+      // Same principal, different share counts (B bought after the price moved)
+      const yesShares = (await token.getAccount(provider.connection, yesAta)).amount;
+      const noShares = (await token.getAccount(provider.connection, noAta)).amount;
+      assert.notEqual(yesShares.toString(), noShares.toString());
+
+      await program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const claim = async (user: Keypair, shareAccount: PublicKey) => {
+        const [userPosition] = derivePosition(marketPda, user.publicKey);
+        const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, user.publicKey);
+        const before = (await token.getAccount(provider.connection, userAta)).amount;
+        await program.methods
+          .claimPayout(marketId)
+          .accounts({
+            market: marketPda,
+            yesMint,
+            noMint,
+            vault,
+            userPosition,
+            userAta,
+            userShareAccount: shareAccount,
+            user: user.publicKey,
+            collateralMint: WSOL_MINT,
+            payoutReserve: null,
+            userPayoutAccount: null,
+          })
+          .signers([user])
+          .rpc();
+        const after = (await token.getAccount(provider.connection, userAta)).amount;
+        return Number(after - before);
+      };
+
+      const refundA = await claim(userA, yesAta);
+      const refundB = await claim(userB, noAta);
+      assert.approximately(refundA, refundB, 1, "Equal principal should be refunded equally");
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.totalDeposited.toNumber(), 0, "All principal claimed");
+      console.log(`  ✓ Invalid refunds: YES ${refundA / LAMPORTS_PER_SOL} SOL, NO ${refundB / LAMPORTS_PER_SOL} SOL`);
+    });
+  });
+
+  // =========================================================================
+  // 9. Early-bird bonus
+  // =========================================================================
+  describe("Early-bird bonus", () => {
+    const BUDGET = 100_000_000;
+    const AMOUNT = 200_000_000;
+
+    async function createBonusMarket(marketId: BN, windowSecs: number) {
+      await fundWsol(adminPayer, BUDGET);
+      return createTestMarket(marketId, {
+        earlyBirdBonusBps: 2000,
+        earlyBirdWindowSecs: new BN(windowSecs),
+        earlyBirdBudget: new BN(BUDGET),
+      });
+    }
+
+    async function sharesFor(marketId: BN) {
+      const shareAta = await placeBet(userA, marketId, "yes", AMOUNT);
+      return (await token.getAccount(provider.connection, shareAta)).amount;
+    }
+
+    it("Credits bonus collateral to bets inside the window", async () => {
+      const plainId = new BN(BASE_ID + 12);
+      const bonusId = new BN(BASE_ID + 13);
+      await createTestMarket(plainId);
+      const bonusPda = await createBonusMarket(bonusId, 3600);
+
+      const plainShares = await sharesFor(plainId);
+      const bonusShares = await sharesFor(bonusId);
+      assert.isTrue(bonusShares > plainShares, "Early bird should receive more shares");
+
+      // 20% of the net bet came out of the budget and into the pool
+      const net = AMOUNT - Math.ceil((AMOUNT * 250) / 10000);
+      const bonus = Math.floor((net * 2000) / 10000);
+      const market = await program.account.market.fetch(bonusPda);
+      assert.equal(market.earlyBirdBudget.toNumber(), BUDGET - bonus);
+      assert.equal(market.totalCollateral.toNumber(), LAMPORTS_PER_SOL + net + bonus);
+
+      const [vault] = deriveVault(bonusPda);
+      const vaultAmount = Number((await token.getAccount(provider.connection, vault)).amount);
+      assert.equal(vaultAmount, market.totalCollateral.toNumber() + market.earlyBirdBudget.toNumber(),
+        "Vault backs the pool plus the unspent budget");
+      console.log(`  ✓ Bonus shares: ${plainShares} -> ${bonusShares}`);
+    });
+
+    it("Pays no bonus once the window has passed", async () => {
+      const plainId = new BN(BASE_ID + 14);
+      const bonusId = new BN(BASE_ID + 15);
+      await createTestMarket(plainId);
+      // Market opened 60s ago with a 30s window
+      const bonusPda = await createBonusMarket(bonusId, 30);
+
+      const plainShares = await sharesFor(plainId);
+      const lateShares = await sharesFor(bonusId);
+      assert.equal(lateShares.toString(), plainShares.toString());
+
+      const market = await program.account.market.fetch(bonusPda);
+      assert.equal(market.earlyBirdBudget.toNumber(), BUDGET, "Budget untouched");
+      console.log("  ✓ Post-window bet priced without bonus");
+    });
+  });
+
+  // =========================================================================
+  // 10. Distributable collateral
+  // =========================================================================
+  describe("Distributable collateral", () => {
+    it("Pays winners exactly the distributable pool and leaves the rest sweepable", async () => {
+      const marketId = new BN(BASE_ID + 16);
+      const marketPda = await createTestMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const [vault] = deriveVault(marketPda);
+      const [userPosition] = derivePosition(marketPda, userA.publicKey);
+
+      const yesAta = await placeBet(userA, marketId, "yes", Math.floor(0.3 * LAMPORTS_PER_SOL));
+
+      // Collateral that reached the vault outside the pool (e.g. accrued fees)
+      const STRAY = 50_000_000;
+      const adminAta = await fundWsol(adminPayer, STRAY);
+      await token.transfer(provider.connection, adminPayer, adminAta, vault, adminPayer, STRAY);
+
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const resolved = await program.account.market.fetch(marketPda);
+      assert.equal(
+        resolved.distributableCollateral.toString(),
+        resolved.totalCollateral.toString(),
+        "Stray vault balance is not distributable"
+      );
+
+      // Sole winner holds the whole YES supply: receives exactly the pool
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey);
+      const balBefore = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault,
+          userPosition,
+          userAta,
+          userShareAccount: yesAta,
+          user: userA.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([userA])
+        .rpc();
+      const balAfter = (await token.getAccount(provider.connection, userAta)).amount;
+      assert.equal((balAfter - balBefore).toString(), resolved.distributableCollateral.toString());
+
+      const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      await program.methods
+        .sweepVaultSurplus(marketId)
+        .accounts({ market: marketPda, platformConfig, treasury: treasuryAta, creatorAta: null, admin: admin.publicKey })
+        .rpc();
+      const treasuryAfter = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      assert.equal(Number(treasuryAfter - treasuryBefore), STRAY, "Surplus swept to treasury");
+      assert.equal(Number((await token.getAccount(provider.connection, vault)).amount), 0);
+      console.log("  ✓ Winner paid the distributable pool; surplus swept");
+    });
+  });
+
+  // =========================================================================
+  // 11. claim_and_rollover
+  // =========================================================================
+  describe("claim_and_rollover", () => {
+    it("Claims a resolved market and bets the payout on another", async () => {
+      const marketId = new BN(BASE_ID + 17);
+      const nextId = new BN(BASE_ID + 18);
+      const marketPda = await createTestMarket(marketId);
+      const nextPda = await createTestMarket(nextId);
+      const [userPosition] = derivePosition(marketPda, userA.publicKey);
+      const [nextPosition] = derivePosition(nextPda, userA.publicKey);
+      const [nextYesMint] = deriveYesMint(nextPda);
+
+      const yesAta = await placeBet(userA, marketId, "yes", Math.floor(0.3 * LAMPORTS_PER_SOL));
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      const resolved = await program.account.market.fetch(marketPda);
+
+      const nextYesAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, nextYesMint, userA.publicKey
+      );
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey);
+      const balBefore = (await token.getAccount(provider.connection, userAta)).amount;
+
+      await program.methods
+        .claimAndRollover(marketId, nextId, { yes: {} }, new BN(0))
+        .accounts({
+          userShareAccount: yesAta,
+          nextUserShareAccount: nextYesAta.address,
+          user: userA.publicKey,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        } as any)
+        .signers([userA])
+        .rpc();
+
+      const position = await program.account.userPosition.fetch(userPosition);
+      assert.equal(position.totalClaimed.toString(), resolved.distributableCollateral.toString());
+      assert.equal(Number((await token.getAccount(provider.connection, yesAta)).amount), 0, "Claimed shares burned");
+
+      const nextPositionAcc = await program.account.userPosition.fetch(nextPosition);
+      assert.isTrue(nextPositionAcc.yesShares.toNumber() > 0, "Proceeds bought YES in the next market");
+      assert.equal(
+        nextPositionAcc.yesShares.toString(),
+        (await token.getAccount(provider.connection, nextYesAta.address)).amount.toString()
+      );
+
+      const balAfter = (await token.getAccount(provider.connection, userAta)).amount;
+      assert.equal(balAfter.toString(), balBefore.toString(), "Whole payout was rolled over");
+
+      // Resolved markets cannot receive a rollover
+      try {
+        await program.methods
+          .claimAndRollover(nextId, marketId, { yes: {} }, new BN(0))
+          .accounts({
+            userShareAccount: nextYesAta.address,
+            nextUserShareAccount: yesAta,
+            user: userA.publicKey,
+            platformConfig,
+            treasury: treasuryAta,
+            collateralMint: WSOL_MINT,
+          } as any)
+          .signers([userA])
+          .rpc();
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "MarketNotActive");
+      }
+      console.log(`  ✓ Rolled ${Number(resolved.distributableCollateral) / LAMPORTS_PER_SOL} SOL into market ${nextId}`);
+    });
+  });
+
+  // =========================================================================
+  // 12. Bet size cap relative to the pool
+  // =========================================================================
+  describe("Pool fraction cap", () => {
+    // 10% of the 1 SOL YES reserve: net 100_000_000 after the 2.5% fee
+    const CAP_OVERRIDES = { maxBetPoolFractionBps: 1000 };
+
+    it("Accepts a bet whose net amount is exactly at the cap", async () => {
+      const marketId = new BN(BASE_ID + 19);
+      const marketPda = await createTestMarket(marketId, CAP_OVERRIDES);
+      // 102_564_103 - ceil(2.5%) = 100_000_000
+      await placeBet(userA, marketId, "yes", 102_564_103);
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.totalCollateral.toNumber(), LAMPORTS_PER_SOL + 100_000_000);
+      console.log("  ✓ Bet at the pool fraction cap accepted");
+    });
+
+    it("Rejects a bet whose net amount exceeds the cap", async () => {
+      const marketId = new BN(BASE_ID + 20);
+      await createTestMarket(marketId, CAP_OVERRIDES);
+      try {
+        await placeBet(userA, marketId, "yes", 102_564_110);
+        assert.fail("Should have thrown BetTooLargeForPool");
+      } catch (e: any) {
+        assert.include(e.message, "BetTooLargeForPool");
+      }
+      console.log("  ✓ Bet just over the cap rejected");
+    });
+  });
+
+  // =========================================================================
+  // 13. Both-sides-win resolution
+  // =========================================================================
+  describe("Both-sides-win resolution", () => {
+    const userB = Keypair.generate();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userB.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    async function claim(user: Keypair, marketPda: PublicKey, marketId: BN, shareAta: PublicKey) {
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, user.publicKey);
+      const before = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint: deriveYesMint(marketPda)[0],
+          noMint: deriveNoMint(marketPda)[0],
+          vault: deriveVault(marketPda)[0],
+          userPosition: derivePosition(marketPda, user.publicKey)[0],
+          userAta,
+          userShareAccount: shareAta,
+          user: user.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([user])
+        .rpc();
+      return Number((await token.getAccount(provider.connection, userAta)).amount - before);
+    }
+
+    async function splitAndClaim(marketId: BN, yesBps: number) {
+      const marketPda = await createTestMarket(marketId, { bothSidesWin: true });
+      const yesAta = await placeBet(userA, marketId, "yes", 300_000_000);
+      const noAta = await placeBet(userB, marketId, "no", 200_000_000);
+
+      const total = (await program.account.market.fetch(marketPda)).totalCollateral;
+      const yesAllocation = total.muln(yesBps).divn(10000);
+      const noAllocation = total.sub(yesAllocation);
+      await program.methods
+        .resolveBothSides(marketId, yesAllocation, noAllocation)
+        .accounts({ market: marketPda, platformConfig, admin: admin.publicKey, payoutReserve: null })
+        .rpc();
+
+      const paidYes = await claim(userA, marketPda, marketId, yesAta);
+      const paidNo = await claim(userB, marketPda, marketId, noAta);
+      assert.equal(paidYes, yesAllocation.toNumber(), "YES holders receive their allocation");
+      assert.equal(paidNo, noAllocation.toNumber(), "NO holders receive their allocation");
+      assert.equal(paidYes + paidNo, total.toNumber(), "Allocations conserve the pool");
+      const [vault] = deriveVault(marketPda);
+      assert.equal(Number((await token.getAccount(provider.connection, vault)).amount), 0, "Vault fully paid out");
+      return { paidYes, paidNo };
+    }
+
+    it("Splits the pool evenly", async () => {
+      const { paidYes, paidNo } = await splitAndClaim(new BN(BASE_ID + 21), 5000);
+      console.log(`  ✓ Even split: YES ${paidYes / LAMPORTS_PER_SOL} SOL, NO ${paidNo / LAMPORTS_PER_SOL} SOL`);
+    });
+
+    it("Splits the pool 80/20", async () => {
+      const { paidYes, paidNo } = await splitAndClaim(new BN(BASE_ID + 22), 8000);
+      console.log(`  ✓ Skewed split: YES ${paidYes / LAMPORTS_PER_SOL} SOL, NO ${paidNo / LAMPORTS_PER_SOL} SOL`);
+    });
+
+    it("Rejects allocations that do not add up to the pool", async () => {
+      const marketId = new BN(BASE_ID + 23);
+      const marketPda = await createTestMarket(marketId, { bothSidesWin: true });
+      try {
+        await program.methods
+          .resolveBothSides(marketId, new BN(LAMPORTS_PER_SOL), new BN(1))
+          .accounts({ market: marketPda, platformConfig, admin: admin.publicKey, payoutReserve: null })
+          .rpc();
+        assert.fail("Should have thrown InvalidAllocation");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidAllocation");
+      }
+      console.log("  ✓ Over-allocation rejected");
+    });
+  });
+
+  // =========================================================================
+  // 14. lock_market
+  // =========================================================================
+  describe("lock_market", () => {
+    const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
+
+    it("Freezes reserves at lock and keeps them through later operations", async () => {
+      const marketId = new BN(BASE_ID + 24);
+      const now = Math.floor(Date.now() / 1000);
+      const marketPda = await createTestMarket(marketId, {
+        lockTimestamp: new BN(now + 6),
+        endTimestamp: new BN(now + 3600),
+      });
+      await placeBet(userA, marketId, "yes", 200_000_000);
+
+      try {
+        await program.methods.lockMarket(marketId).accounts({ market: marketPda }).rpc();
+        assert.fail("Should have thrown BettingStillOpen");
+      } catch (e: any) {
+        assert.include(e.message, "BettingStillOpen");
+      }
+
+      await sleep(8000);
+      await program.methods.lockMarket(marketId).accounts({ market: marketPda }).rpc();
+
+      const locked = await program.account.market.fetch(marketPda);
+      assert.ok(locked.status.locked !== undefined, "Market is Locked");
+      assert.equal(locked.lockedYesReserve.toString(), locked.totalYesShares.toString());
+      assert.equal(locked.lockedNoReserve.toString(), locked.totalNoShares.toString());
+
+      try {
+        await program.methods.lockMarket(marketId).accounts({ market: marketPda }).rpc();
+        assert.fail("Should have thrown MarketNotActive");
+      } catch (e: any) {
+        assert.include(e.message, "MarketNotActive");
+      }
+
+      // Pause/unpause re-locks the market; resolution follows
+      await program.methods
+        .pauseMarket(marketId)
+        .accounts({ market: marketPda, platformConfig, admin: admin.publicKey })
+        .rpc();
+      await program.methods
+        .unpauseMarket(marketId)
+        .accounts({ market: marketPda, platformConfig, admin: admin.publicKey })
+        .rpc();
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const after = await program.account.market.fetch(marketPda);
+      assert.equal(after.lockedYesReserve.toString(), locked.lockedYesReserve.toString(), "YES reserve unchanged");
+      assert.equal(after.lockedNoReserve.toString(), locked.lockedNoReserve.toString(), "NO reserve unchanged");
+      console.log(`  ✓ Closing reserves frozen: YES ${after.lockedYesReserve}, NO ${after.lockedNoReserve}`);
+    });
+  });
+
+  // =========================================================================
+  // 15. Minimum distinct bettors for resolution
+  // =========================================================================
+  describe("Minimum distinct bettors", () => {
+    const userB = Keypair.generate();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userB.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      await program.methods
+        .updateMinBettorsForResolution(new BN(2))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateMinBettorsForResolution(new BN(0))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    async function resolveYes(marketId: BN, marketPda: PublicKey) {
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      return program.account.market.fetch(marketPda);
+    }
+
+    it("Resolves Invalid when a single wallet did all the betting", async () => {
+      const marketId = new BN(BASE_ID + 25);
+      const marketPda = await createTestMarket(marketId);
+      await placeBet(userA, marketId, "yes", 100_000_000);
+      await placeBet(userA, marketId, "yes", 100_000_000);
+
+      assert.equal((await program.account.market.fetch(marketPda)).uniqueBettors.toNumber(), 1, "Repeat bets count once");
+      const market = await resolveYes(marketId, marketPda);
+      assert.ok(market.resolvedOutcome.invalid !== undefined, "Insufficient participation resolves Invalid");
+      console.log("  ✓ Single-bettor market refunded as Invalid");
+    });
+
+    it("Resolves normally once enough wallets have bet", async () => {
+      const marketId = new BN(BASE_ID + 26);
+      const marketPda = await createTestMarket(marketId);
+      await placeBet(userA, marketId, "yes", 100_000_000);
+      await placeBet(userB, marketId, "no", 100_000_000);
+
+      const market = await resolveYes(marketId, marketPda);
+      assert.equal(market.uniqueBettors.toNumber(), 2);
+      assert.ok(market.resolvedOutcome.yes !== undefined, "Well-participated market keeps its outcome");
+      console.log("  ✓ Two-bettor market resolved YES");
+    });
+  });
+
+  // =========================================================================
+  // 16. quote_fee
+  // =========================================================================
+  describe("quote_fee", () => {
+    it("Quotes exactly the fee place_bet charges", async () => {
+      const marketId = new BN(BASE_ID + 27);
+      const marketPda = await createTestMarket(marketId);
+      const [vault] = deriveVault(marketPda);
+      const amount = 123_456_789;
+
+      const quote = await program.methods
+        .quoteFee(marketId, { yes: {} }, new BN(amount), null)
+        .accounts({ market: marketPda })
+        .view();
+      assert.equal(quote.totalFee.toNumber(), Math.ceil((amount * 250) / 10000), "Fee rounds up");
+      assert.equal(
+        quote.platformFee.add(quote.creatorFee).add(quote.referrerFee).add(quote.lpFee).add(quote.insuranceFee).toString(),
+        quote.totalFee.toString(),
+        "Splits add up to the total"
+      );
+      assert.equal(quote.netAmount.add(quote.totalFee).toNumber(), amount);
+
+      const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      const vaultBefore = (await token.getAccount(provider.connection, vault)).amount;
+      await placeBet(userA, marketId, "yes", amount);
+      const treasuryAfter = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      const vaultAfter = (await token.getAccount(provider.connection, vault)).amount;
+
+      assert.equal(Number(treasuryAfter - treasuryBefore), quote.platformFee.toNumber(), "Treasury got the quoted fee");
+      assert.equal(Number(vaultAfter - vaultBefore), quote.netAmount.toNumber(), "Vault got the quoted net");
+      console.log(`  ✓ Quoted fee ${quote.totalFee} lamports charged exactly`);
+    });
+  });
+
+  // =========================================================================
+  // 17. consolidate_dust
+  // =========================================================================
+  describe("consolidate_dust", () => {
+    const userC = Keypair.generate();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userC.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    it("Claims, burns leftovers and closes positions in resolved markets only", async () => {
+      const winId = new BN(BASE_ID + 28);
+      const loseId = new BN(BASE_ID + 29);
+      const openId = new BN(BASE_ID + 30);
+      const remainingAccounts = [];
+      const positions: Record<string, PublicKey> = {};
+      const shareAtas: Record<string, PublicKey[]> = {};
+
+      for (const [marketId, side, outcome] of [
+        [winId, "yes", { yes: {} }],
+        [loseId, "no", { yes: {} }],
+        [openId, "yes", null],
+      ] as [BN, "yes" | "no", any][]) {
+        const marketPda = await createTestMarket(marketId);
+        const [yesMint] = deriveYesMint(marketPda);
+        const [noMint] = deriveNoMint(marketPda);
+        await placeBet(userC, marketId, side, 100_000_000);
+        const yesAta = (await token.getOrCreateAssociatedTokenAccount(
+          provider.connection, adminPayer, yesMint, userC.publicKey
+        )).address;
+        const noAta = (await token.getOrCreateAssociatedTokenAccount(
+          provider.connection, adminPayer, noMint, userC.publicKey
+        )).address;
+        if (outcome) {
+          await program.methods
+            .resolveMarket(marketId, outcome)
+            .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+            .rpc();
+        }
+
+        const [position] = derivePosition(marketPda, userC.publicKey);
+        positions[marketId.toString()] = position;
+        shareAtas[marketId.toString()] = [yesAta, noAta];
+        remainingAccounts.push(
+          { pubkey: marketPda, isWritable: true, isSigner: false },
+          { pubkey: yesMint, isWritable: true, isSigner: false },
+          { pubkey: noMint, isWritable: true, isSigner: false },
+          { pubkey: deriveVault(marketPda)[0], isWritable: true, isSigner: false },
+          { pubkey: position, isWritable: true, isSigner: false },
+          { pubkey: yesAta, isWritable: true, isSigner: false },
+          { pubkey: noAta, isWritable: true, isSigner: false },
+        );
+      }
+
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userC.publicKey);
+      const collateralBefore = Number((await token.getAccount(provider.connection, userAta)).amount);
+      const lamportsBefore = await provider.connection.getBalance(userC.publicKey);
+
+      await program.methods
+        .consolidateDust()
+        .accounts({ userAta, user: userC.publicKey, collateralMint: WSOL_MINT })
+        .remainingAccounts(remainingAccounts)
+        .signers([userC])
+        .rpc();
+
+      for (const marketId of [winId, loseId]) {
+        assert.isNull(await provider.connection.getAccountInfo(positions[marketId.toString()]), "Position closed");
+        for (const ata of shareAtas[marketId.toString()]) {
+          assert.isNull(await provider.connection.getAccountInfo(ata), "Share account closed");
+        }
+      }
+      assert.isNotNull(await provider.connection.getAccountInfo(positions[openId.toString()]), "Unresolved market skipped");
+
+      const collateralAfter = Number((await token.getAccount(provider.connection, userAta)).amount);
+      const lamportsAfter = await provider.connection.getBalance(userC.publicKey);
+      assert.isAbove(collateralAfter, collateralBefore, "Winning payout claimed");
+      assert.isAbove(lamportsAfter, lamportsBefore, "Rent refunded net of the fee");
+      console.log(`  ✓ Claimed ${(collateralAfter - collateralBefore) / LAMPORTS_PER_SOL} SOL, refunded ${(lamportsAfter - lamportsBefore) / LAMPORTS_PER_SOL} SOL rent`);
+    });
+  });
+
+  // =========================================================================
+  // 18. Emergency shutdown
+  // =========================================================================
+  describe("Emergency shutdown", () => {
+    const userD = Keypair.generate();
+    const lp = Keypair.generate();
+    const marketIds = [new BN(BASE_ID + 31), new BN(BASE_ID + 32)];
+    const lpMarketId = new BN(BASE_ID + 62);
+    const LP_DEPOSIT = 400_000_000;
+    let lpMarketPda: PublicKey;
+
+    before(async () => {
+      for (const u of [userD, lp]) {
+        const sig = await provider.connection.requestAirdrop(u.publicKey, 5 * LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+      await createTestMarket(marketIds[0]);
+      await createTestMarket(marketIds[1]);
+      await placeBet(userA, marketIds[0], "yes", 300_000_000);
+      await placeBet(userD, marketIds[0], "no", 200_000_000);
+      await placeBet(userA, marketIds[1], "yes", 100_000_000);
+
+      // A market with LP liquidity alongside its bettors
+      lpMarketPda = await createTestMarket(lpMarketId);
+      await placeBet(userD, lpMarketId, "yes", 200_000_000);
+      const [lpMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lp_mint"), lpMarketPda.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .addLiquidity(lpMarketId, new BN(LP_DEPOSIT))
+        .accounts({
+          market: lpMarketPda,
+          providerAta: await fundWsol(lp, LP_DEPOSIT),
+          providerLpAccount: await token.getAssociatedTokenAddress(lpMint, lp.publicKey),
+          platformConfig,
+          provider: lp.publicKey,
+        } as any)
+        .signers([lp])
+        .rpc();
+    });
+
+    after(async () => {
+      const config = await program.account.platformConfig.fetch(platformConfig);
+      if (config.shutdown) {
+        await program.methods.resumeFromShutdown().accounts({ platformConfig, admin: admin.publicKey }).rpc();
+      }
+      await program.methods.unpausePlatform().accounts({ platformConfig, admin: admin.publicKey }).rpc();
+    });
+
+    async function refund(user: Keypair, marketId: BN) {
+      const [marketPda] = deriveMarketPda(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const userYes = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, yesMint, user.publicKey);
+      const userNo = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, noMint, user.publicKey);
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, user.publicKey);
+
+      // Expected: pool (less the LPs' part) * (yes + no held) / (yes + no supply)
+      const market = await program.account.market.fetch(marketPda);
+      const supply =
+        (await token.getMint(provider.connection, yesMint)).supply +
+        (await token.getMint(provider.connection, noMint)).supply;
+      const held = userYes.amount + userNo.amount;
+      const pool = market.totalCollateral.sub(market.lpLiquidity).sub(market.lpFeesAccrued);
+      const expected = (BigInt(pool.toString()) * held) / supply;
+
+      const before = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .claimEmergencyRefund(marketId)
+        .accounts({
+          market: marketPda,
+          userPosition: derivePosition(marketPda, user.publicKey)[0],
+          userAta,
+          userYesAccount: userYes.address,
+          userNoAccount: userNo.address,
+          platformConfig,
+          user: user.publicKey,
+          collateralMint: WSOL_MINT,
+        } as any)
+        .signers([user])
+        .rpc();
+      const paid = (await token.getAccount(provider.connection, userAta)).amount - before;
+      assert.equal(paid.toString(), expected.toString(), "Pro-rata slice of the pool");
+      return paid;
+    }
+
+    it("Refunds are unavailable before a shutdown", async () => {
+      try {
+        await refund(userA, marketIds[1]);
+        assert.fail("Should have thrown PlatformNotShutdown");
+      } catch (e: any) {
+        assert.include(e.message, "PlatformNotShutdown");
+      }
+      console.log("  ✓ Refund rejected while the platform is running");
+    });
+
+    it("Pays every holder a pro-rata refund across unresolved markets", async () => {
+      await program.methods.emergencyShutdown().accounts({ platformConfig, admin: admin.publicKey }).rpc();
+
+      try {
+        await placeBet(userA, marketIds[1], "no", 100_000_000);
+        assert.fail("Should have thrown PlatformPaused");
+      } catch (e: any) {
+        assert.include(e.message, "PlatformPaused");
+      }
+      try {
+        await program.methods
+          .resolveMarket(marketIds[1], { yes: {} })
+          .accounts({ market: deriveMarketPda(marketIds[1])[0], admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+          .rpc();
+        assert.fail("Should have thrown PlatformShutdown");
+      } catch (e: any) {
+        assert.include(e.message, "PlatformShutdown");
+      }
+
+      const paidA = await refund(userA, marketIds[0]);
+      const paidD = await refund(userD, marketIds[0]);
+      const paidA2 = await refund(userA, marketIds[1]);
+
+      for (const marketId of marketIds) {
+        const [vault] = deriveVault(deriveMarketPda(marketId)[0]);
+        const left = Number((await token.getAccount(provider.connection, vault)).amount);
+        assert.isAtMost(left, 1, "Last holder drains the pool (up to rounding)");
+      }
+      console.log(`  ✓ Refunds: ${paidA} + ${paidD} lamports (market 1), ${paidA2} lamports (market 2)`);
+    });
+
+    it("Keeps LP deposits out of refunds and lets LPs withdraw", async () => {
+      // Bettor first: the refund must leave the LP deposit in the vault
+      await refund(userD, lpMarketId);
+      const [vault] = deriveVault(lpMarketPda);
+      const vaultAmount = async () => Number((await token.getAccount(provider.connection, vault)).amount);
+      assert.isAtLeast(await vaultAmount(), LP_DEPOSIT, "LP deposit still in the vault");
+
+      const [lpMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lp_mint"), lpMarketPda.toBuffer()],
+        program.programId
+      );
+      const providerAta = await token.getAssociatedTokenAddress(WSOL_MINT, lp.publicKey);
+      const before = (await token.getAccount(provider.connection, providerAta)).amount;
+      await program.methods
+        .removeLiquidity(lpMarketId, new BN(LP_DEPOSIT))
+        .accounts({
+          market: lpMarketPda,
+          providerAta,
+          providerLpAccount: await token.getAssociatedTokenAddress(lpMint, lp.publicKey),
+          platformConfig,
+          provider: lp.publicKey,
+        } as any)
+        .signers([lp])
+        .rpc();
+      const paid = (await token.getAccount(provider.connection, providerAta)).amount - before;
+      assert.equal(Number(paid), LP_DEPOSIT, "Deposit returned during the shutdown");
+      assert.isAtMost(await vaultAmount(), 1, "Bettor and LP drain the pool (up to rounding)");
+      console.log(`  ✓ LP withdrew ${paid} lamports from an unresolved market`);
+    });
+
+    it("Resume requires an active shutdown", async () => {
+      await program.methods.resumeFromShutdown().accounts({ platformConfig, admin: admin.publicKey }).rpc();
+      try {
+        await program.methods.resumeFromShutdown().accounts({ platformConfig, admin: admin.publicKey }).rpc();
+        assert.fail("Should have thrown PlatformNotShutdown");
+      } catch (e: any) {
+        assert.include(e.message, "PlatformNotShutdown");
+      }
+      console.log("  ✓ Shutdown lifted once");
+    });
+  });
+
+  // =========================================================================
+  // 19. Dust recipient
+  // =========================================================================
+  describe("Dust recipient", () => {
+    const userE = Keypair.generate();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userE.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    async function claimYes(user: Keypair, marketId: BN, marketPda: PublicKey) {
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, user.publicKey);
+      const [yesMint] = deriveYesMint(marketPda);
+      const before = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint: deriveNoMint(marketPda)[0],
+          vault: deriveVault(marketPda)[0],
+          userPosition: derivePosition(marketPda, user.publicKey)[0],
+          userAta,
+          userShareAccount: await token.getAssociatedTokenAddress(yesMint, user.publicKey),
+          user: user.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([user])
+        .rpc();
+      return (await token.getAccount(provider.connection, userAta)).amount - before;
+    }
+
+    // Two YES winners with uneven stakes claim in turn. Returns the residue the
+    // last claimer's exact share leaves in the pool.
+    async function resolveAndClaim(marketId: BN, overrides: any = {}) {
+      const marketPda = await createTestMarket(marketId, overrides);
+      const ataA = await placeBet(userA, marketId, "yes", 123_456_789);
+      const ataE = await placeBet(userE, marketId, "yes", 98_765_433);
+      await placeBet(userA, marketId, "no", 77_777_777);
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const pool = BigInt((await program.account.market.fetch(marketPda)).distributableCollateral.toString());
+      const supply = (await token.getMint(provider.connection, deriveYesMint(marketPda)[0])).supply;
+      const sharesE = (await token.getAccount(provider.connection, ataE)).amount;
+      assert.isTrue((await token.getAccount(provider.connection, ataA)).amount > 0n);
+
+      const paidA = await claimYes(userA, marketId, marketPda);
+      const paidE = await claimYes(userE, marketId, marketPda);
+      const exactE = (sharesE * pool) / supply;
+      const dust = pool - paidA - exactE;
+      return { marketPda, paidE, exactE, dust };
+    }
+
+    it("Pays the residue to the last claimer by default", async () => {
+      const { marketPda, paidE, exactE, dust } = await resolveAndClaim(new BN(BASE_ID + 33));
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(market.dustRecipient.lastClaimer !== undefined, "LastClaimer is the default");
+      assert.equal(paidE.toString(), (exactE + dust).toString(), "Last claimer takes the residue");
+      assert.equal(market.pendingDust.toNumber(), 0);
+      assert.equal(Number((await token.getAccount(provider.connection, deriveVault(marketPda)[0])).amount), 0);
+      console.log(`  ✓ Last claimer received ${dust} lamports of dust`);
+    });
+
+    it("Sweeps the residue to the treasury", async () => {
+      const marketId = new BN(BASE_ID + 34);
+      const { marketPda, paidE, exactE, dust } = await resolveAndClaim(marketId, { dustRecipient: { treasury: {} } });
+      assert.equal(paidE.toString(), exactE.toString(), "Last claimer gets its exact share");
+      assert.equal((await program.account.market.fetch(marketPda)).pendingDust.toString(), dust.toString());
+
+      if (dust > 0n) {
+        const before = (await token.getAccount(provider.connection, treasuryAta)).amount;
+        await program.methods
+          .sweepVaultSurplus(marketId)
+          .accounts({ market: marketPda, platformConfig, treasury: treasuryAta, creatorAta: null, admin: admin.publicKey })
+          .rpc();
+        const after = (await token.getAccount(provider.connection, treasuryAta)).amount;
+        assert.equal((after - before).toString(), dust.toString(), "Dust swept to treasury");
+        assert.equal((await program.account.market.fetch(marketPda)).pendingDust.toNumber(), 0);
+      }
+      console.log(`  ✓ ${dust} lamports of dust routed to the treasury`);
+    });
+
+    it("Sweeps the residue to the market creator", async () => {
+      const marketId = new BN(BASE_ID + 35);
+      const { marketPda, paidE, exactE, dust } = await resolveAndClaim(marketId, { dustRecipient: { creator: {} } });
+      assert.equal(paidE.toString(), exactE.toString(), "Last claimer gets its exact share");
+
+      // Hand the market to a wallet distinct from the treasury owner
+      await program.methods
+        .transferMarketCreator(marketId, userE.publicKey)
+        .accounts({ market: marketPda, platformConfig, creator: admin.publicKey, admin: admin.publicKey } as any)
+        .rpc();
+      const creatorAta = await token.getAssociatedTokenAddress(WSOL_MINT, userE.publicKey);
+
+      if (dust > 0n) {
+        try {
+          await program.methods
+            .sweepVaultSurplus(marketId)
+            .accounts({ market: marketPda, platformConfig, treasury: treasuryAta, creatorAta: null, admin: admin.publicKey })
+            .rpc();
+          assert.fail("Should have thrown DustAccountRequired");
+        } catch (e: any) {
+          assert.include(e.message, "DustAccountRequired");
+        }
+
+        const creatorBefore = (await token.getAccount(provider.connection, creatorAta)).amount;
+        const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+        await program.methods
+          .sweepVaultSurplus(marketId)
+          .accounts({ market: marketPda, platformConfig, treasury: treasuryAta, creatorAta, admin: admin.publicKey })
+          .rpc();
+        const creatorAfter = (await token.getAccount(provider.connection, creatorAta)).amount;
+        const treasuryAfter = (await token.getAccount(provider.connection, treasuryAta)).amount;
+        assert.equal((creatorAfter - creatorBefore).toString(), dust.toString(), "Dust swept to creator");
+        assert.equal(treasuryAfter, treasuryBefore, "Treasury receives none of it");
+      }
+      console.log(`  ✓ ${dust} lamports of dust routed to the creator`);
+    });
+
+    it("Keeps the residue in the vault of a recurring market", async () => {
+      try {
+        await createTestMarket(new BN(BASE_ID + 36), { dustRecipient: { vault: {} } });
+        assert.fail("Should have thrown InvalidDustRecipient");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidDustRecipient");
+      }
+
+      const marketId = new BN(BASE_ID + 37);
+      const { marketPda, paidE, exactE, dust } = await resolveAndClaim(marketId, {
+        dustRecipient: { vault: {} },
+        isRecurring: true,
+      });
+      assert.equal(paidE.toString(), exactE.toString(), "Last claimer gets its exact share");
+      const vaultAmount = (await token.getAccount(provider.connection, deriveVault(marketPda)[0])).amount;
+      assert.equal(vaultAmount.toString(), dust.toString(), "Dust left in the vault");
+
+      try {
+        await program.methods
+          .sweepVaultSurplus(marketId)
+          .accounts({ market: marketPda, platformConfig, treasury: treasuryAta, creatorAta: null, admin: admin.publicKey })
+          .rpc();
+        assert.fail("Should have thrown InsufficientVault");
+      } catch (e: any) {
+        assert.include(e.message, "InsufficientVault");
+      }
+      console.log(`  ✓ ${dust} lamports of dust kept in the vault`);
+    });
+  });
+
+  // =========================================================================
+  // 20. verify_fee_accounting
+  // =========================================================================
+  describe("verify_fee_accounting", () => {
+    it("Fee destinations reconcile after bets and a cancel", async () => {
+      const marketId = new BN(BASE_ID + 38);
+      const marketPda = await createTestMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+
+      const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      const yesAta = await placeBet(userA, marketId, "yes", 150_000_000);
+      await placeBet(userA, marketId, "no", 80_000_000);
+      await placeBet(userA, marketId, "yes", 33_333_333);
+
+      const shares = (await token.getAccount(provider.connection, yesAta)).amount / 2n;
+      await program.methods
+        .cancelBet(marketId, new BN(shares.toString()))
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault: deriveVault(marketPda)[0],
+          userPosition: derivePosition(marketPda, userA.publicKey)[0],
+          userAta: await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey),
+          userShareAccount: yesAta,
+          platformConfig,
+          treasury: treasuryAta,
+          user: userA.publicKey,
+          collateralMint: WSOL_MINT,
+        } as any)
+        .signers([userA])
+        .rpc();
+      const treasuryAfter = (await token.getAccount(provider.connection, treasuryAta)).amount;
+
+      const ledger = await program.methods
+        .verifyFeeAccounting(marketId)
+        .accounts({ market: marketPda })
+        .view();
+      const credited = ledger.treasury.add(ledger.creator).add(ledger.referrer).add(ledger.lp).add(ledger.insurance);
+      assert.equal(credited.toString(), ledger.feesAccrued.toString(), "Destinations sum to fees charged");
+      assert.equal(ledger.treasury.toString(), (treasuryAfter - treasuryBefore).toString(), "Treasury counter matches transfers");
+      // No referral split exists yet: every fee is credited to the treasury
+      assert.equal(ledger.referrer.toNumber(), 0);
+      console.log(`  ✓ ${ledger.feesAccrued} lamports of fees reconciled`);
+    });
+  });
+
+  // =========================================================================
+  // 21. fork_market
+  // =========================================================================
+  describe("fork_market", () => {
+    const userF = Keypair.generate();
+    const parentId = new BN(BASE_ID + 39);
+    const childIds = { yes: new BN(BASE_ID + 40), no: new BN(BASE_ID + 41) };
+    const YES_BPS = 6000;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userF.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    after(async () => {
+      await program.methods.updateForkingEnabled(false).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+    });
+
+    function fork(outcome: "yes" | "no") {
+      const [parentPda] = deriveMarketPda(parentId);
+      return program.methods
+        .forkMarket(parentId, childIds[outcome], outcome === "yes" ? { yes: {} } : { no: {} }, YES_BPS)
+        .accounts({
+          market: parentPda,
+          disputeRecord: PublicKey.findProgramAddressSync([Buffer.from("dispute"), parentPda.toBuffer()], program.programId)[0],
+          childMarket: deriveMarketPda(childIds[outcome])[0],
+          platformConfig,
+          admin: admin.publicKey,
+          collateralMint: WSOL_MINT,
+        } as any)
+        .rpc();
+    }
+
+    async function shareAtas(marketPda: PublicKey, user: Keypair) {
+      const yes = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, deriveYesMint(marketPda)[0], user.publicKey);
+      const no = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, deriveNoMint(marketPda)[0], user.publicKey);
+      return { yes: yes.address, no: no.address };
+    }
+
+    async function migrate(user: Keypair) {
+      const [parentPda] = deriveMarketPda(parentId);
+      const [yesChild] = deriveMarketPda(childIds.yes);
+      const [noChild] = deriveMarketPda(childIds.no);
+      const parentAtas = await shareAtas(parentPda, user);
+      const yesChildAtas = await shareAtas(yesChild, user);
+      const noChildAtas = await shareAtas(noChild, user);
+      await program.methods
+        .migrateForkPosition()
+        .accounts({
+          market: parentPda,
+          userPosition: derivePosition(parentPda, user.publicKey)[0],
+          userYesAccount: parentAtas.yes,
+          userNoAccount: parentAtas.no,
+          yesChild,
+          yesChildYesMint: deriveYesMint(yesChild)[0],
+          yesChildNoMint: deriveNoMint(yesChild)[0],
+          yesChildUserYes: yesChildAtas.yes,
+          yesChildUserNo: yesChildAtas.no,
+          yesChildPosition: derivePosition(yesChild, user.publicKey)[0],
+          noChild,
+          noChildYesMint: deriveYesMint(noChild)[0],
+          noChildNoMint: deriveNoMint(noChild)[0],
+          noChildUserYes: noChildAtas.yes,
+          noChildUserNo: noChildAtas.no,
+          noChildPosition: derivePosition(noChild, user.publicKey)[0],
+          user: user.publicKey,
+        } as any)
+        .signers([user])
+        .rpc();
+    }
+
+    async function claim(user: Keypair, marketId: BN, shareAta: PublicKey) {
+      const [marketPda] = deriveMarketPda(marketId);
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, user.publicKey);
+      const before = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint: deriveYesMint(marketPda)[0],
+          noMint: deriveNoMint(marketPda)[0],
+          vault: deriveVault(marketPda)[0],
+          userPosition: derivePosition(marketPda, user.publicKey)[0],
+          userAta,
+          userShareAccount: shareAta,
+          user: user.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([user])
+        .rpc();
+      return (await token.getAccount(provider.connection, userAta)).amount - before;
+    }
+
+    it("Forks a disputed market and conserves positions and collateral", async () => {
+      const parentPda = await createTestMarket(parentId);
+      await placeBet(userA, parentId, "yes", 200_000_000);
+      await placeBet(userF, parentId, "no", 150_000_000);
+      await program.methods
+        .resolveMarket(parentId, { yes: {} })
+        .accounts({ market: parentPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      await program.methods
+        .openDispute(parentId, "Contested outcome")
+        .accounts({ market: parentPda, platformConfig, disputer: userF.publicKey } as any)
+        .signers([userF])
+        .rpc();
+
+      try {
+        await fork("yes");
+        assert.fail("Should have thrown ForkingDisabled");
+      } catch (e: any) {
+        assert.include(e.message, "ForkingDisabled");
+      }
+      await program.methods.updateForkingEnabled(true).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+
+      const pool = BigInt((await program.account.market.fetch(parentPda)).distributableCollateral.toString());
+      const supplyBefore = {
+        yes: (await token.getMint(provider.connection, deriveYesMint(parentPda)[0])).supply,
+        no: (await token.getMint(provider.connection, deriveNoMint(parentPda)[0])).supply,
+      };
+      await fork("yes");
+      await fork("no");
+      try {
+        await fork("no");
+        assert.fail("Should have rejected a second NO child");
+      } catch (e: any) {
+        assert.include(e.message, "already in use");
+      }
+
+      // Collateral: the pool is split between the children by YES_BPS
+      const parent = await program.account.market.fetch(parentPda);
+      assert.ok(parent.status.forked !== undefined);
+
+      // A pause/unpause can't turn the parent back into Resolved and block migration
+      for (const [method, error] of [["pauseMarket", "AlreadyResolved"], ["unpauseMarket", "MarketNotPaused"]] as const) {
+        try {
+          await program.methods[method](parentId)
+            .accounts({ market: parentPda, platformConfig, admin: admin.publicKey })
+            .rpc();
+          assert.fail(`Should have thrown ${error}`);
+        } catch (e: any) {
+          assert.include(e.message, error);
+        }
+      }
+      assert.ok((await program.account.market.fetch(parentPda)).status.forked !== undefined, "Still Forked");
+      const vaults = {
+        yes: (await token.getAccount(provider.connection, deriveVault(deriveMarketPda(childIds.yes)[0])[0])).amount,
+        no: (await token.getAccount(provider.connection, deriveVault(deriveMarketPda(childIds.no)[0])[0])).amount,
+      };
+      assert.equal(vaults.yes.toString(), ((pool * BigInt(YES_BPS)) / 10000n).toString());
+      assert.equal((vaults.yes + vaults.no).toString(), pool.toString(), "Children hold the whole pool");
+      assert.equal(parent.distributableCollateral.toNumber(), 0);
+      for (const outcome of ["yes", "no"] as const) {
+        const child = await program.account.market.fetch(deriveMarketPda(childIds[outcome])[0]);
+        assert.ok(child.parentMarket.equals(parentPda));
+        assert.ok(child.resolvedOutcome[outcome] !== undefined, "Child resolved to its outcome");
+        // close_market needs the child's creator fee escrow
+        const [escrow] = PublicKey.findProgramAddressSync(
+          [Buffer.from("creator_fees"), deriveMarketPda(childIds[outcome])[0].toBuffer()],
+          program.programId
+        );
+        assert.ok((await token.getAccount(provider.connection, escrow)).owner.equals(deriveMarketPda(childIds[outcome])[0]));
+      }
+
+      // Positions: every parent share reappears in both children
+      await migrate(userA);
+      await migrate(userF);
+      for (const outcome of ["yes", "no"] as const) {
+        const [childPda] = deriveMarketPda(childIds[outcome]);
+        assert.equal((await token.getMint(provider.connection, deriveYesMint(childPda)[0])).supply, supplyBefore.yes);
+        assert.equal((await token.getMint(provider.connection, deriveNoMint(childPda)[0])).supply, supplyBefore.no);
+      }
+      assert.equal((await token.getMint(provider.connection, deriveYesMint(parentPda)[0])).supply, 0n);
+      assert.equal((await token.getMint(provider.connection, deriveNoMint(parentPda)[0])).supply, 0n);
+
+      // Each side's winners drain their child
+      const paidA = await claim(userA, childIds.yes, (await shareAtas(deriveMarketPda(childIds.yes)[0], userA)).yes);
+      const paidF = await claim(userF, childIds.no, (await shareAtas(deriveMarketPda(childIds.no)[0], userF)).no);
+      assert.equal(paidA.toString(), vaults.yes.toString());
+      assert.equal(paidF.toString(), vaults.no.toString());
+      assert.equal((paidA + paidF).toString(), pool.toString(), "Collateral conserved across children");
+      console.log(`  ✓ Pool ${pool} forked: ${paidA} (YES child) + ${paidF} (NO child)`);
+    });
+  });
+
+  // =========================================================================
+  // 22. forfeit_dust_positions
+  // =========================================================================
+  describe("forfeit_dust_positions", () => {
+    const tiny = Keypair.generate();
+    const loser = Keypair.generate();
+    const MIN_CLAIMABLE_DUST = 50_000_000;
+    const DEFAULT_CLAIM_WINDOW_SECS = 30 * 24 * 60 * 60;
+
+    before(async () => {
+      for (const u of [tiny, loser]) {
+        const sig = await provider.connection.requestAirdrop(u.publicKey, 2 * LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+      await program.methods.updateMinClaimableDust(new BN(MIN_CLAIMABLE_DUST)).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+    });
+
+    after(async () => {
+      await program.methods.updateMinClaimableDust(new BN(0)).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+      await program.methods.updateClaimWindow(new BN(DEFAULT_CLAIM_WINDOW_SECS)).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+    });
+
+    async function positionAccounts(marketPda: PublicKey, user: Keypair) {
+      const yes = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, deriveYesMint(marketPda)[0], user.publicKey);
+      const no = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, deriveNoMint(marketPda)[0], user.publicKey);
+      return [
+        { pubkey: derivePosition(marketPda, user.publicKey)[0], isSigner: false, isWritable: true },
+        { pubkey: yes.address, isSigner: false, isWritable: false },
+        { pubkey: no.address, isSigner: false, isWritable: false },
+      ];
+    }
+
+    function forfeit(marketId: BN, remainingAccounts: any[]) {
+      return program.methods
+        .forfeitDustPositions(marketId)
+        .accounts({ market: deriveMarketPda(marketId)[0], platformConfig, treasury: treasuryAta, admin: admin.publicKey } as any)
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+    }
+
+    it("Forfeits sub-threshold positions so the market closes", async () => {
+      const marketId = new BN(BASE_ID + 42);
+      const marketPda = await createTestMarket(marketId);
+      const [vault] = deriveVault(marketPda);
+      const bigAta = await placeBet(userA, marketId, "yes", 500_000_000);
+      await placeBet(tiny, marketId, "yes", 10_000_000);
+      await placeBet(loser, marketId, "no", 10_000_000);
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const dustAccounts = [...(await positionAccounts(marketPda, tiny)), ...(await positionAccounts(marketPda, loser))];
+      try {
+        await forfeit(marketId, dustAccounts);
+        assert.fail("Should have thrown ClaimWindowOpen");
+      } catch (e: any) {
+        assert.include(e.message, "ClaimWindowOpen");
+      }
+      await program.methods.updateClaimWindow(new BN(0)).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+
+      try {
+        await forfeit(marketId, await positionAccounts(marketPda, userA));
+        assert.fail("Should have thrown PositionAboveDustThreshold");
+      } catch (e: any) {
+        assert.include(e.message, "PositionAboveDustThreshold");
+      }
+
+      // The large winner claims; the tiny winner and the loser never do
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey);
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint: deriveYesMint(marketPda)[0],
+          noMint: deriveNoMint(marketPda)[0],
+          vault,
+          userPosition: derivePosition(marketPda, userA.publicKey)[0],
+          userAta,
+          userShareAccount: bigAta,
+          user: userA.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([userA])
+        .rpc();
+
+      const left = (await token.getAccount(provider.connection, vault)).amount;
+      assert.isTrue(left > 0n && left < BigInt(MIN_CLAIMABLE_DUST), "Only the tiny winner's slice remains");
+      const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      await forfeit(marketId, dustAccounts);
+      const treasuryAfter = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      assert.equal((treasuryAfter - treasuryBefore).toString(), left.toString(), "Forfeited slice sent to the treasury");
+      assert.isTrue((await program.account.market.fetch(marketPda)).dustForfeited);
+
+      // The forfeited position can no longer claim
+      const tinyYes = await token.getAssociatedTokenAddress(deriveYesMint(marketPda)[0], tiny.publicKey);
+      try {
+        await program.methods
+          .claimPayout(marketId)
+          .accounts({
+            market: marketPda,
+            yesMint: deriveYesMint(marketPda)[0],
+            noMint: deriveNoMint(marketPda)[0],
+            vault,
+            userPosition: derivePosition(marketPda, tiny.publicKey)[0],
+            userAta: await token.getAssociatedTokenAddress(WSOL_MINT, tiny.publicKey),
+            userShareAccount: tinyYes,
+            user: tiny.publicKey,
+            collateralMint: WSOL_MINT,
+            payoutReserve: null,
+            userPayoutAccount: null,
+          })
+          .signers([tiny])
+          .rpc();
+        assert.fail("Should have thrown PositionForfeited");
+      } catch (e: any) {
+        assert.include(e.message, "PositionForfeited");
+      }
+
+      // Shares remain outstanding, yet the market closes
+      await program.methods
+        .closeMarket(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint: deriveYesMint(marketPda)[0],
+          noMint: deriveNoMint(marketPda)[0],
+          vault,
+          platformConfig,
+          admin: admin.publicKey,
+        } as any)
+        .rpc();
+      assert.isNull(await provider.connection.getAccountInfo(marketPda), "Market closed");
+      console.log(`  ✓ ${left} lamports of dust forfeited; market closed`);
+    });
+  });
+
+  // =========================================================================
+  // 23. Platform TVL cap
+  // =========================================================================
+  describe("Platform TVL cap", () => {
+    const setCap = (cap: BN) =>
+      program.methods
+        .updateMaxPlatformTvl(cap)
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+    after(async () => {
+      await setCap(new BN(0));
+    });
+
+    it("Fills the platform to its cap and rejects the overflowing bet", async () => {
+      const marketId = new BN(BASE_ID + 43);
+      const marketPda = await createTestMarket(marketId);
+
+      // Bets track the pool: TVL grows by exactly what total_collateral does
+      const market = await program.account.market.fetch(marketPda);
+      const tvlBefore = (await program.account.platformConfig.fetch(platformConfig)).totalTvl;
+      await placeBet(userA, marketId, "yes", 100_000_000);
+      const credited = (await program.account.market.fetch(marketPda)).totalCollateral.sub(market.totalCollateral);
+      const tvlAfter = (await program.account.platformConfig.fetch(platformConfig)).totalTvl;
+      assert.equal(tvlAfter.sub(tvlBefore).toString(), credited.toString(), "TVL follows total_collateral");
+
+      // Room for exactly one more identical bet
+      await setCap(tvlAfter.add(credited));
+      await placeBet(userA, marketId, "no", 100_000_000);
+      const full = await program.account.platformConfig.fetch(platformConfig);
+      assert.equal(full.totalTvl.toString(), full.maxPlatformTvl.toString(), "Filled to the cap");
+
+      try {
+        await placeBet(userA, marketId, "yes", 10_000_000);
+        assert.fail("Should have thrown PlatformTvlCapReached");
+      } catch (e: any) {
+        assert.include(e.message, "PlatformTvlCapReached");
+      }
+      console.log(`  ✓ TVL capped at ${full.maxPlatformTvl} lamports`);
+    });
+  });
+
+  // =========================================================================
+  // 24. Slot-start pricing
+  // =========================================================================
+  describe("Slot-start pricing", () => {
+    const userG = Keypair.generate();
+    const AMOUNT = 200_000_000;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userG.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    async function betIx(marketId: BN, side: "yes" | "no", amount = AMOUNT) {
+      const [marketPda] = deriveMarketPda(marketId);
+      const [shareMint] = side === "yes" ? deriveYesMint(marketPda) : deriveNoMint(marketPda);
+      const shareAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, shareMint, userG.publicKey
+      );
+      const ix = await program.methods
+        .placeBet(marketId, side === "yes" ? { yes: {} } : { no: {} }, new BN(amount), new BN(0))
+        .accounts({
+          user: userG.publicKey,
+          userShareAccount: shareAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .instruction();
+      return { ix, shareAta: shareAta.address };
+    }
+
+    // Both bets in one transaction, so they land in the same slot
+    async function betBothInOneSlot(marketId: BN, order: ("yes" | "no")[]) {
+      await fundWsol(userG, 2 * AMOUNT);
+      const bets: Record<string, { ix: anchor.web3.TransactionInstruction; shareAta: PublicKey }> = {};
+      for (const side of order) {
+        bets[side] = await betIx(marketId, side);
+      }
+      const tx = new anchor.web3.Transaction().add(...order.map((side) => bets[side].ix));
+      await provider.sendAndConfirm(tx, [userG]);
+      const balance = async (ata: PublicKey) => (await token.getAccount(provider.connection, ata)).amount;
+      return { yes: await balance(bets.yes.shareAta), no: await balance(bets.no.shareAta) };
+    }
+
+    it("Two bets in the same slot get the same shares in either order", async () => {
+      const yesFirst = new BN(BASE_ID + 44);
+      const noFirst = new BN(BASE_ID + 45);
+      const sequential = new BN(BASE_ID + 46);
+      await createTestMarket(yesFirst, { slotStartPricing: true });
+      await createTestMarket(noFirst, { slotStartPricing: true });
+      await createTestMarket(sequential);
+
+      const a = await betBothInOneSlot(yesFirst, ["yes", "no"]);
+      const b = await betBothInOneSlot(noFirst, ["no", "yes"]);
+      assert.equal(a.yes.toString(), b.yes.toString(), "YES shares independent of order");
+      assert.equal(a.no.toString(), b.no.toString(), "NO shares independent of order");
+      // Both priced off the even slot-start pool
+      assert.equal(a.yes.toString(), a.no.toString());
+
+      const market = await program.account.market.fetch(deriveMarketPda(yesFirst)[0]);
+      assert.equal(market.slotStartReserves[0].toString(), LAMPORTS_PER_SOL.toString(), "Snapshot is the pre-bet pool");
+
+      // Without the mode the second bet is priced after the first moved the pool
+      const c = await betBothInOneSlot(sequential, ["yes", "no"]);
+      assert.equal(c.yes.toString(), a.yes.toString(), "First bet unaffected");
+      assert.notEqual(c.no.toString(), a.no.toString(), "Second bet sees the moved pool");
+      console.log(`  ✓ ${a.yes} YES / ${a.no} NO shares whichever bet lands first`);
+    });
+
+    it("Splitting a bet across one slot buys no more than a single bet", async () => {
+      const split = new BN(BASE_ID + 63);
+      const single = new BN(BASE_ID + 64);
+      await createTestMarket(split, { slotStartPricing: true });
+      await createTestMarket(single, { slotStartPricing: true });
+      const PIECES = 4;
+      await fundWsol(userG, 2 * PIECES * AMOUNT);
+      const balance = async (ata: PublicKey) => (await token.getAccount(provider.connection, ata)).amount;
+
+      // Four YES bets in one transaction, so all priced off the same snapshot
+      const pieces = [];
+      for (let i = 0; i < PIECES; i++) {
+        pieces.push(await betIx(split, "yes"));
+      }
+      await provider.sendAndConfirm(new anchor.web3.Transaction().add(...pieces.map((p) => p.ix)), [userG]);
+      const splitShares = await balance(pieces[0].shareAta);
+
+      const whole = await betIx(single, "yes", PIECES * AMOUNT);
+      await provider.sendAndConfirm(new anchor.web3.Transaction().add(whole.ix), [userG]);
+      const singleShares = await balance(whole.shareAta);
+
+      assert.isTrue(splitShares <= singleShares, `Split ${splitShares} must not beat single ${singleShares}`);
+      const market = await program.account.market.fetch(deriveMarketPda(split)[0]);
+      assert.equal(market.slotStartReserves[0].toString(), LAMPORTS_PER_SOL.toString(), "Snapshot is the pre-bet pool");
+      console.log(`  ✓ ${PIECES} same-slot bets: ${splitShares} shares vs ${singleShares} for one bet`);
+    });
+  });
+
+  // =========================================================================
+  // 25. get_pnl
+  // =========================================================================
+  describe("get_pnl", () => {
+    const userH = Keypair.generate();
+    const COST = 200_000_000;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userH.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    it("Splits P&L into realized and unrealized through a position's life", async () => {
+      const marketId = new BN(BASE_ID + 47);
+      const marketPda = await createTestMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const [vault] = deriveVault(marketPda);
+      const [userPosition] = derivePosition(marketPda, userH.publicKey);
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userH.publicKey);
+      const wsol = async () => (await token.getAccount(provider.connection, userAta)).amount;
+
+      const yesAta = await placeBet(userH, marketId, "yes", COST);
+      const viewAccounts = { market: marketPda, yesMint, noMint, vault, userPosition, userYesAccount: yesAta, userNoAccount: null };
+      const pnl = () => program.methods.getPnl(marketId).accounts(viewAccounts).view();
+
+      // Bought: nothing realized, fees put the open position under water
+      let p = await pnl();
+      assert.equal(p.realizedPnl.toNumber(), 0);
+      assert.isBelow(p.unrealizedPnl.toNumber(), 0, "Entry and exit fees");
+
+      // Cancel half: its cost basis is realized against the refund
+      const shares = (await token.getAccount(provider.connection, yesAta)).amount;
+      const burn = shares / 2n;
+      const beforeCancel = await wsol();
+      await program.methods
+        .cancelBet(marketId, new BN(burn.toString()))
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault,
+          userPosition,
+          userAta,
+          userShareAccount: yesAta,
+          platformConfig,
+          treasury: treasuryAta,
+          user: userH.publicKey,
+          collateralMint: WSOL_MINT,
+        } as any)
+        .signers([userH])
+        .rpc();
+      const refund = Number((await wsol()) - beforeCancel);
+      const costRemoved = Number((BigInt(COST) * burn) / shares);
+      p = await pnl();
+      assert.equal(p.realizedPnl.toNumber(), refund - costRemoved, "Refund less the cost of the sold half");
+
+      // Resolved YES: held shares marked at their claim value
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      const status = await program.methods.getClaimStatus(marketId).accounts(viewAccounts).view();
+      p = await pnl();
+      assert.equal(p.realizedPnl.toNumber(), refund - costRemoved);
+      assert.equal(
+        p.unrealizedPnl.toNumber(),
+        status.remainingEntitlement.toNumber() - (COST - costRemoved),
+        "Entitlement less the remaining cost basis"
+      );
+
+      // Claimed: everything realized
+      const beforeClaim = await wsol();
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault,
+          userPosition,
+          userAta,
+          userShareAccount: yesAta,
+          user: userH.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([userH])
+        .rpc();
+      const payout = Number((await wsol()) - beforeClaim);
+      p = await pnl();
+      assert.equal(p.realizedPnl.toNumber(), refund + payout - COST, "Proceeds less everything paid in");
+      assert.equal(p.unrealizedPnl.toNumber(), 0);
+      console.log(`  ✓ Realized P&L ${p.realizedPnl} after cancel, resolution and claim`);
+    });
+  });
+
+  // =========================================================================
+  // 26. Platform fee floor
+  // =========================================================================
+  describe("Platform fee floor", () => {
+    const FLOOR = 5_000;
+    const setFloor = (fee: number) =>
+      program.methods
+        .updateMinFeeAbsolute(new BN(fee))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+    after(async () => {
+      await setFloor(0);
+    });
+
+    it("Charges the floor on a micro-bet and rejects bets that can't cover it", async () => {
+      const marketId = new BN(BASE_ID + 48);
+      const marketPda = await createTestMarket(marketId, { minBet: new BN(1_000) });
+      const [vault] = deriveVault(marketPda);
+      await setFloor(FLOOR);
+
+      // 2.5% of 100_000 is 2_500, under the floor
+      const amount = 100_000;
+      const quote = await program.methods
+        .quoteFee(marketId, { yes: {} }, new BN(amount), null)
+        .accounts({ market: marketPda })
+        .view();
+      assert.equal(quote.totalFee.toNumber(), FLOOR, "Quote applies the floor");
+
+      const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      const vaultBefore = (await token.getAccount(provider.connection, vault)).amount;
+      await placeBet(userA, marketId, "yes", amount);
+      const treasuryAfter = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      const vaultAfter = (await token.getAccount(provider.connection, vault)).amount;
+      assert.equal(Number(treasuryAfter - treasuryBefore), FLOOR, "Treasury got the floor");
+      assert.equal(Number(vaultAfter - vaultBefore), amount - FLOOR);
+
+      // Above min_bet, but nothing left once the floor is taken
+      for (const tooSmall of [FLOOR - 1, FLOOR]) {
+        try {
+          await placeBet(userA, marketId, "yes", tooSmall);
+          assert.fail("Should have thrown BelowMinBet");
+        } catch (e: any) {
+          assert.include(e.message, "BelowMinBet");
+        }
+      }
+      console.log(`  ✓ ${amount}-lamport bet charged the ${FLOOR}-lamport floor`);
+    });
+  });
+
+  // =========================================================================
+  // 27. Liquidity providers
+  // =========================================================================
+  describe("Liquidity providers", () => {
+    const lp = Keypair.generate();
+    const DEPOSIT = 500_000_000;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(lp.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    it("Deepens the pool at the same price and returns the deposit after resolution", async () => {
+      const marketId = new BN(BASE_ID + 49);
+      const marketPda = await createTestMarket(marketId);
+      const [lpMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lp_mint"), marketPda.toBuffer()],
+        program.programId
+      );
+      const providerLpAccount = await token.getAssociatedTokenAddress(lpMint, lp.publicKey);
+      const providerAta = await fundWsol(lp, DEPOSIT);
+      const wsol = async () => (await token.getAccount(provider.connection, providerAta)).amount;
+
+      // Move the price first so the deposit has to follow the reserve ratio
+      await placeBet(userA, marketId, "yes", 200_000_000);
+      const before = await program.account.market.fetch(marketPda);
+
+      await program.methods
+        .addLiquidity(marketId, new BN(DEPOSIT))
+        .accounts({ market: marketPda, providerAta, providerLpAccount, platformConfig, provider: lp.publicKey } as any)
+        .signers([lp])
+        .rpc();
+
+      const after = await program.account.market.fetch(marketPda);
+      assert.equal((await token.getAccount(provider.connection, providerLpAccount)).amount.toString(), DEPOSIT.toString(), "LP tokens 1:1");
+      assert.equal(after.lpLiquidity.toNumber(), DEPOSIT);
+      assert.equal(after.totalCollateral.sub(before.totalCollateral).toNumber(), DEPOSIT);
+      const ratio = (m: any) => m.totalYesShares.toNumber() / m.totalNoShares.toNumber();
+      assert.closeTo(ratio(after), ratio(before), 1e-6, "Price unchanged");
+
+      const remove = (lpTokens: BN) =>
+        program.methods
+          .removeLiquidity(marketId, lpTokens)
+          .accounts({ market: marketPda, providerAta, providerLpAccount, platformConfig, provider: lp.publicKey } as any)
+          .signers([lp])
+          .rpc();
+
+      try {
+        await remove(new BN(DEPOSIT));
+        assert.fail("Should have thrown MarketNotResolved");
+      } catch (e: any) {
+        assert.include(e.message, "MarketNotResolved");
+      }
+
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      const resolved = await program.account.market.fetch(marketPda);
+      assert.equal(resolved.lpPool.toNumber(), DEPOSIT, "Deposit set aside");
+      assert.equal(
+        resolved.distributableCollateral.toNumber(),
+        resolved.totalCollateral.toNumber() - DEPOSIT,
+        "Winners split the rest"
+      );
+
+      // Half now, the rest later: both pro-rata of what's left
+      const start = await wsol();
+      await remove(new BN(DEPOSIT / 2));
+      await remove(new BN(DEPOSIT / 2));
+      assert.equal(Number((await wsol()) - start), DEPOSIT, "Deposit returned");
+
+      const position = await program.account.lpPosition.fetch(
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("lp_position"), marketPda.toBuffer(), lp.publicKey.toBuffer()],
+          program.programId
+        )[0]
+      );
+      assert.equal(position.deposited.toNumber(), DEPOSIT);
+      assert.equal(position.withdrawn.toNumber(), DEPOSIT);
+      assert.equal((await program.account.market.fetch(marketPda)).lpPool.toNumber(), 0);
+      console.log(`  ✓ LP deposit of ${DEPOSIT} lamports withdrawn after resolution`);
+    });
+  });
+
+  // =========================================================================
+  // 28. Cancelled markets
+  // =========================================================================
+  describe("Cancel market and claim refund", () => {
+    const userG = Keypair.generate();
+    const marketId = new BN(BASE_ID + 50);
+    let marketPda: PublicKey;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userG.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      await createTestMarket(marketId);
+      [marketPda] = deriveMarketPda(marketId);
+      await placeBet(userA, marketId, "yes", 300_000_000);
+      await placeBet(userG, marketId, "no", 100_000_000);
+    });
+
+    async function claimRefund(user: Keypair) {
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const userYes = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, yesMint, user.publicKey);
+      const userNo = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, noMint, user.publicKey);
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, user.publicKey);
+
+      const before = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .claimRefund(marketId)
+        .accounts({
+          market: marketPda,
+          userPosition: derivePosition(marketPda, user.publicKey)[0],
+          userAta,
+          userYesAccount: userYes.address,
+          userNoAccount: userNo.address,
+          platformConfig,
+          user: user.publicKey,
+          collateralMint: WSOL_MINT,
+        } as any)
+        .signers([user])
+        .rpc();
+      return (await token.getAccount(provider.connection, userAta)).amount - before;
+    }
+
+    it("Refunds are unavailable until the market is cancelled", async () => {
+      try {
+        await claimRefund(userA);
+        assert.fail("Should have thrown MarketNotCancelled");
+      } catch (e: any) {
+        assert.include(e.message, "MarketNotCancelled");
+      }
+      try {
+        await program.methods
+          .cancelMarket(marketId)
+          .accounts({ market: marketPda, platformConfig, admin: userG.publicKey } as any)
+          .signers([userG])
+          .rpc();
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+    });
+
+    it("Cancels the market and refunds every holder pro-rata", async () => {
+      const configBefore = await program.account.platformConfig.fetch(platformConfig);
+      await program.methods
+        .cancelMarket(marketId)
+        .accounts({ market: marketPda, platformConfig, admin: admin.publicKey } as any)
+        .rpc();
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.status, { cancelled: {} });
+      const configAfter = await program.account.platformConfig.fetch(platformConfig);
+      assert.equal(configAfter.activeMarketCount.toNumber(), configBefore.activeMarketCount.toNumber() - 1);
+
+      try {
+        await placeBet(userA, marketId, "yes", 100_000_000);
+        assert.fail("Should have rejected a bet on a cancelled market");
+      } catch (e: any) {
+        assert.notInclude(e.message, "Should have rejected");
+      }
+
+      const paidA = await claimRefund(userA);
+      const paidG = await claimRefund(userG);
+      assert.isTrue(paidA > 0n && paidG > 0n);
+
+      const [vault] = deriveVault(marketPda);
+      const left = Number((await token.getAccount(provider.connection, vault)).amount);
+      assert.isAtMost(left, 1, "Last holder drains the pool (up to rounding)");
+
+      try {
+        await claimRefund(userA);
+        assert.fail("Should have thrown NoPosition");
+      } catch (e: any) {
+        assert.include(e.message, "NoPosition");
+      }
+      console.log(`  ✓ Refunds: ${paidA} + ${paidG} lamports`);
+    });
+
+    it("Keeps a cancelled market cancelled through pause and unpause", async () => {
+      for (const [method, error] of [["pauseMarket", "AlreadyResolved"], ["unpauseMarket", "MarketNotPaused"]] as const) {
+        try {
+          await program.methods[method](marketId)
+            .accounts({ market: marketPda, platformConfig, admin: admin.publicKey })
+            .rpc();
+          assert.fail(`Should have thrown ${error}`);
+        } catch (e: any) {
+          assert.include(e.message, error);
+        }
+      }
+      assert.deepEqual((await program.account.market.fetch(marketPda)).status, { cancelled: {} });
+
+      try {
+        await placeBet(userA, marketId, "yes", 100_000_000);
+        assert.fail("Should have rejected a bet on a cancelled market");
+      } catch (e: any) {
+        assert.notInclude(e.message, "Should have rejected");
+      }
+      try {
+        await program.methods
+          .resolveMarket(marketId, { yes: {} })
+          .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+          .rpc();
+        assert.fail("Should have thrown AlreadyResolved");
+      } catch (e: any) {
+        assert.include(e.message, "AlreadyResolved");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 29. Parimutuel pricing
+  // =========================================================================
+  describe("Parimutuel pricing", () => {
+    const userH = Keypair.generate();
+    const marketId = new BN(BASE_ID + 51);
+    let marketPda: PublicKey;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(userH.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      marketPda = await createTestMarket(marketId, { pricingMode: { parimutuel: {} } });
+    });
+
+    it("Mints one share per unit staked and leaves the reserves alone", async () => {
+      const yesAta = await placeBet(userA, marketId, "yes", 300_000_000);
+      const noAta = await placeBet(userH, marketId, "no", 200_000_000);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.totalYesShares.toNumber(), LAMPORTS_PER_SOL, "Reserves don't move");
+      assert.equal(market.totalNoShares.toNumber(), LAMPORTS_PER_SOL);
+
+      for (const [user, ata] of [[userA, yesAta], [userH, noAta]] as [Keypair, PublicKey][]) {
+        const position = await program.account.userPosition.fetch(derivePosition(marketPda, user.publicKey)[0]);
+        const held = (await token.getAccount(provider.connection, ata)).amount;
+        assert.equal(held.toString(), position.totalDeposited.toString(), "Shares = net stake");
+      }
+      console.log("  ✓ Stakes went into the buckets 1:1");
+    });
+
+    it("Quotes one share per unit and has no Kelly size", async () => {
+      const [yesMint] = deriveYesMint(marketPda);
+      const held = (await token.getAccount(
+        provider.connection, await token.getAssociatedTokenAddress(yesMint, userA.publicKey)
+      )).amount;
+      const quotes: BN[] = await program.methods
+        .quoteBetCurve(marketId, { yes: {} }, [new BN(300_000_000)])
+        .accounts({ market: marketPda })
+        .view();
+      assert.equal(quotes[0].toString(), held.toString(), "Quote matches the shares minted for the same stake");
+
+      try {
+        await program.methods
+          .suggestBetSize(marketId, { yes: {} }, new BN(1000), new BN(LAMPORTS_PER_SOL))
+          .accounts({ market: marketPda })
+          .view();
+        assert.fail("Should have thrown PricingModeUnsupported");
+      } catch (e: any) {
+        // A failed simulation carries the program error in its logs
+        const logs: string[] = e.simulationResponse?.logs ?? e.logs ?? [];
+        assert.include(`${e.message}\n${logs.join("\n")}`, "PricingModeUnsupported");
+      }
+    });
+
+    it("Cancelling takes the stake back out of the bucket", async () => {
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const noAta = await token.getAssociatedTokenAddress(noMint, userH.publicKey);
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userH.publicKey);
+      const burn = 100_000_000n;
+
+      const before = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .cancelBet(marketId, new BN(burn.toString()))
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault: deriveVault(marketPda)[0],
+          userPosition: derivePosition(marketPda, userH.publicKey)[0],
+          userAta,
+          userShareAccount: noAta,
+          platformConfig,
+          treasury: treasuryAta,
+          user: userH.publicKey,
+          collateralMint: WSOL_MINT,
+        } as any)
+        .signers([userH])
+        .rpc();
+      const refund = (await token.getAccount(provider.connection, userAta)).amount - before;
+      // Less the 2.5% exit fee, rounded up
+      assert.equal(refund.toString(), (burn - (burn * 250n + 9999n) / 10000n).toString());
+    });
+
+    it("Pays the whole pot to the winning bucket", async () => {
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      const market = await program.account.market.fetch(marketPda);
+
+      const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, userA.publicKey);
+      const before = (await token.getAccount(provider.connection, userAta)).amount;
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault: deriveVault(marketPda)[0],
+          userPosition: derivePosition(marketPda, userA.publicKey)[0],
+          userAta,
+          userShareAccount: await token.getAssociatedTokenAddress(yesMint, userA.publicKey),
+          user: userA.publicKey,
+          collateralMint: WSOL_MINT,
+          payoutReserve: null,
+          userPayoutAccount: null,
+        })
+        .signers([userA])
+        .rpc();
+      const paid = (await token.getAccount(provider.connection, userAta)).amount - before;
+      assert.equal(paid.toString(), market.distributableCollateral.toString(), "Sole winner takes the pot");
+      console.log(`  ✓ Winner paid ${Number(paid) / LAMPORTS_PER_SOL} SOL`);
+    });
+
+    it("Rejects liquidity provision", async () => {
+      const otherId = new BN(BASE_ID + 52);
+      const otherPda = await createTestMarket(otherId, { pricingMode: { parimutuel: {} } });
+      const creatorAta = await fundWsol(adminPayer, 10_000_000);
+      try {
+        await program.methods
+          .rebalanceLiquidity(otherId, new BN(10_000_000), new BN(0))
+          .accounts({ market: otherPda, creatorAta, platformConfig, creator: admin.publicKey } as any)
+          .rpc();
+        assert.fail("Should have thrown PricingModeUnsupported");
+      } catch (e: any) {
+        assert.include(e.message, "PricingModeUnsupported");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 30. Virtual liquidity
+  // =========================================================================
+  describe("Virtual liquidity", () => {
+    it("Prices a bet against deeper pools but only moves the real reserves", async () => {
+      const plainId = new BN(BASE_ID + 53);
+      const virtualId = new BN(BASE_ID + 54);
+      await createTestMarket(plainId);
+      const virtualPda = await createTestMarket(virtualId, { virtualLiquidity: new BN(9 * LAMPORTS_PER_SOL) });
+
+      const plainAta = await placeBet(userA, plainId, "yes", 100_000_000);
+      const virtualAta = await placeBet(userA, virtualId, "yes", 100_000_000);
+      const plainShares = (await token.getAccount(provider.connection, plainAta)).amount;
+      const virtualShares = (await token.getAccount(provider.connection, virtualAta)).amount;
+      assert.isTrue(virtualShares > plainShares, "Less slippage against the deeper pool");
+
+      const market = await program.account.market.fetch(virtualPda);
+      const net = BigInt(market.totalNoShares.toString()) - BigInt(LAMPORTS_PER_SOL);
+      assert.equal(
+        market.totalYesShares.toString(),
+        (BigInt(LAMPORTS_PER_SOL) + net - virtualShares).toString(),
+        "Real YES reserve pays out the shares"
+      );
+      assert.equal(market.virtualLiquidity.toNumber(), 9 * LAMPORTS_PER_SOL);
+
+      // Depth reflects the pools bets are priced against
+      const depth = await program.methods.getPoolDepth(virtualId).accounts({ market: virtualPda }).view();
+      const virtualYes = BigInt(market.totalYesShares.add(market.virtualLiquidity).toString());
+      const virtualNo = BigInt(market.totalNoShares.add(market.virtualLiquidity).toString());
+      assert.equal(depth.k.toString(), (virtualYes * virtualNo).toString(), "k over the virtual reserves");
+      console.log(`  ✓ ${plainShares} shares plain vs ${virtualShares} with virtual liquidity`);
+    });
+  });
+
+  // =========================================================================
+  // 31. Creator fee split
+  // =========================================================================
+  describe("Creator fees", () => {
+    const marketId = new BN(BASE_ID + 55);
+    let marketPda: PublicKey;
+    let escrow: PublicKey;
+
+    before(async () => {
+      marketPda = await createTestMarket(marketId, { creatorFeeBps: 100 });
+      [escrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("creator_fees"), marketPda.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Escrows the creator's part of each bet fee", async () => {
+      const amount = 1_000_000_000;
+      const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      await placeBet(userA, marketId, "yes", amount);
+
+      const escrowed = (await token.getAccount(provider.connection, escrow)).amount;
+      const toTreasury = (await token.getAccount(provider.connection, treasuryAta)).amount - treasuryBefore;
+      assert.equal(escrowed.toString(), (amount / 100).toString(), "1% of the bet to the creator");
+      assert.equal(toTreasury.toString(), ((amount * 150) / 10000).toString(), "The rest of the 2.5% fee to the treasury");
+
+      const ledger = await program.methods
+        .verifyFeeAccounting(marketId)
+        .accounts({ market: marketPda })
+        .view();
+      assert.equal(ledger.creator.toString(), escrowed.toString());
+      console.log(`  ✓ ${escrowed} escrowed for the creator, ${toTreasury} to the treasury`);
+    });
+
+    it("Pays the escrow out to the creator", async () => {
+      const creatorAta = await fundWsol(adminPayer, 1_000_000);
+      const escrowed = (await token.getAccount(provider.connection, escrow)).amount;
+      const before = (await token.getAccount(provider.connection, creatorAta)).amount;
+      await program.methods
+        .claimCreatorFees(marketId)
+        .accounts({ market: marketPda, creatorAta, creator: admin.publicKey } as any)
+        .rpc();
+      const claimed = (await token.getAccount(provider.connection, creatorAta)).amount - before;
+      assert.equal(claimed.toString(), escrowed.toString());
+
+      try {
+        await program.methods
+          .claimCreatorFees(marketId)
+          .accounts({ market: marketPda, creatorAta, creator: admin.publicKey } as any)
+          .rpc();
+        assert.fail("Should have thrown NoCreatorFees");
+      } catch (e: any) {
+        assert.include(e.message, "NoCreatorFees");
+      }
+    });
+
+    it("Rejects a creator cut above the market fee", async () => {
+      try {
+        await createTestMarket(new BN(BASE_ID + 56), { creatorFeeBps: 300 });
+        assert.fail("Should have thrown FeeExceedsMax");
+      } catch (e: any) {
+        assert.include(e.message, "FeeExceedsMax");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 32. Referral program
+  // =========================================================================
+  describe("Referrals", () => {
+    const referrer = Keypair.generate();
+    const marketId = new BN(BASE_ID + 57);
+    let marketPda: PublicKey;
+    let referralAccount: PublicKey;
+    let referralFeeEscrow: PublicKey;
+
+    const setReferralFee = (bps: number) =>
+      program.methods
+        .updateReferralFee(bps)
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+    async function referredBet(user: Keypair, amount: number) {
+      const [yesMint] = deriveYesMint(marketPda);
+      await fundWsol(user, amount);
+      const shareAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, yesMint, user.publicKey
+      );
+      await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(amount), new BN(0))
+        .accounts({
+          user: user.publicKey,
+          userShareAccount: shareAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+          referralAccount,
+          referralFeeEscrow,
+        } as any)
+        .signers([user])
+        .rpc();
+    }
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(referrer.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      [referralAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), referrer.publicKey.toBuffer()],
+        program.programId
+      );
+      [referralFeeEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral_fees"), referrer.publicKey.toBuffer()],
+        program.programId
+      );
+      marketPda = await createTestMarket(marketId);
+      await setReferralFee(2000);
+    });
+
+    after(async () => {
+      await setReferralFee(0);
+    });
+
+    it("Registers a referrer once", async () => {
+      await program.methods
+        .registerReferrer()
+        .accounts({ referrer: referrer.publicKey, platformConfig, collateralMint: WSOL_MINT } as any)
+        .signers([referrer])
+        .rpc();
+      const account = await program.account.referralAccount.fetch(referralAccount);
+      assert.ok(account.referrer.equals(referrer.publicKey));
+      assert.ok(account.escrow.equals(referralFeeEscrow));
+
+      try {
+        await program.methods
+          .registerReferrer()
+          .accounts({ referrer: referrer.publicKey, platformConfig, collateralMint: WSOL_MINT } as any)
+          .signers([referrer])
+          .rpc();
+        assert.fail("Should not register twice");
+      } catch (e: any) {
+        assert.include(e.message, "already in use");
+      }
+    });
+
+    it("Routes a slice of the platform fee to the referrer", async () => {
+      const amount = 1_000_000_000;
+      const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      await referredBet(userA, amount);
+
+      const fee = (amount * 250) / 10000;
+      const escrowed = (await token.getAccount(provider.connection, referralFeeEscrow)).amount;
+      const toTreasury = (await token.getAccount(provider.connection, treasuryAta)).amount - treasuryBefore;
+      assert.equal(escrowed.toString(), (fee / 5).toString(), "20% of the platform's fee");
+      assert.equal(toTreasury.toString(), ((fee * 4) / 5).toString());
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.feeLedger.referrer.toString(), escrowed.toString());
+      console.log(`  ✓ ${escrowed} to the referrer, ${toTreasury} to the treasury`);
+    });
+
+    it("Rejects self-referral", async () => {
+      try {
+        await referredBet(referrer, 100_000_000);
+        assert.fail("Should have thrown SelfReferral");
+      } catch (e: any) {
+        assert.include(e.message, "SelfReferral");
+      }
+    });
+
+    it("Pays the escrow out to the referrer", async () => {
+      const referrerAta = await fundWsol(referrer, 1_000_000);
+      const escrowed = (await token.getAccount(provider.connection, referralFeeEscrow)).amount;
+      const before = (await token.getAccount(provider.connection, referrerAta)).amount;
+      await program.methods
+        .claimReferralFees()
+        .accounts({ referrer: referrer.publicKey, referrerAta } as any)
+        .signers([referrer])
+        .rpc();
+      const claimed = (await token.getAccount(provider.connection, referrerAta)).amount - before;
+      assert.equal(claimed.toString(), escrowed.toString());
+    });
+  });
+
+  // =========================================================================
+  // 33. LP fee accrual
+  // =========================================================================
+  describe("LP fees", () => {
+    const lp = Keypair.generate();
+    const DEPOSIT = 500_000_000;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(lp.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    it("Keeps part of each trade fee for LPs and pays it out with their deposit", async () => {
+      const marketId = new BN(BASE_ID + 58);
+      const marketPda = await createTestMarket(marketId, { lpFeeBps: 4000 });
+      const [lpMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lp_mint"), marketPda.toBuffer()],
+        program.programId
+      );
+      const providerLpAccount = await token.getAssociatedTokenAddress(lpMint, lp.publicKey);
+      const providerAta = await fundWsol(lp, DEPOSIT);
+
+      // No LPs yet: the whole fee goes to the treasury
+      await placeBet(userA, marketId, "no", 100_000_000);
+      assert.equal((await program.account.market.fetch(marketPda)).lpFeesAccrued.toNumber(), 0);
+
+      await program.methods
+        .addLiquidity(marketId, new BN(DEPOSIT))
+        .accounts({ market: marketPda, providerAta, providerLpAccount, platformConfig, provider: lp.publicKey } as any)
+        .signers([lp])
+        .rpc();
+
+      const treasuryBefore = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      await placeBet(userA, marketId, "yes", 1_000_000_000);
+      const fee = (1_000_000_000 * 250) / 10000;
+      const lpFee = (fee * 4000) / 10000;
+      const toTreasury = (await token.getAccount(provider.connection, treasuryAta)).amount - treasuryBefore;
+      assert.equal(toTreasury.toString(), (fee - lpFee).toString());
+      const accrued = await program.account.market.fetch(marketPda);
+      assert.equal(accrued.lpFeesAccrued.toNumber(), lpFee);
+      assert.equal(accrued.feeLedger.lp.toNumber(), lpFee);
+
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      assert.equal((await program.account.market.fetch(marketPda)).lpPool.toNumber(), DEPOSIT + lpFee);
+
+      const start = (await token.getAccount(provider.connection, providerAta)).amount;
+      await program.methods
+        .removeLiquidity(marketId, new BN(DEPOSIT))
+        .accounts({ market: marketPda, providerAta, providerLpAccount, platformConfig, provider: lp.publicKey } as any)
+        .signers([lp])
+        .rpc();
+      const withdrawn = (await token.getAccount(provider.connection, providerAta)).amount - start;
+      assert.equal(withdrawn.toString(), (DEPOSIT + lpFee).toString(), "Deposit plus accrued fees");
+      console.log(`  ✓ LP earned ${lpFee} lamports in fees`);
+    });
+  });
+
+  // =========================================================================
+  // 34. Volume fee tiers
+  // =========================================================================
+  describe("Volume fee tiers", () => {
+    const trader = Keypair.generate();
+    const marketId = new BN(BASE_ID + 59);
+    let userTier: PublicKey;
+
+    const setTiers = (tiers: { minVolume: BN; discountBps: number }[]) =>
+      program.methods
+        .setFeeTiers(tiers)
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(trader.publicKey, 5 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      [userTier] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_tier"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      await createTestMarket(marketId);
+      await setTiers([{ minVolume: new BN(LAMPORTS_PER_SOL), discountBps: 5000 }]);
+      await program.methods
+        .initUserTier()
+        .accounts({ user: trader.publicKey } as any)
+        .signers([trader])
+        .rpc();
+    });
+
+    after(async () => {
+      await setTiers([]);
+    });
+
+    async function feeForTieredBet(amount: number) {
+      const [marketPda] = deriveMarketPda(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      await fundWsol(trader, amount);
+      const shareAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, yesMint, trader.publicKey
+      );
+      const before = (await token.getAccount(provider.connection, treasuryAta)).amount;
+      await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(amount), new BN(0))
+        .accounts({
+          user: trader.publicKey,
+          userShareAccount: shareAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+          userTier,
+        } as any)
+        .signers([trader])
+        .rpc();
+      return Number((await token.getAccount(provider.connection, treasuryAta)).amount - before);
+    }
+
+    it("Discounts the fee once the bettor's volume reaches a tier", async () => {
+      const amount = LAMPORTS_PER_SOL;
+      assert.equal(await feeForTieredBet(amount), (amount * 250) / 10000, "Full fee below the first tier");
+      assert.equal(await feeForTieredBet(amount), (amount * 125) / 10000, "Half off at the first tier");
+
+      const tier = await program.account.userTier.fetch(userTier);
+      assert.equal(tier.lifetimeVolume.toNumber(), 2 * amount);
+
+      // quote_fee applies the same discount when given the tier
+      const quote = (tier: PublicKey | null) =>
+        program.methods
+          .quoteFee(marketId, { yes: {} }, new BN(amount), null)
+          .accounts({ market: deriveMarketPda(marketId)[0], userTier: tier })
+          .view();
+      assert.equal((await quote(null)).totalFee.toNumber(), (amount * 250) / 10000, "No tier, full fee");
+      const quoted = (await quote(userTier)).totalFee.toNumber();
+      assert.equal(await feeForTieredBet(amount), quoted, "Charged what was quoted");
+      console.log(`  ✓ Fee halved after ${amount} lamports of volume`);
+    });
+
+    it("Rejects tiers whose thresholds don't rise", async () => {
+      try {
+        await setTiers([
+          { minVolume: new BN(2 * LAMPORTS_PER_SOL), discountBps: 1000 },
+          { minVolume: new BN(LAMPORTS_PER_SOL), discountBps: 2000 },
+        ]);
+        assert.fail("Should have thrown InvalidFeeTiers");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidFeeTiers");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 35. start_round
+  // =========================================================================
+  describe("start_round", () => {
+    const ROUND = 7200;
+
+    it("Opens the next round one round_duration later and records it", async () => {
+      const marketId = new BN(BASE_ID + 60);
+      const marketPda = await createTestMarket(marketId, { isRecurring: true, roundDuration: new BN(ROUND) });
+      await program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      const before = await program.account.market.fetch(marketPda);
+
+      const [roundState] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), new BN(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      // Permissionless: any payer may crank the schedule
+      await program.methods
+        .startRound(marketId)
+        .accounts({ market: marketPda, roundState, platformConfig, payer: userA.publicKey } as any)
+        .signers([userA])
+        .rpc();
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.currentRound.toNumber(), 1);
+      assert.equal(market.startTimestamp.toNumber(), before.startTimestamp.toNumber() + ROUND);
+      assert.equal(market.lockTimestamp.toNumber(), before.lockTimestamp.toNumber() + ROUND);
+      assert.equal(market.endTimestamp.toNumber(), before.endTimestamp.toNumber() + ROUND);
+      assert.ok(market.status.pending, "Next round hasn't started yet");
+
+      const round = await program.account.roundState.fetch(roundState);
+      assert.equal(round.roundId.toNumber(), 1);
+      assert.ok(round.market.equals(marketPda));
+      assert.equal(round.lockTs.toNumber(), market.lockTimestamp.toNumber());
+      assert.deepEqual(round.status, { open: {} });
+      console.log(`  ✓ Round 1 opens at ${market.startTimestamp.toNumber()}`);
+    });
+
+    it("Rejects a market without a round duration", async () => {
+      const marketId = new BN(BASE_ID + 61);
+      const marketPda = await createTestMarket(marketId, { isRecurring: true });
+      await program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      const [roundState] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), new BN(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      try {
+        await program.methods
+          .startRound(marketId)
+          .accounts({ market: marketPda, roundState, platformConfig, payer: admin.publicKey } as any)
+          .rpc();
+        assert.fail("Should have thrown NotRecurring");
+      } catch (e: any) {
+        assert.include(e.message, "NotRecurring");
+      }
+    });
+  });
+});
@@ -25,6 +25,11 @@ import {
  *   8. Multi-user payout fairness
  *   9. Slippage guard
  *  10. Edge case: large bet relative to pool
+ *  11. Pool depth read (k invariant + collateral to move price 1pp)
+ *  12. Bet curve quote (shares-out across sizes is monotonic and concave)
+ *  13. Outcome exposure (implied payout per outcome after several bets)
+ *  14. Fractional-Kelly bet sizing for known pool, edge and bankroll
+ *  15. Price history checkpoints recorded by bets across slots
  */
 
 describe("CPMM Migration Tests", () => {
@@ -147,17 +152,11 @@ describe("CPMM Migration Tests", () => {
     }
 
     // Treasury wSOL ATA (owned by admin)
-    treasuryAta = await token.getAssociatedTokenAddress(WSOL_MINT, admin.publicKey);
-    try {
-      await token.getAccount(provider.connection, treasuryAta);
-    } catch {
-      await token.createAssociatedTokenAccount(
-        provider.connection,
-        adminPayer,
-        WSOL_MINT,
-        admin.publicKey
-      );
-    }
+    // Fees accrue in the program-owned treasury for the collateral mint
+    [treasuryAta] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), WSOL_MINT.toBuffer()],
+      program.programId
+    );
   });
 
   // ========================================================================
@@ -760,4 +759,317 @@ describe("CPMM Migration Tests", () => {
     }
     console.log("  ✓ CPMM formula verified: yesPrice = noPool / (yesPool + noPool)");
   });
+
+  // ========================================================================
+  // 11. Pool depth read
+  // ========================================================================
+  it("11. Pool depth increases with larger seeded liquidity", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const depths: any[] = [];
+
+    for (const [offset, liquidity] of [[3, 1 * LAMPORTS_PER_SOL], [4, 5 * LAMPORTS_PER_SOL]]) {
+      const marketId = new BN(Date.now() + offset);
+      const [marketPda] = deriveMarketPda(marketId);
+      const adminAta = await fundWsol(adminPayer, liquidity);
+
+      await program.methods
+        .createMarket(marketId, {
+          title: "Depth Test",
+          description: "Test",
+          category: { crypto: {} },
+          oracleSource: { manualAdmin: {} },
+          oracleFeed: PublicKey.default,
+          oracleThreshold: new BN(0),
+          startTimestamp: new BN(now - 60),
+          lockTimestamp: new BN(now + 3600),
+          endTimestamp: new BN(now + 7200),
+          minBet: new BN(10_000_000),
+          maxBet: new BN(0),
+          isRecurring: false,
+          roundDuration: null,
+          feeBps: 250,
+          initialLiquidity: new BN(liquidity),
+        } as any)
+        .accounts({ adminAta })
+        .rpc();
+
+      const depth = await program.methods
+        .getPoolDepth(marketId)
+        .accounts({ market: marketPda })
+        .view();
+
+      // k = yes * no = liquidity^2 for a fresh 50/50 pool
+      const expectedK = new BN(liquidity).mul(new BN(liquidity));
+      assert.equal(depth.k.toString(), expectedK.toString(), "k should equal liquidity^2");
+      assert.equal(depth.yesDepth.toString(), depth.noDepth.toString(),
+        "Depth should be symmetric for a balanced pool");
+      depths.push(depth);
+    }
+
+    console.log(`  Depth @1 SOL: ${depths[0].yesDepth.toNumber() / LAMPORTS_PER_SOL} SOL`);
+    console.log(`  Depth @5 SOL: ${depths[1].yesDepth.toNumber() / LAMPORTS_PER_SOL} SOL`);
+    assert.isTrue(depths[1].yesDepth.gt(depths[0].yesDepth),
+      "Deeper pool should need more collateral to move the price 1pp");
+    console.log("  ✓ Pool depth scales with liquidity");
+  });
+
+  // ========================================================================
+  // 12. Bet curve quote
+  // ========================================================================
+  it("12. quote_bet_curve is monotonic and concave in bet size", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const marketId = new BN(Date.now() + 5);
+    const [marketPda] = deriveMarketPda(marketId);
+    const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .createMarket(marketId, {
+        title: "Curve Test",
+        description: "Test",
+        category: { crypto: {} },
+        oracleSource: { manualAdmin: {} },
+        oracleFeed: PublicKey.default,
+        oracleThreshold: new BN(0),
+        startTimestamp: new BN(now - 60),
+        lockTimestamp: new BN(now + 3600),
+        endTimestamp: new BN(now + 7200),
+        minBet: new BN(10_000_000),
+        maxBet: new BN(0),
+        isRecurring: false,
+        roundDuration: null,
+        feeBps: 250,
+        initialLiquidity: new BN(LAMPORTS_PER_SOL),
+      } as any)
+      .accounts({ adminAta })
+      .rpc();
+
+    // Evenly spaced sizes: 0.1 .. 1.0 SOL
+    const sizes = Array.from({ length: 10 }, (_, i) => new BN((i + 1) * 100_000_000));
+    const quotes: BN[] = await program.methods
+      .quoteBetCurve(marketId, { yes: {} }, sizes)
+      .accounts({ market: marketPda })
+      .view();
+
+    assert.equal(quotes.length, sizes.length, "One quote per size");
+    for (let i = 1; i < quotes.length; i++) {
+      assert.isTrue(quotes[i].gt(quotes[i - 1]), `Shares-out should increase at size ${i}`);
+      if (i >= 2) {
+        const prevStep = quotes[i - 1].sub(quotes[i - 2]);
+        const step = quotes[i].sub(quotes[i - 1]);
+        assert.isTrue(step.lt(prevStep), `Marginal shares should shrink at size ${i}`);
+      }
+    }
+    console.log(`  Quotes: ${quotes.map((q) => (q.toNumber() / LAMPORTS_PER_SOL).toFixed(4)).join(", ")}`);
+    console.log("  ✓ Bet curve is monotonic and concave");
+  });
+
+  // ========================================================================
+  // 13. Outcome exposure
+  // ========================================================================
+  it("13. get_outcome_exposure matches hand-computed payouts", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const marketId = new BN(Date.now() + 6);
+    const [marketPda] = deriveMarketPda(marketId);
+    const [yesMint] = deriveYesMint(marketPda);
+    const [noMint] = deriveNoMint(marketPda);
+    const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .createMarket(marketId, {
+        title: "Exposure Test",
+        description: "Test",
+        category: { crypto: {} },
+        oracleSource: { manualAdmin: {} },
+        oracleFeed: PublicKey.default,
+        oracleThreshold: new BN(0),
+        startTimestamp: new BN(now - 60),
+        lockTimestamp: new BN(now + 3600),
+        endTimestamp: new BN(now + 7200),
+        minBet: new BN(10_000_000),
+        maxBet: new BN(0),
+        isRecurring: false,
+        roundDuration: null,
+        feeBps: 250,
+        initialLiquidity: new BN(LAMPORTS_PER_SOL),
+      } as any)
+      .accounts({ adminAta })
+      .rpc();
+
+    const bets: [Keypair, "yes" | "no", number][] = [
+      [userA, "yes", 500_000_000],
+      [userB, "no", 300_000_000],
+      [userA, "yes", 200_000_000],
+    ];
+    for (const [user, side, amount] of bets) {
+      const shareMint = side === "yes" ? yesMint : noMint;
+      await fundWsol(user, amount);
+      const shareAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, shareMint, user.publicKey
+      );
+      await program.methods
+        .placeBet(marketId, side === "yes" ? { yes: {} } : { no: {} }, new BN(amount), new BN(0))
+        .accounts({
+          user: user.publicKey,
+          userShareAccount: shareAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .signers([user])
+        .rpc();
+    }
+
+    const exposure = await program.methods
+      .getOutcomeExposure(marketId)
+      .accounts({ market: marketPda, yesMint, noMint })
+      .view();
+
+    const market = await program.account.market.fetch(marketPda);
+    const pool = market.totalCollateral;
+    const yesSupply = new BN((await token.getMint(provider.connection, yesMint)).supply.toString());
+    const noSupply = new BN((await token.getMint(provider.connection, noMint)).supply.toString());
+    const unit = new BN(LAMPORTS_PER_SOL);
+
+    assert.equal(exposure.yesSupply.toString(), yesSupply.toString(), "YES supply");
+    assert.equal(exposure.noSupply.toString(), noSupply.toString(), "NO supply");
+    // Winners split the whole pool whichever side wins
+    assert.equal(exposure.yesPayout.toString(), pool.toString(), "YES payout");
+    assert.equal(exposure.noPayout.toString(), pool.toString(), "NO payout");
+    assert.equal(exposure.yesPayoutPerShare.toString(), unit.mul(pool).div(yesSupply).toString());
+    assert.equal(exposure.noPayoutPerShare.toString(), unit.mul(pool).div(noSupply).toString());
+    // Fewer NO shares outstanding: each pays more if NO wins
+    assert.isTrue(exposure.noPayoutPerShare.gt(exposure.yesPayoutPerShare));
+    console.log(`  YES pays ${exposure.yesPayoutPerShare.toNumber() / LAMPORTS_PER_SOL}/share, NO pays ${exposure.noPayoutPerShare.toNumber() / LAMPORTS_PER_SOL}/share`);
+    console.log("  ✓ Outcome exposure matches hand-computed values");
+  });
+
+  // ========================================================================
+  // 14. Fractional-Kelly sizing
+  // ========================================================================
+  it("14. suggest_bet_size returns quarter Kelly, capped by slippage", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const marketId = new BN(Date.now() + 7);
+    const [marketPda] = deriveMarketPda(marketId);
+    const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .createMarket(marketId, {
+        title: "Kelly Test",
+        description: "Test",
+        category: { crypto: {} },
+        oracleSource: { manualAdmin: {} },
+        oracleFeed: PublicKey.default,
+        oracleThreshold: new BN(0),
+        startTimestamp: new BN(now - 60),
+        lockTimestamp: new BN(now + 3600),
+        endTimestamp: new BN(now + 7200),
+        minBet: new BN(10_000_000),
+        maxBet: new BN(0),
+        isRecurring: false,
+        roundDuration: null,
+        feeBps: 250,
+        initialLiquidity: new BN(LAMPORTS_PER_SOL),
+      } as any)
+      .accounts({ adminAta })
+      .rpc();
+
+    const suggest = (edgeBps: number, bankroll: number): Promise<BN> =>
+      program.methods
+        .suggestBetSize(marketId, { yes: {} }, new BN(edgeBps), new BN(bankroll))
+        .accounts({ market: marketPda })
+        .view();
+
+    // p = 0.5, q = 0.6: full Kelly = 0.1 / 0.5 = 20% of bankroll, quarter = 5%
+    const small = await suggest(1000, LAMPORTS_PER_SOL);
+    assert.equal(small.toNumber(), 50_000_000, "5% of a 1 SOL bankroll");
+
+    // 5% of 10 SOL (0.5 SOL) would push YES past 0.6. Cap = net depth to 0.6,
+    // sqrt(1e18 * 1.5) - 1e9 = 224_744_871, grossed up for the 2.5% fee
+    const large = await suggest(1000, 10 * LAMPORTS_PER_SOL);
+    assert.equal(large.toNumber(), Math.floor((224_744_871 * 10000) / 9750), "Capped by slippage");
+
+    assert.equal((await suggest(0, LAMPORTS_PER_SOL)).toNumber(), 0, "No edge, no bet");
+    console.log(`  ✓ Kelly sizes: ${small.toNumber() / LAMPORTS_PER_SOL} SOL, ${large.toNumber() / LAMPORTS_PER_SOL} SOL (capped)`);
+  });
+
+  // ========================================================================
+  // 15. Price history
+  // ========================================================================
+  it("15. get_price_history returns checkpoints at most once per interval", async () => {
+    const CHECKPOINT_INTERVAL_SLOTS = 25; // PRICE_CHECKPOINT_INTERVAL_SLOTS
+    const now = Math.floor(Date.now() / 1000);
+    const marketId = new BN(Date.now() + 8);
+    const [marketPda] = deriveMarketPda(marketId);
+    const [yesMint] = deriveYesMint(marketPda);
+    const [noMint] = deriveNoMint(marketPda);
+    const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .createMarket(marketId, {
+        title: "History Test",
+        description: "Test",
+        category: { crypto: {} },
+        oracleSource: { manualAdmin: {} },
+        oracleFeed: PublicKey.default,
+        oracleThreshold: new BN(0),
+        startTimestamp: new BN(now - 60),
+        lockTimestamp: new BN(now + 3600),
+        endTimestamp: new BN(now + 7200),
+        minBet: new BN(10_000_000),
+        maxBet: new BN(0),
+        isRecurring: false,
+        roundDuration: null,
+        feeBps: 250,
+        initialLiquidity: new BN(LAMPORTS_PER_SOL),
+      } as any)
+      .accounts({ adminAta })
+      .rpc();
+
+    async function bet(user: Keypair, side: "yes" | "no", amount: number) {
+      await fundWsol(user, amount);
+      const shareAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, side === "yes" ? yesMint : noMint, user.publicKey
+      );
+      await program.methods
+        .placeBet(marketId, side === "yes" ? { yes: {} } : { no: {} }, new BN(amount), new BN(0))
+        .accounts({
+          user: user.publicKey,
+          userShareAccount: shareAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+    async function waitForSlot(target: number) {
+      while ((await provider.connection.getSlot("confirmed")) < target) {
+        await new Promise((r) => setTimeout(r, 400));
+      }
+    }
+    const history = () =>
+      program.methods.getPriceHistory(marketId).accounts({ market: marketPda }).view();
+
+    // Two bets inside one interval record a single checkpoint
+    await bet(userA, "yes", 300_000_000);
+    await bet(userA, "yes", 100_000_000);
+    let checkpoints = await history();
+    assert.equal(checkpoints.length, 1, "Second bet falls within the interval");
+
+    await waitForSlot(checkpoints[0].slot.toNumber() + CHECKPOINT_INTERVAL_SLOTS);
+    await bet(userB, "no", 600_000_000);
+    checkpoints = await history();
+    assert.equal(checkpoints.length, 2, "Bet after the interval adds a checkpoint");
+
+    const [first, second] = checkpoints;
+    assert.isAtLeast(second.slot.toNumber() - first.slot.toNumber(), CHECKPOINT_INTERVAL_SLOTS, "Oldest first, spaced by the interval");
+    const yesProb = (c: any) => c.noReserve.toNumber() / (c.yesReserve.toNumber() + c.noReserve.toNumber());
+    assert.isAbove(yesProb(first), 0.5, "YES favoured after the YES bets");
+    assert.isBelow(yesProb(second), yesProb(first), "NO bet pulls YES back down");
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.equal(second.yesReserve.toString(), market.totalYesShares.toString(), "Latest checkpoint is the live pool");
+    assert.equal(second.noReserve.toString(), market.totalNoShares.toString());
+    console.log(`  ✓ History: YES ${yesProb(first).toFixed(3)} @${first.slot} -> ${yesProb(second).toFixed(3)} @${second.slot}`);
+  });
 });
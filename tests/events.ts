@@ -0,0 +1,514 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { SolanaPredict } from "../target/types/solana_predict";
+import * as token from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  PublicKey,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+} from "@solana/web3.js";
+
+/**
+ * Event Payload Tests
+ * --------------------
+ * Verifies the data indexers rely on:
+ *   1. scaled_amount_1e6 on BetPlaced for 9- and 6-decimal collateral
+ *   2. MarketClosing audit record on a clean close
+ *   3. DisputeStatusChanged on dispute opening and settlement
+ *   4. DisputeVotingExtended when the admin extends a dispute's vote
+ *   5. FeesWithdrawn from the program-owned treasury
+ */
+
+describe("Event Payload Tests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.solanaPredict as Program<SolanaPredict>;
+  const admin = provider.wallet;
+  const adminPayer = (admin as any).payer as Keypair;
+
+  const user = Keypair.generate();
+
+  const WSOL_MINT = new PublicKey("So11111111111111111111111111111111111111112");
+
+  const [platformConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_config")],
+    program.programId
+  );
+
+  let treasuryAta: PublicKey;
+
+  // Unique market IDs to avoid collisions with other test files
+  const BASE_ID = Math.floor(Date.now() / 1000) * 1000 + 700;
+
+  function deriveMarketPda(marketId: BN) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+  function deriveYesMint(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+
+  function deriveNoMint(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+  function deriveVault(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+  function derivePosition(marketPda: PublicKey, owner: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), marketPda.toBuffer(), owner.toBuffer()],
+      program.programId
+    );
+  }
+
+  function deriveDispute(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+
+  async function fundWsol(owner: Keypair, lamports: number) {
+    const ata = await token.getAssociatedTokenAddress(WSOL_MINT, owner.publicKey);
+    try {
+      await token.getAccount(provider.connection, ata);
+    } catch {
+      await token.createAssociatedTokenAccount(
+        provider.connection,
+        adminPayer,
+        WSOL_MINT,
+        owner.publicKey
+      );
+    }
+    const tx = new anchor.web3.Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: owner.publicKey,
+        toPubkey: ata,
+        lamports,
+      }),
+      token.createSyncNativeInstruction(ata)
+    );
+    await provider.sendAndConfirm(tx, [owner]);
+    return ata;
+  }
+
+  // Parse Anchor events out of a confirmed transaction's logs
+  async function getEvents(signature: string) {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    return [...parser.parseLogs(tx.meta.logMessages)];
+  }
+
+  async function createMarket(marketId: BN, adminAta: PublicKey, initialLiquidity: number) {
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createMarket(marketId, {
+        title: "Event Test Market",
+        description: "Test",
+        category: { crypto: {} },
+        oracleSource: { manualAdmin: {} },
+        oracleFeed: PublicKey.default,
+        oracleThreshold: new BN(0),
+        startTimestamp: new BN(now - 60),
+        lockTimestamp: new BN(now + 3600),
+        endTimestamp: new BN(now + 7200),
+        minBet: new BN(0),
+        maxBet: new BN(0),
+        isRecurring: false,
+        roundDuration: null,
+        feeBps: 250,
+        initialLiquidity: new BN(initialLiquidity),
+      } as any)
+      .accounts({ adminAta })
+      .rpc();
+  }
+
+  async function openDisputedMarket(marketId: BN) {
+    const [marketPda] = deriveMarketPda(marketId);
+    const [disputePda] = deriveDispute(marketPda);
+    const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+    await createMarket(marketId, adminAta, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .resolveMarket(marketId, { yes: {} })
+      .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+      .rpc();
+    const openSig = await program.methods
+      .openDispute(marketId, "Event test")
+      .accounts({
+        market: marketPda,
+        disputeRecord: disputePda,
+        platformConfig,
+        disputer: user.publicKey,
+      })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+    return { marketPda, disputePda, openSig };
+  }
+
+  before(async () => {
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    // Fees accrue in the program-owned treasury for the collateral mint
+    [treasuryAta] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), WSOL_MINT.toBuffer()],
+      program.programId
+    );
+
+    // Init platform (skip if already initialized by other test suite)
+    try {
+      await program.account.platformConfig.fetch(platformConfig);
+    } catch {
+      await program.methods
+        .initPlatform(250, new BN(1_000_000))
+        .accounts({
+          platformConfig,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          collateralMint: WSOL_MINT,
+          treasury: treasuryAta,
+        })
+        .rpc();
+    }
+  });
+
+  // =========================================================================
+  // 1. scaled_amount_1e6
+  // =========================================================================
+  describe("scaled_amount_1e6", () => {
+    it("9-decimal collateral (wSOL) is scaled down to 1e6", async () => {
+      const marketId = new BN(BASE_ID + 1);
+      const [marketPda] = deriveMarketPda(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await createMarket(marketId, adminAta, LAMPORTS_PER_SOL);
+
+      const BET = 123_456_789; // 0.123456789 SOL
+      await fundWsol(user, BET);
+      const userYesAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, yesMint, user.publicKey
+      );
+
+      const sig = await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(BET), new BN(0))
+        .accounts({
+          user: user.publicKey,
+          userShareAccount: userYesAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+
+      const betPlaced = (await getEvents(sig)).find((e) => e.name === "betPlaced");
+      assert.ok(betPlaced, "BetPlaced should be emitted");
+      assert.equal(betPlaced.data.amount.toNumber(), BET, "Raw amount is kept");
+      assert.equal(betPlaced.data.scaledAmount1e6.toNumber(), 123_456, "9 -> 6 decimals truncates");
+      console.log("  ✓ 9-decimal bet reported as", betPlaced.data.scaledAmount1e6.toNumber());
+    });
+
+    it("6-decimal collateral is reported unchanged", async () => {
+      // Temporarily switch the platform to a fresh 6-decimal collateral
+      const usdMint = await token.createMint(
+        provider.connection, adminPayer, admin.publicKey, null, 6
+      );
+      const adminUsd = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, usdMint, admin.publicKey
+      );
+      const userUsd = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, usdMint, user.publicKey
+      );
+      await token.mintTo(provider.connection, adminPayer, usdMint, adminUsd.address, adminPayer, 1_000_000_000);
+      await token.mintTo(provider.connection, adminPayer, usdMint, userUsd.address, adminPayer, 1_000_000_000);
+
+      const [usdTreasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury"), usdMint.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .updateCollateralMint()
+        .accounts({
+          platformConfig,
+          admin: admin.publicKey,
+          newCollateralMint: usdMint,
+          newTreasury: usdTreasury,
+        })
+        .rpc();
+
+      try {
+        const marketId = new BN(BASE_ID + 2);
+        const [marketPda] = deriveMarketPda(marketId);
+        const [yesMint] = deriveYesMint(marketPda);
+        await createMarket(marketId, adminUsd.address, 100_000_000);
+
+        const userYesAta = await token.getOrCreateAssociatedTokenAccount(
+          provider.connection, adminPayer, yesMint, user.publicKey
+        );
+
+        const BET = 12_345_678; // 12.345678 USD
+        const sig = await program.methods
+          .placeBet(marketId, { yes: {} }, new BN(BET), new BN(0))
+          .accounts({
+            user: user.publicKey,
+            userShareAccount: userYesAta.address,
+            platformConfig,
+            treasury: usdTreasury,
+            collateralMint: usdMint,
+          })
+          .signers([user])
+          .rpc({ commitment: "confirmed" });
+
+        const betPlaced = (await getEvents(sig)).find((e) => e.name === "betPlaced");
+        assert.ok(betPlaced, "BetPlaced should be emitted");
+        assert.equal(betPlaced.data.scaledAmount1e6.toNumber(), BET, "6-decimal amount is already 1e6");
+        console.log("  ✓ 6-decimal bet reported as", betPlaced.data.scaledAmount1e6.toNumber());
+      } finally {
+        // Restore wSOL collateral for the other suites
+        await program.methods
+          .updateCollateralMint()
+          .accounts({
+            platformConfig,
+            admin: admin.publicKey,
+            newCollateralMint: WSOL_MINT,
+            newTreasury: treasuryAta,
+          })
+          .rpc();
+      }
+    });
+  });
+
+  // =========================================================================
+  // 2. MarketClosing
+  // =========================================================================
+  describe("MarketClosing", () => {
+    it("Reports zero supplies and vault balance for a clean close", async () => {
+      const marketId = new BN(BASE_ID + 3);
+      const [marketPda] = deriveMarketPda(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const [vault] = deriveVault(marketPda);
+      const [userPosition] = derivePosition(marketPda, user.publicKey);
+
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await createMarket(marketId, adminAta, LAMPORTS_PER_SOL);
+
+      // Single YES holder wins the whole vault, leaving nothing behind
+      const BET = 100_000_000;
+      const userAta = await fundWsol(user, BET);
+      const userYesAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, yesMint, user.publicKey
+      );
+      await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(BET), new BN(0))
+        .accounts({
+          user: user.publicKey,
+          userShareAccount: userYesAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .signers([user])
+        .rpc();
+
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      await program.methods
+        .claimPayout(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault,
+          userPosition,
+          userAta,
+          userShareAccount: userYesAta.address,
+          user: user.publicKey,
+          collateralMint: WSOL_MINT,
+        })
+        .signers([user])
+        .rpc();
+
+      const sig = await program.methods
+        .closeMarket(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint,
+          noMint,
+          vault,
+          platformConfig,
+          admin: admin.publicKey,
+        })
+        .rpc({ commitment: "confirmed" });
+
+      const closing = (await getEvents(sig)).find((e) => e.name === "marketClosing");
+      assert.ok(closing, "MarketClosing should be emitted");
+      assert.equal(closing.data.marketId.toNumber(), marketId.toNumber());
+      assert.equal(closing.data.yesSupply.toNumber(), 0, "No YES tokens left behind");
+      assert.equal(closing.data.noSupply.toNumber(), 0, "No NO tokens left behind");
+      assert.equal(closing.data.vaultBalance.toNumber(), 0, "Vault fully paid out");
+      assert.isNull(await provider.connection.getAccountInfo(marketPda), "Market account closed");
+      console.log("  ✓ Clean close recorded with zero supplies and balance");
+    });
+  });
+
+  // =========================================================================
+  // 3. DisputeStatusChanged
+  // =========================================================================
+  describe("DisputeStatusChanged", () => {
+    it("Reports the new Open dispute when one is opened", async () => {
+      const marketId = new BN(BASE_ID + 8);
+      const { openSig } = await openDisputedMarket(marketId);
+
+      const events = await getEvents(openSig);
+      const changed = events.find((e) => e.name === "disputeStatusChanged");
+      assert.ok(changed, "DisputeStatusChanged should be emitted");
+      assert.equal(changed.data.marketId.toNumber(), marketId.toNumber());
+      assert.isNull(changed.data.old, "No status before the dispute existed");
+      assert.deepEqual(changed.data.new, { open: {} });
+      assert.ok(events.find((e) => e.name === "disputeOpened"), "DisputeOpened is still emitted");
+      console.log("  ✓ Opening recorded");
+    });
+
+    it("Reports Open -> Upheld when the dispute is upheld", async () => {
+      const marketId = new BN(BASE_ID + 4);
+      const { marketPda, disputePda } = await openDisputedMarket(marketId);
+
+      const sig = await program.methods
+        .settleDispute(marketId, { no: {} })
+        .accounts({ market: marketPda, disputeRecord: disputePda, platformConfig, platformAdmin: admin.publicKey, admin: admin.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+      const events = await getEvents(sig);
+      const changed = events.find((e) => e.name === "disputeStatusChanged");
+      assert.ok(changed, "DisputeStatusChanged should be emitted");
+      assert.deepEqual(changed.data.old, { open: {} });
+      assert.deepEqual(changed.data.new, { upheld: {} });
+      assert.ok(events.find((e) => e.name === "disputeSettled"), "DisputeSettled is still emitted");
+      console.log("  ✓ Open -> Upheld recorded");
+    });
+
+    it("Reports Open -> Rejected when the dispute is rejected", async () => {
+      const marketId = new BN(BASE_ID + 5);
+      const { marketPda, disputePda } = await openDisputedMarket(marketId);
+
+      const sig = await program.methods
+        .settleDispute(marketId, null)
+        .accounts({ market: marketPda, disputeRecord: disputePda, platformConfig, platformAdmin: admin.publicKey, admin: admin.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+      const changed = (await getEvents(sig)).find((e) => e.name === "disputeStatusChanged");
+      assert.ok(changed, "DisputeStatusChanged should be emitted");
+      assert.deepEqual(changed.data.old, { open: {} });
+      assert.deepEqual(changed.data.new, { rejected: {} });
+      console.log("  ✓ Open -> Rejected recorded");
+    });
+  });
+
+  // =========================================================================
+  // 4. DisputeVotingExtended
+  // =========================================================================
+  describe("DisputeVotingExtended", () => {
+    it("Extends an active dispute's voting window up to the platform maximum", async () => {
+      const marketId = new BN(BASE_ID + 6);
+      const { marketPda, disputePda } = await openDisputedMarket(marketId);
+      const before = await program.account.disputeRecord.fetch(disputePda);
+
+      const sig = await program.methods
+        .extendDisputeVoting(marketId, new BN(3600))
+        .accounts({ market: marketPda, disputeRecord: disputePda, platformConfig, admin: admin.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+      const dispute = await program.account.disputeRecord.fetch(disputePda);
+      assert.equal(dispute.votingEndsAt.toNumber(), before.votingEndsAt.toNumber() + 3600);
+      const extended = (await getEvents(sig)).find((e) => e.name === "disputeVotingExtended");
+      assert.ok(extended, "DisputeVotingExtended should be emitted");
+      assert.equal(extended.data.oldEndsAt.toNumber(), before.votingEndsAt.toNumber());
+      assert.equal(extended.data.newEndsAt.toNumber(), dispute.votingEndsAt.toNumber());
+
+      // Default cap is 7 days from opening; another 30 days is far past it
+      try {
+        await program.methods
+          .extendDisputeVoting(marketId, new BN(30 * 24 * 60 * 60))
+          .accounts({ market: marketPda, disputeRecord: disputePda, platformConfig, admin: admin.publicKey })
+          .rpc();
+        assert.fail("Should have thrown DisputeVotingTooLong");
+      } catch (e: any) {
+        assert.include(e.message, "DisputeVotingTooLong");
+      }
+      console.log("  ✓ Voting extended by 1h; over-long extension rejected");
+    });
+  });
+
+  // =========================================================================
+  // 5. FeesWithdrawn
+  // =========================================================================
+  describe("FeesWithdrawn", () => {
+    it("Withdraws from the treasury PDA and records the withdrawal", async () => {
+      // Top the treasury up so there is something to withdraw
+      const AMOUNT = 500_000n;
+      const source = await fundWsol(adminPayer, Number(AMOUNT));
+      await token.transfer(provider.connection, adminPayer, source, treasuryAta, adminPayer, AMOUNT);
+      const destination = await fundWsol(adminPayer, 1);
+      const balance = async (account: PublicKey) => (await token.getAccount(provider.connection, account)).amount;
+      const held = await balance(treasuryAta);
+      assert.isTrue(held >= AMOUNT, "Treasury holds the top-up");
+
+      const treasury = await token.getAccount(provider.connection, treasuryAta);
+      assert.equal(treasury.owner.toBase58(), platformConfig.toBase58(), "Treasury owned by the platform config");
+
+      const withdraw = (amount: bigint, signer = admin.publicKey) =>
+        program.methods
+          .withdrawFees(new BN(amount.toString()))
+          .accounts({ platformConfig, treasury: treasuryAta, destination, admin: signer } as any);
+
+      const destBefore = await balance(destination);
+      const sig = await withdraw(AMOUNT).rpc({ commitment: "confirmed" });
+      assert.equal(await balance(destination) - destBefore, AMOUNT);
+      assert.equal(held - (await balance(treasuryAta)), AMOUNT);
+
+      const withdrawn = (await getEvents(sig)).find((e) => e.name === "feesWithdrawn");
+      assert.ok(withdrawn, "FeesWithdrawn should be emitted");
+      assert.equal(withdrawn.data.mint.toBase58(), WSOL_MINT.toBase58());
+      assert.equal(withdrawn.data.destination.toBase58(), destination.toBase58());
+      assert.equal(withdrawn.data.amount.toString(), AMOUNT.toString());
+      assert.equal(withdrawn.data.remaining.toString(), (held - AMOUNT).toString());
+
+      try {
+        await withdraw((await balance(treasuryAta)) + 1n).rpc();
+        assert.fail("Should have thrown InsufficientTreasury");
+      } catch (e: any) {
+        assert.include(e.message, "InsufficientTreasury");
+      }
+      try {
+        await withdraw(1n, user.publicKey).signers([user]).rpc();
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+      console.log(`  ✓ Withdrew ${AMOUNT} lamports of fees; ${held - AMOUNT} left in the treasury`);
+    });
+  });
+});
@@ -0,0 +1,1761 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { SolanaPredict } from "../target/types/solana_predict";
+import * as token from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  PublicKey,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+} from "@solana/web3.js";
+
+/**
+ * Market Configuration Tests
+ * ---------------------------
+ * Platform- and market-level knobs enforced at creation time:
+ *   1. Per-creator creation cooldown
+ *   2. Per-category allowed oracle sources
+ *   3. abort_market for freshly created, un-bet markets
+ *   4. reclaim_seed after resolution
+ *   5. Platform-wide cap on concurrently active markets
+ *   6. Default outcome applied by resolve_on_timeout
+ *   7. Per-market admins for pause and resolution
+ *   8. Maximum backdate of start_timestamp
+ *   9. transfer_market_creator hands over creator rights
+ *  10. rebalance_liquidity shifts a skewed pool's odds
+ *  11. validate_market_params reports every problem at once
+ *  12. Two-step close with a grace window for cancel_close
+ *  13. Creator betting restricted on ManualAdmin markets
+ *  14. Two-step platform admin transfer
+ *  15. M-of-N approvals for sensitive admin actions
+ *  16. Pauser / operator / resolver roles
+ *  17. Optimistic resolution: propose, challenge, finalize
+ *  18. M-of-N resolution committee
+ *  19. Oracle feed registry
+ *  20. force_invalidate for abandoned markets without a default outcome
+ *  21. Skewed initial pools from an opening probability
+ *  22. Fee ramp towards lock
+ */
+
+describe("Market Configuration Tests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.solanaPredict as Program<SolanaPredict>;
+  const admin = provider.wallet;
+  const adminPayer = (admin as any).payer as Keypair;
+
+  const WSOL_MINT = new PublicKey("So11111111111111111111111111111111111111112");
+
+  const [platformConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_config")],
+    program.programId
+  );
+
+  let treasuryAta: PublicKey;
+
+  // Unique market IDs to avoid collisions with other test files
+  const BASE_ID = Math.floor(Date.now() / 1000) * 1000 + 900;
+
+  function deriveMarketPda(marketId: BN) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+  function deriveCreatorState(creator: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("creator"), creator.toBuffer()],
+      program.programId
+    );
+  }
+
+  async function fundWsol(owner: Keypair, lamports: number) {
+    const ata = await token.getAssociatedTokenAddress(WSOL_MINT, owner.publicKey);
+    try {
+      await token.getAccount(provider.connection, ata);
+    } catch {
+      await token.createAssociatedTokenAccount(
+        provider.connection,
+        adminPayer,
+        WSOL_MINT,
+        owner.publicKey
+      );
+    }
+    const tx = new anchor.web3.Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: owner.publicKey,
+        toPubkey: ata,
+        lamports,
+      }),
+      token.createSyncNativeInstruction(ata)
+    );
+    await provider.sendAndConfirm(tx, [owner]);
+    return ata;
+  }
+
+  function marketParams(overrides: any = {}) {
+    const now = Math.floor(Date.now() / 1000);
+    return {
+      title: "Config Test Market",
+      description: "Test",
+      category: { crypto: {} },
+      oracleSource: { manualAdmin: {} },
+      oracleFeed: PublicKey.default,
+      oracleThreshold: new BN(0),
+      startTimestamp: new BN(now - 60),
+      lockTimestamp: new BN(now + 3600),
+      endTimestamp: new BN(now + 7200),
+      minBet: new BN(10_000_000),
+      maxBet: new BN(0),
+      isRecurring: false,
+      roundDuration: null,
+      feeBps: 250,
+      initialLiquidity: new BN(LAMPORTS_PER_SOL),
+      ...overrides,
+    };
+  }
+
+  async function createMarket(marketId: BN, overrides: any = {}) {
+    const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+    await program.methods
+      .createMarket(marketId, marketParams(overrides) as any)
+      .accounts({ adminAta })
+      .rpc();
+    const [marketPda] = deriveMarketPda(marketId);
+    return marketPda;
+  }
+
+  const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
+
+  before(async () => {
+    // Fees accrue in the program-owned treasury for the collateral mint
+    [treasuryAta] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), WSOL_MINT.toBuffer()],
+      program.programId
+    );
+
+    // Init platform (skip if already initialized by other test suite)
+    try {
+      await program.account.platformConfig.fetch(platformConfig);
+    } catch {
+      await program.methods
+        .initPlatform(250, new BN(1_000_000))
+        .accounts({
+          platformConfig,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          collateralMint: WSOL_MINT,
+          treasury: treasuryAta,
+        })
+        .rpc();
+    }
+  });
+
+  // =========================================================================
+  // 1. Creation cooldown
+  // =========================================================================
+  describe("Creation cooldown", () => {
+    after(async () => {
+      await program.methods
+        .updateCreationCooldown(new BN(0))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Rejects a second market within the cooldown", async () => {
+      await program.methods
+        .updateCreationCooldown(new BN(3600))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+      await createMarket(new BN(BASE_ID + 1));
+
+      const [creatorState] = deriveCreatorState(admin.publicKey);
+      const state = await program.account.creatorState.fetch(creatorState);
+      assert.isAbove(state.lastCreatedAt.toNumber(), 0, "last_created_at should be recorded");
+
+      try {
+        await createMarket(new BN(BASE_ID + 2));
+        assert.fail("Should have thrown CreationCooldownActive");
+      } catch (e: any) {
+        assert.include(e.message, "CreationCooldownActive");
+        console.log("  ✓ Second creation within cooldown rejected");
+      }
+    });
+
+    it("Allows a new market once the cooldown has elapsed", async () => {
+      await program.methods
+        .updateCreationCooldown(new BN(1))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+      await sleep(2000);
+
+      const marketPda = await createMarket(new BN(BASE_ID + 3));
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(market.creator.equals(admin.publicKey));
+      console.log("  ✓ Creation allowed after cooldown");
+    });
+  });
+
+  // =========================================================================
+  // 2. Category oracle policy
+  // =========================================================================
+  describe("Category oracle policy", () => {
+    // Bitmask of 1 << OracleSource (Pyth = 0, Switchboard = 1, ManualAdmin = 2, ProgramState = 3, Chainlink = 4, SignedAttestation = 5)
+    const MANUAL_ONLY = 1 << 2;
+    const ALL_SOURCES = 0b111111;
+
+    before(async () => {
+      await program.methods
+        .setCategoryPolicy({ sports: {} }, MANUAL_ONLY)
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .setCategoryPolicy({ sports: {} }, ALL_SOURCES)
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Rejects a Sports market backed by a Pyth price feed", async () => {
+      try {
+        await createMarket(new BN(BASE_ID + 4), {
+          category: { sports: {} },
+          oracleSource: { pyth: {} },
+          oracleFeed: Keypair.generate().publicKey,
+        });
+        assert.fail("Should have thrown OracleSourceNotAllowedForCategory");
+      } catch (e: any) {
+        assert.include(e.message, "OracleSourceNotAllowedForCategory");
+        console.log("  ✓ Sports + Pyth rejected");
+      }
+    });
+
+    it("Allows a Sports market resolved by the admin", async () => {
+      const marketPda = await createMarket(new BN(BASE_ID + 5), { category: { sports: {} } });
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(market.category.sports);
+      assert.ok(market.oracleSource.manualAdmin);
+      console.log("  ✓ Sports + ManualAdmin allowed");
+    });
+
+    it("Leaves categories without a restriction untouched", async () => {
+      const marketPda = await createMarket(new BN(BASE_ID + 6));
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(market.category.crypto);
+      console.log("  ✓ Crypto + ManualAdmin still allowed");
+    });
+  });
+
+  // =========================================================================
+  // 3. abort_market
+  // =========================================================================
+  describe("abort_market", () => {
+    function deriveVault(marketPda: PublicKey) {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), marketPda.toBuffer()],
+        program.programId
+      );
+    }
+
+    it("Refunds the seed liquidity and closes the market and vault", async () => {
+      const marketId = new BN(BASE_ID + 7);
+      const marketPda = await createMarket(marketId);
+      const [vault] = deriveVault(marketPda);
+      const [creatorFeeEscrow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("creator_fees"), marketPda.toBuffer()],
+        program.programId
+      );
+      const adminAta = await token.getAssociatedTokenAddress(WSOL_MINT, admin.publicKey);
+
+      const wsolBefore = Number((await token.getAccount(provider.connection, adminAta)).amount);
+      const rent =
+        (await provider.connection.getAccountInfo(marketPda)).lamports +
+        (await provider.connection.getAccountInfo(vault)).lamports +
+        (await provider.connection.getAccountInfo(creatorFeeEscrow)).lamports;
+      const lamportsBefore = await provider.connection.getBalance(admin.publicKey);
+
+      await program.methods
+        .abortMarket(marketId)
+        .accounts({ market: marketPda, creatorAta: adminAta, admin: admin.publicKey })
+        .rpc();
+
+      const wsolAfter = Number((await token.getAccount(provider.connection, adminAta)).amount);
+      assert.equal(wsolAfter - wsolBefore, LAMPORTS_PER_SOL, "Full seed liquidity refunded");
+      assert.isNull(await provider.connection.getAccountInfo(marketPda), "Market closed");
+      assert.isNull(await provider.connection.getAccountInfo(vault), "Vault closed");
+      assert.isNull(await provider.connection.getAccountInfo(creatorFeeEscrow), "Creator fee escrow closed");
+
+      // Rent comes back minus the transaction fee
+      const lamportsAfter = await provider.connection.getBalance(admin.publicKey);
+      assert.isAbove(lamportsAfter - lamportsBefore, rent - 100_000, "Rent reclaimed");
+      console.log("  ✓ Aborted market refunded 1 SOL and its rent");
+    });
+
+    it("Rejects aborting a resolved market", async () => {
+      const marketId = new BN(BASE_ID + 8);
+      const marketPda = await createMarket(marketId);
+      await program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      const adminAta = await token.getAssociatedTokenAddress(WSOL_MINT, admin.publicKey);
+      try {
+        await program.methods
+          .abortMarket(marketId)
+          .accounts({ market: marketPda, creatorAta: adminAta, admin: admin.publicKey })
+          .rpc();
+        assert.fail("Should have thrown MarketNotCloseable");
+      } catch (e: any) {
+        assert.include(e.message, "MarketNotCloseable");
+        console.log("  ✓ Resolved market cannot be aborted");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 4. reclaim_seed
+  // =========================================================================
+  describe("reclaim_seed", () => {
+    function deriveVault(marketPda: PublicKey) {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), marketPda.toBuffer()],
+        program.programId
+      );
+    }
+    function deriveYesMint(marketPda: PublicKey) {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("yes_mint"), marketPda.toBuffer()],
+        program.programId
+      );
+    }
+
+    async function resolve(marketId: BN, marketPda: PublicKey, outcome: any) {
+      await program.methods
+        .resolveMarket(marketId, outcome)
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+    }
+
+    it("Returns unused seed after a no-volume market resolves, allowing close", async () => {
+      const marketId = new BN(BASE_ID + 9);
+      const marketPda = await createMarket(marketId);
+      const [vault] = deriveVault(marketPda);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("no_mint"), marketPda.toBuffer()],
+        program.programId
+      );
+      await resolve(marketId, marketPda, { no: {} });
+
+      const adminAta = await token.getAssociatedTokenAddress(WSOL_MINT, admin.publicKey);
+      const before = Number((await token.getAccount(provider.connection, adminAta)).amount);
+
+      await program.methods
+        .reclaimSeed(marketId)
+        .accounts({ market: marketPda, creatorAta: adminAta, creator: admin.publicKey })
+        .rpc();
+
+      const after = Number((await token.getAccount(provider.connection, adminAta)).amount);
+      assert.equal(after - before, LAMPORTS_PER_SOL, "Full seed returned");
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.initialLiquidity.toNumber(), 0, "Seed marked as reclaimed");
+      assert.equal(Number((await token.getAccount(provider.connection, vault)).amount), 0);
+
+      // Zero vault and zero supply: the market can now be closed
+      await program.methods
+        .closeMarket(marketId)
+        .accounts({ market: marketPda, yesMint, noMint, vault, platformConfig, admin: admin.publicKey })
+        .rpc();
+      assert.isNull(await provider.connection.getAccountInfo(marketPda), "Market closed");
+      console.log("  ✓ Seed reclaimed and market closed");
+    });
+
+    it("Waits for outstanding winners to claim first", async () => {
+      const marketId = new BN(BASE_ID + 10);
+      const marketPda = await createMarket(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+
+      // Admin takes a small YES position and wins
+      const adminAta = await fundWsol(adminPayer, 50_000_000);
+      const yesAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, yesMint, admin.publicKey
+      );
+      await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(50_000_000), new BN(0))
+        .accounts({
+          user: admin.publicKey,
+          userShareAccount: yesAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .rpc();
+      await resolve(marketId, marketPda, { yes: {} });
+
+      try {
+        await program.methods
+          .reclaimSeed(marketId)
+          .accounts({ market: marketPda, creatorAta: adminAta, creator: admin.publicKey })
+          .rpc();
+        assert.fail("Should have thrown OutstandingPositions");
+      } catch (e: any) {
+        assert.include(e.message, "OutstandingPositions");
+        console.log("  ✓ Reclaim blocked until winners have claimed");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 5. Active market cap
+  // =========================================================================
+  describe("Active market cap", () => {
+    after(async () => {
+      await program.methods
+        .updateMaxActiveMarkets(new BN(0))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Rejects creations beyond the cap until a market resolves", async () => {
+      // Other suites leave markets open: cap relative to the current count
+      const config = await program.account.platformConfig.fetch(platformConfig);
+      const open = config.activeMarketCount.toNumber();
+      await program.methods
+        .updateMaxActiveMarkets(new BN(open + 2))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+      const first = new BN(BASE_ID + 11);
+      const firstPda = await createMarket(first);
+      await createMarket(new BN(BASE_ID + 12));
+      const full = await program.account.platformConfig.fetch(platformConfig);
+      assert.equal(full.activeMarketCount.toNumber(), open + 2);
+
+      try {
+        await createMarket(new BN(BASE_ID + 13));
+        assert.fail("Should have thrown ActiveMarketLimitReached");
+      } catch (e: any) {
+        assert.include(e.message, "ActiveMarketLimitReached");
+        console.log("  ✓ Creation beyond the cap rejected");
+      }
+
+      // Resolving one frees a slot
+      await program.methods
+        .resolveMarket(first, { yes: {} })
+        .accounts({ market: firstPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      await createMarket(new BN(BASE_ID + 13));
+      console.log("  ✓ Slot freed by resolution");
+    });
+  });
+
+  // =========================================================================
+  // 6. Default outcome on timeout
+  // =========================================================================
+  describe("resolve_on_timeout", () => {
+    const caller = Keypair.generate();
+
+    function lapsedParams(overrides: any = {}) {
+      const now = Math.floor(Date.now() / 1000);
+      return {
+        title: "Will it happen?",
+        startTimestamp: new BN(now - 300),
+        lockTimestamp: new BN(now - 200),
+        endTimestamp: new BN(now - 100),
+        ...overrides,
+      };
+    }
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(caller.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    after(async () => {
+      await program.methods
+        .updateResolutionTimeout(new BN(7 * 24 * 60 * 60))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Rejects the default before the timeout has lapsed", async () => {
+      const marketId = new BN(BASE_ID + 14);
+      const marketPda = await createMarket(
+        marketId,
+        lapsedParams({ defaultOutcomeOnTimeout: { no: {} } })
+      );
+
+      try {
+        await program.methods
+          .resolveOnTimeout(marketId)
+          .accounts({ market: marketPda, caller: caller.publicKey, payoutReserve: null })
+          .signers([caller])
+          .rpc();
+        assert.fail("Should have thrown RoundIncomplete");
+      } catch (e: any) {
+        assert.include(e.message, "RoundIncomplete");
+        console.log("  ✓ Default held back within the timeout window");
+      }
+    });
+
+    it("Anyone resolves a lapsed market to its default after the timeout", async () => {
+      await program.methods
+        .updateResolutionTimeout(new BN(60))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+      const marketId = new BN(BASE_ID + 14);
+      const [marketPda] = deriveMarketPda(marketId);
+      const before = await program.account.platformConfig.fetch(platformConfig);
+
+      await program.methods
+        .resolveOnTimeout(marketId)
+        .accounts({ market: marketPda, caller: caller.publicKey, payoutReserve: null })
+        .signers([caller])
+        .rpc();
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.status, { resolved: {} });
+      assert.deepEqual(market.resolvedOutcome, { no: {} });
+      assert.isNull(market.resolutionPrice);
+
+      const after = await program.account.platformConfig.fetch(platformConfig);
+      assert.equal(after.activeMarketCount.toNumber(), before.activeMarketCount.toNumber() - 1);
+      console.log("  ✓ Lapsed market resolved to NO by a third party");
+    });
+
+    it("Rejects markets created without a default outcome", async () => {
+      const marketId = new BN(BASE_ID + 15);
+      const marketPda = await createMarket(marketId, lapsedParams());
+
+      try {
+        await program.methods
+          .resolveOnTimeout(marketId)
+          .accounts({ market: marketPda, caller: caller.publicKey, payoutReserve: null })
+          .signers([caller])
+          .rpc();
+        assert.fail("Should have thrown NoDefaultOutcome");
+      } catch (e: any) {
+        assert.include(e.message, "NoDefaultOutcome");
+        console.log("  ✓ No default, no timeout resolution");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 7. Per-market admins
+  // =========================================================================
+  describe("Market admins", () => {
+    const operator = Keypair.generate();
+    const ownId = new BN(BASE_ID + 16);
+    const otherId = new BN(BASE_ID + 17);
+    let ownPda: PublicKey;
+    let otherPda: PublicKey;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(operator.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      ownPda = await createMarket(ownId, { marketAdmins: [operator.publicKey] });
+      otherPda = await createMarket(otherId);
+    });
+
+    it("Lets a market admin pause and unpause their market", async () => {
+      await program.methods
+        .pauseMarket(ownId)
+        .accounts({ market: ownPda, platformConfig, admin: operator.publicKey })
+        .signers([operator])
+        .rpc();
+      let market = await program.account.market.fetch(ownPda);
+      assert.ok(market.status.paused);
+
+      await program.methods
+        .unpauseMarket(ownId)
+        .accounts({ market: ownPda, platformConfig, admin: operator.publicKey })
+        .signers([operator])
+        .rpc();
+      market = await program.account.market.fetch(ownPda);
+      assert.ok(market.status.active);
+      console.log("  ✓ Market admin paused and unpaused their market");
+    });
+
+    it("Lets a market admin resolve their ManualAdmin market", async () => {
+      await program.methods
+        .resolveMarket(ownId, { no: {} })
+        .accounts({ market: ownPda, admin: operator.publicKey, platformConfig, pythPriceFeed: null })
+        .signers([operator])
+        .rpc();
+      const market = await program.account.market.fetch(ownPda);
+      assert.deepEqual(market.resolvedOutcome, { no: {} });
+      console.log("  ✓ Market admin resolved their market");
+    });
+
+    it("Rejects the market admin on a different market", async () => {
+      for (const attempt of [
+        () => program.methods
+          .pauseMarket(otherId)
+          .accounts({ market: otherPda, platformConfig, admin: operator.publicKey })
+          .signers([operator])
+          .rpc(),
+        () => program.methods
+          .resolveMarket(otherId, { yes: {} })
+          .accounts({ market: otherPda, admin: operator.publicKey, platformConfig, pythPriceFeed: null })
+          .signers([operator])
+          .rpc(),
+      ]) {
+        try {
+          await attempt();
+          assert.fail("Should have thrown Unauthorized");
+        } catch (e: any) {
+          assert.include(e.message, "Unauthorized");
+        }
+      }
+      console.log("  ✓ No authority over other markets");
+    });
+  });
+
+  // =========================================================================
+  // 8. Maximum backdate
+  // =========================================================================
+  describe("Maximum backdate", () => {
+    before(async () => {
+      await program.methods
+        .updateMaxBackdate(new BN(300))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateMaxBackdate(new BN(0))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Rejects a market whose start lies far in the past", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      try {
+        await createMarket(new BN(BASE_ID + 18), { startTimestamp: new BN(now - 86400) });
+        assert.fail("Should have thrown InvalidTimestamps");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidTimestamps");
+      }
+      console.log("  ✓ Day-old start rejected");
+    });
+
+    it("Accepts a start within the backdate window", async () => {
+      const marketPda = await createMarket(new BN(BASE_ID + 19));
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(market.status.active);
+      console.log("  ✓ Start 60s ago accepted");
+    });
+  });
+
+  // =========================================================================
+  // 9. transfer_market_creator
+  // =========================================================================
+  describe("transfer_market_creator", () => {
+    const newCreator = Keypair.generate();
+    const marketId = new BN(BASE_ID + 20);
+    let marketPda: PublicKey;
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(newCreator.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      marketPda = await createMarket(marketId);
+    });
+
+    it("Requires the platform admin alongside the creator", async () => {
+      try {
+        await program.methods
+          .transferMarketCreator(marketId, newCreator.publicKey)
+          .accounts({ market: marketPda, platformConfig, creator: admin.publicKey, admin: newCreator.publicKey } as any)
+          .signers([newCreator])
+          .rpc();
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+      console.log("  ✓ Transfer without the platform admin rejected");
+    });
+
+    it("Moves seed reclaim to the new creator", async () => {
+      await program.methods
+        .transferMarketCreator(marketId, newCreator.publicKey)
+        .accounts({ market: marketPda, platformConfig, creator: admin.publicKey, admin: admin.publicKey } as any)
+        .rpc();
+      let market = await program.account.market.fetch(marketPda);
+      assert.ok(market.creator.equals(newCreator.publicKey));
+
+      await program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+
+      // Former creator has lost the right
+      const adminAta = await token.getAssociatedTokenAddress(WSOL_MINT, admin.publicKey);
+      try {
+        await program.methods
+          .reclaimSeed(marketId)
+          .accounts({ market: marketPda, creatorAta: adminAta, creator: admin.publicKey })
+          .rpc();
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+
+      const newCreatorAta = await token.createAssociatedTokenAccount(
+        provider.connection, adminPayer, WSOL_MINT, newCreator.publicKey
+      );
+      await program.methods
+        .reclaimSeed(marketId)
+        .accounts({ market: marketPda, creatorAta: newCreatorAta, creator: newCreator.publicKey })
+        .signers([newCreator])
+        .rpc();
+      const received = Number((await token.getAccount(provider.connection, newCreatorAta)).amount);
+      assert.equal(received, LAMPORTS_PER_SOL, "New creator receives the seed");
+      market = await program.account.market.fetch(marketPda);
+      assert.equal(market.initialLiquidity.toNumber(), 0);
+      console.log("  ✓ New creator reclaimed the seed");
+    });
+  });
+
+  // =========================================================================
+  // 10. rebalance_liquidity
+  // =========================================================================
+  describe("rebalance_liquidity", () => {
+    const marketId = new BN(BASE_ID + 21);
+    let marketPda: PublicKey;
+    let vault: PublicKey;
+
+    before(async () => {
+      marketPda = await createMarket(marketId);
+      [vault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), marketPda.toBuffer()],
+        program.programId
+      );
+      const [yesMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("yes_mint"), marketPda.toBuffer()],
+        program.programId
+      );
+
+      // One-sided betting skews the pool towards YES
+      await fundWsol(adminPayer, 500_000_000);
+      const yesAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, yesMint, admin.publicKey
+      );
+      await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(500_000_000), new BN(0))
+        .accounts({
+          user: admin.publicKey,
+          userShareAccount: yesAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .rpc();
+    });
+
+    it("Rejects a caller other than the creator", async () => {
+      const stranger = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(stranger.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      const strangerAta = await fundWsol(stranger, 100_000_000);
+      try {
+        await program.methods
+          .rebalanceLiquidity(marketId, new BN(100_000_000), new BN(0))
+          .accounts({ market: marketPda, creatorAta: strangerAta, platformConfig, creator: stranger.publicKey } as any)
+          .signers([stranger])
+          .rpc();
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+      console.log("  ✓ Non-creator rebalance rejected");
+    });
+
+    it("Adds to the YES reserve and restores even odds", async () => {
+      const before = await program.account.market.fetch(marketPda);
+      const yesPriceBefore =
+        before.totalNoShares.toNumber() / (before.totalYesShares.toNumber() + before.totalNoShares.toNumber());
+      assert.isAbove(yesPriceBefore, 0.5, "Pool is skewed towards YES");
+
+      const yesAdd = before.totalNoShares.sub(before.totalYesShares);
+      const creatorAta = await fundWsol(adminPayer, yesAdd.toNumber());
+      const vaultBefore = Number((await token.getAccount(provider.connection, vault)).amount);
+      await program.methods
+        .rebalanceLiquidity(marketId, yesAdd, new BN(0))
+        .accounts({ market: marketPda, creatorAta, platformConfig, creator: admin.publicKey } as any)
+        .rpc();
+
+      const after = await program.account.market.fetch(marketPda);
+      assert.equal(after.totalYesShares.toString(), after.totalNoShares.toString(), "Implied YES odds back at 50%");
+      assert.equal(
+        after.totalCollateral.sub(before.totalCollateral).toString(),
+        yesAdd.toString(),
+        "total_collateral grows by the deposit"
+      );
+      const vaultAfter = Number((await token.getAccount(provider.connection, vault)).amount);
+      assert.equal(vaultAfter - vaultBefore, yesAdd.toNumber(), "Deposit landed in the vault");
+      assert.equal(
+        after.initialLiquidity.sub(before.initialLiquidity).toString(),
+        yesAdd.toString(),
+        "Deposit joins the creator's liquidity"
+      );
+      console.log(`  ✓ YES price ${yesPriceBefore.toFixed(3)} -> 0.500 after adding ${yesAdd} to the YES reserve`);
+    });
+  });
+
+  // =========================================================================
+  // 11. validate_market_params
+  // =========================================================================
+  describe("validate_market_params", () => {
+    const validate = async (overrides: any, creator = admin.publicKey) => {
+      const problems = await program.methods
+        .validateMarketParams(marketParams(overrides) as any)
+        .accounts({ creator, pythPriceFeed: null } as any)
+        .view();
+      return problems.map((p: any) => Object.keys(p)[0]);
+    };
+
+    it("Reports nothing for params create_market accepts", async () => {
+      assert.deepEqual(await validate({}), []);
+    });
+
+    it("Returns every problem with a broken config", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      const problems = await validate({
+        title: "x".repeat(129),
+        lockTimestamp: new BN(now - 30),
+        feeBps: 2000,
+        initialLiquidity: new BN(0),
+      });
+      assert.deepEqual(problems, [
+        "titleTooLong",
+        "invalidTimestamps",
+        "feeExceedsMax",
+        "insufficientLiquidity",
+      ]);
+      console.log(`  ✓ ${problems.length} problems reported in one call`);
+    });
+
+    it("Flags a creator who is not the platform admin", async () => {
+      assert.deepEqual(await validate({}, Keypair.generate().publicKey), ["unauthorized"]);
+    });
+
+    it("Agrees with create_market on the first problem", async () => {
+      try {
+        await createMarket(new BN(BASE_ID + 22), { feeBps: 2000, initialLiquidity: new BN(0) });
+        assert.fail("Should have thrown FeeExceedsMax");
+      } catch (e: any) {
+        assert.include(e.message, "FeeExceedsMax");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 12. Close grace window
+  // =========================================================================
+  describe("Close grace window", () => {
+    const marketId = new BN(BASE_ID + 23);
+    let marketPda: PublicKey;
+    const GRACE_SECS = 3;
+
+    const derive = (seed: string) =>
+      PublicKey.findProgramAddressSync([Buffer.from(seed), marketPda.toBuffer()], program.programId)[0];
+    const closeMarket = () =>
+      program.methods
+        .closeMarket(marketId)
+        .accounts({
+          market: marketPda,
+          yesMint: derive("yes_mint"),
+          noMint: derive("no_mint"),
+          vault: derive("vault"),
+          platformConfig,
+          admin: admin.publicKey,
+        })
+        .rpc();
+    const request = (method: "markForClose" | "cancelClose") =>
+      program.methods[method](marketId)
+        .accounts({ market: marketPda, platformConfig, admin: admin.publicKey } as any)
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .updateCloseGrace(new BN(GRACE_SECS))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+      // Resolved, no volume, seed reclaimed: otherwise closeable right away
+      marketPda = await createMarket(marketId);
+      await program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      const adminAta = await token.getAssociatedTokenAddress(WSOL_MINT, admin.publicKey);
+      await program.methods
+        .reclaimSeed(marketId)
+        .accounts({ market: marketPda, creatorAta: adminAta, creator: admin.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateCloseGrace(new BN(0))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Requires a mark before close_market", async () => {
+      try {
+        await closeMarket();
+        assert.fail("Should have thrown CloseNotRequested");
+      } catch (e: any) {
+        assert.include(e.message, "CloseNotRequested");
+      }
+    });
+
+    it("Holds the close during the grace window, where cancel_close backs out", async () => {
+      await request("markForClose");
+      const market = await program.account.market.fetch(marketPda);
+      assert.isAbove(market.closeRequestedAt.toNumber(), 0, "Close requested");
+
+      try {
+        await closeMarket();
+        assert.fail("Should have thrown CloseGraceActive");
+      } catch (e: any) {
+        assert.include(e.message, "CloseGraceActive");
+      }
+
+      await request("cancelClose");
+      const cancelled = await program.account.market.fetch(marketPda);
+      assert.equal(cancelled.closeRequestedAt.toNumber(), 0, "Request withdrawn");
+
+      await sleep((GRACE_SECS + 1) * 1000);
+      try {
+        await closeMarket();
+        assert.fail("Should have thrown CloseNotRequested");
+      } catch (e: any) {
+        assert.include(e.message, "CloseNotRequested");
+      }
+      console.log("  ✓ Cancelled close cannot be finalized");
+    });
+
+    it("Closes once the grace window has elapsed", async () => {
+      await request("markForClose");
+      await sleep((GRACE_SECS + 1) * 1000);
+      await closeMarket();
+      assert.isNull(await provider.connection.getAccountInfo(marketPda), "Market closed");
+      console.log("  ✓ Market closed after the grace window");
+    });
+  });
+
+  // =========================================================================
+  // 13. Creator betting restriction
+  // =========================================================================
+  describe("Creator betting restriction", () => {
+    const marketId = new BN(BASE_ID + 24);
+    const bettor = Keypair.generate();
+    let yesMint: PublicKey;
+
+    async function bet(user: Keypair) {
+      await fundWsol(user, 50_000_000);
+      const yesAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, yesMint, user.publicKey
+      );
+      await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(50_000_000), new BN(0))
+        .accounts({
+          user: user.publicKey,
+          userShareAccount: yesAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .signers([user])
+        .rpc();
+    }
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(bettor.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      const marketPda = await createMarket(marketId, { creatorBettingRestricted: true });
+      [yesMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("yes_mint"), marketPda.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Rejects a bet from the creator who resolves the market", async () => {
+      try {
+        await bet(adminPayer);
+        assert.fail("Should have thrown ResolverCannotBet");
+      } catch (e: any) {
+        assert.include(e.message, "ResolverCannotBet");
+      }
+      console.log("  ✓ Creator/resolver bet rejected");
+    });
+
+    it("Still accepts other bettors", async () => {
+      await bet(bettor);
+      const [marketPda] = deriveMarketPda(marketId);
+      const market = await program.account.market.fetch(marketPda);
+      assert.isTrue(market.totalCollateral.gtn(LAMPORTS_PER_SOL), "Bet credited");
+    });
+  });
+
+  // =========================================================================
+  // 14. Two-step admin transfer
+  // =========================================================================
+  describe("Two-step admin transfer", () => {
+    const newAdmin = Keypair.generate();
+    const stranger = Keypair.generate();
+
+    const propose = (key: PublicKey, signer?: Keypair) => {
+      const call = program.methods
+        .proposeAdmin(key)
+        .accounts({ platformConfig, admin: signer ? signer.publicKey : admin.publicKey });
+      return signer ? call.signers([signer]).rpc() : call.rpc();
+    };
+    const accept = (signer?: Keypair) => {
+      const call = program.methods
+        .acceptAdmin()
+        .accounts({ platformConfig, newAdmin: signer ? signer.publicKey : admin.publicKey });
+      return signer ? call.signers([signer]).rpc() : call.rpc();
+    };
+
+    it("Hands the admin key over only when the proposed key accepts", async () => {
+      await propose(newAdmin.publicKey);
+      let config = await program.account.platformConfig.fetch(platformConfig);
+      assert.equal(config.pendingAdmin.toBase58(), newAdmin.publicKey.toBase58());
+      assert.equal(config.admin.toBase58(), admin.publicKey.toBase58(), "Current admin still in charge");
+
+      try {
+        await accept(stranger);
+        assert.fail("Should have thrown NotPendingAdmin");
+      } catch (e: any) {
+        assert.include(e.message, "NotPendingAdmin");
+      }
+
+      await accept(newAdmin);
+      try {
+        config = await program.account.platformConfig.fetch(platformConfig);
+        assert.equal(config.admin.toBase58(), newAdmin.publicKey.toBase58());
+        assert.equal(config.pendingAdmin.toBase58(), PublicKey.default.toBase58(), "Proposal consumed");
+
+        // The old admin lost its rights
+        try {
+          await propose(stranger.publicKey);
+          assert.fail("Should have thrown Unauthorized");
+        } catch (e: any) {
+          assert.include(e.message, "Unauthorized");
+        }
+      } finally {
+        // Hand it back for the other suites
+        await propose(admin.publicKey, newAdmin);
+        await accept();
+      }
+      config = await program.account.platformConfig.fetch(platformConfig);
+      assert.equal(config.admin.toBase58(), admin.publicKey.toBase58());
+      console.log("  ✓ Admin rotated and rotated back");
+    });
+  });
+
+  // =========================================================================
+  // 15. M-of-N approvals
+  // =========================================================================
+  describe("M-of-N approvals", () => {
+    const approverA = Keypair.generate();
+    const approverB = Keypair.generate();
+    const noAccounts = {
+      newCollateralMint: null,
+      newTreasury: null,
+      market: null,
+      disputeRecord: null,
+      disputer: null,
+      platformAdmin: null,
+      treasury: null,
+      destination: null,
+    };
+    let destination: PublicKey;
+
+    function deriveProposal(id: BN) {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), id.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    }
+
+    async function propose(action: any, proposer: Keypair) {
+      const id = (await program.account.platformConfig.fetch(platformConfig)).proposalCount;
+      await program.methods
+        .proposeAction(action)
+        .accounts({ platformConfig, proposal: deriveProposal(id), proposer: proposer.publicKey } as any)
+        .signers([proposer])
+        .rpc();
+      return id;
+    }
+
+    const approve = (id: BN, approver: Keypair) =>
+      program.methods
+        .approveAction(id)
+        .accounts({ platformConfig, proposal: deriveProposal(id), approver: approver.publicKey } as any)
+        .signers([approver])
+        .rpc();
+
+    const execute = (id: BN, executor: Keypair, accounts: any = {}) =>
+      program.methods
+        .executeAction()
+        .accounts({ platformConfig, proposal: deriveProposal(id), executor: executor.publicKey, ...noAccounts, ...accounts } as any)
+        .signers([executor])
+        .rpc();
+
+    before(async () => {
+      for (const k of [approverA, approverB]) {
+        const sig = await provider.connection.requestAirdrop(k.publicKey, LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+      destination = await fundWsol(approverA, 1);
+      await program.methods
+        .setApprovers([admin.publicKey, approverA.publicKey, approverB.publicKey], 2)
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      // Turn approvals back off for the other suites
+      const id = await propose({ setApprovers: { approvers: Array(5).fill(PublicKey.default), threshold: 0 } }, approverA);
+      await approve(id, approverB);
+      await execute(id, approverA);
+      const config = await program.account.platformConfig.fetch(platformConfig);
+      assert.equal(config.approvalThreshold, 0);
+    });
+
+    it("Blocks the admin acting alone once approvals are on", async () => {
+      try {
+        await program.methods
+          .withdrawFees(new BN(1))
+          .accounts({ platformConfig, treasury: treasuryAta, destination, admin: admin.publicKey } as any)
+          .rpc();
+        assert.fail("Should have thrown ApprovalRequired");
+      } catch (e: any) {
+        assert.include(e.message, "ApprovalRequired");
+      }
+      try {
+        await program.methods
+          .setApprovers([admin.publicKey], 1)
+          .accounts({ platformConfig, admin: admin.publicKey })
+          .rpc();
+        assert.fail("Should have thrown ApprovalRequired");
+      } catch (e: any) {
+        assert.include(e.message, "ApprovalRequired");
+      }
+      console.log("  ✓ withdraw_fees and set_approvers need a proposal");
+    });
+
+    it("Executes a fee withdrawal once two of three approve", async () => {
+      const id = await propose({ withdrawFees: { destination, amount: new BN(1) } }, approverA);
+      const accounts = { treasury: treasuryAta, destination };
+
+      // The proposer's approval alone is one of two
+      try {
+        await execute(id, approverA, accounts);
+        assert.fail("Should have thrown InsufficientApprovals");
+      } catch (e: any) {
+        assert.include(e.message, "InsufficientApprovals");
+      }
+      try {
+        await approve(id, approverA);
+        assert.fail("Should have thrown AlreadyApproved");
+      } catch (e: any) {
+        assert.include(e.message, "AlreadyApproved");
+      }
+
+      await approve(id, approverB);
+      const before = (await token.getAccount(provider.connection, destination)).amount;
+      await execute(id, approverB, accounts);
+      const after = (await token.getAccount(provider.connection, destination)).amount;
+      assert.equal(Number(after - before), 1, "Fee withdrawn");
+      assert.isTrue((await program.account.actionProposal.fetch(deriveProposal(id))).executed);
+
+      try {
+        await execute(id, approverB, accounts);
+        assert.fail("Should have thrown ActionAlreadyExecuted");
+      } catch (e: any) {
+        assert.include(e.message, "ActionAlreadyExecuted");
+      }
+      console.log("  ✓ 2-of-3 approved withdrawal executed once");
+    });
+
+    it("Blocks a single resolver from forking a dispute", async () => {
+      const marketId = new BN(BASE_ID + 39);
+      const marketPda = await createMarket(marketId);
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      await program.methods
+        .openDispute(marketId, "Contested outcome")
+        .accounts({ market: marketPda, platformConfig, disputer: admin.publicKey } as any)
+        .rpc();
+      const childId = new BN(BASE_ID + 40);
+      try {
+        await program.methods
+          .forkMarket(marketId, childId, { yes: {} }, 5000)
+          .accounts({
+            market: marketPda,
+            disputeRecord: PublicKey.findProgramAddressSync([Buffer.from("dispute"), marketPda.toBuffer()], program.programId)[0],
+            childMarket: deriveMarketPda(childId)[0],
+            platformConfig,
+            admin: admin.publicKey,
+            collateralMint: WSOL_MINT,
+          } as any)
+          .rpc();
+        assert.fail("Should have thrown ApprovalRequired");
+      } catch (e: any) {
+        assert.include(e.message, "ApprovalRequired");
+      }
+      console.log("  ✓ fork_market needs approvals off, like settle_dispute");
+    });
+
+    it("Shuts down and resumes only through approved proposals", async () => {
+      for (const method of ["emergencyShutdown", "resumeFromShutdown"] as const) {
+        try {
+          await program.methods[method]().accounts({ platformConfig, admin: admin.publicKey }).rpc();
+          assert.fail("Should have thrown ApprovalRequired");
+        } catch (e: any) {
+          assert.include(e.message, "ApprovalRequired");
+        }
+      }
+      assert.isFalse((await program.account.platformConfig.fetch(platformConfig)).shutdown);
+
+      const shutdown = await propose({ emergencyShutdown: {} }, approverA);
+      await approve(shutdown, approverB);
+      await execute(shutdown, approverB);
+      let config = await program.account.platformConfig.fetch(platformConfig);
+      assert.isTrue(config.shutdown);
+      assert.isTrue(config.paused);
+
+      const resume = await propose({ resumeFromShutdown: {} }, approverA);
+      await approve(resume, approverB);
+      await execute(resume, approverB);
+      await program.methods.unpausePlatform().accounts({ platformConfig, admin: admin.publicKey }).rpc();
+      config = await program.account.platformConfig.fetch(platformConfig);
+      assert.isFalse(config.shutdown);
+      assert.isFalse(config.paused);
+      console.log("  ✓ Shutdown and resume went through 2-of-3 proposals");
+    });
+  });
+
+  // =========================================================================
+  // 16. Roles
+  // =========================================================================
+  describe("Roles", () => {
+    const delegate = Keypair.generate();
+    const stranger = Keypair.generate();
+    const marketId = new BN(BASE_ID + 25);
+    let marketPda: PublicKey;
+
+    const setRole = (role: any, granted: boolean) =>
+      (granted ? program.methods.grantRole(delegate.publicKey, role) : program.methods.revokeRole(delegate.publicKey, role))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    const togglePlatform = (pause: boolean, signer: Keypair) =>
+      (pause ? program.methods.pausePlatform() : program.methods.unpausePlatform())
+        .accounts({ platformConfig, admin: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    const resolve = (signer: Keypair) =>
+      program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: signer.publicKey, platformConfig, pythPriceFeed: null })
+        .signers([signer])
+        .rpc();
+
+    before(async () => {
+      for (const k of [delegate, stranger]) {
+        const sig = await provider.connection.requestAirdrop(k.publicKey, LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+      marketPda = await createMarket(marketId);
+    });
+
+    after(async () => {
+      await setRole({ pauser: {} }, false);
+      await setRole({ resolver: {} }, false);
+      const config = await program.account.platformConfig.fetch(platformConfig);
+      assert.isFalse(config.paused);
+      assert.isFalse(
+        config.roleGrants.some((g: any) => g.key.equals(delegate.publicKey)),
+        "Slot freed once no roles remain"
+      );
+    });
+
+    it("Lets a Pauser pause and unpause the platform", async () => {
+      try {
+        await togglePlatform(true, delegate);
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+
+      await setRole({ pauser: {} }, true);
+      await togglePlatform(true, delegate);
+      try {
+        assert.isTrue((await program.account.platformConfig.fetch(platformConfig)).paused);
+      } finally {
+        await togglePlatform(false, delegate);
+      }
+      assert.isFalse((await program.account.platformConfig.fetch(platformConfig)).paused);
+      console.log("  ✓ Pauser toggled the platform");
+    });
+
+    it("Lets only a Resolver resolve a ManualAdmin market", async () => {
+      for (const signer of [stranger, delegate]) {
+        try {
+          await resolve(signer);
+          assert.fail("Should have thrown Unauthorized");
+        } catch (e: any) {
+          assert.include(e.message, "Unauthorized");
+        }
+      }
+
+      await setRole({ resolver: {} }, true);
+      await resolve(delegate);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      console.log("  ✓ Resolver resolved; Pauser alone and strangers could not");
+    });
+  });
+
+  // =========================================================================
+  // 17. Optimistic resolution
+  // =========================================================================
+  describe("Optimistic resolution", () => {
+    const proposer = Keypair.generate();
+    const challenger = Keypair.generate();
+    const BOND = LAMPORTS_PER_SOL / 10;
+    const WINDOW_SECS = 3;
+
+    const derive = (seed: string, marketPda: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from(seed), marketPda.toBuffer()], program.programId)[0];
+
+    async function createEnded(marketId: BN) {
+      const now = Math.floor(Date.now() / 1000);
+      return createMarket(marketId, {
+        startTimestamp: new BN(now - 300),
+        lockTimestamp: new BN(now - 200),
+        endTimestamp: new BN(now - 100),
+      });
+    }
+
+    const propose = (marketId: BN, marketPda: PublicKey, outcome: any) =>
+      program.methods
+        .proposeResolution(marketId, outcome)
+        .accounts({
+          market: marketPda,
+          proposal: derive("resolution_proposal", marketPda),
+          platformConfig,
+          payoutReserve: null,
+          proposer: proposer.publicKey,
+        } as any)
+        .signers([proposer])
+        .rpc();
+
+    const finalize = (marketId: BN, marketPda: PublicKey, withDispute = false) =>
+      program.methods
+        .finalizeResolution(marketId)
+        .accounts({
+          market: marketPda,
+          proposal: derive("resolution_proposal", marketPda),
+          proposer: proposer.publicKey,
+          platformConfig,
+          disputeRecord: withDispute ? derive("dispute", marketPda) : null,
+          challenger: withDispute ? challenger.publicKey : null,
+          caller: admin.publicKey,
+        } as any)
+        .rpc();
+
+    const balance = (key: PublicKey) => provider.connection.getBalance(key);
+
+    before(async () => {
+      for (const k of [proposer, challenger]) {
+        const sig = await provider.connection.requestAirdrop(k.publicKey, 2 * LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+      await program.methods
+        .updateOptimisticResolution(new BN(BOND), new BN(WINDOW_SECS))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      const config = await program.account.platformConfig.fetch(platformConfig);
+      await program.methods
+        .updateOptimisticResolution(config.disputeBondLamports, new BN(24 * 60 * 60))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Rejects a proposal before the market ends", async () => {
+      const marketId = new BN(BASE_ID + 26);
+      const marketPda = await createMarket(marketId);
+      try {
+        await propose(marketId, marketPda, { yes: {} });
+        assert.fail("Should have thrown RoundIncomplete");
+      } catch (e: any) {
+        assert.include(e.message, "RoundIncomplete");
+      }
+    });
+
+    it("Applies an unchallenged proposal after the window and returns the bond", async () => {
+      const marketId = new BN(BASE_ID + 27);
+      const marketPda = await createEnded(marketId);
+      await propose(marketId, marketPda, { yes: {} });
+      let market = await program.account.market.fetch(marketPda);
+      assert.ok(market.status.resolving, "Held for the challenge window");
+
+      try {
+        await finalize(marketId, marketPda);
+        assert.fail("Should have thrown ChallengeWindowOpen");
+      } catch (e: any) {
+        assert.include(e.message, "ChallengeWindowOpen");
+      }
+
+      await sleep((WINDOW_SECS + 1) * 1000);
+      const before = await balance(proposer.publicKey);
+      await finalize(marketId, marketPda);
+      market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.ok(market.resolutionProof.resolver.equals(proposer.publicKey));
+      assert.isAtLeast((await balance(proposer.publicKey)) - before, BOND, "Bond and rent returned");
+      assert.isNull(await provider.connection.getAccountInfo(derive("resolution_proposal", marketPda)));
+      console.log("  ✓ Proposal finalized without the admin");
+    });
+
+    it("Escalates a challenge to a dispute and pays an upheld challenger the bond", async () => {
+      const marketId = new BN(BASE_ID + 28);
+      const marketPda = await createEnded(marketId);
+      await propose(marketId, marketPda, { no: {} });
+
+      await program.methods
+        .challengeResolution(marketId, "It happened")
+        .accounts({
+          market: marketPda,
+          proposal: derive("resolution_proposal", marketPda),
+          disputeRecord: derive("dispute", marketPda),
+          platformConfig,
+          challenger: challenger.publicKey,
+        } as any)
+        .signers([challenger])
+        .rpc();
+      let market = await program.account.market.fetch(marketPda);
+      assert.ok(market.status.disputed);
+      assert.deepEqual(market.resolvedOutcome, { no: {} }, "Proposal stands until settled");
+
+      try {
+        await finalize(marketId, marketPda, true);
+        assert.fail("Should have thrown DisputeNotSettled");
+      } catch (e: any) {
+        assert.include(e.message, "DisputeNotSettled");
+      }
+
+      await program.methods
+        .settleDispute(marketId, { yes: {} })
+        .accounts({ market: marketPda, platformConfig, platformAdmin: admin.publicKey, admin: admin.publicKey } as any)
+        .rpc();
+      const before = await balance(challenger.publicKey);
+      await finalize(marketId, marketPda, true);
+      market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.equal((await balance(challenger.publicKey)) - before, BOND, "Challenger took the bond");
+      console.log("  ✓ Upheld challenge overturned the proposal");
+    });
+  });
+
+  // =========================================================================
+  // 18. Resolution committee
+  // =========================================================================
+  describe("Resolution committee", () => {
+    const members = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const stranger = Keypair.generate();
+    const marketId = new BN(BASE_ID + 29);
+    let marketPda: PublicKey;
+
+    const attest = (member: Keypair, outcome: any) =>
+      program.methods
+        .attestOutcome(marketId, outcome)
+        .accounts({ market: marketPda, platformConfig, payoutReserve: null, member: member.publicKey } as any)
+        .signers([member])
+        .rpc();
+
+    before(async () => {
+      for (const k of [...members, stranger]) {
+        const sig = await provider.connection.requestAirdrop(k.publicKey, LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+      await program.methods
+        .setResolutionCommittee(members.map((m) => m.publicKey), 2)
+        .accounts({ platformConfig, admin: admin.publicKey } as any)
+        .rpc();
+      marketPda = await createMarket(marketId, { committeeResolution: true });
+    });
+
+    it("Keeps the single resolver out of a committee market", async () => {
+      try {
+        await program.methods
+          .resolveMarket(marketId, { yes: {} })
+          .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+          .rpc();
+        assert.fail("Should have thrown CommitteeResolutionRequired");
+      } catch (e: any) {
+        assert.include(e.message, "CommitteeResolutionRequired");
+      }
+      try {
+        await attest(stranger, { yes: {} });
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+    });
+
+    it("Resolves once two of three members attest the same outcome", async () => {
+      await attest(members[0], { yes: {} });
+      await attest(members[1], { no: {} });
+      let market = await program.account.market.fetch(marketPda);
+      assert.isNull(market.resolvedOutcome, "Split attestations don't resolve");
+
+      // A member may change their attestation
+      await attest(members[1], { yes: {} });
+      market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.ok(market.status.resolved);
+      assert.ok(market.resolutionProof.resolver.equals(members[1].publicKey));
+
+      try {
+        await attest(members[2], { no: {} });
+        assert.fail("Should have thrown AlreadyResolved");
+      } catch (e: any) {
+        assert.include(e.message, "AlreadyResolved");
+      }
+      console.log("  ✓ 2-of-3 committee resolved YES");
+    });
+  });
+
+  // =========================================================================
+  // 19. Oracle feed registry
+  // =========================================================================
+  describe("Oracle feed registry", () => {
+    const PYTH_SOL_USD = new PublicKey("AVE9TzEZnBNsn9bNVwSggCHAsYqm73zDN5wr5xfCuxea");
+    const unlisted = Keypair.generate().publicKey;
+
+    const pythParams = (oracleFeed: PublicKey) => ({
+      oracleSource: { pyth: {} },
+      oracleFeed,
+      oracleThreshold: new BN(14_000),
+    });
+
+    after(async () => {
+      // An empty registry restricts nothing
+      await program.methods
+        .deregisterFeed(PYTH_SOL_USD)
+        .accounts({ platformConfig, admin: admin.publicKey } as any)
+        .rpc();
+    });
+
+    it("Only allows markets on registered feeds once one is listed", async () => {
+      await program.methods
+        .registerFeed(PYTH_SOL_USD, { pyth: {} }, "SOL/USD", -2)
+        .accounts({ platformConfig, admin: admin.publicKey } as any)
+        .rpc();
+
+      try {
+        await createMarket(new BN(BASE_ID + 30), pythParams(unlisted));
+        assert.fail("Should have thrown FeedNotRegistered");
+      } catch (e: any) {
+        assert.include(e.message, "FeedNotRegistered");
+      }
+      // Listed for Pyth only
+      try {
+        await createMarket(new BN(BASE_ID + 30), { ...pythParams(PYTH_SOL_USD), oracleSource: { switchboard: {} } });
+        assert.fail("Should have thrown FeedNotRegistered");
+      } catch (e: any) {
+        assert.include(e.message, "FeedNotRegistered");
+      }
+
+      const marketPda = await createMarket(new BN(BASE_ID + 30), pythParams(PYTH_SOL_USD));
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.oracleExponent, -2, "Threshold takes the registered exponent");
+
+      // ManualAdmin markets don't name a feed
+      await createMarket(new BN(BASE_ID + 31));
+      console.log("  ✓ Unlisted feed refused, listed feed accepted");
+    });
+
+    it("Rejects registry changes from a non-admin", async () => {
+      const stranger = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(stranger.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      try {
+        await program.methods
+          .registerFeed(unlisted, { pyth: {} }, "FAKE/USD", -8)
+          .accounts({ platformConfig, admin: stranger.publicKey } as any)
+          .signers([stranger])
+          .rpc();
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 20. Force-invalidating abandoned markets
+  // =========================================================================
+  describe("Force invalidate", () => {
+    const caller = Keypair.generate();
+
+    function lapsedParams(overrides: any = {}) {
+      const now = Math.floor(Date.now() / 1000);
+      return {
+        startTimestamp: new BN(now - 300),
+        lockTimestamp: new BN(now - 200),
+        endTimestamp: new BN(now - 100),
+        ...overrides,
+      };
+    }
+
+    const forceInvalidate = (marketId: BN, marketPda: PublicKey) =>
+      program.methods
+        .forceInvalidate(marketId)
+        .accounts({ market: marketPda, caller: caller.publicKey, payoutReserve: null })
+        .signers([caller])
+        .rpc();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(caller.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    after(async () => {
+      await program.methods
+        .updateResolutionTimeout(new BN(7 * 24 * 60 * 60))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Holds off until the resolution timeout has lapsed", async () => {
+      const marketId = new BN(BASE_ID + 32);
+      const marketPda = await createMarket(marketId, lapsedParams());
+      try {
+        await forceInvalidate(marketId, marketPda);
+        assert.fail("Should have thrown RoundIncomplete");
+      } catch (e: any) {
+        assert.include(e.message, "RoundIncomplete");
+      }
+    });
+
+    it("Anyone resolves an abandoned market Invalid after the timeout", async () => {
+      await program.methods
+        .updateResolutionTimeout(new BN(60))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+      const marketId = new BN(BASE_ID + 32);
+      const [marketPda] = deriveMarketPda(marketId);
+      await forceInvalidate(marketId, marketPda);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.status, { resolved: {} });
+      assert.deepEqual(market.resolvedOutcome, { invalid: {} });
+      assert.ok(market.resolutionProof.resolver.equals(caller.publicKey));
+      console.log("  ✓ Abandoned market invalidated by a third party");
+    });
+
+    it("Leaves markets with a default outcome to resolve_on_timeout", async () => {
+      const marketId = new BN(BASE_ID + 33);
+      const marketPda = await createMarket(marketId, lapsedParams({ defaultOutcomeOnTimeout: { yes: {} } }));
+      try {
+        await forceInvalidate(marketId, marketPda);
+        assert.fail("Should have thrown DefaultOutcomeSet");
+      } catch (e: any) {
+        assert.include(e.message, "DefaultOutcomeSet");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 21. Opening probability
+  // =========================================================================
+  describe("Initial probability", () => {
+    it("Seeds the pools so YES opens at the given prior", async () => {
+      const marketPda = await createMarket(new BN(BASE_ID + 34), { initialProbBps: 8000 });
+      const market = await program.account.market.fetch(marketPda);
+
+      const yes = market.totalYesShares.toNumber();
+      const no = market.totalNoShares.toNumber();
+      assert.equal(no / (yes + no), 0.8, "YES price = NO reserve / both");
+      assert.equal(yes + no, 2 * LAMPORTS_PER_SOL);
+      assert.equal(market.totalCollateral.toNumber(), LAMPORTS_PER_SOL, "Collateral is the seed, whatever the prior");
+      console.log(`  ✓ Opened at 80/20 (YES ${yes}, NO ${no})`);
+    });
+
+    it("Rejects a prior that would leave a pool nearly empty", async () => {
+      try {
+        await createMarket(new BN(BASE_ID + 35), { initialProbBps: 9950 });
+        assert.fail("Should have thrown InvalidInitialProbability");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidInitialProbability");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 22. Time-decaying fee schedule
+  // =========================================================================
+  describe("Fee ramp", () => {
+    const amount = LAMPORTS_PER_SOL;
+
+    async function quotedFeeBps(marketId: BN, marketPda: PublicKey) {
+      const quote = await program.methods
+        .quoteFee(marketId, { yes: {} }, new BN(amount), null)
+        .accounts({ market: marketPda })
+        .view();
+      return (quote.totalFee.toNumber() * 10000) / amount;
+    }
+
+    it("Raises the fee linearly over the ramp before lock", async () => {
+      // Lock is an hour out and the ramp two hours long: about halfway up
+      const marketId = new BN(BASE_ID + 36);
+      const marketPda = await createMarket(marketId, { maxFeeBps: 1000, feeRampSecs: new BN(7200) });
+      const feeBps = await quotedFeeBps(marketId, marketPda);
+      assert.isAbove(feeBps, 600);
+      assert.isBelow(feeBps, 650);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.maxFeeBps, 1000);
+      assert.equal(market.feeBps, 250, "Base fee unchanged");
+      console.log(`  ✓ Fee at ${feeBps} bps an hour before lock`);
+    });
+
+    it("Keeps the base fee before the ramp starts", async () => {
+      const marketId = new BN(BASE_ID + 37);
+      const marketPda = await createMarket(marketId, { maxFeeBps: 1000, feeRampSecs: new BN(600) });
+      assert.equal(await quotedFeeBps(marketId, marketPda), 250);
+    });
+
+    it("Rejects a ramp below the base fee", async () => {
+      try {
+        await createMarket(new BN(BASE_ID + 38), { maxFeeBps: 100, feeRampSecs: new BN(600) });
+        assert.fail("Should have thrown InvalidFeeRamp");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidFeeRamp");
+      }
+    });
+  });
+});
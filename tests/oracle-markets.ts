@@ -0,0 +1,2002 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { SolanaPredict } from "../target/types/solana_predict";
+import * as token from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  PublicKey,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+
+/**
+ * Oracle Market Tests
+ * --------------------
+ * Pyth-backed markets, exercised against the PriceUpdateV2 fixture loaded by
+ * Anchor.toml (tests/fixtures/pyth-sol-usd.json):
+ *   SOL/USD = 150.00000000 (expo -8), conf 0.015
+ * and a copy last published at 1700000000 (tests/fixtures/pyth-sol-usd-quiet.json).
+ * A signed funding-rate feed (tests/fixtures/pyth-funding-rate.json) publishes
+ *   -0.0002505 (expo -8), conf 0.00001
+ * and a Switchboard pull feed (tests/fixtures/switchboard-sol-usd.json) holds
+ *   SOL/USD = 150 (18 decimals), std dev 0.01
+ * and a Chainlink feed (tests/fixtures/chainlink-sol-usd.json) answers
+ *   SOL/USD = 150.00000000 (8 decimals)
+ *
+ * Tests cover:
+ *   1. Minimum threshold distance from the live price at creation
+ *   2. Resolution against a price snapshotted at end_timestamp
+ *   3. Boundary band / confidence Invalid fallback
+ *   4. Resolution grace for a feed that went quiet right after close
+ *   5. ProgramState markets reading another program's account
+ *   6. Per-slot oracle cache reuse
+ *   7. Resolution proof read-back
+ *   8. Confidence split: partial credit when the conf band straddles the threshold
+ *   9. Minimum slots between the last bet and an oracle resolution
+ *  10. Threshold kept in the creation-time exponent when the feed's exponent differs
+ *  11. Signed feeds: negative prices and thresholds through exponent rescaling
+ *  12. Chain-native markets resolved at a slot/epoch instead of end_timestamp
+ *  13. Migrating an unresolved market's oracle source
+ *  14. Permissionless resolution of ended Pyth markets
+ *  15. Switchboard markets resolved from a pull feed
+ *  16. Chainlink markets resolved from the latest round
+ *  17. Ed25519-signed attestations from an off-chain attester
+ *  18. TWAP resolution over recorded price snapshots
+ *  19. Scalar markets interpolated between a lower and upper bound
+ *  20. Range markets: YES when the price lands between two thresholds
+ *  21. Barrier markets resolved early by check_barrier
+ *  22. Lock price of a recurring market's round recorded by lock_round
+ *  23. Up/down round resolution against the lock price
+ *  24. Per-round parimutuel bets and claims
+ *  25. crank_round resolving one round and opening the next
+ *  26. Reclaiming rent from settled rounds and their positions
+ */
+
+describe("Oracle Market Tests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.solanaPredict as Program<SolanaPredict>;
+  const admin = provider.wallet;
+  const adminPayer = (admin as any).payer as Keypair;
+
+  const WSOL_MINT = new PublicKey("So11111111111111111111111111111111111111112");
+
+  // Fixture feed (see Anchor.toml)
+  const PYTH_SOL_USD = new PublicKey("AVE9TzEZnBNsn9bNVwSggCHAsYqm73zDN5wr5xfCuxea");
+  const FIXTURE_PRICE = 15_000_000_000; // 150.00000000
+  const PYTH_SOL_USD_QUIET = new PublicKey("8JkfpGsRvZEw93mHYShQgjw25M5gc7gEcBNFKbV33Tw9");
+  const QUIET_PUBLISH_TIME = 1_700_000_000;
+  const PYTH_FUNDING_RATE = new PublicKey("EtFhkU2huVY9rmrv2bvNghedXK8RnwsskwL44rY7Rfwf");
+  const FUNDING_RATE_PRICE = -25_050; // -0.0002505 (-0.02505%)
+  const SWITCHBOARD_SOL_USD = new PublicKey("FkTbkQknSGzQbK77x41rmfDbu3NAVBKXvGFWDGGLrGK9");
+  const CHAINLINK_SOL_USD = new PublicKey("2nrGQxsiJLmTJPz2YU6MpbVHRr8U8TxBu7L8689GmdWm");
+
+  const [platformConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_config")],
+    program.programId
+  );
+
+  let treasuryAta: PublicKey;
+
+  // Unique market IDs to avoid collisions with other test files
+  const BASE_ID = Math.floor(Date.now() / 1000) * 1000 + 600;
+
+  function deriveMarketPda(marketId: BN) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), marketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+  }
+  function deriveYesMint(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+  function deriveNoMint(marketPda: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPda.toBuffer()],
+      program.programId
+    );
+  }
+
+  async function fundWsol(owner: Keypair, lamports: number) {
+    const ata = await token.getAssociatedTokenAddress(WSOL_MINT, owner.publicKey);
+    try {
+      await token.getAccount(provider.connection, ata);
+    } catch {
+      await token.createAssociatedTokenAccount(
+        provider.connection,
+        adminPayer,
+        WSOL_MINT,
+        owner.publicKey
+      );
+    }
+    const tx = new anchor.web3.Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: owner.publicKey,
+        toPubkey: ata,
+        lamports,
+      }),
+      token.createSyncNativeInstruction(ata)
+    );
+    await provider.sendAndConfirm(tx, [owner]);
+    return ata;
+  }
+
+  const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
+
+  function pythMarketParams(threshold: number, overrides: any = {}) {
+    const now = Math.floor(Date.now() / 1000);
+    return {
+      title: "Oracle Test: SOL > threshold?",
+      description: "Test",
+      category: { crypto: {} },
+      oracleSource: { pyth: {} },
+      oracleFeed: PYTH_SOL_USD,
+      oracleThreshold: new BN(threshold),
+      startTimestamp: new BN(now - 60),
+      lockTimestamp: new BN(now + 3600),
+      endTimestamp: new BN(now + 7200),
+      minBet: new BN(10_000_000),
+      maxBet: new BN(0),
+      isRecurring: false,
+      roundDuration: null,
+      feeBps: 250,
+      initialLiquidity: new BN(LAMPORTS_PER_SOL),
+      ...overrides,
+    };
+  }
+
+  async function createPythMarket(marketId: BN, overrides: any = {}) {
+    const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+    await program.methods
+      .createMarket(marketId, pythMarketParams(14_000_000_000, overrides) as any)
+      .accounts({ adminAta })
+      .rpc();
+    return deriveMarketPda(marketId)[0];
+  }
+
+  // Ended 30s ago, so it can be resolved right away
+  async function createEndedMarket(marketId: BN, overrides: any = {}) {
+    const now = Math.floor(Date.now() / 1000);
+    return createPythMarket(marketId, {
+      startTimestamp: new BN(now - 120),
+      lockTimestamp: new BN(now - 60),
+      endTimestamp: new BN(now - 30),
+      ...overrides,
+    });
+  }
+
+  before(async () => {
+    // Fees accrue in the program-owned treasury for the collateral mint
+    [treasuryAta] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), WSOL_MINT.toBuffer()],
+      program.programId
+    );
+
+    // Init platform (skip if already initialized by other test suite)
+    try {
+      await program.account.platformConfig.fetch(platformConfig);
+    } catch {
+      await program.methods
+        .initPlatform(250, new BN(1_000_000))
+        .accounts({
+          platformConfig,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          collateralMint: WSOL_MINT,
+          treasury: treasuryAta,
+        })
+        .rpc();
+    }
+  });
+
+  // =========================================================================
+  // 1. Minimum threshold distance at creation
+  // =========================================================================
+  describe("Minimum threshold distance", () => {
+    before(async () => {
+      await program.methods
+        .updateMinThresholdDistance(500) // 5%
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateMinThresholdDistance(0)
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Rejects a threshold right at the current price", async () => {
+      const marketId = new BN(BASE_ID + 1);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+
+      try {
+        await program.methods
+          .createMarket(marketId, pythMarketParams(FIXTURE_PRICE) as any)
+          .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+          .rpc();
+        assert.fail("Should have thrown ThresholdTooClose");
+      } catch (e: any) {
+        assert.include(e.message, "ThresholdTooClose");
+        console.log("  ✓ Threshold at current price rejected");
+      }
+    });
+
+    it("Accepts a threshold outside the minimum distance", async () => {
+      const marketId = new BN(BASE_ID + 2);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+
+      // 10% above the fixture price
+      await program.methods
+        .createMarket(marketId, pythMarketParams(16_500_000_000) as any)
+        .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      const [marketPda] = deriveMarketPda(marketId);
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.oracleThreshold.toNumber(), 16_500_000_000);
+      console.log("  ✓ Threshold 10% away accepted");
+    });
+  });
+
+  // =========================================================================
+  // 2. Resolution price snapshot at end_timestamp
+  // =========================================================================
+  describe("Resolution price snapshot", () => {
+    it("Snapshots near end and resolves later against the snapshot", async () => {
+      const marketId = new BN(BASE_ID + 3);
+      const [marketPda] = deriveMarketPda(marketId);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      const now = Math.floor(Date.now() / 1000);
+
+      // Threshold below the fixture price -> YES
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000, {
+          lockTimestamp: new BN(now + 2),
+          endTimestamp: new BN(now + 4),
+        }) as any)
+        .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      await sleep(6000);
+
+      await program.methods
+        .snapshotResolutionPrice(marketId)
+        .accounts({ market: marketPda, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      const snapshotted = await program.account.market.fetch(marketPda);
+      assert.ok(snapshotted.status.resolving, "Market should be Resolving after snapshot");
+      assert.equal(snapshotted.resolutionPrice.toNumber(), FIXTURE_PRICE);
+
+      // Resolve later without a feed: the stored snapshot is used
+      await program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({
+          market: marketPda,
+          admin: admin.publicKey,
+          platformConfig,
+          pythPriceFeed: null,
+        })
+        .rpc();
+
+      const resolved = await program.account.market.fetch(marketPda);
+      assert.ok(resolved.status.resolved, "Market should be resolved");
+      assert.deepEqual(resolved.resolvedOutcome, { yes: {} }, "Snapshot 150 > 140 threshold -> YES");
+      assert.equal(resolved.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      console.log("  ✓ Resolved against snapshotted price");
+    });
+
+    it("Rejects a snapshot before end_timestamp", async () => {
+      const marketId = new BN(BASE_ID + 4);
+      const [marketPda] = deriveMarketPda(marketId);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000) as any)
+        .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      try {
+        await program.methods
+          .snapshotResolutionPrice(marketId)
+          .accounts({ market: marketPda, pythPriceFeed: PYTH_SOL_USD })
+          .rpc();
+        assert.fail("Should have thrown SnapshotWindowClosed");
+      } catch (e: any) {
+        assert.include(e.message, "SnapshotWindowClosed");
+        console.log("  ✓ Early snapshot rejected");
+      }
+    });
+
+    async function snapshotFails(marketId: BN, params: any, feed: PublicKey) {
+      const marketPda = await createPythMarket(marketId, { oracleFeed: feed, ...params });
+      try {
+        await program.methods
+          .snapshotResolutionPrice(marketId)
+          .accounts({ market: marketPda, pythPriceFeed: feed })
+          .rpc();
+        assert.fail("Should have thrown SnapshotWindowClosed");
+      } catch (e: any) {
+        assert.include(e.message, "SnapshotWindowClosed");
+      }
+    }
+
+    it("Rejects a snapshot more than a minute after end_timestamp", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      await snapshotFails(new BN(BASE_ID + 61), {
+        startTimestamp: new BN(now - 300),
+        lockTimestamp: new BN(now - 200),
+        endTimestamp: new BN(now - 120),
+      }, PYTH_SOL_USD);
+      console.log("  ✓ Late snapshot rejected");
+    });
+
+    it("Rejects a print published before end_timestamp", async () => {
+      // The quiet feed last printed long before this market ended
+      const now = Math.floor(Date.now() / 1000);
+      await snapshotFails(new BN(BASE_ID + 62), {
+        startTimestamp: new BN(now - 300),
+        lockTimestamp: new BN(now - 200),
+        endTimestamp: new BN(now - 10),
+      }, PYTH_SOL_USD_QUIET);
+      console.log("  ✓ Pre-end print rejected");
+    });
+  });
+
+  // =========================================================================
+  // 3. Boundary band
+  // =========================================================================
+  describe("Boundary band", () => {
+    const BAND = 10_000_000; // 0.10
+    const cases = [
+      { name: "clearly above the threshold", offset: 5, threshold: 14_000_000_000, band: BAND, expected: { yes: {} } },
+      { name: "clearly below the threshold", offset: 6, threshold: 16_000_000_000, band: BAND, expected: { no: {} } },
+      { name: "within the boundary band", offset: 7, threshold: FIXTURE_PRICE + 5_000_000, band: BAND, expected: { invalid: {} } },
+      // Band of 1 but the 0.015 confidence still covers a 0.01 gap
+      { name: "within the oracle confidence", offset: 8, threshold: FIXTURE_PRICE - 1_000_000, band: 1, expected: { invalid: {} } },
+    ];
+
+    before(async () => {
+      const now = Math.floor(Date.now() / 1000);
+      for (const c of cases) {
+        const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+        await program.methods
+          .createMarket(new BN(BASE_ID + c.offset), pythMarketParams(c.threshold, {
+            lockTimestamp: new BN(now + 2),
+            endTimestamp: new BN(now + 4),
+            boundaryBand: new BN(c.band),
+          }) as any)
+          .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+          .rpc();
+      }
+      await sleep(6000);
+    });
+
+    for (const c of cases) {
+      it(`Resolves ${Object.keys(c.expected)[0].toUpperCase()} when the price is ${c.name}`, async () => {
+        const marketId = new BN(BASE_ID + c.offset);
+        const [marketPda] = deriveMarketPda(marketId);
+
+        await program.methods
+          .resolveMarket(marketId, { invalid: {} })
+          .accounts({
+            market: marketPda,
+            admin: admin.publicKey,
+            platformConfig,
+            pythPriceFeed: PYTH_SOL_USD,
+          })
+          .rpc();
+
+        const market = await program.account.market.fetch(marketPda);
+        assert.deepEqual(market.resolvedOutcome, c.expected);
+        assert.equal(market.boundaryBand.toNumber(), c.band);
+        console.log(`  ✓ Price ${c.name} -> ${Object.keys(c.expected)[0]}`);
+      });
+    }
+  });
+
+  // =========================================================================
+  // 4. Resolution grace
+  // =========================================================================
+  describe("Resolution grace", () => {
+    // Market closed 30s after the quiet feed's last print: fresh at close, stale now
+    const END = QUIET_PUBLISH_TIME + 30;
+    const marketId = new BN(BASE_ID + 9);
+
+    before(async () => {
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000, {
+          oracleFeed: PYTH_SOL_USD_QUIET,
+          startTimestamp: new BN(END - 120),
+          lockTimestamp: new BN(END - 60),
+          endTimestamp: new BN(END),
+        }) as any)
+        .accounts({ adminAta })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateResolutionGrace(new BN(0))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    const resolve = () => {
+      const [marketPda] = deriveMarketPda(marketId);
+      return program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({
+          market: marketPda,
+          admin: admin.publicKey,
+          platformConfig,
+          pythPriceFeed: PYTH_SOL_USD_QUIET,
+        })
+        .rpc();
+    };
+
+    it("Rejects the quiet feed as stale without a grace window", async () => {
+      try {
+        await resolve();
+        assert.fail("Should have thrown OracleStale");
+      } catch (e: any) {
+        assert.include(e.message, "OracleStale");
+        console.log("  ✓ Stale print rejected with grace disabled");
+      }
+    });
+
+    it("Resolves from the last print before close within the grace window", async () => {
+      // Grace long enough to reach back to END from the current clock
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .updateResolutionGrace(new BN(now - END + 3600))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+
+      await resolve();
+
+      const [marketPda] = deriveMarketPda(marketId);
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(market.status.resolved);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.equal(market.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      console.log("  ✓ Quiet feed resolved using the print fresh at end_timestamp");
+    });
+  });
+
+  // =========================================================================
+  // 5. Cross-program (ProgramState) oracle
+  // =========================================================================
+  describe("ProgramState oracle", () => {
+    // Watched account: a wSOL token account owned by the Token program.
+    // SPL token account layout: mint (32) | owner (32) | amount (u64 LE) | ...
+    const AMOUNT_OFFSET = 64;
+    const HELD = 700_000_000;
+    const holder = Keypair.generate();
+    let watched: PublicKey;
+
+    function stateParams(query: any, threshold: number, overrides: any = {}) {
+      const now = Math.floor(Date.now() / 1000);
+      return {
+        title: "Will the wallet hold the threshold?",
+        description: "Test",
+        category: { crypto: {} },
+        oracleSource: { programState: {} },
+        oracleFeed: watched,
+        oracleThreshold: new BN(threshold),
+        startTimestamp: new BN(now - 120),
+        lockTimestamp: new BN(now - 60),
+        endTimestamp: new BN(now - 30),
+        minBet: new BN(10_000_000),
+        maxBet: new BN(0),
+        isRecurring: false,
+        roundDuration: null,
+        feeBps: 250,
+        initialLiquidity: new BN(LAMPORTS_PER_SOL),
+        programStateQuery: query,
+        ...overrides,
+      };
+    }
+
+    function amountQuery(comparator: any, program = token.TOKEN_PROGRAM_ID) {
+      return { program, offset: AMOUNT_OFFSET, size: 8, comparator };
+    }
+
+    async function createStateMarket(marketId: BN, params: any) {
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await program.methods
+        .createMarket(marketId, params)
+        .accounts({ adminAta })
+        .rpc();
+      return deriveMarketPda(marketId)[0];
+    }
+
+    const resolve = (marketId: BN, marketPda: PublicKey) =>
+      program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({
+          market: marketPda,
+          admin: admin.publicKey,
+          platformConfig,
+          pythPriceFeed: null,
+          stateAccount: watched,
+        })
+        .rpc();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(holder.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      watched = await fundWsol(holder, HELD);
+    });
+
+    it("Resolves YES when the watched value satisfies the comparator", async () => {
+      const marketId = new BN(BASE_ID + 10);
+      const marketPda = await createStateMarket(
+        marketId,
+        stateParams(amountQuery({ greaterOrEqual: {} }), 500_000_000)
+      );
+
+      await resolve(marketId, marketPda);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.equal(market.resolutionPrice.toNumber(), HELD);
+      console.log("  ✓ Token balance >= threshold -> YES");
+    });
+
+    it("Resolves NO when it does not", async () => {
+      const marketId = new BN(BASE_ID + 11);
+      const marketPda = await createStateMarket(
+        marketId,
+        stateParams(amountQuery({ greaterThan: {} }), LAMPORTS_PER_SOL)
+      );
+
+      await resolve(marketId, marketPda);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { no: {} });
+      console.log("  ✓ Token balance <= threshold -> NO");
+    });
+
+    it("Rejects a watched account owned by a different program", async () => {
+      const marketId = new BN(BASE_ID + 12);
+      const marketPda = await createStateMarket(
+        marketId,
+        stateParams(amountQuery({ greaterThan: {} }, SystemProgram.programId), 0)
+      );
+
+      try {
+        await resolve(marketId, marketPda);
+        assert.fail("Should have thrown ProgramStateOwnerMismatch");
+      } catch (e: any) {
+        assert.include(e.message, "ProgramStateOwnerMismatch");
+        console.log("  ✓ Owner mismatch rejected");
+      }
+    });
+
+    it("Rejects a ProgramState market created without a query", async () => {
+      try {
+        await createStateMarket(new BN(BASE_ID + 13), stateParams(null, 0));
+        assert.fail("Should have thrown InvalidProgramStateQuery");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidProgramStateQuery");
+        console.log("  ✓ Missing query rejected");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 6. Oracle cache
+  // =========================================================================
+  describe("Oracle cache", () => {
+    const refreshIx = (marketId: BN, marketPda: PublicKey) =>
+      program.methods
+        .refreshOracleCache(marketId)
+        .accounts({ market: marketPda, pythPriceFeed: PYTH_SOL_USD })
+        .instruction();
+
+    // Resolve without the feed account: only possible from the cache
+    const resolveFromCacheIx = (marketId: BN, marketPda: PublicKey) =>
+      program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .instruction();
+
+    async function logsOf(sig: string) {
+      const tx = await provider.connection.getTransaction(sig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return tx?.meta?.logMessages ?? [];
+    }
+
+    it("Serves a second read in the same slot from the cache", async () => {
+      const marketId = new BN(BASE_ID + 14);
+      const marketPda = await createEndedMarket(marketId);
+
+      const tx = new anchor.web3.Transaction().add(
+        await refreshIx(marketId, marketPda),
+        await refreshIx(marketId, marketPda),
+        await resolveFromCacheIx(marketId, marketPda)
+      );
+      const sig = await provider.sendAndConfirm(tx, [], { commitment: "confirmed" });
+
+      const hits = (await logsOf(sig)).filter((l) => l.includes("Oracle cache hit"));
+      assert.equal(hits.length, 2, "Second refresh and resolve should both hit the cache");
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.equal(market.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      assert.equal(market.oracleCache.price.toNumber(), FIXTURE_PRICE);
+      console.log("  ✓ Cached print reused within the slot");
+    });
+
+    it("Does not reuse the cache from an earlier slot", async () => {
+      const marketId = new BN(BASE_ID + 15);
+      const marketPda = await createEndedMarket(marketId);
+
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(await refreshIx(marketId, marketPda))
+      );
+      await sleep(1000);
+
+      try {
+        await provider.sendAndConfirm(
+          new anchor.web3.Transaction().add(await resolveFromCacheIx(marketId, marketPda))
+        );
+        assert.fail("Should have thrown OracleMismatch");
+      } catch (e: any) {
+        assert.include(e.message + (e.logs ?? []).join("\n"), "OracleMismatch");
+        console.log("  ✓ Stale-slot cache ignored");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 7. Resolution proof
+  // =========================================================================
+  describe("Resolution proof", () => {
+    // PriceUpdateV2 layout: disc (8) | write_authority (32) | verification_level (1)
+    // | feed_id (32) | price | conf | exponent (i32) | publish_time | prev_publish_time
+    // | ema_price | ema_conf | posted_slot
+    const PUBLISH_TIME_OFFSET = 93;
+    const POSTED_SLOT_OFFSET = 125;
+
+    it("Records the feed, price, publish time and resolver", async () => {
+      const marketId = new BN(BASE_ID + 16);
+      const [marketPda] = deriveMarketPda(marketId);
+      const now = Math.floor(Date.now() / 1000);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000, {
+          startTimestamp: new BN(now - 120),
+          lockTimestamp: new BN(now - 60),
+          endTimestamp: new BN(now - 30),
+        }) as any)
+        .accounts({ adminAta })
+        .rpc();
+
+      await program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      const proof = await program.methods
+        .getResolutionProof(marketId)
+        .accounts({ market: marketPda })
+        .view();
+
+      const feed = (await provider.connection.getAccountInfo(PYTH_SOL_USD))!.data;
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(proof.oracleFeed.equals(PYTH_SOL_USD), "Proof names the market's feed");
+      assert.equal(proof.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      assert.equal(proof.publishTime.toString(), feed.readBigInt64LE(PUBLISH_TIME_OFFSET).toString());
+      assert.equal(proof.feedSlot.toString(), feed.readBigUInt64LE(POSTED_SLOT_OFFSET).toString());
+      assert.equal(proof.resolvedAt.toString(), market.resolvedAt.toString());
+      assert.ok(proof.resolver.equals(admin.publicKey));
+      console.log("  ✓ Resolution proof matches the feed print");
+    });
+  });
+
+  // =========================================================================
+  // 8. Confidence split
+  // =========================================================================
+  describe("Confidence split", () => {
+    // Fixture band: 150 +/- 0.015, i.e. [FIXTURE_PRICE - CONF, FIXTURE_PRICE + CONF]
+    const CONF = 1_500_000;
+    const cases = [
+      { name: "a quarter of the band below", offset: 17, threshold: FIXTURE_PRICE - CONF / 2, expected: 7500 },
+      { name: "the band centre", offset: 18, threshold: FIXTURE_PRICE, expected: 5000 },
+      { name: "40% of the band above the centre", offset: 19, threshold: FIXTURE_PRICE + (CONF * 2) / 5, expected: 3000 },
+    ];
+
+    async function createSplitMarket(marketId: BN, threshold: number, lock: number, end: number) {
+      const now = Math.floor(Date.now() / 1000);
+      return createPythMarket(marketId, {
+        oracleThreshold: new BN(threshold),
+        startTimestamp: new BN(now - 120),
+        lockTimestamp: new BN(now + lock),
+        endTimestamp: new BN(now + end),
+        confidenceSplit: true,
+      });
+    }
+
+    async function resolve(marketId: BN, marketPda: PublicKey) {
+      await program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+      return program.account.market.fetch(marketPda);
+    }
+
+    for (const c of cases) {
+      it(`Splits ${c.expected / 100}% to YES when the threshold sits at ${c.name}`, async () => {
+        const marketId = new BN(BASE_ID + c.offset);
+        const marketPda = await createSplitMarket(marketId, c.threshold, -60, -30);
+        const market = await resolve(marketId, marketPda);
+        assert.deepEqual(market.resolvedOutcome, { split: {} });
+        assert.equal(market.splitYesBps, c.expected);
+        assert.equal(
+          market.splitYesPool.toString(),
+          market.distributableCollateral.muln(c.expected).divn(10000).toString()
+        );
+        console.log(`  ✓ Threshold at ${c.name} -> ${c.expected} bps YES`);
+      });
+    }
+
+    it("Resolves outright when the band clears the threshold", async () => {
+      const marketId = new BN(BASE_ID + 20);
+      const marketPda = await createSplitMarket(marketId, FIXTURE_PRICE - 2 * CONF, -60, -30);
+      const market = await resolve(marketId, marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      console.log("  ✓ Band entirely above the threshold -> YES");
+    });
+
+    it("Pays YES and NO holders their allocations of the pool", async () => {
+      const marketId = new BN(BASE_ID + 21);
+      const marketPda = await createSplitMarket(marketId, FIXTURE_PRICE - CONF / 2, 6, 8);
+      const [yesMint] = deriveYesMint(marketPda);
+      const [noMint] = deriveNoMint(marketPda);
+      const yesUser = Keypair.generate();
+      const noUser = Keypair.generate();
+
+      const bets: [Keypair, "yes" | "no"][] = [[yesUser, "yes"], [noUser, "no"]];
+      const shareAtas: PublicKey[] = [];
+      for (const [user, side] of bets) {
+        const sig = await provider.connection.requestAirdrop(user.publicKey, 2 * LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+        await fundWsol(user, 300_000_000);
+        const shareAta = await token.getOrCreateAssociatedTokenAccount(
+          provider.connection, adminPayer, side === "yes" ? yesMint : noMint, user.publicKey
+        );
+        shareAtas.push(shareAta.address);
+        await program.methods
+          .placeBet(marketId, side === "yes" ? { yes: {} } : { no: {} }, new BN(300_000_000), new BN(0))
+          .accounts({
+            user: user.publicKey,
+            userShareAccount: shareAta.address,
+            platformConfig,
+            treasury: treasuryAta,
+            collateralMint: WSOL_MINT,
+          })
+          .signers([user])
+          .rpc();
+      }
+
+      await sleep(10000);
+      const market = await resolve(marketId, marketPda);
+      assert.equal(market.splitYesBps, 7500);
+
+      const paid: number[] = [];
+      for (const [i, [user]] of bets.entries()) {
+        const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, user.publicKey);
+        const before = (await token.getAccount(provider.connection, userAta)).amount;
+        await program.methods
+          .claimPayout(marketId)
+          .accounts({
+            market: marketPda,
+            yesMint,
+            noMint,
+            userAta,
+            userShareAccount: shareAtas[i],
+            user: user.publicKey,
+            collateralMint: WSOL_MINT,
+            payoutReserve: null,
+            userPayoutAccount: null,
+          } as any)
+          .signers([user])
+          .rpc();
+        const after = (await token.getAccount(provider.connection, userAta)).amount;
+        paid.push(Number(after - before));
+      }
+
+      // Each side is the sole holder of its mint: it receives its whole allocation
+      const pool = market.distributableCollateral.toNumber();
+      const yesAllocation = Math.floor((pool * 7500) / 10000);
+      assert.equal(paid[0], yesAllocation, "YES receives 75% of the pool");
+      assert.equal(paid[1], pool - yesAllocation, "NO receives the remaining 25%");
+      const settled = await program.account.market.fetch(marketPda);
+      assert.equal(settled.distributableCollateral.toNumber(), 0, "Pool fully paid out");
+      console.log(`  ✓ Split payouts: YES ${paid[0] / LAMPORTS_PER_SOL} SOL, NO ${paid[1] / LAMPORTS_PER_SOL} SOL`);
+    });
+  });
+
+  // =========================================================================
+  // 9. Resolution gap after the last bet
+  // =========================================================================
+  describe("Minimum slots before resolve", () => {
+    after(async () => {
+      await program.methods
+        .updateMinSlotsBeforeResolve(new BN(0))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("Rejects resolution too soon after a bet, then allows it once disabled", async () => {
+      const marketId = new BN(BASE_ID + 22);
+      const [marketPda] = deriveMarketPda(marketId);
+      const [yesMint] = deriveYesMint(marketPda);
+      const now = Math.floor(Date.now() / 1000);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000, {
+          lockTimestamp: new BN(now + 3),
+          endTimestamp: new BN(now + 5),
+        }) as any)
+        .accounts({ adminAta })
+        .rpc();
+
+      await fundWsol(adminPayer, 100_000_000);
+      const yesAta = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, yesMint, admin.publicKey
+      );
+      await program.methods
+        .placeBet(marketId, { yes: {} }, new BN(100_000_000), new BN(0))
+        .accounts({
+          user: admin.publicKey,
+          userShareAccount: yesAta.address,
+          platformConfig,
+          treasury: treasuryAta,
+          collateralMint: WSOL_MINT,
+        })
+        .rpc();
+      const market = await program.account.market.fetch(marketPda);
+      assert.isTrue(market.lastBetSlot.toNumber() > 0, "Bet slot recorded");
+
+      // A gap far longer than the test waits
+      await program.methods
+        .updateMinSlotsBeforeResolve(new BN(1_000_000))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+      await sleep(6000);
+
+      const resolve = () =>
+        program.methods
+          .resolveMarket(marketId, { invalid: {} })
+          .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+          .rpc();
+      try {
+        await resolve();
+        assert.fail("Should have thrown ResolutionTooSoonAfterBet");
+      } catch (e: any) {
+        assert.include(e.message, "ResolutionTooSoonAfterBet");
+      }
+
+      await program.methods
+        .updateMinSlotsBeforeResolve(new BN(0))
+        .accounts({ platformConfig, admin: admin.publicKey })
+        .rpc();
+      await resolve();
+      const resolved = await program.account.market.fetch(marketPda);
+      assert.deepEqual(resolved.resolvedOutcome, { yes: {} });
+      console.log("  ✓ Resolution held back until the slot gap is satisfied");
+    });
+  });
+
+  // =========================================================================
+  // 10. Oracle exponent
+  // =========================================================================
+  describe("Oracle exponent", () => {
+    it("Records the feed's exponent at creation", async () => {
+      const marketId = new BN(BASE_ID + 23);
+      const [marketPda] = deriveMarketPda(marketId);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000) as any)
+        .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.oracleExponent, -8, "Fixture feed publishes with expo -8");
+      console.log("  ✓ Creation-time exponent recorded:", market.oracleExponent);
+    });
+
+    // Threshold set while the feed published with expo -6; the fixture now uses -8
+    const cases = [
+      { name: "above", offset: 24, threshold: 149_000_000, expected: { yes: {} } },
+      { name: "below", offset: 25, threshold: 151_000_000, expected: { no: {} } },
+    ];
+    for (const c of cases) {
+      it(`Resolves a threshold ${c.name} the price in the creation-time exponent`, async () => {
+        const marketId = new BN(BASE_ID + c.offset);
+        const [marketPda] = deriveMarketPda(marketId);
+        const now = Math.floor(Date.now() / 1000);
+        const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+        await program.methods
+          .createMarket(marketId, pythMarketParams(c.threshold, {
+            lockTimestamp: new BN(now + 2),
+            endTimestamp: new BN(now + 3),
+            oracleExponent: -6,
+          }) as any)
+          .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+          .rpc();
+        await sleep(4000);
+
+        await program.methods
+          .resolveMarket(marketId, { invalid: {} })
+          .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+          .rpc();
+        const market = await program.account.market.fetch(marketPda);
+        assert.equal(market.oracleExponent, -6, "Declared exponent kept");
+        assert.deepEqual(market.resolvedOutcome, c.expected);
+        // 15_000_000_000 @ -8 == 150_000_000 @ -6
+        assert.equal(market.resolutionPrice.toNumber(), FIXTURE_PRICE / 100, "Price rescaled to expo -6");
+        console.log(`  ✓ Threshold ${c.threshold} @ -6 vs rescaled ${market.resolutionPrice} ->`, Object.keys(c.expected)[0]);
+      });
+    }
+  });
+
+  // =========================================================================
+  // 11. Signed feeds
+  // =========================================================================
+  describe("Signed feeds", () => {
+    // Each case: feed, threshold (in the market's exponent), declared exponent
+    // (null = the feed's -8), expected outcome and resolution price
+    const cases = [
+      { name: "funding rate below a -0.01% threshold", offset: 26, feed: "funding", threshold: -10_000, exponent: null, expected: { no: {} }, price: FUNDING_RATE_PRICE },
+      { name: "funding rate above a -0.03% threshold", offset: 27, feed: "funding", threshold: -30_000, exponent: null, expected: { yes: {} }, price: FUNDING_RATE_PRICE },
+      { name: "negative price vs a positive threshold", offset: 28, feed: "funding", threshold: 10_000, exponent: null, expected: { no: {} }, price: FUNDING_RATE_PRICE },
+      { name: "positive price vs a negative threshold", offset: 29, feed: "sol", threshold: -10_000, exponent: null, expected: { yes: {} }, price: FIXTURE_PRICE },
+      // -25_050 @ -8 == -250.5 @ -6: truncates toward zero, so it stays above -251...
+      { name: "coarser exponent, just above the threshold", offset: 30, feed: "funding", threshold: -251, exponent: -6, expected: { yes: {} }, price: -250 },
+      // ...and not above -250
+      { name: "coarser exponent, just below the threshold", offset: 31, feed: "funding", threshold: -250, exponent: -6, expected: { no: {} }, price: -250 },
+      { name: "finer exponent keeps the magnitude", offset: 32, feed: "funding", threshold: -2_505_001, exponent: -10, expected: { yes: {} }, price: -2_505_000 },
+    ];
+    for (const c of cases) {
+      it(`Resolves ${c.name}`, async () => {
+        const marketId = new BN(BASE_ID + c.offset);
+        const [marketPda] = deriveMarketPda(marketId);
+        const feed = c.feed === "funding" ? PYTH_FUNDING_RATE : PYTH_SOL_USD;
+        const now = Math.floor(Date.now() / 1000);
+        const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+        await program.methods
+          .createMarket(marketId, pythMarketParams(c.threshold, {
+            title: "Oracle Test: signed feed > threshold?",
+            oracleFeed: feed,
+            lockTimestamp: new BN(now + 2),
+            endTimestamp: new BN(now + 3),
+            oracleExponent: c.exponent,
+          }) as any)
+          .accounts({ adminAta, pythPriceFeed: feed })
+          .rpc();
+        await sleep(4000);
+
+        await program.methods
+          .resolveMarket(marketId, { invalid: {} })
+          .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: feed })
+          .rpc();
+        const market = await program.account.market.fetch(marketPda);
+        assert.deepEqual(market.resolvedOutcome, c.expected);
+        assert.equal(market.resolutionPrice.toNumber(), c.price, "Sign and magnitude preserved");
+        console.log(`  ✓ ${market.resolutionPrice} vs ${c.threshold} @ ${market.oracleExponent} ->`, Object.keys(c.expected)[0]);
+      });
+    }
+  });
+
+  // =========================================================================
+  // 12. Slot-based end
+  // =========================================================================
+  describe("Slot-based end", () => {
+    const holder = Keypair.generate();
+    let watched: PublicKey;
+
+    // Far-future end_timestamp: only the slot/epoch gate can let these resolve
+    function slotParams(overrides: any) {
+      const now = Math.floor(Date.now() / 1000);
+      return {
+        title: "Will the wallet still hold the threshold at slot N?",
+        description: "Test",
+        category: { crypto: {} },
+        oracleSource: { programState: {} },
+        oracleFeed: watched,
+        oracleThreshold: new BN(0),
+        startTimestamp: new BN(now - 60),
+        lockTimestamp: new BN(now + 3600),
+        endTimestamp: new BN(now + 7200),
+        minBet: new BN(10_000_000),
+        maxBet: new BN(0),
+        isRecurring: false,
+        roundDuration: null,
+        feeBps: 250,
+        initialLiquidity: new BN(LAMPORTS_PER_SOL),
+        programStateQuery: { program: token.TOKEN_PROGRAM_ID, offset: 64, size: 8, comparator: { greaterThan: {} } },
+        ...overrides,
+      };
+    }
+
+    async function createSlotMarket(marketId: BN, overrides: any) {
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await program.methods
+        .createMarket(marketId, slotParams(overrides) as any)
+        .accounts({ adminAta })
+        .rpc();
+      return deriveMarketPda(marketId)[0];
+    }
+
+    const resolve = (marketId: BN, marketPda: PublicKey) =>
+      program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({
+          market: marketPda,
+          admin: admin.publicKey,
+          platformConfig,
+          pythPriceFeed: null,
+          stateAccount: watched,
+        })
+        .rpc();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(holder.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      watched = await fundWsol(holder, 100_000_000);
+    });
+
+    it("Holds resolution until end_slot, then resolves before end_timestamp", async () => {
+      const marketId = new BN(BASE_ID + 33);
+      const endSlot = (await provider.connection.getSlot()) + 20;
+      const marketPda = await createSlotMarket(marketId, { endSlot: new BN(endSlot) });
+
+      try {
+        await resolve(marketId, marketPda);
+        assert.fail("Should have thrown RoundIncomplete");
+      } catch (e: any) {
+        assert.include(e.message, "RoundIncomplete");
+      }
+
+      while ((await provider.connection.getSlot()) < endSlot) {
+        await sleep(400);
+      }
+      await resolve(marketId, marketPda);
+
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.isBelow(market.resolvedAt.toNumber(), market.endTimestamp.toNumber(), "Resolved ahead of end_timestamp");
+      console.log(`  ✓ Resolved at slot >= ${endSlot}`);
+    });
+
+    it("Gates on end_epoch", async () => {
+      const marketId = new BN(BASE_ID + 34);
+      const { epoch } = await provider.connection.getEpochInfo();
+      const marketPda = await createSlotMarket(marketId, { endEpoch: new BN(epoch + 1000) });
+      try {
+        await resolve(marketId, marketPda);
+        assert.fail("Should have thrown RoundIncomplete");
+      } catch (e: any) {
+        assert.include(e.message, "RoundIncomplete");
+      }
+      console.log("  ✓ Future epoch holds resolution");
+    });
+
+    it("Rejects a slot end on a recurring market", async () => {
+      try {
+        await createSlotMarket(new BN(BASE_ID + 35), {
+          endSlot: new BN(1),
+          isRecurring: true,
+          roundDuration: new BN(3600),
+        });
+        assert.fail("Should have thrown InvalidTimestamps");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidTimestamps");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 13. Oracle source migration
+  // =========================================================================
+  describe("Oracle source migration", () => {
+    const migrate = (marketId: BN, source: any, feed: PublicKey, threshold: number, pythPriceFeed: PublicKey | null) =>
+      program.methods
+        .migrateOracleSource(marketId, source, feed, new BN(threshold))
+        .accounts({
+          market: deriveMarketPda(marketId)[0],
+          platformConfig,
+          admin: admin.publicKey,
+          pythPriceFeed,
+        } as any)
+        .rpc();
+
+    it("Migrates a ManualAdmin market to Pyth and resolves through the feed", async () => {
+      const marketId = new BN(BASE_ID + 36);
+      const [marketPda] = deriveMarketPda(marketId);
+      const now = Math.floor(Date.now() / 1000);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      await program.methods
+        .createMarket(marketId, pythMarketParams(0, {
+          oracleSource: { manualAdmin: {} },
+          oracleFeed: PublicKey.default,
+          lockTimestamp: new BN(now + 3),
+          endTimestamp: new BN(now + 5),
+        }) as any)
+        .accounts({ adminAta })
+        .rpc();
+
+      try {
+        await migrate(marketId, { pyth: {} }, PYTH_SOL_USD, 14_000_000_000, null);
+        assert.fail("Should have thrown OracleMismatch");
+      } catch (e: any) {
+        assert.include(e.message, "OracleMismatch");
+      }
+
+      await migrate(marketId, { pyth: {} }, PYTH_SOL_USD, 14_000_000_000, PYTH_SOL_USD);
+      const migrated = await program.account.market.fetch(marketPda);
+      assert.deepEqual(migrated.oracleSource, { pyth: {} });
+      assert.equal(migrated.oracleFeed.toBase58(), PYTH_SOL_USD.toBase58());
+      assert.equal(migrated.oracleExponent, -8, "Threshold taken in the feed's exponent");
+
+      // The admin's outcome is ignored now: the feed decides
+      await sleep(6000);
+      await program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+      const resolved = await program.account.market.fetch(marketPda);
+      assert.deepEqual(resolved.resolvedOutcome, { yes: {} });
+      assert.equal(resolved.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      console.log("  ✓ ManualAdmin -> Pyth, resolved YES at 150 > 140");
+    });
+
+    it("Rejects migrating a resolved market", async () => {
+      try {
+        await migrate(new BN(BASE_ID + 36), { manualAdmin: {} }, PublicKey.default, 0, null);
+        assert.fail("Should have thrown AlreadyResolved");
+      } catch (e: any) {
+        assert.include(e.message, "AlreadyResolved");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 14. Permissionless Pyth resolution
+  // =========================================================================
+  describe("Permissionless resolution", () => {
+    const keeper = Keypair.generate();
+
+    const resolve = (marketId: BN, marketPda: PublicKey, pythPriceFeed: PublicKey | null) =>
+      program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: keeper.publicKey, platformConfig, pythPriceFeed })
+        .signers([keeper])
+        .rpc();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(keeper.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    it("Lets anyone resolve a Pyth market once it has ended", async () => {
+      const marketId = new BN(BASE_ID + 37);
+      const marketPda = await createEndedMarket(marketId);
+
+      await resolve(marketId, marketPda, PYTH_SOL_USD);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} }, "Feed decides, not the caller");
+      assert.ok(market.resolutionProof.resolver.equals(keeper.publicKey));
+      console.log("  ✓ Keeper resolved an ended Pyth market");
+    });
+
+    it("Still rejects a Pyth market before its end", async () => {
+      const marketId = new BN(BASE_ID + 38);
+      const marketPda = await createPythMarket(marketId);
+      try {
+        await resolve(marketId, marketPda, PYTH_SOL_USD);
+        assert.fail("Should have thrown RoundIncomplete");
+      } catch (e: any) {
+        assert.include(e.message, "RoundIncomplete");
+      }
+    });
+
+    it("Keeps ManualAdmin markets resolver-only", async () => {
+      const marketId = new BN(BASE_ID + 39);
+      const marketPda = await createPythMarket(marketId, {
+        oracleSource: { manualAdmin: {} },
+        oracleFeed: PublicKey.default,
+        oracleThreshold: new BN(0),
+      });
+      try {
+        await resolve(marketId, marketPda, null);
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+      console.log("  ✓ ManualAdmin market refused the keeper");
+    });
+  });
+
+  // =========================================================================
+  // 15. Switchboard oracle
+  // =========================================================================
+  describe("Switchboard oracle", () => {
+    const switchboard = (oracleFeed: PublicKey) => ({ oracleSource: { switchboard: {} }, oracleFeed });
+
+    const resolve = (marketId: BN, marketPda: PublicKey, switchboardFeed: PublicKey) =>
+      program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null, switchboardFeed } as any)
+        .rpc();
+
+    it("Resolves against the feed value in the threshold's exponent", async () => {
+      const marketId = new BN(BASE_ID + 40);
+      const marketPda = await createEndedMarket(marketId, switchboard(SWITCHBOARD_SOL_USD));
+
+      await resolve(marketId, marketPda, SWITCHBOARD_SOL_USD);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.equal(market.resolutionPrice.toNumber(), FIXTURE_PRICE, "18 decimals rescaled to expo -8");
+      assert.equal(market.oracleExponent, -8);
+      console.log("  ✓ Switchboard 150 > 140 resolved YES");
+    });
+
+    it("Rejects a feed other than the market's", async () => {
+      const marketId = new BN(BASE_ID + 41);
+      const marketPda = await createEndedMarket(marketId, switchboard(SWITCHBOARD_SOL_USD));
+      try {
+        await resolve(marketId, marketPda, PYTH_SOL_USD);
+        assert.fail("Should have thrown InvalidSwitchboardFeed");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidSwitchboardFeed");
+      }
+    });
+
+    it("Rejects an account not owned by Switchboard", async () => {
+      const marketId = new BN(BASE_ID + 42);
+      const marketPda = await createEndedMarket(marketId, switchboard(PYTH_SOL_USD));
+      try {
+        await resolve(marketId, marketPda, PYTH_SOL_USD);
+        assert.fail("Should have thrown InvalidSwitchboardFeed");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidSwitchboardFeed");
+      }
+      console.log("  ✓ Wrong or foreign-owned feeds refused");
+    });
+  });
+
+  // =========================================================================
+  // 16. Chainlink oracle
+  // =========================================================================
+  describe("Chainlink oracle", () => {
+    const CHAINLINK = { oracleSource: { chainlink: {} }, oracleFeed: CHAINLINK_SOL_USD };
+
+    const resolve = (marketId: BN, marketPda: PublicKey, chainlinkFeed: PublicKey) =>
+      program.methods
+        .resolveMarket(marketId, { no: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null, chainlinkFeed } as any)
+        .rpc();
+
+    it("Resolves against the latest round in the feed's decimals", async () => {
+      const marketId = new BN(BASE_ID + 43);
+      const marketPda = await createEndedMarket(marketId, CHAINLINK);
+
+      await resolve(marketId, marketPda, CHAINLINK_SOL_USD);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.equal(market.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      assert.equal(market.oracleExponent, -8, "Threshold adopted the feed's 8 decimals");
+      console.log("  ✓ Chainlink 150 > 140 resolved YES");
+    });
+
+    it("Rescales to a declared exponent", async () => {
+      const marketId = new BN(BASE_ID + 44);
+      // 151.00 in expo -2: the 150.00 answer resolves NO
+      const marketPda = await createEndedMarket(marketId, { ...CHAINLINK, oracleThreshold: new BN(15_100), oracleExponent: -2 });
+
+      await resolve(marketId, marketPda, CHAINLINK_SOL_USD);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { no: {} });
+      assert.equal(market.resolutionPrice.toNumber(), 15_000);
+      console.log("  ✓ Answer rescaled to expo -2");
+    });
+
+    it("Rejects an account not owned by the Chainlink store", async () => {
+      const marketId = new BN(BASE_ID + 45);
+      const marketPda = await createEndedMarket(marketId, { ...CHAINLINK, oracleFeed: PYTH_SOL_USD });
+      try {
+        await resolve(marketId, marketPda, PYTH_SOL_USD);
+        assert.fail("Should have thrown InvalidChainlinkFeed");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidChainlinkFeed");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 17. Signed attestation oracle
+  // =========================================================================
+  describe("Signed attestation oracle", () => {
+    const attester = Keypair.generate();
+    const impostor = Keypair.generate();
+    const OUTCOME_CODE = { yes: 0, no: 1, invalid: 2 };
+
+    // market_id (u64 LE) | outcome (u8) | timestamp (i64 LE)
+    function attestation(marketId: BN, outcome: keyof typeof OUTCOME_CODE, timestamp: number) {
+      const message = Buffer.alloc(17);
+      marketId.toArrayLike(Buffer, "le", 8).copy(message, 0);
+      message.writeUInt8(OUTCOME_CODE[outcome], 8);
+      message.writeBigInt64LE(BigInt(timestamp), 9);
+      return message;
+    }
+
+    async function createAttested(marketId: BN) {
+      const marketPda = await createEndedMarket(marketId, {
+        oracleSource: { signedAttestation: {} },
+        oracleFeed: attester.publicKey,
+        oracleThreshold: new BN(0),
+      });
+      const market = await program.account.market.fetch(marketPda);
+      return { marketPda, endTimestamp: market.endTimestamp.toNumber() };
+    }
+
+    const resolveSigned = (marketId: BN, marketPda: PublicKey, signer: Keypair, message: Buffer) =>
+      program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({
+          market: marketPda,
+          admin: admin.publicKey,
+          platformConfig,
+          pythPriceFeed: null,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        } as any)
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message }),
+        ])
+        .rpc();
+
+    it("Resolves to the outcome the attester signed", async () => {
+      const marketId = new BN(BASE_ID + 46);
+      const { marketPda, endTimestamp } = await createAttested(marketId);
+
+      await resolveSigned(marketId, marketPda, attester, attestation(marketId, "no", endTimestamp + 1));
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { no: {} }, "Signed outcome, not the caller's");
+      assert.equal(market.resolutionProof.publishTime.toNumber(), endTimestamp + 1);
+      console.log("  ✓ Attested NO applied");
+    });
+
+    it("Rejects another key's signature and attestations from before the end", async () => {
+      const marketId = new BN(BASE_ID + 47);
+      const { marketPda, endTimestamp } = await createAttested(marketId);
+
+      for (const [signer, timestamp] of [
+        [impostor, endTimestamp + 1],
+        [attester, endTimestamp - 1],
+      ] as [Keypair, number][]) {
+        try {
+          await resolveSigned(marketId, marketPda, signer, attestation(marketId, "yes", timestamp));
+          assert.fail("Should have thrown InvalidAttestation");
+        } catch (e: any) {
+          assert.include(e.message, "InvalidAttestation");
+        }
+      }
+
+      // Signed for a different market
+      try {
+        await resolveSigned(marketId, marketPda, attester, attestation(marketId.addn(1), "yes", endTimestamp + 1));
+        assert.fail("Should have thrown InvalidAttestation");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidAttestation");
+      }
+      console.log("  ✓ Impostor, early and cross-market attestations refused");
+    });
+  });
+
+  // =========================================================================
+  // 18. TWAP resolution
+  // =========================================================================
+  describe("TWAP resolution", () => {
+    async function createTwapMarket(marketId: BN, threshold: number) {
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      const now = Math.floor(Date.now() / 1000);
+      // The final 20s window opens right away
+      await program.methods
+        .createMarket(marketId, pythMarketParams(threshold, {
+          lockTimestamp: new BN(now + 3),
+          endTimestamp: new BN(now + 5),
+          twapWindowSecs: new BN(20),
+        }) as any)
+        .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+      return deriveMarketPda(marketId)[0];
+    }
+
+    const recordSnapshot = (marketId: BN) =>
+      program.methods
+        .recordPriceSnapshot(marketId)
+        .accounts({ pythPriceFeed: PYTH_SOL_USD, cranker: admin.publicKey } as any)
+        .rpc();
+
+    const resolveTwap = (marketId: BN, marketPda: PublicKey, priceSnapshots: PublicKey | null) =>
+      program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({
+          market: marketPda,
+          admin: admin.publicKey,
+          platformConfig,
+          pythPriceFeed: PYTH_SOL_USD,
+          priceSnapshots,
+        } as any)
+        .rpc();
+
+    it("Resolves on the average of the recorded snapshots", async () => {
+      const marketId = new BN(BASE_ID + 48);
+      const marketPda = await createTwapMarket(marketId, 14_000_000_000);
+      const [priceSnapshots] = PublicKey.findProgramAddressSync(
+        [Buffer.from("price_snapshots"), marketPda.toBuffer()],
+        program.programId
+      );
+
+      await recordSnapshot(marketId);
+      try {
+        await recordSnapshot(marketId);
+        assert.fail("Should have thrown SnapshotTooSoon");
+      } catch (e: any) {
+        assert.include(e.message, "SnapshotTooSoon");
+      }
+      await sleep(1500);
+      await recordSnapshot(marketId);
+
+      const ring = await program.account.priceSnapshots.fetch(priceSnapshots);
+      assert.equal(ring.head, 2);
+      assert.equal(ring.snapshots[0].price.toNumber(), FIXTURE_PRICE);
+
+      await sleep(5000);
+      try {
+        await recordSnapshot(marketId);
+        assert.fail("Should have thrown SnapshotWindowClosed");
+      } catch (e: any) {
+        assert.include(e.message, "SnapshotWindowClosed");
+      }
+
+      await resolveTwap(marketId, marketPda, priceSnapshots);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} }, "TWAP 150 > 140 -> YES");
+      assert.equal(market.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      console.log("  ✓ Resolved on a 2-snapshot TWAP");
+    });
+
+    it("Refuses a spot resolution when no snapshots were recorded", async () => {
+      const marketId = new BN(BASE_ID + 49);
+      const marketPda = await createTwapMarket(marketId, 14_000_000_000);
+      await sleep(6000);
+
+      try {
+        await resolveTwap(marketId, marketPda, null);
+        assert.fail("Should have thrown InsufficientSnapshots");
+      } catch (e: any) {
+        assert.include(e.message, "InsufficientSnapshots");
+      }
+      console.log("  ✓ TWAP market not resolvable from a single print");
+    });
+  });
+
+  // =========================================================================
+  // 19. Scalar markets
+  // =========================================================================
+  describe("Scalar markets", () => {
+    const scalar = (lower: number, upper: number) => ({
+      oracleThreshold: new BN(0),
+      scalarRange: { lower: new BN(lower), upper: new BN(upper) },
+    });
+
+    const resolve = (marketId: BN, marketPda: PublicKey) =>
+      program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+    it("Splits the pool by where the price falls in the range", async () => {
+      const marketId = new BN(BASE_ID + 50);
+      // 150 in [120, 220]: 30% LONG
+      const marketPda = await createEndedMarket(marketId, scalar(12_000_000_000, 22_000_000_000));
+
+      await resolve(marketId, marketPda);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { split: {} });
+      assert.equal(market.splitYesBps, 3000);
+      assert.equal(market.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      console.log("  ✓ 150 over [120, 220] -> 3000 bps LONG");
+    });
+
+    it("Pays one side in full outside the range", async () => {
+      const marketId = new BN(BASE_ID + 51);
+      const marketPda = await createEndedMarket(marketId, scalar(16_000_000_000, 20_000_000_000));
+
+      await resolve(marketId, marketPda);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { no: {} }, "Below the lower bound: all SHORT");
+      console.log("  ✓ 150 below [160, 200] -> NO");
+    });
+
+    it("Rejects an empty range and non-price sources", async () => {
+      for (const [lower, upper, overrides, error] of [
+        [20_000_000_000, 20_000_000_000, {}, "InvalidScalarRange"],
+        [10_000_000_000, 20_000_000_000, { oracleSource: { manualAdmin: {} }, oracleFeed: PublicKey.default }, "OracleMismatch"],
+      ] as [number, number, any, string][]) {
+        try {
+          await createEndedMarket(new BN(BASE_ID + 52), { ...scalar(lower, upper), ...overrides });
+          assert.fail(`Should have thrown ${error}`);
+        } catch (e: any) {
+          assert.include(e.message, error);
+        }
+      }
+    });
+  });
+
+  // =========================================================================
+  // 20. Range markets
+  // =========================================================================
+  describe("Range markets", () => {
+    const range = (lower: number, upper: number) => ({
+      oracleThreshold: new BN(lower),
+      oracleUpperThreshold: new BN(upper),
+    });
+
+    const resolve = (marketId: BN, marketPda: PublicKey) =>
+      program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+    it("Resolves YES inside the range and NO above it", async () => {
+      for (const [offset, lower, upper, expected] of [
+        [53, 14_000_000_000, 16_000_000_000, { yes: {} }],
+        [54, 12_000_000_000, 14_000_000_000, { no: {} }],
+      ] as [number, number, number, any][]) {
+        const marketId = new BN(BASE_ID + offset);
+        const marketPda = await createEndedMarket(marketId, range(lower, upper));
+        await resolve(marketId, marketPda);
+        const market = await program.account.market.fetch(marketPda);
+        assert.deepEqual(market.resolvedOutcome, expected);
+      }
+      console.log("  ✓ 150 in (140, 160] -> YES, above (120, 140] -> NO");
+    });
+
+    it("Rejects an inverted range", async () => {
+      try {
+        await createEndedMarket(new BN(BASE_ID + 55), range(16_000_000_000, 14_000_000_000));
+        assert.fail("Should have thrown InvalidRangeThresholds");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidRangeThresholds");
+      }
+    });
+  });
+
+  // =========================================================================
+  // 21. Barrier markets
+  // =========================================================================
+  describe("Barrier markets", () => {
+    const keeper = Keypair.generate();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(keeper.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    const createBarrier = (marketId: BN, threshold: number) =>
+      createPythMarket(marketId, { oracleThreshold: new BN(threshold), barrier: true });
+
+    const checkBarrier = (marketId: BN, marketPda: PublicKey) =>
+      program.methods
+        .checkBarrier(marketId)
+        .accounts({ market: marketPda, pythPriceFeed: PYTH_SOL_USD, payoutReserve: null, caller: keeper.publicKey } as any)
+        .signers([keeper])
+        .rpc();
+
+    it("Resolves YES before expiry once the barrier is touched", async () => {
+      const marketId = new BN(BASE_ID + 56);
+      const marketPda = await createBarrier(marketId, 14_000_000_000);
+
+      await checkBarrier(marketId, marketPda);
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.resolvedOutcome, { yes: {} });
+      assert.equal(market.resolutionPrice.toNumber(), FIXTURE_PRICE);
+      assert.isBelow(market.resolvedAt.toNumber(), market.endTimestamp.toNumber(), "Resolved early");
+      assert.ok(market.resolutionProof.resolver.equals(keeper.publicKey));
+      console.log("  ✓ 150 touched the 140 barrier two hours early");
+    });
+
+    it("Leaves an untouched barrier open", async () => {
+      const marketId = new BN(BASE_ID + 57);
+      const marketPda = await createBarrier(marketId, 16_000_000_000);
+      try {
+        await checkBarrier(marketId, marketPda);
+        assert.fail("Should have thrown BarrierNotTouched");
+      } catch (e: any) {
+        assert.include(e.message, "BarrierNotTouched");
+      }
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.status, { active: {} });
+    });
+  });
+
+  // =========================================================================
+  // 22. Round lock price
+  // =========================================================================
+  describe("Round lock price", () => {
+    it("Records the feed price at the round's lock", async () => {
+      const marketId = new BN(BASE_ID + 58);
+      const [marketPda] = deriveMarketPda(marketId);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000, {
+          lockTimestamp: new BN(now + 2),
+          endTimestamp: new BN(now + 3),
+          isRecurring: true,
+          roundDuration: new BN(6),
+        }) as any)
+        .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      await sleep(4000);
+      await program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      const [roundState] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), new BN(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .startRound(marketId)
+        .accounts({ market: marketPda, roundState, platformConfig, payer: admin.publicKey } as any)
+        .rpc();
+
+      const lockRound = () =>
+        program.methods
+          .lockRound(marketId)
+          .accounts({ market: marketPda, roundState, pythPriceFeed: PYTH_SOL_USD } as any)
+          .rpc();
+
+      try {
+        await lockRound();
+        assert.fail("Should have thrown BettingStillOpen");
+      } catch (e: any) {
+        assert.include(e.message, "BettingStillOpen");
+      }
+
+      await sleep(5000);
+      await lockRound();
+
+      const round = await program.account.roundState.fetch(roundState);
+      assert.equal(round.lockPrice.toNumber(), FIXTURE_PRICE);
+      assert.deepEqual(round.status, { locked: {} });
+      const market = await program.account.market.fetch(marketPda);
+      assert.deepEqual(market.status, { locked: {} });
+
+      try {
+        await lockRound();
+        assert.fail("Should have thrown RoundNotOpen");
+      } catch (e: any) {
+        assert.include(e.message, "RoundNotOpen");
+      }
+      console.log("  ✓ Round 1 locked at 150");
+    });
+  });
+
+  // =========================================================================
+  // 23. Round resolution
+  // =========================================================================
+  describe("Round resolution", () => {
+    it("Resolves a flat round as INVALID against its lock price", async () => {
+      // Round 1 of the market locked in section 22
+      const marketId = new BN(BASE_ID + 58);
+      const [marketPda] = deriveMarketPda(marketId);
+      const [roundState] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), new BN(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const resolveRound = () =>
+        program.methods
+          .resolveRound(marketId)
+          .accounts({ market: marketPda, roundState, platformConfig, pythPriceFeed: PYTH_SOL_USD, payoutReserve: null } as any)
+          .rpc();
+
+      const { endTs } = await program.account.roundState.fetch(roundState);
+      const wait = endTs.toNumber() * 1000 - Date.now() + 1500;
+      if (wait > 0) await sleep(wait);
+      await resolveRound();
+
+      const round = await program.account.roundState.fetch(roundState);
+      assert.deepEqual(round.status, { resolved: {} });
+      assert.equal(round.closePrice.toNumber(), FIXTURE_PRICE);
+      assert.deepEqual(round.outcome, { invalid: {} }, "Close == lock -> refund");
+      const market = await program.account.market.fetch(marketPda);
+      assert.ok(market.status.resolved);
+      assert.deepEqual(market.resolvedOutcome, { invalid: {} });
+
+      try {
+        await resolveRound();
+        assert.fail("Should have thrown RoundNotLocked");
+      } catch (e: any) {
+        assert.include(e.message, "RoundNotLocked");
+      }
+      console.log("  ✓ Flat round refunded");
+    });
+  });
+
+  // =========================================================================
+  // 24. Round bets
+  // =========================================================================
+  describe("Round bets", () => {
+    it("Stakes on a round and refunds both sides when it ends flat", async () => {
+      const marketId = new BN(BASE_ID + 59);
+      const [marketPda] = deriveMarketPda(marketId);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000, {
+          lockTimestamp: new BN(now + 2),
+          endTimestamp: new BN(now + 3),
+          isRecurring: true,
+          roundDuration: new BN(10),
+        }) as any)
+        .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+      await sleep(4000);
+      await program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      const roundId = new BN(1);
+      const [roundState] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .startRound(marketId)
+        .accounts({ market: marketPda, roundState, platformConfig, payer: admin.publicKey } as any)
+        .rpc();
+
+      const bettors: [Keypair, any][] = [[Keypair.generate(), { yes: {} }], [Keypair.generate(), { no: {} }]];
+      for (const [user, side] of bettors) {
+        const sig = await provider.connection.requestAirdrop(user.publicKey, 2 * LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+        await fundWsol(user, 100_000_000);
+        await program.methods
+          .placeRoundBet(marketId, roundId, side, new BN(100_000_000))
+          .accounts({
+            market: marketPda,
+            roundState,
+            user: user.publicKey,
+            platformConfig,
+            treasury: treasuryAta,
+            collateralMint: WSOL_MINT,
+          } as any)
+          .signers([user])
+          .rpc();
+      }
+      const staked = await program.account.roundState.fetch(roundState);
+      const net = 100_000_000 - 2_500_000; // 2.5% fee
+      assert.equal(staked.totalYes.toNumber(), net);
+      assert.equal(staked.totalNo.toNumber(), net);
+      assert.equal(staked.uniqueBettors.toNumber(), 2, "Round bettors counted for min_bettors_for_resolution");
+
+      // Lock and close the round; the fixture doesn't move, so it ends flat
+      await sleep(staked.lockTs.toNumber() * 1000 - Date.now() + 1000);
+      await program.methods
+        .lockRound(marketId)
+        .accounts({ market: marketPda, roundState, pythPriceFeed: PYTH_SOL_USD } as any)
+        .rpc();
+      await sleep(staked.endTs.toNumber() * 1000 - Date.now() + 1500);
+      await program.methods
+        .resolveRound(marketId)
+        .accounts({ market: marketPda, roundState, platformConfig, pythPriceFeed: PYTH_SOL_USD, payoutReserve: null } as any)
+        .rpc();
+
+      const claim = (user: Keypair) =>
+        program.methods
+          .claimRoundPayout(marketId, roundId)
+          .accounts({ market: marketPda, roundState, user: user.publicKey } as any)
+          .signers([user])
+          .rpc();
+      for (const [user] of bettors) {
+        const userAta = await token.getAssociatedTokenAddress(WSOL_MINT, user.publicKey);
+        const before = (await token.getAccount(provider.connection, userAta)).amount;
+        await claim(user);
+        const after = (await token.getAccount(provider.connection, userAta)).amount;
+        assert.equal(Number(after - before), net, "Flat round refunds the stake");
+      }
+
+      try {
+        await claim(bettors[0][0]);
+        assert.fail("Should have thrown AlreadyClaimed");
+      } catch (e: any) {
+        assert.include(e.message, "AlreadyClaimed");
+      }
+      console.log("  ✓ Both stakes refunded from the round vault");
+
+      // Paid out: the positions' rent goes back to their owners
+      for (const [user] of bettors) {
+        const [roundPosition] = PublicKey.findProgramAddressSync(
+          [Buffer.from("round_position"), roundState.toBuffer(), user.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .closeRoundPosition()
+          .accounts({ roundPosition, roundState, user: user.publicKey } as any)
+          .signers([user])
+          .rpc();
+        assert.isNull(await provider.connection.getAccountInfo(roundPosition));
+      }
+    });
+  });
+
+  // =========================================================================
+  // 25. Round crank
+  // =========================================================================
+  describe("Round crank", () => {
+    it("Resolves the ended round and opens the next in one call", async () => {
+      const marketId = new BN(BASE_ID + 60);
+      const [marketPda] = deriveMarketPda(marketId);
+      const adminAta = await fundWsol(adminPayer, LAMPORTS_PER_SOL);
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .createMarket(marketId, pythMarketParams(14_000_000_000, {
+          lockTimestamp: new BN(now + 2),
+          endTimestamp: new BN(now + 3),
+          isRecurring: true,
+          roundDuration: new BN(6),
+        }) as any)
+        .accounts({ adminAta, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+      await sleep(4000);
+      await program.methods
+        .resolveMarket(marketId, { invalid: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: PYTH_SOL_USD })
+        .rpc();
+
+      const deriveRound = (id: number) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("round"), marketPda.toBuffer(), new BN(id).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      const round1 = deriveRound(1);
+      const round2 = deriveRound(2);
+      await program.methods
+        .startRound(marketId)
+        .accounts({ market: marketPda, roundState: round1, platformConfig, payer: admin.publicKey } as any)
+        .rpc();
+
+      // CPMM shares would outlive the round and block the crank's restart
+      const [yesMint] = deriveYesMint(marketPda);
+      const shareAta = await token.getOrCreateAssociatedTokenAccount(provider.connection, adminPayer, yesMint, admin.publicKey);
+      await fundWsol(adminPayer, 100_000_000);
+      try {
+        await program.methods
+          .placeBet(marketId, { yes: {} }, new BN(100_000_000), new BN(0))
+          .accounts({
+            user: admin.publicKey,
+            userShareAccount: shareAta.address,
+            platformConfig,
+            treasury: treasuryAta,
+            collateralMint: WSOL_MINT,
+          })
+          .rpc();
+        assert.fail("Should have thrown RoundBetsOnly");
+      } catch (e: any) {
+        assert.include(e.message, "RoundBetsOnly");
+      }
+
+      const { lockTs, endTs } = await program.account.roundState.fetch(round1);
+      await sleep(lockTs.toNumber() * 1000 - Date.now() + 1000);
+      await program.methods
+        .lockRound(marketId)
+        .accounts({ market: marketPda, roundState: round1, pythPriceFeed: PYTH_SOL_USD } as any)
+        .rpc();
+      await sleep(endTs.toNumber() * 1000 - Date.now() + 1500);
+
+      await program.methods
+        .crankRound(marketId)
+        .accounts({
+          market: marketPda,
+          roundState: round1,
+          nextRoundState: round2,
+          platformConfig,
+          pythPriceFeed: PYTH_SOL_USD,
+          payoutReserve: null,
+          payer: admin.publicKey,
+        } as any)
+        .rpc();
+
+      const resolved = await program.account.roundState.fetch(round1);
+      assert.deepEqual(resolved.status, { resolved: {} });
+      assert.equal(resolved.closePrice.toNumber(), FIXTURE_PRICE);
+      const next = await program.account.roundState.fetch(round2);
+      assert.deepEqual(next.status, { open: {} });
+      assert.equal(next.roundId.toNumber(), 2);
+      assert.isAbove(next.lockTs.toNumber(), Math.floor(Date.now() / 1000) - 1);
+      const market = await program.account.market.fetch(marketPda);
+      assert.equal(market.currentRound.toNumber(), 2);
+      assert.equal(market.lockTimestamp.toNumber(), next.lockTs.toNumber());
+      console.log(`  ✓ Round 2 opened, locking at ${next.lockTs.toNumber()}`);
+    });
+  });
+
+  // =========================================================================
+  // 26. Round cleanup
+  // =========================================================================
+  describe("Round cleanup", () => {
+    // Round 1 of the market from section 24: settled, both stakes refunded
+    const marketId = new BN(BASE_ID + 59);
+    const roundId = new BN(1);
+    let marketPda: PublicKey;
+    let roundState: PublicKey;
+    let claimWindow: BN;
+
+    before(async () => {
+      [marketPda] = deriveMarketPda(marketId);
+      [roundState] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      claimWindow = (await program.account.platformConfig.fetch(platformConfig)).claimWindowSecs;
+    });
+
+    after(async () => {
+      await program.methods.updateClaimWindow(claimWindow).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+    });
+
+    const closeRound = () =>
+      program.methods
+        .closeRound(marketId, roundId)
+        .accounts({ market: marketPda, roundState, treasury: treasuryAta, platformConfig, admin: admin.publicKey } as any)
+        .rpc();
+
+    it("Keeps a round open for claims until its window passes", async () => {
+      await program.methods.updateClaimWindow(new BN(3600)).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+      try {
+        await closeRound();
+        assert.fail("Should have thrown ClaimWindowOpen");
+      } catch (e: any) {
+        assert.include(e.message, "ClaimWindowOpen");
+      }
+    });
+
+    it("Closes the round and its vault once aged out", async () => {
+      await program.methods.updateClaimWindow(new BN(0)).accounts({ platformConfig, admin: admin.publicKey }).rpc();
+      const [roundVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round_vault"), roundState.toBuffer()],
+        program.programId
+      );
+      const before = await provider.connection.getBalance(admin.publicKey);
+      await closeRound();
+
+      assert.isNull(await provider.connection.getAccountInfo(roundState));
+      assert.isNull(await provider.connection.getAccountInfo(roundVault));
+      assert.isAbove(await provider.connection.getBalance(admin.publicKey), before, "Rent returned");
+      console.log("  ✓ Round 1 closed");
+    });
+  });
+});
@@ -21,6 +21,9 @@ import {
  *   PDA seed validation on mints (cancel_bet, claim_payout)
  *   ToggleMarketCtx PDA constraint (pause/unpause market)
  *   H-3: checked_sub instead of .unwrap() in claim_payout
+ *   Frozen collateral accounts fail with AccountFrozen
+ *   init_platform rejects re-initialization and a zero dispute bond
+ *   Dispute bond is held in the dispute record and paid out on settlement
  */
 
 describe("Security Fixes Tests", () => {
@@ -148,17 +151,11 @@ describe("Security Fixes Tests", () => {
       await provider.connection.confirmTransaction(sig);
     }
 
-    treasuryAta = await token.getAssociatedTokenAddress(WSOL_MINT, admin.publicKey);
-    try {
-      await token.getAccount(provider.connection, treasuryAta);
-    } catch {
-      await token.createAssociatedTokenAccount(
-        provider.connection,
-        adminPayer,
-        WSOL_MINT,
-        admin.publicKey
-      );
-    }
+    // Fees accrue in the program-owned treasury for the collateral mint
+    [treasuryAta] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), WSOL_MINT.toBuffer()],
+      program.programId
+    );
 
     // Init platform (skip if already initialized by other test suite)
     try {
@@ -209,7 +206,6 @@ describe("Security Fixes Tests", () => {
           disputeRecord: disputePda,
           platformConfig,
           disputer: attacker.publicKey,
-          treasury: treasuryAta,
           systemProgram: SystemProgram.programId,
         })
         .signers([attacker])
@@ -223,6 +219,7 @@ describe("Security Fixes Tests", () => {
             market: marketPda,
             disputeRecord: disputePda,
             platformConfig,
+            platformAdmin: admin.publicKey,
             admin: attacker.publicKey,
           })
           .signers([attacker])
@@ -249,6 +246,7 @@ describe("Security Fixes Tests", () => {
           market: marketPda,
           disputeRecord: disputePda,
           platformConfig,
+          platformAdmin: admin.publicKey,
           admin: admin.publicKey,
         })
         .rpc();
@@ -577,6 +575,278 @@ describe("Security Fixes Tests", () => {
     });
   });
 
+  // =========================================================================
+  // Frozen collateral accounts
+  // =========================================================================
+  describe("Frozen collateral accounts", () => {
+    it("cancel_bet and claim_payout report AccountFrozen", async () => {
+      // Temporarily switch the platform to a collateral mint the admin can freeze
+      const usdMint = await token.createMint(
+        provider.connection, adminPayer, admin.publicKey, admin.publicKey, 6
+      );
+      const adminUsd = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, usdMint, admin.publicKey
+      );
+      const userUsd = await token.getOrCreateAssociatedTokenAccount(
+        provider.connection, adminPayer, usdMint, userA.publicKey
+      );
+      await token.mintTo(provider.connection, adminPayer, usdMint, adminUsd.address, adminPayer, 1_000_000_000);
+      await token.mintTo(provider.connection, adminPayer, usdMint, userUsd.address, adminPayer, 1_000_000_000);
+
+      const [usdTreasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury"), usdMint.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .updateCollateralMint()
+        .accounts({
+          platformConfig,
+          admin: admin.publicKey,
+          newCollateralMint: usdMint,
+          newTreasury: usdTreasury,
+        })
+        .rpc();
+
+      try {
+        const marketId = new BN(BASE_ID + 5);
+        const [marketPda] = deriveMarketPda(marketId);
+        const [yesMint] = deriveYesMint(marketPda);
+        const [noMint] = deriveNoMint(marketPda);
+        const [vault] = deriveVault(marketPda);
+        const [userPosition] = derivePosition(marketPda, userA.publicKey);
+        const now = Math.floor(Date.now() / 1000);
+
+        await program.methods
+          .createMarket(marketId, {
+            title: "Frozen Collateral Market",
+            description: "Test",
+            category: { crypto: {} },
+            oracleSource: { manualAdmin: {} },
+            oracleFeed: PublicKey.default,
+            oracleThreshold: new BN(0),
+            startTimestamp: new BN(now - 60),
+            lockTimestamp: new BN(now + 3600),
+            endTimestamp: new BN(now + 7200),
+            minBet: new BN(1_000_000),
+            maxBet: new BN(0),
+            isRecurring: false,
+            roundDuration: null,
+            feeBps: 250,
+            initialLiquidity: new BN(100_000_000),
+          } as any)
+          .accounts({ adminAta: adminUsd.address })
+          .rpc();
+
+        const userYesAta = await token.getOrCreateAssociatedTokenAccount(
+          provider.connection, adminPayer, yesMint, userA.publicKey
+        );
+        await program.methods
+          .placeBet(marketId, { yes: {} }, new BN(10_000_000), new BN(0))
+          .accounts({
+            user: userA.publicKey,
+            userShareAccount: userYesAta.address,
+            platformConfig,
+            treasury: usdTreasury,
+            collateralMint: usdMint,
+          })
+          .signers([userA])
+          .rpc();
+
+        // Issuer freezes the user's collateral account
+        await token.freezeAccount(provider.connection, adminPayer, userUsd.address, usdMint, adminPayer);
+        const shares = (await token.getAccount(provider.connection, userYesAta.address)).amount;
+        try {
+          await program.methods
+            .cancelBet(marketId, new BN(shares.toString()))
+            .accounts({
+              market: marketPda,
+              yesMint,
+              noMint,
+              vault,
+              userPosition,
+              userAta: userUsd.address,
+              userShareAccount: userYesAta.address,
+              platformConfig,
+              treasury: usdTreasury,
+              user: userA.publicKey,
+              collateralMint: usdMint,
+            })
+            .signers([userA])
+            .rpc();
+          assert.fail("Should have thrown AccountFrozen");
+        } catch (e: any) {
+          assert.include(e.message, "AccountFrozen");
+        }
+        await token.thawAccount(provider.connection, adminPayer, userUsd.address, usdMint, adminPayer);
+        console.log("  ✓ cancel_bet against a frozen user account: AccountFrozen");
+
+        // Issuer freezes the market vault after resolution
+        await program.methods
+          .resolveMarket(marketId, { yes: {} })
+          .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+          .rpc();
+        await token.freezeAccount(provider.connection, adminPayer, vault, usdMint, adminPayer);
+        try {
+          await program.methods
+            .claimPayout(marketId)
+            .accounts({
+              market: marketPda,
+              yesMint,
+              noMint,
+              vault,
+              userPosition,
+              userAta: userUsd.address,
+              userShareAccount: userYesAta.address,
+              user: userA.publicKey,
+              collateralMint: usdMint,
+              payoutReserve: null,
+              userPayoutAccount: null,
+            })
+            .signers([userA])
+            .rpc();
+          assert.fail("Should have thrown AccountFrozen");
+        } catch (e: any) {
+          assert.include(e.message, "AccountFrozen");
+        }
+        await token.thawAccount(provider.connection, adminPayer, vault, usdMint, adminPayer);
+        console.log("  ✓ claim_payout against a frozen vault: AccountFrozen");
+      } finally {
+        // Restore wSOL collateral for the other suites
+        await program.methods
+          .updateCollateralMint()
+          .accounts({
+            platformConfig,
+            admin: admin.publicKey,
+            newCollateralMint: WSOL_MINT,
+            newTreasury: treasuryAta,
+          })
+          .rpc();
+      }
+    });
+  });
+
+  // =========================================================================
+  // Dispute bond: held in the dispute record, paid out on settlement
+  // =========================================================================
+  describe("Dispute bond", () => {
+    const balance = (key: PublicKey) => provider.connection.getBalance(key);
+
+    async function openDispute(marketId: BN) {
+      const marketPda = await createTestMarket(marketId);
+      const [disputePda] = deriveDispute(marketPda);
+      await program.methods
+        .resolveMarket(marketId, { yes: {} })
+        .accounts({ market: marketPda, admin: admin.publicKey, platformConfig, pythPriceFeed: null })
+        .rpc();
+      const before = await balance(attacker.publicKey);
+      await program.methods
+        .openDispute(marketId, "Bond test")
+        .accounts({ market: marketPda, disputeRecord: disputePda, platformConfig, disputer: attacker.publicKey })
+        .signers([attacker])
+        .rpc();
+      const bond = (await program.account.disputeRecord.fetch(disputePda)).bondAmount.toNumber();
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(
+        (await provider.connection.getAccountInfo(disputePda)).data.length
+      );
+      assert.equal(await balance(disputePda), rent + bond, "Record holds the bond on top of its rent");
+      assert.isAtLeast(before - (await balance(attacker.publicKey)), rent + bond);
+      return { marketPda, disputePda, bond };
+    }
+
+    const settle = (marketId: BN, marketPda: PublicKey, disputePda: PublicKey, outcome: any) =>
+      program.methods
+        .settleDispute(marketId, outcome)
+        .accounts({
+          market: marketPda,
+          disputeRecord: disputePda,
+          platformConfig,
+          platformAdmin: admin.publicKey,
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+    it("Returns the bond to the disputer when the dispute is upheld", async () => {
+      const marketId = new BN(BASE_ID + 6);
+      const { marketPda, disputePda, bond } = await openDispute(marketId);
+
+      const before = await balance(attacker.publicKey);
+      const held = await balance(disputePda);
+      await settle(marketId, marketPda, disputePda, { no: {} });
+
+      assert.equal((await balance(attacker.publicKey)) - before, bond, "Disputer got the bond back");
+      assert.equal(held - (await balance(disputePda)), bond, "Record keeps only its rent");
+      console.log("  ✓ Upheld dispute refunded its bond");
+    });
+
+    it("Forfeits the bond to the platform admin when the dispute is rejected", async () => {
+      const marketId = new BN(BASE_ID + 7);
+      const { marketPda, disputePda, bond } = await openDispute(marketId);
+
+      const before = await balance(attacker.publicKey);
+      const held = await balance(disputePda);
+      try {
+        await program.methods
+          .settleDispute(marketId, null)
+          .accounts({
+            market: marketPda,
+            disputeRecord: disputePda,
+            platformConfig,
+            platformAdmin: attacker.publicKey,
+            admin: admin.publicKey,
+          })
+          .rpc();
+        assert.fail("Should have thrown Unauthorized");
+      } catch (e: any) {
+        assert.include(e.message, "Unauthorized");
+      }
+      await settle(marketId, marketPda, disputePda, null);
+
+      assert.equal(await balance(attacker.publicKey), before, "Disputer gets nothing back");
+      assert.equal(held - (await balance(disputePda)), bond, "Bond left the record");
+      console.log("  ✓ Rejected dispute forfeited its bond");
+    });
+  });
+
+  // =========================================================================
+  // init_platform guards
+  // =========================================================================
+  describe("init_platform guards", () => {
+    const reinit = (disputeBond: BN) =>
+      program.methods
+        .initPlatform(250, disputeBond)
+        .accounts({
+          platformConfig,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          collateralMint: WSOL_MINT,
+          treasury: treasuryAta,
+        })
+        .rpc();
+
+    it("Rejects re-initialization with a clear error", async () => {
+      const before = await program.account.platformConfig.fetch(platformConfig);
+      try {
+        await reinit(new BN(1_000_000));
+        assert.fail("Should have thrown PlatformAlreadyInitialized");
+      } catch (e: any) {
+        assert.include(e.message, "PlatformAlreadyInitialized");
+      }
+      const after = await program.account.platformConfig.fetch(platformConfig);
+      assert.equal(after.totalMarkets.toString(), before.totalMarkets.toString(), "Config untouched");
+      console.log("  ✓ Re-init rejected with PlatformAlreadyInitialized");
+    });
+
+    it("Rejects a zero dispute bond", async () => {
+      try {
+        await reinit(new BN(0));
+        assert.fail("Should have thrown InvalidDisputeBond");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidDisputeBond");
+      }
+      console.log("  ✓ Zero dispute bond rejected");
+    });
+  });
+
   // =========================================================================
   // Summary
   // =========================================================================
@@ -593,6 +863,8 @@ describe("Security Fixes Tests", () => {
       console.log("  ToggleMarketCtx PDA constraint         ✓ PATCHED");
       console.log("  H-1: Oracle staleness check            ✓ PATCHED");
       console.log("  H-3: checked_sub in claim_payout       ✓ PATCHED");
+      console.log("  init_platform re-init / zero bond      ✓ PATCHED");
+      console.log("  Dispute bond held until settlement     ✓ PATCHED");
       console.log("  ==========================================\n");
     });
   });